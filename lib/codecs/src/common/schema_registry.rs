@@ -0,0 +1,616 @@
+//! Support for [Confluent Schema Registry][schema_registry] compatible registries and the wire
+//! format used by messages whose schemas are held in one.
+//!
+//! [schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use http::{
+    header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE},
+    HeaderValue, Method, Request, StatusCode,
+};
+use hyper::{client::HttpConnector, Body, Client};
+use hyper_openssl::HttpsConnector;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use prost::Message;
+use prost_reflect::{
+    prost_types::FileDescriptorProto, DescriptorPool, FileDescriptor, MessageDescriptor,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use tokio::runtime::{Handle, RuntimeFlavor};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use vector_core::tls::{tls_connector_builder, MaybeTlsSettings, TlsConfig};
+
+/// The magic byte that starts every message in the schema registry wire format.
+pub const MAGIC_BYTE: u8 = 0;
+
+/// The length of the magic byte and schema ID that prefix every message in the wire format.
+pub const SCHEMA_ID_PREFIX_LEN: usize = 5;
+
+const CONTENT_TYPE_SCHEMA_REGISTRY: &str = "application/vnd.schemaregistry.v1+json";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The characters encoded in the segments of request paths: all but the unreserved ones.
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// How long a failure to fetch a schema is cached for, so that messages written with a schema that
+/// can't be fetched don't each wait on the registry.
+const FAILURE_TTL: Duration = Duration::from_secs(30);
+
+/// Schema registry connection options.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct SchemaRegistryConfig {
+    /// The URL of the schema registry.
+    #[configurable(metadata(docs::examples = "http://localhost:8081"))]
+    pub url: String,
+
+    /// Basic authentication for the schema registry.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<SchemaRegistryAuth>,
+
+    /// TLS configuration for connecting to the schema registry.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsConfig>,
+}
+
+/// Basic authentication for the schema registry.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct SchemaRegistryAuth {
+    /// The basic authentication username.
+    #[configurable(metadata(docs::examples = "${SCHEMA_REGISTRY_USERNAME}"))]
+    pub user: String,
+
+    /// The basic authentication password.
+    #[configurable(metadata(docs::examples = "${SCHEMA_REGISTRY_PASSWORD}"))]
+    pub password: SensitiveString,
+}
+
+/// The type of a schema held in the registry.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SchemaType {
+    /// An Apache Avro schema. The registry omits the type for these.
+    #[default]
+    Avro,
+    /// A Protobuf schema.
+    Protobuf,
+    /// A JSON schema.
+    Json,
+}
+
+impl SchemaType {
+    const fn as_str(self) -> &'static str {
+        match self {
+            SchemaType::Avro => "AVRO",
+            SchemaType::Protobuf => "PROTOBUF",
+            SchemaType::Json => "JSON",
+        }
+    }
+}
+
+/// A reference from one schema to another, registered under its own subject.
+#[derive(Clone, Debug, Deserialize)]
+pub struct SchemaReference {
+    /// The name the referencing schema imports the referenced schema by.
+    pub name: String,
+    /// The subject the referenced schema is registered under.
+    pub subject: String,
+    /// The version of the referenced schema within its subject.
+    pub version: i32,
+}
+
+/// A schema as returned by the registry.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisteredSchema {
+    /// The ID of the schema, when the registry includes it.
+    #[serde(default)]
+    pub id: Option<u32>,
+    /// The type of the schema.
+    #[serde(default)]
+    pub schema_type: SchemaType,
+    /// The schema itself.
+    pub schema: String,
+    /// The schemas this schema references.
+    #[serde(default)]
+    pub references: Vec<SchemaReference>,
+}
+
+#[derive(Deserialize)]
+struct SchemaId {
+    id: u32,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    message: String,
+}
+
+/// A client for a schema registry.
+///
+/// Requests are made synchronously, as schemas are needed from within (de)serializers, and block
+/// the calling thread. Callers are expected to cache what they fetch.
+#[derive(Clone)]
+pub struct SchemaRegistryClient {
+    url: String,
+    authorization: Option<HeaderValue>,
+    client: Client<HttpsConnector<HttpConnector>>,
+}
+
+impl fmt::Debug for SchemaRegistryClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SchemaRegistryClient")
+            .field("url", &self.url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SchemaRegistryClient {
+    /// Creates a new `SchemaRegistryClient` from the given configuration.
+    pub fn new(config: &SchemaRegistryConfig) -> vector_common::Result<Self> {
+        let tls_settings = MaybeTlsSettings::tls_client(&config.tls)?;
+        let mut http = HttpConnector::new();
+        http.enforce_http(false);
+        let mut https =
+            HttpsConnector::with_connector(http, tls_connector_builder(&tls_settings)?)?;
+        let settings = tls_settings.tls().cloned();
+        https.set_callback(move |c, _uri| {
+            if let Some(settings) = &settings {
+                settings.apply_connect_configuration(c);
+            }
+            Ok(())
+        });
+
+        let authorization = config
+            .auth
+            .as_ref()
+            .map(|auth| {
+                let credentials =
+                    BASE64_STANDARD.encode(format!("{}:{}", auth.user, auth.password.inner()));
+                HeaderValue::from_str(&format!("Basic {}", credentials))
+            })
+            .transpose()?;
+
+        // Requests may run on different runtimes, so connections can't be reused across them.
+        let client = Client::builder().pool_max_idle_per_host(0).build(https);
+
+        Ok(Self {
+            url: config.url.trim_end_matches('/').to_owned(),
+            authorization,
+            client,
+        })
+    }
+
+    /// Fetches the schema with the given ID.
+    ///
+    /// Protobuf schemas are returned as a base64-encoded `FileDescriptorProto` rather than as
+    /// `.proto` source.
+    pub fn schema_by_id(&self, id: u32) -> vector_common::Result<RegisteredSchema> {
+        let mut schema: RegisteredSchema =
+            self.request(Method::GET, &format!("/schemas/ids/{}", id), None)?;
+        if schema.schema_type == SchemaType::Protobuf {
+            schema = self.request(
+                Method::GET,
+                &format!("/schemas/ids/{}?format=serialized", id),
+                None,
+            )?;
+        }
+        schema.id = Some(id);
+        Ok(schema)
+    }
+
+    /// Fetches a version of the schema registered under the given subject, where the version is
+    /// either a version number or `latest`.
+    ///
+    /// Protobuf schemas are returned as a base64-encoded `FileDescriptorProto` rather than as
+    /// `.proto` source.
+    pub fn subject_version(
+        &self,
+        subject: &str,
+        version: &str,
+    ) -> vector_common::Result<RegisteredSchema> {
+        let path = format!(
+            "/subjects/{}/versions/{}",
+            encode_path_segment(subject),
+            encode_path_segment(version)
+        );
+        let schema: RegisteredSchema = self.request(Method::GET, &path, None)?;
+        if schema.schema_type == SchemaType::Protobuf {
+            self.request(Method::GET, &format!("{}?format=serialized", path), None)
+        } else {
+            Ok(schema)
+        }
+    }
+
+    /// Registers the schema under the given subject, returning its ID.
+    ///
+    /// Registering a schema that is already registered under the subject returns its existing ID.
+    pub fn register(
+        &self,
+        subject: &str,
+        schema: &str,
+        schema_type: SchemaType,
+    ) -> vector_common::Result<u32> {
+        let body = schema_body(schema, schema_type);
+        let path = format!("/subjects/{}/versions", encode_path_segment(subject));
+        let response: SchemaId = self.request(Method::POST, &path, Some(body))?;
+        Ok(response.id)
+    }
+
+    /// Looks up the ID of a schema already registered under the given subject.
+    pub fn lookup(
+        &self,
+        subject: &str,
+        schema: &str,
+        schema_type: SchemaType,
+    ) -> vector_common::Result<u32> {
+        let body = schema_body(schema, schema_type);
+        let path = format!("/subjects/{}", encode_path_segment(subject));
+        let response: SchemaId = self.request(Method::POST, &path, Some(body))?;
+        Ok(response.id)
+    }
+
+    /// Builds the Protobuf file described by a schema fetched from the registry, along with
+    /// every file it references.
+    pub fn protobuf_file(
+        &self,
+        schema: &RegisteredSchema,
+    ) -> vector_common::Result<FileDescriptor> {
+        let mut pool = DescriptorPool::new();
+        let name = self.add_protobuf_file(&mut pool, schema)?;
+        pool.get_file_by_name(&name)
+            .ok_or_else(|| format!("Protobuf file {:?} missing from its own pool", name).into())
+    }
+
+    fn add_protobuf_file(
+        &self,
+        pool: &mut DescriptorPool,
+        schema: &RegisteredSchema,
+    ) -> vector_common::Result<String> {
+        for reference in &schema.references {
+            if pool.get_file_by_name(&reference.name).is_none() {
+                let referenced =
+                    self.subject_version(&reference.subject, &reference.version.to_string())?;
+                self.add_protobuf_file(pool, &referenced)?;
+            }
+        }
+
+        let bytes = BASE64_STANDARD.decode(&schema.schema)?;
+        let file = FileDescriptorProto::decode(bytes.as_slice())?;
+        let name = file.name().to_owned();
+        pool.add_file_descriptor_proto(file)?;
+        Ok(name)
+    }
+
+    fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> vector_common::Result<T> {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(format!("{}{}", self.url, path))
+            .header(ACCEPT, CONTENT_TYPE_SCHEMA_REGISTRY);
+        if body.is_some() {
+            request = request.header(CONTENT_TYPE, CONTENT_TYPE_SCHEMA_REGISTRY);
+        }
+        if let Some(authorization) = &self.authorization {
+            request = request.header(AUTHORIZATION, authorization.clone());
+        }
+        let body = match body {
+            Some(body) => Body::from(serde_json::to_vec(&body)?),
+            None => Body::empty(),
+        };
+        let request = request.body(body)?;
+
+        let client = self.client.clone();
+        let (status, body) = block_on(async move {
+            let response = tokio::time::timeout(REQUEST_TIMEOUT, client.request(request))
+                .await
+                .map_err(|_| "Schema registry request timed out")??;
+            let status = response.status();
+            let body = hyper::body::to_bytes(response.into_body()).await?;
+            Ok::<(StatusCode, Bytes), vector_common::Error>((status, body))
+        })??;
+
+        if !status.is_success() {
+            let message = serde_json::from_slice::<ErrorResponse>(&body)
+                .map(|error| error.message)
+                .unwrap_or_else(|_| String::from_utf8_lossy(&body).into_owned());
+            return Err(format!("Schema registry responded with {}: {}", status, message).into());
+        }
+
+        Ok(serde_json::from_slice(&body)?)
+    }
+}
+
+/// Drives a future to completion from synchronous code, such as a (de)serializer, which may itself
+/// be called from within a runtime.
+fn block_on<F>(future: F) -> vector_common::Result<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let new_runtime = || {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+    };
+    match Handle::try_current() {
+        // Let the runtime hand the other tasks of this worker thread over to other workers while
+        // it blocks.
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            Ok(tokio::task::block_in_place(move || handle.block_on(future)))
+        }
+        // The only thread of the runtime can't block on it, so the future runs on a thread of its
+        // own.
+        Ok(_) => std::thread::spawn(move || {
+            Ok::<_, vector_common::Error>(new_runtime()?.block_on(future))
+        })
+        .join()
+        .map_err(|_| "Schema registry request panicked")?,
+        Err(_) => Ok(new_runtime()?.block_on(future)),
+    }
+}
+
+/// Encodes a subject or version to be used as a segment of a request path.
+fn encode_path_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, PATH_SEGMENT).to_string()
+}
+
+fn schema_body(schema: &str, schema_type: SchemaType) -> serde_json::Value {
+    serde_json::json!({
+        "schema": schema,
+        "schemaType": schema_type.as_str(),
+    })
+}
+
+/// A cache of values built from schemas fetched from the registry, keyed by schema ID.
+///
+/// Failures to build values are cached too, for a while, and clones share the same entries.
+#[derive(Clone, Debug)]
+pub struct SchemaCache<T> {
+    entries: Arc<Mutex<HashMap<u32, CacheEntry<T>>>>,
+}
+
+#[derive(Debug)]
+enum CacheEntry<T> {
+    Built(T),
+    Failed { error: String, at: Instant },
+}
+
+impl<T> Default for SchemaCache<T> {
+    fn default() -> Self {
+        Self {
+            entries: Default::default(),
+        }
+    }
+}
+
+impl<T: Clone> SchemaCache<T> {
+    /// Returns the cached value for the given schema ID, building and caching it first if
+    /// necessary.
+    ///
+    /// If building the value failed less than `FAILURE_TTL` ago, that failure is returned instead
+    /// of trying again. The cache is not locked while the value is built, so concurrent misses on
+    /// the same ID may each build it.
+    pub fn get_or_try_insert(
+        &self,
+        id: u32,
+        build: impl FnOnce() -> vector_common::Result<T>,
+    ) -> vector_common::Result<T> {
+        match self.entries.lock().expect("poisoned lock").get(&id) {
+            Some(CacheEntry::Built(value)) => return Ok(value.clone()),
+            Some(CacheEntry::Failed { error, at }) if at.elapsed() < FAILURE_TTL => {
+                return Err(error.clone().into())
+            }
+            _ => {}
+        }
+
+        let (entry, result) = match build() {
+            Ok(value) => (CacheEntry::Built(value.clone()), Ok(value)),
+            Err(error) => {
+                let error = error.to_string();
+                let entry = CacheEntry::Failed {
+                    error: error.clone(),
+                    at: Instant::now(),
+                };
+                (entry, Err(error.into()))
+            }
+        };
+        self.entries
+            .lock()
+            .expect("poisoned lock")
+            .insert(id, entry);
+        result
+    }
+}
+
+/// Splits the magic byte and schema ID off a message in the wire format, returning the schema ID
+/// and the remaining payload.
+pub fn split_schema_id(mut bytes: Bytes) -> vector_common::Result<(u32, Bytes)> {
+    if bytes.len() < SCHEMA_ID_PREFIX_LEN || bytes[0] != MAGIC_BYTE {
+        return Err("Message is not in the schema registry wire format".into());
+    }
+    bytes.advance(1);
+    let id = bytes.get_u32();
+    Ok((id, bytes))
+}
+
+/// Writes the magic byte and schema ID that start a message in the wire format.
+pub fn put_schema_id(id: u32, buffer: &mut BytesMut) {
+    buffer.put_u8(MAGIC_BYTE);
+    buffer.put_u32(id);
+}
+
+/// Reads the message indexes that follow the schema ID of Protobuf messages, identifying the
+/// message type within the schema's file.
+pub fn read_message_indexes(bytes: &mut Bytes) -> vector_common::Result<Vec<i32>> {
+    let count = read_zigzag(bytes)?;
+    if count == 0 {
+        // The common case of the first message in the file is written as a lone zero.
+        return Ok(vec![0]);
+    }
+    if count < 0 || count as usize > bytes.len() {
+        return Err(format!("Invalid Protobuf message index count {}", count).into());
+    }
+    (0..count).map(|_| read_zigzag(bytes)).collect()
+}
+
+/// Writes the message indexes that follow the schema ID of Protobuf messages.
+pub fn put_message_indexes(indexes: &[i32], buffer: &mut BytesMut) {
+    if indexes == [0] {
+        put_zigzag(0, buffer);
+        return;
+    }
+    put_zigzag(indexes.len() as i32, buffer);
+    for index in indexes {
+        put_zigzag(*index, buffer);
+    }
+}
+
+/// Resolves message indexes to the message type they identify within a Protobuf file.
+pub fn message_by_indexes(
+    file: &FileDescriptor,
+    indexes: &[i32],
+) -> vector_common::Result<MessageDescriptor> {
+    let not_found = || format!("No Protobuf message at indexes {:?}", indexes);
+    let (first, rest) = indexes.split_first().ok_or_else(not_found)?;
+    let mut message = file.messages().nth(*first as usize).ok_or_else(not_found)?;
+    for index in rest {
+        message = message
+            .child_messages()
+            .nth(*index as usize)
+            .ok_or_else(not_found)?;
+    }
+    Ok(message)
+}
+
+/// Computes the message indexes that identify a message type within its Protobuf file.
+pub fn message_indexes(message: &MessageDescriptor) -> Vec<i32> {
+    let mut indexes = Vec::new();
+    let mut current = message.clone();
+    loop {
+        let parent = current.parent_message();
+        let index = match &parent {
+            Some(parent) => parent
+                .child_messages()
+                .position(|sibling| sibling == current),
+            None => current
+                .parent_file()
+                .messages()
+                .position(|sibling| sibling == current),
+        };
+        indexes.push(index.unwrap_or_default() as i32);
+        match parent {
+            Some(parent) => current = parent,
+            None => break,
+        }
+    }
+    indexes.reverse();
+    indexes
+}
+
+fn read_zigzag(bytes: &mut Bytes) -> vector_common::Result<i32> {
+    let value = prost::encoding::decode_varint(bytes)? as u32;
+    Ok(((value >> 1) as i32) ^ -((value & 1) as i32))
+}
+
+fn put_zigzag(value: i32, buffer: &mut BytesMut) {
+    prost::encoding::encode_varint(((value << 1) ^ (value >> 31)) as u32 as u64, buffer);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_id_roundtrip() {
+        let mut buffer = BytesMut::new();
+        put_schema_id(258, &mut buffer);
+        buffer.put_slice(b"payload");
+
+        let (id, payload) = split_schema_id(buffer.freeze()).unwrap();
+        assert_eq!(id, 258);
+        assert_eq!(payload, Bytes::from_static(b"payload"));
+    }
+
+    #[test]
+    fn split_schema_id_rejects_other_formats() {
+        assert!(split_schema_id(Bytes::from_static(b"\x01\x00\x00\x00\x01")).is_err());
+        assert!(split_schema_id(Bytes::from_static(b"\x00\x00")).is_err());
+    }
+
+    #[test]
+    fn message_indexes_roundtrip() {
+        for indexes in [vec![0], vec![1], vec![0, 2], vec![3, 1, 64]] {
+            let mut buffer = BytesMut::new();
+            put_message_indexes(&indexes, &mut buffer);
+            buffer.put_slice(b"payload");
+
+            let mut bytes = buffer.freeze();
+            assert_eq!(read_message_indexes(&mut bytes).unwrap(), indexes);
+            assert_eq!(bytes, Bytes::from_static(b"payload"));
+        }
+    }
+
+    #[test]
+    fn schema_cache_caches_failures() {
+        let cache = SchemaCache::<u32>::default();
+        let mut builds = 0;
+
+        for _ in 0..2 {
+            let error = cache
+                .clone()
+                .get_or_try_insert(1, || {
+                    builds += 1;
+                    Err("unreachable".into())
+                })
+                .unwrap_err();
+            assert_eq!(error.to_string(), "unreachable");
+        }
+        assert_eq!(builds, 1);
+
+        for _ in 0..2 {
+            let value = cache.get_or_try_insert(2, || {
+                builds += 1;
+                Ok(2)
+            });
+            assert_eq!(value.unwrap(), 2);
+        }
+        assert_eq!(builds, 2);
+    }
+
+    #[test]
+    fn path_segments_are_encoded() {
+        assert_eq!(encode_path_segment("logs-value"), "logs-value");
+        assert_eq!(encode_path_segment("a/b?c d"), "a%2Fb%3Fc%20d");
+        assert_eq!(encode_path_segment("latest"), "latest");
+    }
+
+    #[test]
+    fn first_message_index_is_a_single_byte() {
+        let mut buffer = BytesMut::new();
+        put_message_indexes(&[0], &mut buffer);
+        assert_eq!(&buffer[..], &[0]);
+    }
+}
@@ -10,6 +10,7 @@ mod json;
 mod native;
 mod native_json;
 mod protobuf;
+mod schema_registry;
 #[cfg(feature = "syslog")]
 mod syslog;
 mod vrl;
@@ -24,6 +25,7 @@ pub use native_json::{
     NativeJsonDeserializer, NativeJsonDeserializerConfig, NativeJsonDeserializerOptions,
 };
 pub use protobuf::{ProtobufDeserializer, ProtobufDeserializerConfig, ProtobufDeserializerOptions};
+pub use schema_registry::{SchemaRegistryDeserializer, SchemaRegistryDeserializerConfig};
 use smallvec::SmallVec;
 #[cfg(feature = "syslog")]
 pub use syslog::{SyslogDeserializer, SyslogDeserializerConfig, SyslogDeserializerOptions};
@@ -0,0 +1,127 @@
+use bytes::Bytes;
+use prost_reflect::FileDescriptor;
+use smallvec::{smallvec, SmallVec};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::Event,
+    schema,
+};
+use vrl::value::Kind;
+
+use super::{AvroDeserializer, Deserializer, ProtobufDeserializer};
+use crate::common::schema_registry::{
+    message_by_indexes, read_message_indexes, split_schema_id, SchemaCache, SchemaRegistryClient,
+    SchemaRegistryConfig, SchemaType,
+};
+
+/// Config used to build a `SchemaRegistryDeserializer`.
+#[configurable_component]
+#[derive(Debug, Clone)]
+pub struct SchemaRegistryDeserializerConfig {
+    /// Schema registry-specific decoding options.
+    pub schema_registry: SchemaRegistryConfig,
+}
+
+impl SchemaRegistryDeserializerConfig {
+    /// Build the `SchemaRegistryDeserializer` from this configuration.
+    pub fn build(&self) -> vector_common::Result<SchemaRegistryDeserializer> {
+        Ok(SchemaRegistryDeserializer::new(SchemaRegistryClient::new(
+            &self.schema_registry,
+        )?))
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        match log_namespace {
+            LogNamespace::Legacy => {
+                let mut definition =
+                    schema::Definition::empty_legacy_namespace().unknown_fields(Kind::any());
+
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition = definition.try_with_field(
+                        timestamp_key,
+                        Kind::any().or_timestamp(),
+                        Some("timestamp"),
+                    );
+                }
+                definition
+            }
+            LogNamespace::Vector => {
+                schema::Definition::new_with_default_metadata(Kind::any(), [log_namespace])
+            }
+        }
+    }
+}
+
+/// A schema fetched from the registry, ready to decode the messages written with it.
+#[derive(Debug, Clone)]
+enum WriterSchema {
+    Avro(AvroDeserializer),
+    Protobuf(FileDescriptor),
+}
+
+/// Deserializer that builds `Event`s from Avro or Protobuf messages in the schema registry wire
+/// format, fetching the schema each message was written with from the registry.
+#[derive(Debug, Clone)]
+pub struct SchemaRegistryDeserializer {
+    client: SchemaRegistryClient,
+    schemas: SchemaCache<WriterSchema>,
+}
+
+impl SchemaRegistryDeserializer {
+    /// Creates a new `SchemaRegistryDeserializer`.
+    pub fn new(client: SchemaRegistryClient) -> Self {
+        Self {
+            client,
+            schemas: SchemaCache::default(),
+        }
+    }
+
+    fn fetch_schema(&self, id: u32) -> vector_common::Result<WriterSchema> {
+        let schema = self.client.schema_by_id(id)?;
+        match schema.schema_type {
+            SchemaType::Avro => {
+                if !schema.references.is_empty() {
+                    return Err("Avro schemas with references are not supported".into());
+                }
+                let schema = apache_avro::Schema::parse_str(&schema.schema)?;
+                Ok(WriterSchema::Avro(AvroDeserializer::new(schema, false)))
+            }
+            SchemaType::Protobuf => Ok(WriterSchema::Protobuf(self.client.protobuf_file(&schema)?)),
+            SchemaType::Json => Err("JSON schemas are not supported".into()),
+        }
+    }
+}
+
+impl Deserializer for SchemaRegistryDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        if bytes.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let (id, mut payload) = split_schema_id(bytes)?;
+        let schema = self
+            .schemas
+            .get_or_try_insert(id, || self.fetch_schema(id))
+            .map_err(|error| format!("Failed fetching schema {}: {}", id, error))?;
+
+        match schema {
+            WriterSchema::Avro(deserializer) => deserializer.parse(payload, log_namespace),
+            WriterSchema::Protobuf(file) => {
+                let indexes = read_message_indexes(&mut payload)?;
+                let message = message_by_indexes(&file, &indexes)?;
+                ProtobufDeserializer::new(message).parse(payload, log_namespace)
+            }
+        }
+    }
+}
@@ -13,7 +13,8 @@ pub use format::{
     GelfDeserializerConfig, GelfDeserializerOptions, JsonDeserializer, JsonDeserializerConfig,
     JsonDeserializerOptions, NativeDeserializer, NativeDeserializerConfig, NativeJsonDeserializer,
     NativeJsonDeserializerConfig, NativeJsonDeserializerOptions, ProtobufDeserializer,
-    ProtobufDeserializerConfig, ProtobufDeserializerOptions,
+    ProtobufDeserializerConfig, ProtobufDeserializerOptions, SchemaRegistryDeserializer,
+    SchemaRegistryDeserializerConfig,
 };
 #[cfg(feature = "syslog")]
 pub use format::{SyslogDeserializer, SyslogDeserializerConfig, SyslogDeserializerOptions};
@@ -207,6 +208,14 @@ pub enum DeserializerConfig {
     /// [protobuf]: https://protobuf.dev/
     Protobuf(ProtobufDeserializerConfig),
 
+    /// Decodes the raw bytes as Avro or Protobuf in the [schema registry wire format][wire_format].
+    ///
+    /// The schema each message was written with is fetched from the registry by the ID that
+    /// prefixes the message, and cached.
+    ///
+    /// [wire_format]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+    SchemaRegistry(SchemaRegistryDeserializerConfig),
+
     #[cfg(feature = "syslog")]
     /// Decodes the raw bytes as a Syslog message.
     ///
@@ -285,6 +294,12 @@ impl From<SyslogDeserializerConfig> for DeserializerConfig {
     }
 }
 
+impl From<SchemaRegistryDeserializerConfig> for DeserializerConfig {
+    fn from(config: SchemaRegistryDeserializerConfig) -> Self {
+        Self::SchemaRegistry(config)
+    }
+}
+
 impl From<GelfDeserializerConfig> for DeserializerConfig {
     fn from(config: GelfDeserializerConfig) -> Self {
         Self::Gelf(config)
@@ -316,6 +331,9 @@ impl DeserializerConfig {
             DeserializerConfig::Bytes => Ok(Deserializer::Bytes(BytesDeserializerConfig.build())),
             DeserializerConfig::Json(config) => Ok(Deserializer::Json(config.build())),
             DeserializerConfig::Protobuf(config) => Ok(Deserializer::Protobuf(config.build()?)),
+            DeserializerConfig::SchemaRegistry(config) => {
+                Ok(Deserializer::SchemaRegistry(config.build()?))
+            }
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(config) => Ok(Deserializer::Syslog(config.build())),
             DeserializerConfig::Native => {
@@ -338,7 +356,9 @@ impl DeserializerConfig {
             | DeserializerConfig::NativeJson(_) => {
                 FramingConfig::NewlineDelimited(Default::default())
             }
-            DeserializerConfig::Protobuf(_) => FramingConfig::Bytes,
+            DeserializerConfig::Protobuf(_) | DeserializerConfig::SchemaRegistry(_) => {
+                FramingConfig::Bytes
+            }
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(_) => FramingConfig::NewlineDelimited(Default::default()),
             DeserializerConfig::Vrl(_) => FramingConfig::Bytes,
@@ -355,6 +375,7 @@ impl DeserializerConfig {
             DeserializerConfig::Bytes => BytesDeserializerConfig.output_type(),
             DeserializerConfig::Json(config) => config.output_type(),
             DeserializerConfig::Protobuf(config) => config.output_type(),
+            DeserializerConfig::SchemaRegistry(config) => config.output_type(),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(config) => config.output_type(),
             DeserializerConfig::Native => NativeDeserializerConfig.output_type(),
@@ -374,6 +395,7 @@ impl DeserializerConfig {
             DeserializerConfig::Bytes => BytesDeserializerConfig.schema_definition(log_namespace),
             DeserializerConfig::Json(config) => config.schema_definition(log_namespace),
             DeserializerConfig::Protobuf(config) => config.schema_definition(log_namespace),
+            DeserializerConfig::SchemaRegistry(config) => config.schema_definition(log_namespace),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(config) => config.schema_definition(log_namespace),
             DeserializerConfig::Native => NativeDeserializerConfig.schema_definition(log_namespace),
@@ -405,7 +427,9 @@ impl DeserializerConfig {
             (DeserializerConfig::Native, _) | (DeserializerConfig::Avro { .. }, _) => {
                 "application/octet-stream"
             }
-            (DeserializerConfig::Protobuf(_), _) => "application/octet-stream",
+            (DeserializerConfig::Protobuf(_) | DeserializerConfig::SchemaRegistry(_), _) => {
+                "application/octet-stream"
+            }
             (
                 DeserializerConfig::Json(_)
                 | DeserializerConfig::NativeJson(_)
@@ -431,6 +455,8 @@ pub enum Deserializer {
     Json(JsonDeserializer),
     /// Uses a `ProtobufDeserializer` for deserialization.
     Protobuf(ProtobufDeserializer),
+    /// Uses a `SchemaRegistryDeserializer` for deserialization.
+    SchemaRegistry(SchemaRegistryDeserializer),
     #[cfg(feature = "syslog")]
     /// Uses a `SyslogDeserializer` for deserialization.
     Syslog(SyslogDeserializer),
@@ -457,6 +483,7 @@ impl format::Deserializer for Deserializer {
             Deserializer::Bytes(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Json(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Protobuf(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::SchemaRegistry(deserializer) => deserializer.parse(bytes, log_namespace),
             #[cfg(feature = "syslog")]
             Deserializer::Syslog(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Native(deserializer) => deserializer.parse(bytes, log_namespace),
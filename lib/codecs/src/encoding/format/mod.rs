@@ -11,8 +11,11 @@ mod json;
 mod logfmt;
 mod native;
 mod native_json;
+#[cfg(feature = "parquet")]
+mod parquet;
 mod protobuf;
 mod raw_message;
+mod schema_registry;
 mod text;
 
 use std::fmt::Debug;
@@ -25,8 +28,15 @@ pub use json::{JsonSerializer, JsonSerializerConfig, JsonSerializerOptions};
 pub use logfmt::{LogfmtSerializer, LogfmtSerializerConfig};
 pub use native::{NativeSerializer, NativeSerializerConfig};
 pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
+#[cfg(feature = "parquet")]
+pub use parquet::{
+    ParquetCompression, ParquetSerializer, ParquetSerializerConfig, ParquetSerializerOptions,
+};
 pub use protobuf::{ProtobufSerializer, ProtobufSerializerConfig, ProtobufSerializerOptions};
 pub use raw_message::{RawMessageSerializer, RawMessageSerializerConfig};
+pub use schema_registry::{
+    SchemaRegistrySerializer, SchemaRegistrySerializerConfig, SchemaRegistrySerializerOptions,
+};
 pub use text::{TextSerializer, TextSerializerConfig};
 use vector_core::event::Event;
 
@@ -0,0 +1,327 @@
+use std::sync::Arc;
+
+use arrow_json::reader::{infer_json_schema_from_iterator, ReaderBuilder};
+use arrow_schema::{Schema, SchemaRef};
+use bytes::{BufMut, BytesMut};
+use parquet::{
+    arrow::{parquet_to_arrow_schema, ArrowWriter},
+    basic::{Compression, GzipLevel, ZstdLevel},
+    file::properties::{WriterProperties, DEFAULT_MAX_ROW_GROUP_SIZE},
+    schema::{parser::parse_message_type, types::SchemaDescriptor},
+};
+use vector_config::configurable_component;
+use vector_core::{config::DataType, event::Event, schema};
+
+use crate::encoding::BuildError;
+
+/// Config used to build a `ParquetSerializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct ParquetSerializerConfig {
+    /// Apache Parquet-specific encoder options.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub parquet: ParquetSerializerOptions,
+}
+
+impl ParquetSerializerConfig {
+    /// Build the `ParquetSerializer` from this configuration.
+    pub fn build(&self) -> Result<ParquetSerializer, BuildError> {
+        let schema = self
+            .parquet
+            .schema
+            .as_deref()
+            .map(|schema| {
+                let message_type = parse_message_type(schema)?;
+                let descriptor = SchemaDescriptor::new(Arc::new(message_type));
+                parquet_to_arrow_schema(&descriptor, None).map(Arc::new)
+            })
+            .transpose()
+            .map_err(|error| format!("Failed building Parquet serializer: {}", error))?;
+
+        if self.parquet.row_group_size == 0 {
+            return Err("Parquet row group size must be greater than zero".into());
+        }
+
+        let properties = WriterProperties::builder()
+            .set_compression(self.parquet.compression.into())
+            .set_max_row_group_size(self.parquet.row_group_size)
+            .build();
+
+        Ok(ParquetSerializer {
+            schema,
+            row_group_size: self.parquet.row_group_size,
+            properties,
+        })
+    }
+
+    /// The data type of events that are accepted by `ParquetSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Apache Parquet serializer options.
+#[configurable_component]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParquetSerializerOptions {
+    /// The schema of the Parquet files, as a Parquet message type definition.
+    ///
+    /// Fields of events that aren't part of the schema are dropped. If unset, the schema is
+    /// inferred from the events of each batch, so files written from different batches may have
+    /// different schemas.
+    #[configurable(metadata(
+        docs::examples = "message log { required binary message (STRING); optional int64 status; }"
+    ))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub schema: Option<String>,
+
+    /// The maximum number of rows in each row group.
+    #[serde(default = "default_row_group_size")]
+    #[configurable(metadata(docs::type_unit = "rows"))]
+    pub row_group_size: usize,
+
+    /// The compression applied to the pages of each column.
+    #[serde(default)]
+    pub compression: ParquetCompression,
+}
+
+impl Default for ParquetSerializerOptions {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            row_group_size: default_row_group_size(),
+            compression: ParquetCompression::default(),
+        }
+    }
+}
+
+const fn default_row_group_size() -> usize {
+    DEFAULT_MAX_ROW_GROUP_SIZE
+}
+
+/// Compression codecs for Parquet column pages.
+#[configurable_component]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetCompression {
+    /// No compression.
+    None,
+
+    /// [Snappy][snappy] compression.
+    ///
+    /// [snappy]: https://github.com/google/snappy
+    #[default]
+    Snappy,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+
+    /// [LZ4][lz4] compression.
+    ///
+    /// [lz4]: https://lz4.org/
+    Lz4,
+}
+
+impl From<ParquetCompression> for Compression {
+    fn from(compression: ParquetCompression) -> Self {
+        match compression {
+            ParquetCompression::None => Compression::UNCOMPRESSED,
+            ParquetCompression::Snappy => Compression::SNAPPY,
+            ParquetCompression::Gzip => Compression::GZIP(GzipLevel::default()),
+            ParquetCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+            ParquetCompression::Lz4 => Compression::LZ4_RAW,
+        }
+    }
+}
+
+/// Serializer that converts a batch of `Event`s to a single Apache Parquet file.
+#[derive(Debug, Clone)]
+pub struct ParquetSerializer {
+    schema: Option<SchemaRef>,
+    row_group_size: usize,
+    properties: WriterProperties,
+}
+
+impl ParquetSerializer {
+    /// Encodes the batch of events as a Parquet file, written to the buffer.
+    pub fn encode(&self, events: Vec<Event>, buffer: &mut BytesMut) -> vector_common::Result<()> {
+        let rows = events
+            .into_iter()
+            .map(|event| -> vector_common::Result<serde_json::Value> {
+                match event {
+                    Event::Log(log) => Ok(serde_json::to_value(log.value())?),
+                    Event::Metric(_) | Event::Trace(_) => {
+                        Err("Parquet can only encode log events".into())
+                    }
+                }
+            })
+            .collect::<vector_common::Result<Vec<serde_json::Value>>>()?;
+
+        let schema = match &self.schema {
+            Some(schema) => Arc::clone(schema),
+            None => Arc::new(infer_schema(&rows)?),
+        };
+
+        let mut writer = ArrowWriter::try_new(
+            buffer.writer(),
+            Arc::clone(&schema),
+            Some(self.properties.clone()),
+        )?;
+        for chunk in rows.chunks(self.row_group_size) {
+            // Fields holding both numbers and strings are inferred as strings, so numbers are
+            // written as strings rather than rejected.
+            let mut decoder = ReaderBuilder::new(Arc::clone(&schema))
+                .with_batch_size(chunk.len())
+                .with_coerce_primitive(true)
+                .build_decoder()?;
+            decoder.serialize(chunk)?;
+            if let Some(batch) = decoder.flush()? {
+                writer.write(&batch)?;
+            }
+        }
+        writer.close()?;
+
+        Ok(())
+    }
+}
+
+fn infer_schema(rows: &[serde_json::Value]) -> vector_common::Result<Schema> {
+    Ok(infer_json_schema_from_iterator(rows.iter().map(Ok))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use parquet::{
+        arrow::arrow_reader::ParquetRecordBatchReaderBuilder, file::reader::FileReader,
+        file::serialized_reader::SerializedFileReader, record::RowAccessor,
+    };
+    use vector_core::event::{LogEvent, Value};
+    use vrl::btreemap;
+
+    use super::*;
+
+    fn events(count: i64) -> Vec<Event> {
+        (0..count)
+            .map(|i| {
+                Event::Log(LogEvent::from(btreemap! {
+                    "message" => Value::from(format!("message {}", i)),
+                    "status" => Value::Integer(200 + i),
+                }))
+            })
+            .collect()
+    }
+
+    fn serialize(options: ParquetSerializerOptions, events: Vec<Event>) -> Bytes {
+        let serializer = ParquetSerializerConfig { parquet: options }
+            .build()
+            .unwrap();
+        let mut buffer = BytesMut::new();
+        serializer.encode(events, &mut buffer).unwrap();
+        buffer.freeze()
+    }
+
+    #[test]
+    fn serialize_parquet_inferred_schema() {
+        let bytes = serialize(ParquetSerializerOptions::default(), events(3));
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(bytes)
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches.len(), 1);
+
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 3);
+        let schema = batch.schema();
+        let mut fields = schema
+            .fields()
+            .iter()
+            .map(|field| (field.name().as_str(), field.data_type().to_string()))
+            .collect::<Vec<_>>();
+        fields.sort();
+        assert_eq!(
+            fields,
+            vec![
+                ("message", String::from("Utf8")),
+                ("status", String::from("Int64"))
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_parquet_mixed_types() {
+        let events = vec![
+            Event::Log(LogEvent::from(
+                btreemap! { "status" => Value::Integer(200) },
+            )),
+            Event::Log(LogEvent::from(btreemap! { "status" => Value::from("404") })),
+        ];
+        let bytes = serialize(ParquetSerializerOptions::default(), events);
+
+        let reader = SerializedFileReader::new(bytes).unwrap();
+        let statuses = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| row.unwrap().get_string(0).unwrap().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(statuses, vec!["200", "404"]);
+    }
+
+    #[test]
+    fn serialize_parquet_explicit_schema() {
+        let options = ParquetSerializerOptions {
+            schema: Some("message log { required binary message (STRING); }".to_owned()),
+            ..Default::default()
+        };
+        let bytes = serialize(options, events(2));
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(bytes).unwrap();
+        let schema = reader.schema();
+        assert_eq!(schema.fields().len(), 1);
+        assert_eq!(schema.field(0).name(), "message");
+    }
+
+    #[test]
+    fn serialize_parquet_row_groups() {
+        let options = ParquetSerializerOptions {
+            row_group_size: 2,
+            compression: ParquetCompression::Zstd,
+            ..Default::default()
+        };
+        let bytes = serialize(options, events(5));
+
+        let reader = SerializedFileReader::new(bytes).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.num_row_groups(), 3);
+        assert_eq!(metadata.file_metadata().num_rows(), 5);
+        assert_eq!(
+            metadata.row_group(0).column(0).compression(),
+            Compression::ZSTD(ZstdLevel::default())
+        );
+    }
+
+    #[test]
+    fn invalid_schema() {
+        let config = ParquetSerializerConfig {
+            parquet: ParquetSerializerOptions {
+                schema: Some("message log {".to_owned()),
+                ..Default::default()
+            },
+        };
+        assert!(config.build().is_err());
+    }
+}
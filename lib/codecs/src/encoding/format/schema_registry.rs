@@ -0,0 +1,210 @@
+use crate::common::schema_registry::{
+    message_indexes, put_message_indexes, put_schema_id, SchemaRegistryClient,
+    SchemaRegistryConfig, SchemaType,
+};
+use crate::encoding::BuildError;
+use bytes::BytesMut;
+use tokio_util::codec::Encoder;
+use vector_core::{config::DataType, event::Event, schema};
+
+use super::{AvroSerializer, ProtobufSerializer};
+
+/// Config used to build a `SchemaRegistrySerializer`.
+#[crate::configurable_component]
+#[derive(Debug, Clone)]
+pub struct SchemaRegistrySerializerConfig {
+    /// Options for the schema registry serializer.
+    pub schema_registry: SchemaRegistrySerializerOptions,
+}
+
+impl SchemaRegistrySerializerConfig {
+    /// Build the `SchemaRegistrySerializer` from this configuration.
+    ///
+    /// This resolves the schema against the registry.
+    pub fn build(&self) -> Result<SchemaRegistrySerializer, BuildError> {
+        if let Some(schema) = &self.schema_registry.avro_schema {
+            apache_avro::Schema::parse_str(schema)
+                .map_err(|error| format!("Failed building Avro serializer: {}", error))?;
+        }
+        let client = SchemaRegistryClient::new(&self.schema_registry.registry)?;
+        let serializer =
+            SchemaRegistrySerializer::new(&client, &self.schema_registry).map_err(|error| {
+                format!(
+                    "Failed resolving schema for subject {:?}: {}",
+                    self.schema_registry.subject, error
+                )
+            })?;
+        Ok(serializer)
+    }
+
+    /// The data type of events that are accepted by `SchemaRegistrySerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Schema registry serializer options.
+#[crate::configurable_component]
+#[derive(Debug, Clone)]
+pub struct SchemaRegistrySerializerOptions {
+    /// Schema registry connection options.
+    #[serde(flatten)]
+    #[configurable(derived)]
+    pub registry: SchemaRegistryConfig,
+
+    /// The subject the schema events are encoded with is registered under.
+    ///
+    /// With the default `TopicNameStrategy`, this is the topic name followed by `-value`.
+    #[configurable(metadata(docs::examples = "logs-value"))]
+    pub subject: String,
+
+    /// An Avro schema to encode events with.
+    ///
+    /// The schema's ID is looked up under `subject`, and the schema is registered there first
+    /// if `auto_register` is enabled. If unset, events are encoded with the latest version of
+    /// the subject's schema, which may be either Avro or Protobuf.
+    #[configurable(metadata(
+        docs::examples = r#"{ "type": "record", "name": "log", "fields": [{ "name": "message", "type": "string" }] }"#
+    ))]
+    #[configurable(metadata(docs::human_name = "Avro Schema JSON"))]
+    pub avro_schema: Option<String>,
+
+    /// Whether to register `avro_schema` under `subject` if it isn't already registered there.
+    #[serde(default)]
+    pub auto_register: bool,
+
+    /// The name of the Protobuf message type to encode events as.
+    ///
+    /// Only used when the subject's schema is a Protobuf schema. Defaults to the first message
+    /// type defined in the schema.
+    #[configurable(metadata(docs::examples = "package.Message"))]
+    pub message_type: Option<String>,
+}
+
+/// The serializer for a schema resolved from the registry, along with its ID.
+#[derive(Debug, Clone)]
+enum ResolvedSchema {
+    Avro {
+        id: u32,
+        serializer: AvroSerializer,
+    },
+    Protobuf {
+        id: u32,
+        indexes: Vec<i32>,
+        serializer: ProtobufSerializer,
+    },
+}
+
+/// Serializer that converts an `Event` to an Avro or Protobuf message in the schema registry wire
+/// format, using a schema registered under a subject.
+///
+/// The schema is resolved against the registry when the serializer is created, and reused for
+/// every event.
+#[derive(Debug, Clone)]
+pub struct SchemaRegistrySerializer {
+    resolved: ResolvedSchema,
+}
+
+impl SchemaRegistrySerializer {
+    /// Creates a new `SchemaRegistrySerializer`, resolving the schema to encode events with.
+    pub fn new(
+        client: &SchemaRegistryClient,
+        options: &SchemaRegistrySerializerOptions,
+    ) -> vector_common::Result<Self> {
+        Ok(Self {
+            resolved: Self::resolve(client, options)?,
+        })
+    }
+
+    fn resolve(
+        client: &SchemaRegistryClient,
+        options: &SchemaRegistrySerializerOptions,
+    ) -> vector_common::Result<ResolvedSchema> {
+        let subject = &options.subject;
+
+        if let Some(schema) = &options.avro_schema {
+            let id = if options.auto_register {
+                client.register(subject, schema, SchemaType::Avro)?
+            } else {
+                client.lookup(subject, schema, SchemaType::Avro)?
+            };
+            let schema = apache_avro::Schema::parse_str(schema)?;
+            return Ok(ResolvedSchema::Avro {
+                id,
+                serializer: AvroSerializer::new(schema),
+            });
+        }
+
+        let latest = client.subject_version(subject, "latest")?;
+        let id = latest
+            .id
+            .ok_or_else(|| format!("Schema registry returned no ID for subject {:?}", subject))?;
+        match latest.schema_type {
+            SchemaType::Avro => {
+                if !latest.references.is_empty() {
+                    return Err("Avro schemas with references are not supported".into());
+                }
+                let schema = apache_avro::Schema::parse_str(&latest.schema)?;
+                Ok(ResolvedSchema::Avro {
+                    id,
+                    serializer: AvroSerializer::new(schema),
+                })
+            }
+            SchemaType::Protobuf => {
+                let file = client.protobuf_file(&latest)?;
+                let message = match &options.message_type {
+                    Some(message_type) => file
+                        .parent_pool()
+                        .get_message_by_name(message_type)
+                        .filter(|message| message.parent_file() == file),
+                    None => file.messages().next(),
+                }
+                .ok_or_else(|| {
+                    format!(
+                        "Protobuf message type not found in the schema of subject {:?}",
+                        subject
+                    )
+                })?;
+                Ok(ResolvedSchema::Protobuf {
+                    id,
+                    indexes: message_indexes(&message),
+                    serializer: ProtobufSerializer::new(message),
+                })
+            }
+            SchemaType::Json => Err("JSON schemas are not supported".into()),
+        }
+    }
+}
+
+impl Encoder<Event> for SchemaRegistrySerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let start = buffer.len();
+        let result = match &mut self.resolved {
+            ResolvedSchema::Avro { id, serializer } => {
+                put_schema_id(*id, buffer);
+                serializer.encode(event, buffer)
+            }
+            ResolvedSchema::Protobuf {
+                id,
+                indexes,
+                serializer,
+            } => {
+                put_schema_id(*id, buffer);
+                put_message_indexes(indexes, buffer);
+                serializer.encode(event, buffer)
+            }
+        };
+        if result.is_err() {
+            // Don't leave a dangling schema ID behind.
+            buffer.truncate(start);
+        }
+        result
+    }
+}
@@ -13,7 +13,12 @@ pub use format::{
     JsonSerializerConfig, LogfmtSerializer, LogfmtSerializerConfig, NativeJsonSerializer,
     NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig, ProtobufSerializer,
     ProtobufSerializerConfig, ProtobufSerializerOptions, RawMessageSerializer,
-    RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+    RawMessageSerializerConfig, SchemaRegistrySerializer, SchemaRegistrySerializerConfig,
+    SchemaRegistrySerializerOptions, TextSerializer, TextSerializerConfig,
+};
+#[cfg(feature = "parquet")]
+pub use format::{
+    ParquetCompression, ParquetSerializer, ParquetSerializerConfig, ParquetSerializerOptions,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
@@ -248,6 +253,14 @@ pub enum SerializerConfig {
     /// could lead to the encoding emitting empty strings for the given event.
     RawMessage,
 
+    /// Encodes an event as Avro or Protobuf in the [schema registry wire format][wire_format].
+    ///
+    /// The schema is resolved against a subject in the registry, and its ID prefixes every
+    /// encoded message.
+    ///
+    /// [wire_format]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+    SchemaRegistry(SchemaRegistrySerializerConfig),
+
     /// Plain text encoding.
     ///
     /// This encoding uses the `message` field of a log event. For metrics, it uses an
@@ -313,6 +326,12 @@ impl From<RawMessageSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<SchemaRegistrySerializerConfig> for SerializerConfig {
+    fn from(config: SchemaRegistrySerializerConfig) -> Self {
+        Self::SchemaRegistry(config)
+    }
+}
+
 impl From<TextSerializerConfig> for SerializerConfig {
     fn from(config: TextSerializerConfig) -> Self {
         Self::Text(config)
@@ -338,6 +357,9 @@ impl SerializerConfig {
             SerializerConfig::RawMessage => {
                 Ok(Serializer::RawMessage(RawMessageSerializerConfig.build()))
             }
+            SerializerConfig::SchemaRegistry(config) => {
+                Ok(Serializer::SchemaRegistry(config.build()?))
+            }
             SerializerConfig::Text(config) => Ok(Serializer::Text(config.build())),
         }
     }
@@ -358,7 +380,8 @@ impl SerializerConfig {
             // [1]: https://avro.apache.org/docs/1.11.1/specification/_print/#message-framing
             SerializerConfig::Avro { .. }
             | SerializerConfig::Native
-            | SerializerConfig::Protobuf(_) => {
+            | SerializerConfig::Protobuf(_)
+            | SerializerConfig::SchemaRegistry(_) => {
                 FramingConfig::LengthDelimited(LengthDelimitedEncoderConfig::default())
             }
             SerializerConfig::Csv(_)
@@ -385,6 +408,7 @@ impl SerializerConfig {
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.input_type(),
             SerializerConfig::Protobuf(config) => config.input_type(),
             SerializerConfig::RawMessage => RawMessageSerializerConfig.input_type(),
+            SerializerConfig::SchemaRegistry(config) => config.input_type(),
             SerializerConfig::Text(config) => config.input_type(),
         }
     }
@@ -403,6 +427,7 @@ impl SerializerConfig {
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
             SerializerConfig::Protobuf(config) => config.schema_requirement(),
             SerializerConfig::RawMessage => RawMessageSerializerConfig.schema_requirement(),
+            SerializerConfig::SchemaRegistry(config) => config.schema_requirement(),
             SerializerConfig::Text(config) => config.schema_requirement(),
         }
     }
@@ -429,6 +454,8 @@ pub enum Serializer {
     Protobuf(ProtobufSerializer),
     /// Uses a `RawMessageSerializer` for serialization.
     RawMessage(RawMessageSerializer),
+    /// Uses a `SchemaRegistrySerializer` for serialization.
+    SchemaRegistry(SchemaRegistrySerializer),
     /// Uses a `TextSerializer` for serialization.
     Text(TextSerializer),
 }
@@ -444,7 +471,8 @@ impl Serializer {
             | Serializer::Text(_)
             | Serializer::Native(_)
             | Serializer::Protobuf(_)
-            | Serializer::RawMessage(_) => false,
+            | Serializer::RawMessage(_)
+            | Serializer::SchemaRegistry(_) => false,
         }
     }
 
@@ -465,7 +493,8 @@ impl Serializer {
             | Serializer::Text(_)
             | Serializer::Native(_)
             | Serializer::Protobuf(_)
-            | Serializer::RawMessage(_) => {
+            | Serializer::RawMessage(_)
+            | Serializer::SchemaRegistry(_) => {
                 panic!("Serializer does not support JSON")
             }
         }
@@ -526,6 +555,12 @@ impl From<RawMessageSerializer> for Serializer {
     }
 }
 
+impl From<SchemaRegistrySerializer> for Serializer {
+    fn from(serializer: SchemaRegistrySerializer) -> Self {
+        Self::SchemaRegistry(serializer)
+    }
+}
+
 impl From<TextSerializer> for Serializer {
     fn from(serializer: TextSerializer) -> Self {
         Self::Text(serializer)
@@ -546,7 +581,102 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
             Serializer::NativeJson(serializer) => serializer.encode(event, buffer),
             Serializer::Protobuf(serializer) => serializer.encode(event, buffer),
             Serializer::RawMessage(serializer) => serializer.encode(event, buffer),
+            Serializer::SchemaRegistry(serializer) => serializer.encode(event, buffer),
             Serializer::Text(serializer) => serializer.encode(event, buffer),
         }
     }
 }
+
+/// Encodes each batch of events as a whole, rather than encoding events one at a time.
+///
+/// When set, this replaces the codec and framing of `encoding`, though the `encoding` options that
+/// transform events, such as `only_fields`, still apply. Batch codecs compress their output
+/// themselves, so `compression` is not applied on top of them, and files are named with the
+/// extension of the codec, such as `parquet`, unless one is configured.
+#[cfg(feature = "parquet")]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "codec", rename_all = "snake_case")]
+#[configurable(metadata(
+    docs::enum_tag_description = "The codec to use for encoding batches of events."
+))]
+pub enum BatchSerializerConfig {
+    /// Encodes each batch of events as an [Apache Parquet][apache_parquet] file.
+    ///
+    /// [apache_parquet]: https://parquet.apache.org/
+    Parquet(ParquetSerializerConfig),
+}
+
+#[cfg(feature = "parquet")]
+impl From<ParquetSerializerConfig> for BatchSerializerConfig {
+    fn from(config: ParquetSerializerConfig) -> Self {
+        Self::Parquet(config)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl BatchSerializerConfig {
+    /// Build the `BatchSerializer` from this configuration.
+    pub fn build(&self) -> Result<BatchSerializer, BuildError> {
+        match self {
+            BatchSerializerConfig::Parquet(config) => Ok(BatchSerializer::Parquet(config.build()?)),
+        }
+    }
+
+    /// The data type of events that are accepted by this `BatchSerializer`.
+    pub fn input_type(&self) -> DataType {
+        match self {
+            BatchSerializerConfig::Parquet(config) => config.input_type(),
+        }
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        match self {
+            BatchSerializerConfig::Parquet(config) => config.schema_requirement(),
+        }
+    }
+}
+
+/// Serialize whole batches of structured events as bytes.
+#[cfg(feature = "parquet")]
+#[derive(Debug, Clone)]
+pub enum BatchSerializer {
+    /// Uses a `ParquetSerializer` for serialization.
+    Parquet(ParquetSerializer),
+}
+
+#[cfg(feature = "parquet")]
+impl BatchSerializer {
+    /// Encodes the batch of events into the buffer.
+    pub fn encode(
+        &self,
+        events: Vec<Event>,
+        buffer: &mut BytesMut,
+    ) -> Result<(), vector_common::Error> {
+        match self {
+            BatchSerializer::Parquet(serializer) => serializer.encode(events, buffer),
+        }
+    }
+
+    /// Get the HTTP content type.
+    pub const fn content_type(&self) -> &'static str {
+        match self {
+            BatchSerializer::Parquet(_) => "application/vnd.apache.parquet",
+        }
+    }
+
+    /// Get the extension of the files holding the encoded batches.
+    pub const fn file_extension(&self) -> &'static str {
+        match self {
+            BatchSerializer::Parquet(_) => "parquet",
+        }
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<ParquetSerializer> for BatchSerializer {
+    fn from(serializer: ParquetSerializer) -> Self {
+        Self::Parquet(serializer)
+    }
+}
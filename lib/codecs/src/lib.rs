@@ -9,6 +9,7 @@ pub mod decoding;
 pub mod encoding;
 pub mod gelf;
 
+pub use common::schema_registry;
 pub use decoding::{
     BytesDecoder, BytesDecoderConfig, BytesDeserializer, BytesDeserializerConfig,
     CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, GelfDeserializer,
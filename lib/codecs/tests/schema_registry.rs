@@ -0,0 +1,341 @@
+//! Tests for the behaviour of the schema registry serializer and deserializer (together), against a
+//! stand-in registry.
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use bytes::{Bytes, BytesMut};
+use prost::Message;
+use prost_reflect::prost_types::FileDescriptorSet;
+use serde_json::json;
+use similar_asserts::assert_eq;
+use tokio_util::codec::Encoder;
+use vector_core::{
+    config::LogNamespace,
+    event::{Event, LogEvent, Value},
+};
+use vrl::btreemap;
+
+use codecs::decoding::format::Deserializer;
+use codecs::decoding::{
+    ProtobufDeserializerConfig, ProtobufDeserializerOptions, SchemaRegistryDeserializer,
+    SchemaRegistryDeserializerConfig,
+};
+use codecs::encoding::{
+    SchemaRegistrySerializer, SchemaRegistrySerializerConfig, SchemaRegistrySerializerOptions,
+};
+use codecs::schema_registry::{SchemaRegistryAuth, SchemaRegistryConfig};
+
+const USER: &str = "vector";
+const PASSWORD: &str = "hunter2";
+
+const AVRO_SCHEMA: &str = r#"{
+    "type": "record",
+    "name": "Log",
+    "fields": [
+        { "name": "message", "type": "string" },
+        { "name": "count", "type": "long" }
+    ]
+}"#;
+
+/// A stand-in for a schema registry that serves canned responses and records the requests it
+/// receives.
+struct Registry {
+    url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl Registry {
+    /// Serves the given responses, keyed by request method and path.
+    fn start(responses: HashMap<String, serde_json::Value>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let expected_authorization = format!(
+            "Basic {}",
+            BASE64_STANDARD.encode(format!("{}:{}", USER, PASSWORD))
+        );
+
+        let recorded = Arc::clone(&requests);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut parts = request_line.split_whitespace();
+                let request = format!("{} {}", parts.next().unwrap(), parts.next().unwrap());
+
+                let mut content_length = 0;
+                let mut authorized = false;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let header = header.trim_end();
+                    if header.is_empty() {
+                        break;
+                    }
+                    let (name, value) = header.split_once(':').unwrap();
+                    match name.to_ascii_lowercase().as_str() {
+                        "content-length" => content_length = value.trim().parse().unwrap(),
+                        "authorization" => authorized = value.trim() == expected_authorization,
+                        _ => {}
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let (status, response) = if !authorized {
+                    (
+                        "401 Unauthorized",
+                        json!({ "error_code": 401, "message": "Unauthorized" }),
+                    )
+                } else if let Some(response) = responses.get(&request) {
+                    ("200 OK", response.clone())
+                } else {
+                    (
+                        "404 Not Found",
+                        json!({ "error_code": 40403, "message": "Schema not found" }),
+                    )
+                };
+                recorded.lock().unwrap().push(request);
+
+                let response = response.to_string();
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Type: application/vnd.schemaregistry.v1+json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    fn config(&self, password: &str) -> SchemaRegistryConfig {
+        SchemaRegistryConfig {
+            url: self.url.clone(),
+            auth: Some(SchemaRegistryAuth {
+                user: USER.to_owned(),
+                password: password.to_owned().into(),
+            }),
+            tls: None,
+        }
+    }
+
+    fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn build_serializer(
+    registry: &Registry,
+    subject: &str,
+    avro_schema: Option<&str>,
+    message_type: Option<&str>,
+) -> SchemaRegistrySerializer {
+    SchemaRegistrySerializerConfig {
+        schema_registry: SchemaRegistrySerializerOptions {
+            registry: registry.config(PASSWORD),
+            subject: subject.to_owned(),
+            avro_schema: avro_schema.map(ToOwned::to_owned),
+            auto_register: true,
+            message_type: message_type.map(ToOwned::to_owned),
+        },
+    }
+    .build()
+    .unwrap()
+}
+
+fn build_deserializer(registry: &Registry, password: &str) -> SchemaRegistryDeserializer {
+    SchemaRegistryDeserializerConfig {
+        schema_registry: registry.config(password),
+    }
+    .build()
+    .unwrap()
+}
+
+fn protobuf_data_dir() -> PathBuf {
+    PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap()).join("tests/data/protobuf")
+}
+
+/// The `test_protobuf.proto` file descriptor, base64-encoded as the registry serves it.
+fn serialized_protobuf_schema() -> String {
+    let set = std::fs::read(protobuf_data_dir().join("protos/test_protobuf.desc")).unwrap();
+    let set = FileDescriptorSet::decode(set.as_slice()).unwrap();
+    let file = set
+        .file
+        .into_iter()
+        .find(|file| file.name() == "test_protobuf.proto")
+        .unwrap();
+    BASE64_STANDARD.encode(file.encode_to_vec())
+}
+
+#[test]
+fn avro_roundtrip() {
+    let registry = Registry::start(HashMap::from([
+        (
+            "POST /subjects/logs-value/versions".to_owned(),
+            json!({ "id": 7 }),
+        ),
+        (
+            "GET /schemas/ids/7".to_owned(),
+            json!({ "schema": AVRO_SCHEMA }),
+        ),
+    ]));
+    let mut serializer = build_serializer(&registry, "logs-value", Some(AVRO_SCHEMA), None);
+    let deserializer = build_deserializer(&registry, PASSWORD);
+
+    for count in 0..3 {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "message" => Value::from("hello"),
+            "count" => Value::Integer(count),
+        }));
+
+        let mut buffer = BytesMut::new();
+        serializer.encode(event.clone(), &mut buffer).unwrap();
+        assert_eq!(&buffer[..5], &[0u8, 0, 0, 0, 7][..]);
+
+        let events = deserializer
+            .parse(buffer.freeze(), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log().value(), event.as_log().value());
+    }
+
+    // The schema is only registered and fetched once.
+    assert_eq!(
+        registry.requests(),
+        vec![
+            "POST /subjects/logs-value/versions".to_owned(),
+            "GET /schemas/ids/7".to_owned(),
+        ]
+    );
+}
+
+#[test]
+fn protobuf_roundtrip() {
+    let schema = serialized_protobuf_schema();
+    let registry = Registry::start(HashMap::from([
+        (
+            "GET /subjects/people-value/versions/latest".to_owned(),
+            json!({ "subject": "people-value", "version": 1, "id": 3, "schemaType": "PROTOBUF", "schema": "syntax = \"proto2\";" }),
+        ),
+        (
+            "GET /subjects/people-value/versions/latest?format=serialized".to_owned(),
+            json!({ "subject": "people-value", "version": 1, "id": 3, "schemaType": "PROTOBUF", "schema": schema }),
+        ),
+        (
+            "GET /schemas/ids/3".to_owned(),
+            json!({ "schemaType": "PROTOBUF", "schema": "syntax = \"proto2\";" }),
+        ),
+        (
+            "GET /schemas/ids/3?format=serialized".to_owned(),
+            json!({ "schemaType": "PROTOBUF", "schema": schema }),
+        ),
+    ]));
+    let mut serializer = build_serializer(
+        &registry,
+        "people-value",
+        None,
+        Some("test_protobuf.Person"),
+    );
+    let deserializer = build_deserializer(&registry, PASSWORD);
+
+    let person = ProtobufDeserializerConfig {
+        protobuf: ProtobufDeserializerOptions {
+            desc_file: protobuf_data_dir().join("protos/test_protobuf.desc"),
+            message_type: "test_protobuf.Person".to_owned(),
+        },
+    }
+    .build()
+    .unwrap()
+    .parse(
+        Bytes::from(std::fs::read(protobuf_data_dir().join("pbs/person_someone.pb")).unwrap()),
+        LogNamespace::Vector,
+    )
+    .unwrap()
+    .remove(0);
+
+    let mut buffer = BytesMut::new();
+    serializer.encode(person.clone(), &mut buffer).unwrap();
+    // `Person` is the first message in its file, so its message indexes are a single zero.
+    assert_eq!(&buffer[..6], &[0u8, 0, 0, 0, 3, 0][..]);
+
+    let events = deserializer
+        .parse(buffer.freeze(), LogNamespace::Vector)
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].as_log().value(), person.as_log().value());
+}
+
+#[test]
+fn rejected_credentials() {
+    let registry = Registry::start(HashMap::from([(
+        "GET /schemas/ids/7".to_owned(),
+        json!({ "schema": AVRO_SCHEMA }),
+    )]));
+    let deserializer = build_deserializer(&registry, "wrong");
+
+    let error = deserializer
+        .parse(
+            Bytes::from_static(&[0, 0, 0, 0, 7, 2]),
+            LogNamespace::Vector,
+        )
+        .unwrap_err();
+    assert!(error.to_string().contains("401"), "{}", error);
+}
+
+#[test]
+fn unknown_schema_id() {
+    let registry = Registry::start(HashMap::new());
+    let deserializer = build_deserializer(&registry, PASSWORD);
+
+    for _ in 0..2 {
+        let error = deserializer
+            .clone()
+            .parse(
+                Bytes::from_static(&[0, 0, 0, 0, 9, 2]),
+                LogNamespace::Vector,
+            )
+            .unwrap_err();
+        assert!(error.to_string().contains("Schema not found"), "{}", error);
+    }
+
+    // The failure is cached, and shared between clones.
+    assert_eq!(registry.requests(), vec!["GET /schemas/ids/9".to_owned()]);
+}
+
+#[test]
+fn unknown_subject_fails_build() {
+    let registry = Registry::start(HashMap::new());
+
+    let error = SchemaRegistrySerializerConfig {
+        schema_registry: SchemaRegistrySerializerOptions {
+            registry: registry.config(PASSWORD),
+            subject: "team/logs-value".to_owned(),
+            avro_schema: None,
+            auto_register: false,
+            message_type: None,
+        },
+    }
+    .build()
+    .unwrap_err();
+    assert!(error.to_string().contains("Schema not found"), "{}", error);
+
+    // The subject is encoded in the request path.
+    assert_eq!(
+        registry.requests(),
+        vec!["GET /subjects/team%2Flogs-value/versions/latest".to_owned()]
+    );
+}
@@ -0,0 +1,62 @@
+//! Config queries and mutations, for previewing and triggering reloads of a running Vector
+//! instance.
+
+use graphql_client::GraphQLQuery;
+
+/// ConfigDiffQuery compares a configuration against the running configuration, without
+/// applying it. The result is a JSON-encoded report.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/queries/config_diff.graphql",
+    response_derives = "Debug"
+)]
+pub struct ConfigDiffQuery;
+
+/// Extension methods for config queries.
+pub trait ConfigQueryExt {
+    /// Executes a config diff query against a JSON-encoded `config`.
+    async fn config_diff_query(&self, config: String) -> crate::QueryResult<ConfigDiffQuery>;
+}
+
+impl ConfigQueryExt for crate::Client {
+    /// Executes a config diff query against a JSON-encoded `config`.
+    async fn config_diff_query(&self, config: String) -> crate::QueryResult<ConfigDiffQuery> {
+        self.query::<ConfigDiffQuery>(&ConfigDiffQuery::build_query(
+            config_diff_query::Variables { config },
+        ))
+        .await
+    }
+}
+
+/// ReloadComponentsMutation asks a running Vector instance to reload its configuration from disk,
+/// rebuilding the given components even if their configuration didn't change.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/mutations/reload_components.graphql",
+    response_derives = "Debug"
+)]
+pub struct ReloadComponentsMutation;
+
+/// Extension methods for config mutations.
+pub trait ConfigMutationExt {
+    /// Executes a reload components mutation.
+    async fn reload_components_mutation(
+        &self,
+        component_ids: Vec<String>,
+    ) -> crate::QueryResult<ReloadComponentsMutation>;
+}
+
+impl ConfigMutationExt for crate::Client {
+    /// Executes a reload components mutation.
+    async fn reload_components_mutation(
+        &self,
+        component_ids: Vec<String>,
+    ) -> crate::QueryResult<ReloadComponentsMutation> {
+        self.query::<ReloadComponentsMutation>(&ReloadComponentsMutation::build_query(
+            reload_components_mutation::Variables { component_ids },
+        ))
+        .await
+    }
+}
@@ -147,6 +147,36 @@ impl component_sent_events_throughputs_subscription::ComponentSentEventsThroughp
 )]
 pub struct ComponentErrorsTotalsSubscription;
 
+/// ComponentErrorsByTypeSubscription contains metrics on the number of errors
+/// (metrics ending in `_errors_total`), split by `error_type`, against specific components.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_errors_by_type.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentErrorsByTypeSubscription;
+
+/// ComponentBufferUsagesSubscription contains metrics on the number of events and bytes
+/// currently held in buffers, against specific components.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_buffer_usages.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentBufferUsagesSubscription;
+
+/// ComponentRequestLatenciesSubscription contains metrics on the mean HTTP request
+/// round-trip time between `interval` samples, against specific components.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_request_latencies.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentRequestLatenciesSubscription;
+
 /// Extension methods for metrics subscriptions
 pub trait MetricsSubscriptionExt {
     /// Executes an uptime metrics subscription.
@@ -210,6 +240,24 @@ pub trait MetricsSubscriptionExt {
         &self,
         interval: i64,
     ) -> crate::BoxedSubscription<ComponentErrorsTotalsSubscription>;
+
+    /// Executes a component errors by error type subscription.
+    fn component_errors_by_type_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentErrorsByTypeSubscription>;
+
+    /// Executes a component buffer usages subscription.
+    fn component_buffer_usages_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentBufferUsagesSubscription>;
+
+    /// Executes a component request latencies subscription.
+    fn component_request_latencies_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentRequestLatenciesSubscription>;
 }
 
 impl MetricsSubscriptionExt for crate::SubscriptionClient {
@@ -338,4 +386,40 @@ impl MetricsSubscriptionExt for crate::SubscriptionClient {
 
         self.start::<ComponentErrorsTotalsSubscription>(&request_body)
     }
+
+    /// Executes a component errors by error type subscription.
+    fn component_errors_by_type_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentErrorsByTypeSubscription> {
+        let request_body = ComponentErrorsByTypeSubscription::build_query(
+            component_errors_by_type_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentErrorsByTypeSubscription>(&request_body)
+    }
+
+    /// Executes a component buffer usages subscription.
+    fn component_buffer_usages_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentBufferUsagesSubscription> {
+        let request_body = ComponentBufferUsagesSubscription::build_query(
+            component_buffer_usages_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentBufferUsagesSubscription>(&request_body)
+    }
+
+    /// Executes a component request latencies subscription.
+    fn component_request_latencies_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentRequestLatenciesSubscription> {
+        let request_body = ComponentRequestLatenciesSubscription::build_query(
+            component_request_latencies_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentRequestLatenciesSubscription>(&request_body)
+    }
 }
@@ -1,12 +1,14 @@
 //! Queries, subscriptions, and extension methods for executing them
 
 mod components;
+mod config;
 mod health;
 mod meta;
 mod metrics;
 mod tap;
 
 pub use components::*;
+pub use config::*;
 pub use health::*;
 pub use metrics::*;
 pub use tap::*;
@@ -272,6 +272,10 @@ where
             );
             BufferType::DiskV2 {
                 max_size: max_size_bytes,
+                max_events: None,
+                max_age_secs: None,
+                encryption: None,
+                shared_group: None,
                 when_full,
             }
         }
@@ -11,7 +11,10 @@ use tracing::{Instrument, Span};
 use vector_common::internal_event::emit;
 
 use crate::{
-    internal_events::{BufferCreated, BufferEventsDropped, BufferEventsReceived, BufferEventsSent},
+    internal_events::{
+        BufferCreated, BufferEventsDropped, BufferEventsOverflowed, BufferEventsReceived,
+        BufferEventsSent,
+    },
     spawn_named,
 };
 
@@ -127,6 +130,21 @@ impl BufferUsageHandle {
         self.state.sent.increment(count, byte_size);
     }
 
+    /// Increments the number of events (and their total size) expired by this buffer component.
+    ///
+    /// This represents the events that were dropped, instead of being read out of the buffer, because they were older
+    /// than the maximum age of the buffer.
+    pub fn increment_expired_event_count_and_byte_size(&self, count: u64, byte_size: u64) {
+        self.state.expired.increment(count, byte_size);
+    }
+
+    /// Increments the number of events (and their total size) overflowed by this buffer component.
+    ///
+    /// This represents the events that were sent to the next buffer stage because this one was full.
+    pub fn increment_overflowed_event_count_and_byte_size(&self, count: u64, byte_size: u64) {
+        self.state.overflowed.increment(count, byte_size);
+    }
+
     /// Increment the number of dropped events (and their total size) for this buffer component.
     pub fn increment_dropped_event_count_and_byte_size(
         &self,
@@ -140,6 +158,14 @@ impl BufferUsageHandle {
             self.state.dropped.increment(count, byte_size);
         }
     }
+
+    /// Increments the number of events (and their total size) dropped by this buffer component to make room for newer
+    /// events.
+    ///
+    /// This represents the events that were evicted from the buffer when using the "drop oldest" mode.
+    pub fn increment_dropped_oldest_event_count_and_byte_size(&self, count: u64, byte_size: u64) {
+        self.state.dropped_oldest.increment(count, byte_size);
+    }
 }
 
 #[derive(Debug, Default)]
@@ -149,6 +175,9 @@ struct BufferUsageData {
     sent: CategoryMetrics,
     dropped: CategoryMetrics,
     dropped_intentional: CategoryMetrics,
+    dropped_oldest: CategoryMetrics,
+    expired: CategoryMetrics,
+    overflowed: CategoryMetrics,
     max_size: CategoryMetrics,
 }
 
@@ -165,6 +194,9 @@ impl BufferUsageData {
         let sent = self.sent.get();
         let dropped = self.dropped.get();
         let dropped_intentional = self.dropped_intentional.get();
+        let dropped_oldest = self.dropped_oldest.get();
+        let expired = self.expired.get();
+        let overflowed = self.overflowed.get();
         let max_size = self.max_size.get();
 
         BufferUsageSnapshot {
@@ -176,6 +208,12 @@ impl BufferUsageData {
             dropped_event_byte_size: dropped.event_byte_size,
             dropped_event_count_intentional: dropped_intentional.event_count,
             dropped_event_byte_size_intentional: dropped_intentional.event_byte_size,
+            dropped_oldest_event_count: dropped_oldest.event_count,
+            dropped_oldest_byte_size: dropped_oldest.event_byte_size,
+            expired_event_count: expired.event_count,
+            expired_byte_size: expired.event_byte_size,
+            overflowed_event_count: overflowed.event_count,
+            overflowed_byte_size: overflowed.event_byte_size,
             max_size_bytes: max_size.event_byte_size,
             max_size_events: max_size
                 .event_count
@@ -196,6 +234,12 @@ pub struct BufferUsageSnapshot {
     pub dropped_event_byte_size: u64,
     pub dropped_event_count_intentional: u64,
     pub dropped_event_byte_size_intentional: u64,
+    pub dropped_oldest_event_count: u64,
+    pub dropped_oldest_byte_size: u64,
+    pub expired_event_count: u64,
+    pub expired_byte_size: u64,
+    pub overflowed_event_count: u64,
+    pub overflowed_byte_size: u64,
     pub max_size_bytes: u64,
     pub max_size_events: usize,
 }
@@ -300,6 +344,37 @@ impl BufferUsage {
                             byte_size: dropped_intentional.event_byte_size,
                         });
                     }
+
+                    let dropped_oldest = stage.dropped_oldest.consume();
+                    if dropped_oldest.has_updates() {
+                        emit(BufferEventsDropped {
+                            idx: stage.idx,
+                            intentional: true,
+                            reason: "drop_oldest",
+                            count: dropped_oldest.event_count,
+                            byte_size: dropped_oldest.event_byte_size,
+                        });
+                    }
+
+                    let expired = stage.expired.consume();
+                    if expired.has_updates() {
+                        emit(BufferEventsDropped {
+                            idx: stage.idx,
+                            intentional: true,
+                            reason: "expired",
+                            count: expired.event_count,
+                            byte_size: expired.event_byte_size,
+                        });
+                    }
+
+                    let overflowed = stage.overflowed.consume();
+                    if overflowed.has_updates() {
+                        emit(BufferEventsOverflowed {
+                            idx: stage.idx,
+                            count: overflowed.event_count,
+                            byte_size: overflowed.event_byte_size,
+                        });
+                    }
                 }
             }
         };
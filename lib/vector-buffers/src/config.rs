@@ -3,12 +3,15 @@ use std::{
     num::{NonZeroU64, NonZeroUsize},
     path::{Path, PathBuf},
     slice,
+    time::Duration,
 };
 
 use serde::{de, Deserialize, Deserializer, Serialize};
 use snafu::{ResultExt, Snafu};
 use tracing::Span;
-use vector_common::{config::ComponentKey, finalization::Finalizable};
+use vector_common::{
+    config::ComponentKey, finalization::Finalizable, sensitive_string::SensitiveString,
+};
 use vector_config::configurable_component;
 
 use crate::{
@@ -16,7 +19,10 @@ use crate::{
         builder::{TopologyBuilder, TopologyError},
         channel::{BufferReceiver, BufferSender},
     },
-    variants::{DiskV2Buffer, MemoryBuffer},
+    variants::{
+        disk_v2::{EncryptionKey, EncryptionKeyError, RecordCipher},
+        DiskV2Buffer, MemoryBuffer,
+    },
     Bufferable, WhenFull,
 };
 
@@ -28,6 +34,10 @@ pub enum BufferBuildError {
     FailedToBuildTopology { source: TopologyError },
     #[snafu(display("`max_events` must be greater than zero"))]
     InvalidMaxEvents,
+    #[snafu(display("invalid disk buffer encryption key: {}", source))]
+    InvalidEncryptionKey { source: EncryptionKeyError },
+    #[snafu(display("a shared disk buffer must be the only stage of the buffer"))]
+    SharedRequiresSingleStage,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -38,7 +48,15 @@ enum BufferTypeKind {
     DiskV2,
 }
 
-const ALL_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
+const ALL_FIELDS: [&str; 7] = [
+    "type",
+    "max_events",
+    "max_size",
+    "max_age_secs",
+    "encryption",
+    "shared_group",
+    "when_full",
+];
 
 struct BufferTypeVisitor;
 
@@ -50,6 +68,9 @@ impl BufferTypeVisitor {
         let mut kind: Option<BufferTypeKind> = None;
         let mut max_events: Option<NonZeroUsize> = None;
         let mut max_size: Option<NonZeroU64> = None;
+        let mut max_age_secs: Option<NonZeroU64> = None;
+        let mut encryption: Option<DiskBufferEncryption> = None;
+        let mut shared_group: Option<String> = None;
         let mut when_full: Option<WhenFull> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
@@ -71,6 +92,24 @@ impl BufferTypeVisitor {
                     }
                     max_size = Some(map.next_value()?);
                 }
+                "max_age_secs" => {
+                    if max_age_secs.is_some() {
+                        return Err(de::Error::duplicate_field("max_age_secs"));
+                    }
+                    max_age_secs = Some(map.next_value()?);
+                }
+                "encryption" => {
+                    if encryption.is_some() {
+                        return Err(de::Error::duplicate_field("encryption"));
+                    }
+                    encryption = Some(map.next_value()?);
+                }
+                "shared_group" => {
+                    if shared_group.is_some() {
+                        return Err(de::Error::duplicate_field("shared_group"));
+                    }
+                    shared_group = Some(map.next_value()?);
+                }
                 "when_full" => {
                     if when_full.is_some() {
                         return Err(de::Error::duplicate_field("when_full"));
@@ -92,20 +131,39 @@ impl BufferTypeVisitor {
                         &["type", "max_events", "when_full"],
                     ));
                 }
+                if max_age_secs.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "max_age_secs",
+                        &["type", "max_events", "when_full"],
+                    ));
+                }
+                if encryption.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "encryption",
+                        &["type", "max_events", "when_full"],
+                    ));
+                }
+                if shared_group.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "shared_group",
+                        &["type", "max_events", "when_full"],
+                    ));
+                }
                 Ok(BufferType::Memory {
                     max_events: max_events.unwrap_or_else(memory_buffer_default_max_events),
                     when_full,
                 })
             }
             BufferTypeKind::DiskV2 => {
-                if max_events.is_some() {
-                    return Err(de::Error::unknown_field(
-                        "max_events",
-                        &["type", "max_size", "when_full"],
-                    ));
-                }
+                let max_events = max_events
+                    .and_then(|n| u64::try_from(n.get()).ok())
+                    .and_then(NonZeroU64::new);
                 Ok(BufferType::DiskV2 {
                     max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
+                    max_events,
+                    max_age_secs,
+                    encryption,
+                    shared_group,
                     when_full,
                 })
             }
@@ -175,9 +233,42 @@ impl DiskUsage {
     }
 }
 
+/// Encryption at rest for disk buffers.
+///
+/// Records are encrypted with XChaCha20-Poly1305 before being written to disk.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct DiskBufferEncryption {
+    /// The key used to encrypt the buffer, as 64 hexadecimal characters (32 bytes).
+    ///
+    /// This is typically provided as a secret, such as `SECRET[backend.buffer_key]`.
+    #[configurable(metadata(docs::examples = "SECRET[backend.buffer_key]"))]
+    pub key: SensitiveString,
+
+    /// Keys that were previously used to encrypt the buffer.
+    ///
+    /// These keys are only used to decrypt records that were written before the key was rotated,
+    /// and can be removed once those records have been read out of the buffer.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_keys: Vec<SensitiveString>,
+}
+
+impl DiskBufferEncryption {
+    fn to_cipher(&self) -> Result<RecordCipher, EncryptionKeyError> {
+        let current = EncryptionKey::from_hex(self.key.inner())?;
+        let previous = self
+            .previous_keys
+            .iter()
+            .map(|key| EncryptionKey::from_hex(key.inner()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(RecordCipher::new(&current, &previous))
+    }
+}
+
 /// A specific type of buffer stage.
 #[configurable_component(no_deser)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case", tag = "type")]
 #[configurable(metadata(docs::enum_tag_description = "The type of buffer to use."))]
 pub enum BufferType {
@@ -215,6 +306,42 @@ pub enum BufferType {
         )]
         max_size: NonZeroU64,
 
+        /// The maximum number of events allowed in the buffer.
+        ///
+        /// When set, the buffer is considered full once it holds this many events, even if it has
+        /// not yet reached `max_size`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_events: Option<NonZeroU64>,
+
+        /// The maximum age of events in the buffer.
+        ///
+        /// Events that have been in the buffer for longer than this are expired, and dropped
+        /// instead of being sent to the sink. Expiration happens a whole data file at a time, so
+        /// events may be expired up to a tenth of this duration early. Expired data files are
+        /// deleted even while the sink isn't reading from the buffer.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        max_age_secs: Option<NonZeroU64>,
+
+        /// Encrypts the data written to disk.
+        ///
+        /// When not set, data is written to disk unencrypted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[configurable(derived)]
+        encryption: Option<DiskBufferEncryption>,
+
+        /// The name of a disk buffer shared with other sinks.
+        ///
+        /// Sinks with the same shared group write their events to disk once, into a single buffer,
+        /// and each sink reads from that buffer at its own pace. Data is only removed from disk
+        /// once every sink in the group has acknowledged it.
+        ///
+        /// All sinks in a shared group must have the same inputs and the same buffer
+        /// configuration, and the disk buffer must be the only stage of their buffer.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[configurable(metadata(docs::examples = "durable_fanout"))]
+        shared_group: Option<String>,
+
         #[configurable(derived)]
         #[serde(default)]
         when_full: WhenFull,
@@ -249,11 +376,21 @@ impl BufferType {
             None => None,
             Some(global_data_dir) => match self {
                 Self::Memory { .. } => None,
-                Self::DiskV2 { max_size, .. } => {
-                    let data_dir = crate::variants::disk_v2::get_disk_v2_data_dir_path(
-                        &global_data_dir,
-                        id.id(),
-                    );
+                Self::DiskV2 {
+                    max_size,
+                    shared_group,
+                    ..
+                } => {
+                    let data_dir = match shared_group {
+                        Some(group) => crate::variants::disk_v2::get_shared_disk_v2_data_dir_path(
+                            &global_data_dir,
+                            group,
+                        ),
+                        None => crate::variants::disk_v2::get_disk_v2_data_dir_path(
+                            &global_data_dir,
+                            id.id(),
+                        ),
+                    };
 
                     Some(DiskUsage::new(id.clone(), data_dir, *max_size))
                 }
@@ -261,6 +398,52 @@ impl BufferType {
         }
     }
 
+    /// Gets the behavior of this buffer stage when it is full.
+    pub fn when_full(&self) -> WhenFull {
+        match self {
+            Self::Memory { when_full, .. } | Self::DiskV2 { when_full, .. } => *when_full,
+        }
+    }
+
+    /// Gets the name of the shared disk buffer this buffer stage reads from, if any.
+    pub fn shared_group(&self) -> Option<&str> {
+        match self {
+            Self::Memory { .. } => None,
+            Self::DiskV2 { shared_group, .. } => shared_group.as_deref(),
+        }
+    }
+
+    fn to_disk_v2_buffer(
+        &self,
+        data_dir: Option<PathBuf>,
+        id: String,
+    ) -> Result<Option<DiskV2Buffer>, BufferBuildError> {
+        let BufferType::DiskV2 {
+            max_size,
+            max_events,
+            max_age_secs,
+            encryption,
+            shared_group,
+            ..
+        } = self
+        else {
+            return Ok(None);
+        };
+
+        let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
+        let encryption = encryption
+            .as_ref()
+            .map(DiskBufferEncryption::to_cipher)
+            .transpose()
+            .context(InvalidEncryptionKeySnafu)?;
+        let buffer = DiskV2Buffer::new(id, data_dir, *max_size)
+            .with_max_events(*max_events)
+            .with_max_age(max_age_secs.map(|secs| Duration::from_secs(secs.get())))
+            .with_encryption(encryption)
+            .with_shared_group(shared_group.clone());
+        Ok(Some(buffer))
+    }
+
     /// Adds this buffer type as a stage to an existing [`TopologyBuilder`].
     ///
     /// # Errors
@@ -276,19 +459,18 @@ impl BufferType {
     where
         T: Bufferable + Clone + Finalizable,
     {
-        match *self {
+        match self {
             BufferType::Memory {
                 when_full,
                 max_events,
             } => {
-                builder.stage(MemoryBuffer::new(max_events), when_full);
+                builder.stage(MemoryBuffer::new(*max_events), *when_full);
             }
-            BufferType::DiskV2 {
-                when_full,
-                max_size,
-            } => {
-                let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
-                builder.stage(DiskV2Buffer::new(id, data_dir, max_size), when_full);
+            BufferType::DiskV2 { when_full, .. } => {
+                let buffer = self
+                    .to_disk_v2_buffer(data_dir, id)?
+                    .expect("stage should be a disk buffer");
+                builder.stage(buffer, *when_full);
             }
         };
 
@@ -310,10 +492,9 @@ impl BufferType {
 /// functionality to allow chaining buffers together, you'll see "buffer topology" used in internal
 /// documentation to correctly reflect the internal structure.
 ///
-// TODO: We need to limit chained buffers to only allowing a single copy of each buffer type to be
-// defined, otherwise, for example, two instances of the same disk buffer type in a single chained
-// buffer topology would try to both open the same buffer files on disk, which wouldn't work or
-// would go horribly wrong.
+// Chained buffers can only have a single disk buffer stage, as two instances of the disk buffer in a
+// single chained buffer topology would both try to open the same buffer files on disk. This is
+// checked when validating the configuration.
 #[configurable_component]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[serde(untagged)]
@@ -383,12 +564,56 @@ impl BufferConfig {
             .await
             .context(FailedToBuildTopologySnafu)
     }
+
+    /// Gets the name of the shared disk buffer this buffer reads from, if any.
+    pub fn shared_group(&self) -> Option<&str> {
+        self.stages().iter().find_map(BufferType::shared_group)
+    }
+
+    /// Builds the shared disk buffer represented by this configuration, with a reader for each of
+    /// the given readers.
+    ///
+    /// A sender and receiver are returned for each reader, in the same order as `reader_ids`.  As
+    /// the buffer is only written to once, only the sender of the first reader writes to the
+    /// buffer, and the other senders discard every item sent to them.  This lets every reader be
+    /// connected to the same inputs, like any other buffer.
+    ///
+    /// # Errors
+    ///
+    /// If the buffer is not a shared disk buffer, or is configured with more than a single stage,
+    /// an error variant will be thrown.
+    ///
+    /// If the data directory provided is `None`, an error variant will be thrown.
+    #[allow(clippy::needless_pass_by_value)]
+    pub async fn build_shared<T>(
+        &self,
+        data_dir: Option<PathBuf>,
+        group: String,
+        reader_ids: &[String],
+        span: Span,
+    ) -> Result<Vec<(BufferSender<T>, BufferReceiver<T>)>, BufferBuildError>
+    where
+        T: Bufferable + Clone + Finalizable,
+    {
+        let [stage] = self.stages() else {
+            return Err(BufferBuildError::SharedRequiresSingleStage);
+        };
+        let buffer = match stage.to_disk_v2_buffer(data_dir, group.clone())? {
+            Some(buffer) if stage.shared_group().is_some() => buffer,
+            _ => return Err(BufferBuildError::SharedRequiresSingleStage),
+        };
+
+        TopologyBuilder::build_shared(buffer, stage.when_full(), reader_ids, group, span)
+            .await
+            .context(FailedToBuildTopologySnafu)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use std::num::{NonZeroU64, NonZeroUsize};
 
+    use super::DiskBufferEncryption;
     use crate::{BufferConfig, BufferType, WhenFull};
 
     fn check_single_stage(source: &str, expected: BufferType) {
@@ -500,6 +725,17 @@ max_events: 42
             },
         );
 
+        check_single_stage(
+            r"
+          type: memory
+          when_full: drop_oldest
+          ",
+            BufferType::Memory {
+                max_events: NonZeroUsize::new(500).unwrap(),
+                when_full: WhenFull::DropOldest,
+            },
+        );
+
         check_single_stage(
             r"
           type: memory
@@ -518,8 +754,104 @@ max_events: 42
           ",
             BufferType::DiskV2 {
                 max_size: NonZeroU64::new(1024).unwrap(),
+                max_events: None,
+                max_age_secs: None,
+                encryption: None,
+                shared_group: None,
+                when_full: WhenFull::Block,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_disk_with_max_events_and_max_age() {
+        check_single_stage(
+            r"
+          type: disk
+          max_size: 1024
+          max_events: 500
+          max_age_secs: 3600
+          ",
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                max_events: NonZeroU64::new(500),
+                max_age_secs: NonZeroU64::new(3600),
+                encryption: None,
+                shared_group: None,
                 when_full: WhenFull::Block,
             },
         );
     }
+
+    #[test]
+    fn parse_memory_with_max_age_fails() {
+        let source = r"
+          type: memory
+          max_age_secs: 3600
+          ";
+        serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+    }
+
+    #[test]
+    fn parse_disk_with_encryption() {
+        check_single_stage(
+            r"
+          type: disk
+          max_size: 1024
+          encryption:
+            key: new-key
+            previous_keys:
+              - old-key
+          ",
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                max_events: None,
+                max_age_secs: None,
+                encryption: Some(DiskBufferEncryption {
+                    key: String::from("new-key").into(),
+                    previous_keys: vec![String::from("old-key").into()],
+                }),
+                shared_group: None,
+                when_full: WhenFull::Block,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_memory_with_encryption_fails() {
+        let source = r"
+          type: memory
+          encryption:
+            key: new-key
+          ";
+        serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+    }
+
+    #[test]
+    fn parse_disk_with_shared_group() {
+        check_single_stage(
+            r"
+          type: disk
+          max_size: 1024
+          shared_group: durable_fanout
+          ",
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                max_events: None,
+                max_age_secs: None,
+                encryption: None,
+                shared_group: Some(String::from("durable_fanout")),
+                when_full: WhenFull::Block,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_memory_with_shared_group_fails() {
+        let source = r"
+          type: memory
+          shared_group: durable_fanout
+          ";
+        serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+    }
 }
@@ -56,6 +56,24 @@ impl InternalEvent for BufferEventsSent {
     }
 }
 
+pub struct BufferEventsOverflowed {
+    pub idx: usize,
+    pub count: u64,
+    pub byte_size: u64,
+}
+
+impl InternalEvent for BufferEventsOverflowed {
+    fn emit(self) {
+        trace!(
+            message = "Events overflowed to next buffer stage.",
+            count = %self.count,
+            stage = %self.idx,
+        );
+        counter!("buffer_overflowed_events_total", self.count, "stage" => self.idx.to_string());
+        counter!("buffer_overflowed_bytes_total", self.byte_size, "stage" => self.idx.to_string());
+    }
+}
+
 pub struct BufferEventsDropped {
     pub idx: usize,
     pub count: u64,
@@ -88,6 +106,7 @@ impl InternalEvent for BufferEventsDropped {
         counter!(
             "buffer_discarded_events_total", self.count,
             "intentional" => intentional_str,
+            "reason" => self.reason,
         );
         decrement_gauge!("buffer_events", self.count as f64, "stage" => self.idx.to_string());
         decrement_gauge!("buffer_byte_size", self.byte_size as f64, "stage" => self.idx.to_string());
@@ -32,6 +32,7 @@ pub mod test;
 pub mod topology;
 
 pub(crate) mod variants;
+pub use variants::disk_v2::{inspect, EncryptionKey, EncryptionKeyError, RecordCipher};
 
 use std::fmt::Debug;
 
@@ -59,6 +60,17 @@ pub enum WhenFull {
     /// slowdown in the acceptance/consumption of events.
     DropNewest,
 
+    /// Drops the oldest events in the buffer to make room for the new event.
+    ///
+    /// The oldest events will be intentionally dropped. This mode is typically used when the most
+    /// recent data is the most valuable, and it is preferable to lose older events rather than
+    /// cause a slowdown in the acceptance/consumption of events.
+    ///
+    /// For disk buffers, events are dropped a whole data file at a time, starting with the oldest
+    /// data file that the reader is not currently reading from. If there is no such data file, the
+    /// newest event is dropped instead.
+    DropOldest,
+
     /// Overflows to the next stage in the buffer topology.
     ///
     /// If the current buffer stage is full, attempt to send this event to the next buffer stage.
@@ -66,21 +78,24 @@ pub enum WhenFull {
     /// buffer topology must use one of the other handling behaviors. This means that next stage may
     /// potentially be able to buffer the event, but it may also block or drop the event.
     ///
+    /// Once an event has overflowed, subsequent events are also sent to the next stage until it
+    /// has been drained, so that events are still read out of the buffer in the order they were
+    /// written.
+    ///
     /// This mode can only be used when two or more buffer stages are configured.
-    #[configurable(metadata(docs::hidden))]
     Overflow,
 }
 
 #[cfg(test)]
 impl Arbitrary for WhenFull {
     fn arbitrary(g: &mut Gen) -> Self {
-        // TODO: We explicitly avoid generating "overflow" as a possible value because nothing yet
-        // supports handling it, and will be defaulted to using "block" if they encounter
-        // "overflow".  Thus, there's no reason to emit it here... yet.
-        if bool::arbitrary(g) {
-            WhenFull::Block
-        } else {
-            WhenFull::DropNewest
+        // We explicitly avoid generating "overflow" as a possible value because it's only valid
+        // for stages followed by another stage, and single-stage buffers will default to using
+        // "block" if they encounter "overflow".
+        match u8::arbitrary(g) % 3 {
+            0 => WhenFull::Block,
+            1 => WhenFull::DropNewest,
+            _ => WhenFull::DropOldest,
         }
     }
 }
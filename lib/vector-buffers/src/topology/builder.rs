@@ -1,14 +1,19 @@
-use std::{error::Error, num::NonZeroUsize};
+use std::{
+    error::Error,
+    num::NonZeroUsize,
+    sync::{atomic::AtomicUsize, Arc},
+};
 
 use async_trait::async_trait;
 use snafu::{ResultExt, Snafu};
 use tracing::Span;
+use vector_common::finalization::Finalizable;
 
 use super::channel::{ReceiverAdapter, SenderAdapter};
 use crate::{
     buffer_usage_data::{BufferUsage, BufferUsageHandle},
     topology::channel::{BufferReceiver, BufferSender},
-    variants::MemoryBuffer,
+    variants::{DiskV2Buffer, MemoryBuffer},
     Bufferable, WhenFull,
 };
 
@@ -122,9 +127,9 @@ impl<T: Bufferable> TopologyBuilder<T> {
                         return Err(TopologyError::OverflowWhenLast);
                     }
                 }
-                // If there's already an inner stage, then blocking or dropping the newest events
-                // doesn't no sense.  Overflowing is the only valid transition to another stage.
-                WhenFull::Block | WhenFull::DropNewest => {
+                // If there's already an inner stage, then blocking or dropping events doesn't make
+                // sense.  Overflowing is the only valid transition to another stage.
+                WhenFull::Block | WhenFull::DropNewest | WhenFull::DropOldest => {
                     if current_stage.is_some() {
                         return Err(TopologyError::NextStageNotUsed { stage_idx });
                     }
@@ -148,10 +153,19 @@ impl<T: Bufferable> TopologyBuilder<T> {
                     BufferSender::new(sender, stage.when_full),
                     BufferReceiver::new(receiver),
                 ),
-                Some((current_sender, current_receiver)) => (
-                    BufferSender::with_overflow(sender, current_sender),
-                    BufferReceiver::with_overflow(receiver, current_receiver),
-                ),
+                Some((current_sender, current_receiver)) => {
+                    let overflow_pending = Arc::new(AtomicUsize::new(0));
+                    let mut sender = BufferSender::with_overflow(
+                        sender,
+                        current_sender,
+                        Arc::clone(&overflow_pending),
+                    );
+                    sender.with_overflow_instrumentation(usage_handle.clone());
+                    (
+                        sender,
+                        BufferReceiver::with_overflow(receiver, current_receiver, overflow_pending),
+                    )
+                }
             };
 
             sender.with_send_duration_instrumentation(stage_idx, &span);
@@ -174,6 +188,51 @@ impl<T: Bufferable> TopologyBuilder<T> {
     }
 }
 
+impl<T> TopologyBuilder<T>
+where
+    T: Bufferable + Clone + Finalizable,
+{
+    /// Creates a shared disk buffer topology, with a sender and receiver for each of the given
+    /// readers.
+    ///
+    /// The senders all write to the same buffer writer, and every reader reads every item written
+    /// to it, so each item must only be sent through one of the senders.
+    ///
+    /// # Errors
+    ///
+    /// If the buffer is configured to overflow, or there was an error building the buffer itself, an
+    /// error variant will be returned explaining the issue.
+    pub(crate) async fn build_shared(
+        buffer: DiskV2Buffer,
+        when_full: WhenFull,
+        reader_ids: &[String],
+        buffer_id: String,
+        span: Span,
+    ) -> Result<Vec<(BufferSender<T>, BufferReceiver<T>)>, TopologyError> {
+        if matches!(when_full, WhenFull::Overflow) {
+            return Err(TopologyError::OverflowWhenLast);
+        }
+
+        let mut buffer_usage = BufferUsage::from_span(span.clone());
+        let usage_handle = buffer_usage.add_stage(0);
+        let parts = buffer
+            .into_shared_buffer_parts(reader_ids, usage_handle)
+            .await
+            .context(FailedToBuildStageSnafu { stage_idx: 0 })?
+            .into_iter()
+            .map(|(sender, receiver)| {
+                let mut sender = BufferSender::new(sender, when_full);
+                sender.with_send_duration_instrumentation(0, &span);
+                (sender, BufferReceiver::new(receiver))
+            })
+            .collect();
+
+        buffer_usage.install(buffer_id.as_str());
+
+        Ok(parts)
+    }
+}
+
 impl<T: Bufferable> TopologyBuilder<T> {
     /// Creates a memory-only buffer topology.
     ///
@@ -257,15 +316,16 @@ impl<T: Bufferable> Default for TopologyBuilder<T> {
 
 #[cfg(test)]
 mod tests {
-    use std::num::NonZeroUsize;
+    use std::num::{NonZeroU64, NonZeroUsize};
 
     use tracing::Span;
 
     use super::TopologyBuilder;
     use crate::{
+        test::{with_temp_dir, SizedRecord},
         topology::builder::TopologyError,
         topology::test_util::{assert_current_send_capacity, Sample},
-        variants::MemoryBuffer,
+        variants::{DiskV2Buffer, MemoryBuffer},
         WhenFull,
     };
 
@@ -365,4 +425,95 @@ mod tests {
         let (mut sender, _) = result.unwrap();
         assert_current_send_capacity(&mut sender, Some(1), Some(1));
     }
+
+    #[tokio::test]
+    async fn memory_overflow_to_disk_topology() {
+        with_temp_dir(|dir| {
+            let data_dir = dir.to_path_buf();
+
+            async move {
+                let mut builder = TopologyBuilder::<Sample>::default();
+                builder.stage(
+                    MemoryBuffer::new(NonZeroUsize::new(2).unwrap()),
+                    WhenFull::Overflow,
+                );
+                builder.stage(
+                    DiskV2Buffer::new(
+                        String::from("test"),
+                        data_dir,
+                        NonZeroU64::new(268_435_488).unwrap(),
+                    ),
+                    WhenFull::Block,
+                );
+                let (mut sender, mut receiver) = builder
+                    .build(String::from("test"), Span::none())
+                    .await
+                    .expect("topology should build");
+
+                // The first two items fill up the in-memory stage, and the rest spill to disk.
+                for i in 0..10 {
+                    sender.send(Sample(i), None).await.unwrap();
+                }
+                sender.flush().await.unwrap();
+                assert_current_send_capacity(&mut sender, Some(0), None);
+                drop(sender);
+
+                // The items drain back out of both stages in the order they were sent.
+                let mut received = Vec::new();
+                while let Some(item) = receiver.next().await {
+                    received.push(item.0);
+                }
+                assert_eq!(received, (0..10).collect::<Vec<_>>());
+            }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn shared_disk_topology_senders_share_the_writer() {
+        with_temp_dir(|dir| {
+            let data_dir = dir.to_path_buf();
+
+            async move {
+                let buffer = DiskV2Buffer::new(
+                    String::from("test"),
+                    data_dir,
+                    NonZeroU64::new(268_435_488).unwrap(),
+                );
+                let reader_ids = vec![String::from("a"), String::from("b")];
+                let parts = TopologyBuilder::<SizedRecord>::build_shared(
+                    buffer,
+                    WhenFull::Block,
+                    &reader_ids,
+                    String::from("test"),
+                    Span::none(),
+                )
+                .await
+                .expect("topology should build");
+
+                // Items sent through either sender are written once, for both readers, and the
+                // remaining sender keeps writing after the other one is dropped.
+                let (mut receiver_a, mut receiver_b) = {
+                    let mut parts = parts.into_iter();
+                    let (mut sender_a, receiver_a) = parts.next().unwrap();
+                    let (mut sender_b, receiver_b) = parts.next().unwrap();
+                    sender_a.send(SizedRecord::new(1), None).await.unwrap();
+                    sender_a.flush().await.unwrap();
+                    drop(sender_a);
+                    sender_b.send(SizedRecord::new(2), None).await.unwrap();
+                    sender_b.flush().await.unwrap();
+                    (receiver_a, receiver_b)
+                };
+
+                for receiver in [&mut receiver_a, &mut receiver_b] {
+                    let mut received = Vec::new();
+                    while let Some(item) = receiver.next().await {
+                        received.push(item.0);
+                    }
+                    assert_eq!(received, vec![1, 2]);
+                }
+            }
+        })
+        .await;
+    }
 }
@@ -163,6 +163,58 @@ impl<T: InMemoryBufferable> LimitedSender<T> {
 
         Ok(())
     }
+
+    /// Sends an item into the channel, dropping the oldest items in the channel to make room for it.
+    ///
+    /// Items are removed from the front of the channel until enough capacity is available for the
+    /// given item, and the removed items are returned to the caller so that they can be accounted
+    /// for.
+    ///
+    /// # Errors
+    ///
+    /// If the receiver has disconnected (does not exist anymore), then `Err(SendError)` be returned
+    /// with the given `item`.
+    pub async fn send_dropping_oldest(&mut self, item: T) -> Result<Vec<T>, SendError<T>> {
+        let permits_required = self.get_required_permits_for_item(&item);
+        let mut dropped = Vec::new();
+        let permits = loop {
+            match self
+                .inner
+                .limiter
+                .clone()
+                .try_acquire_many_owned(permits_required)
+            {
+                Ok(permits) => break permits,
+                Err(TryAcquireError::Closed) => return Err(SendError(item)),
+                Err(TryAcquireError::NoPermits) => match self.inner.data.pop() {
+                    // Dropping the permits of the oldest item returns them to the limiter.
+                    Some((_permits, oldest)) => dropped.push(oldest),
+                    // The channel is empty but the capacity is still held, which means another
+                    // sender has acquired permits and not yet pushed its item, so just wait.
+                    None => match self
+                        .inner
+                        .limiter
+                        .clone()
+                        .acquire_many_owned(permits_required)
+                        .await
+                    {
+                        Ok(permits) => break permits,
+                        Err(_) => return Err(SendError(item)),
+                    },
+                },
+            }
+        };
+
+        self.inner
+            .data
+            .push((permits, item))
+            .unwrap_or_else(|_| unreachable!("acquired permits but channel reported being full"));
+        self.inner.read_waker.notify_one();
+
+        trace!(dropped = dropped.len(), "Sent item, dropping oldest items.");
+
+        Ok(dropped)
+    }
 }
 
 impl<T> Clone for LimitedSender<T> {
@@ -1,6 +1,10 @@
 use std::{
     mem,
     pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     task::{ready, Context, Poll},
 };
 
@@ -25,6 +29,9 @@ pub enum ReceiverAdapter<T: Bufferable> {
 
     /// The disk v2 buffer.
     DiskV2(disk_v2::BufferReader<T, ProductionFilesystem>),
+
+    /// A reader of a shared disk v2 buffer.
+    SharedDiskV2(disk_v2::SharedBufferReader<T>),
 }
 
 impl<T: Bufferable> From<LimitedReceiver<T>> for ReceiverAdapter<T> {
@@ -39,6 +46,12 @@ impl<T: Bufferable> From<disk_v2::BufferReader<T, ProductionFilesystem>> for Rec
     }
 }
 
+impl<T: Bufferable> From<disk_v2::SharedBufferReader<T>> for ReceiverAdapter<T> {
+    fn from(v: disk_v2::SharedBufferReader<T>) -> Self {
+        Self::SharedDiskV2(v)
+    }
+}
+
 impl<T> ReceiverAdapter<T>
 where
     T: Bufferable,
@@ -60,6 +73,7 @@ where
                     },
                 }
             },
+            ReceiverAdapter::SharedDiskV2(reader) => reader.next().await,
         }
     }
 }
@@ -69,13 +83,17 @@ where
 /// The receiver handles retrieving events from the buffer, regardless of the overall buffer configuration.
 ///
 /// If a buffer was configured to operate in "overflow" mode, then the receiver will be responsible
-/// for querying the overflow buffer as well.  The ordering of events when operating in "overflow"
-/// is undefined, as the receiver will try to manage polling both its own buffer, as well as the
-/// overflow buffer, in order to fairly balance throughput.
+/// for querying the overflow buffer as well.  The base buffer is always drained before the overflow
+/// buffer, which, as [`BufferSender`](super::BufferSender) stops sending to the base buffer while
+/// the overflow buffer holds any items, preserves the order in which events were sent.
+///
+/// Items left in a disk-backed overflow buffer by a previous run of Vector are the exception, as
+/// they are read alongside any new items sent to the base buffer.
 #[derive(Debug)]
 pub struct BufferReceiver<T: Bufferable> {
     base: ReceiverAdapter<T>,
     overflow: Option<Box<BufferReceiver<T>>>,
+    overflow_pending: Arc<AtomicUsize>,
     instrumentation: Option<BufferUsageHandle>,
 }
 
@@ -85,15 +103,24 @@ impl<T: Bufferable> BufferReceiver<T> {
         Self {
             base,
             overflow: None,
+            overflow_pending: Arc::default(),
             instrumentation: None,
         }
     }
 
     /// Creates a new [`BufferReceiver`] wrapping the given channel receiver and overflow receiver.
-    pub fn with_overflow(base: ReceiverAdapter<T>, overflow: BufferReceiver<T>) -> Self {
+    ///
+    /// `overflow_pending` tracks the number of items held by the overflow stage, and must be shared
+    /// with the [`BufferSender`](super::BufferSender) writing to the same buffer stages.
+    pub fn with_overflow(
+        base: ReceiverAdapter<T>,
+        overflow: BufferReceiver<T>,
+        overflow_pending: Arc<AtomicUsize>,
+    ) -> Self {
         Self {
             base,
             overflow: Some(Box::new(overflow)),
+            overflow_pending,
             instrumentation: None,
         }
     }
@@ -103,8 +130,13 @@ impl<T: Bufferable> BufferReceiver<T> {
     /// Note: this resets the internal state of this sender, and so this should not be called except
     /// when initially constructing `BufferSender<T>`.
     #[cfg(test)]
-    pub fn switch_to_overflow(&mut self, overflow: BufferReceiver<T>) {
+    pub fn switch_to_overflow(
+        &mut self,
+        overflow: BufferReceiver<T>,
+        overflow_pending: Arc<AtomicUsize>,
+    ) {
         self.overflow = Some(Box::new(overflow));
+        self.overflow_pending = overflow_pending;
     }
 
     /// Configures this receiver to instrument the items passing through it.
@@ -114,14 +146,9 @@ impl<T: Bufferable> BufferReceiver<T> {
 
     #[async_recursion]
     pub async fn next(&mut self) -> Option<T> {
-        // We want to poll both our base and overflow receivers without waiting for one or the
-        // other to entirely drain before checking the other.  This ensures that we're fairly
-        // servicing both receivers, and avoiding stalls in one or the other.
-        //
-        // This is primarily important in situations where an overflow-triggering event has
-        // occurred, and is over, and items are flowing through the base receiver.  If we waited to
-        // entirely drain the overflow receiver, we might cause another small stall of the pipeline
-        // attached to the base receiver.
+        // We poll both our base and overflow receivers, but always prefer the base receiver.  Any
+        // item in the base receiver was sent before the items in the overflow receiver, since the
+        // sender only goes back to the base receiver once the overflow receiver has been drained.
         let overflow = self.overflow.as_mut().map(Pin::new);
 
         let (item, from_base) = match overflow {
@@ -131,13 +158,22 @@ impl<T: Bufferable> BufferReceiver<T> {
             },
             Some(mut overflow) => {
                 select! {
-                    Some(item) = overflow.next() => (item, false),
+                    biased;
                     Some(item) = self.base.next() => (item, true),
+                    Some(item) = overflow.next() => (item, false),
                     else => return None,
                 }
             }
         };
 
+        if !from_base {
+            // Items left over from a previous run of a disk-backed overflow stage were never
+            // counted, so don't let the count wrap around.
+            let _ = self
+                .overflow_pending
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| n.checked_sub(1));
+        }
+
         // If instrumentation is enabled, and we got the item from the base receiver, then and only
         // then do we track sending the event out.
         if let Some(handle) = self.instrumentation.as_ref() {
@@ -1,4 +1,10 @@
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
 
 use async_recursion::async_recursion;
 use derivative::Derivative;
@@ -83,6 +89,42 @@ where
         }
     }
 
+    /// Sends an item, dropping the oldest items in the buffer to make room for it if necessary.
+    ///
+    /// Returns the oldest items that were dropped, if the backend is able to give them back, as
+    /// otherwise the backend accounts for them itself. If no room could be made, which can happen
+    /// for the disk buffer, the given item is returned instead, and should be dropped.
+    pub(crate) async fn send_dropping_oldest(
+        &mut self,
+        item: T,
+    ) -> crate::Result<(Vec<T>, Option<T>)> {
+        match self {
+            Self::InMemory(tx) => tx
+                .send_dropping_oldest(item)
+                .await
+                .map(|dropped| (dropped, None))
+                .map_err(Into::into),
+            Self::DiskV2(writer) => {
+                let mut writer = writer.lock().await;
+
+                writer
+                    .try_write_record_dropping_oldest(item)
+                    .await
+                    .map(|item| (Vec::new(), item))
+                    .map_err(|e| {
+                        // TODO: Could some errors be handled and not be unrecoverable? Right now,
+                        // encoding should theoretically be recoverable -- encoded value was too
+                        // big, or error during encoding -- but the traits don't allow for
+                        // recovering the original event value because we have to consume it to do
+                        // the encoding... but that might not always be the case.
+                        error!("Disk buffer writer has encountered an unrecoverable error.");
+
+                        e.into()
+                    })
+            }
+        }
+    }
+
     pub(crate) async fn flush(&mut self) -> crate::Result<()> {
         match self {
             Self::InMemory(_) => Ok(()),
@@ -112,17 +154,24 @@ where
 /// events when the internal channel is full.
 ///
 /// When creating a buffer sender/receiver pair, callers can specify the "when full" behavior of the
-/// sender.  This controls how events are handled when the internal channel is full.  Three modes
+/// sender.  This controls how events are handled when the internal channel is full.  Four modes
 /// are possible:
 /// - block
 /// - drop newest
+/// - drop oldest
 /// - overflow
 ///
 /// In "block" mode, callers are simply forced to wait until the channel has enough capacity to
 /// accept the event.  In "drop newest" mode, any event being sent when the channel is full will be
-/// dropped and proceed no further. In "overflow" mode, events will be sent to another buffer
+/// dropped and proceed no further. In "drop oldest" mode, the oldest events in the channel are
+/// dropped until there is enough capacity to accept the event. In "overflow" mode, events will be sent to another buffer
 /// sender.  Callers can specify the overflow sender to use when constructing their buffers initially.
 ///
+/// In "overflow" mode, events keep going to the overflow sender for as long as the overflow stage
+/// holds any events, even if the base channel has free capacity again.  Together with
+/// [`BufferReceiver`] always draining the base channel first, this ensures that events leave the
+/// buffer in the same order they were sent in.
+///
 /// TODO: We should eventually rework `BufferSender`/`BufferReceiver` so that they contain a vector
 /// of the fields we already have here, but instead of cascading via calling into `overflow`, we'd
 /// linearize the nesting instead, so that `BufferSender` would only ever be calling the underlying
@@ -133,8 +182,10 @@ where
 pub struct BufferSender<T: Bufferable> {
     base: SenderAdapter<T>,
     overflow: Option<Box<BufferSender<T>>>,
+    overflow_pending: Arc<AtomicUsize>,
     when_full: WhenFull,
     instrumentation: Option<BufferUsageHandle>,
+    overflow_instrumentation: Option<BufferUsageHandle>,
     #[derivative(Debug = "ignore")]
     send_duration: Option<Registered<BufferSendDuration>>,
 }
@@ -145,19 +196,30 @@ impl<T: Bufferable> BufferSender<T> {
         Self {
             base,
             overflow: None,
+            overflow_pending: Arc::default(),
             when_full,
             instrumentation: None,
+            overflow_instrumentation: None,
             send_duration: None,
         }
     }
 
     /// Creates a new [`BufferSender`] wrapping the given channel sender and overflow sender.
-    pub fn with_overflow(base: SenderAdapter<T>, overflow: BufferSender<T>) -> Self {
+    ///
+    /// `overflow_pending` tracks the number of items held by the overflow stage, and must be shared
+    /// with the [`BufferReceiver`] reading from the same buffer stages.
+    pub fn with_overflow(
+        base: SenderAdapter<T>,
+        overflow: BufferSender<T>,
+        overflow_pending: Arc<AtomicUsize>,
+    ) -> Self {
         Self {
             base,
             overflow: Some(Box::new(overflow)),
+            overflow_pending,
             when_full: WhenFull::Overflow,
             instrumentation: None,
+            overflow_instrumentation: None,
             send_duration: None,
         }
     }
@@ -167,8 +229,13 @@ impl<T: Bufferable> BufferSender<T> {
     /// Note: this resets the internal state of this sender, and so this should not be called except
     /// when initially constructing `BufferSender<T>`.
     #[cfg(test)]
-    pub fn switch_to_overflow(&mut self, overflow: BufferSender<T>) {
+    pub fn switch_to_overflow(
+        &mut self,
+        overflow: BufferSender<T>,
+        overflow_pending: Arc<AtomicUsize>,
+    ) {
         self.overflow = Some(Box::new(overflow));
+        self.overflow_pending = overflow_pending;
         self.when_full = WhenFull::Overflow;
     }
 
@@ -177,6 +244,14 @@ impl<T: Bufferable> BufferSender<T> {
         self.instrumentation = Some(handle);
     }
 
+    /// Configures this sender to instrument the items it overflows to the next stage.
+    ///
+    /// Unlike [`BufferSender::with_usage_instrumentation`], this is independent of whether or not
+    /// the stage provides its own instrumentation, as only the sender knows when an item overflows.
+    pub fn with_overflow_instrumentation(&mut self, handle: BufferUsageHandle) {
+        self.overflow_instrumentation = Some(handle);
+    }
+
     /// Configures this sender to instrument the send duration.
     pub fn with_send_duration_instrumentation(&mut self, stage: usize, span: &Span) {
         let _enter = span.enter();
@@ -195,15 +270,28 @@ impl<T: Bufferable> BufferSender<T> {
         self.overflow.as_ref().map(AsRef::as_ref)
     }
 
-    #[async_recursion]
     pub async fn send(&mut self, item: T, send_reference: Option<Instant>) -> crate::Result<()> {
+        self.send_inner(item, send_reference).await.map(|_| ())
+    }
+
+    /// Sends an item into the buffer, returning whether it was buffered by any of the stages, or
+    /// intentionally dropped instead.
+    #[async_recursion]
+    async fn send_inner(
+        &mut self,
+        item: T,
+        send_reference: Option<Instant>,
+    ) -> crate::Result<bool> {
         let item_sizing = self
             .instrumentation
             .as_ref()
+            .or(self.overflow_instrumentation.as_ref())
             .map(|_| (item.event_count(), item.size_of()));
 
         let mut sent_to_base = true;
         let mut was_dropped = false;
+        let mut dropped_oldest = Vec::new();
+        let mut buffered = true;
         match self.when_full {
             WhenFull::Block => self.base.send(item).await?,
             WhenFull::DropNewest => {
@@ -211,14 +299,46 @@ impl<T: Bufferable> BufferSender<T> {
                     was_dropped = true;
                 }
             }
+            WhenFull::DropOldest => {
+                let (oldest, newest) = self.base.send_dropping_oldest(item).await?;
+                dropped_oldest = oldest;
+                was_dropped = newest.is_some();
+            }
             WhenFull::Overflow => {
-                if let Some(item) = self.base.try_send(item).await? {
+                // Once items have overflowed, keep sending to the overflow stage until it has been
+                // drained, as otherwise newer items could be read out of the base stage before
+                // older items in the overflow stage.
+                let item = if self.overflow_pending.load(Ordering::Acquire) > 0 {
+                    Some(item)
+                } else {
+                    self.base.try_send(item).await?
+                };
+
+                if let Some(item) = item {
                     sent_to_base = false;
-                    self.overflow
+
+                    // The pending count has to be incremented before sending, as the receiver may
+                    // otherwise read the item, and decrement the count, before we've incremented it.
+                    self.overflow_pending.fetch_add(1, Ordering::AcqRel);
+                    let result = self
+                        .overflow
                         .as_mut()
                         .unwrap_or_else(|| unreachable!("overflow must exist"))
-                        .send(item, send_reference)
-                        .await?;
+                        .send_inner(item, send_reference)
+                        .await;
+                    if !matches!(result, Ok(true)) {
+                        self.overflow_pending.fetch_sub(1, Ordering::AcqRel);
+                    }
+                    buffered = result?;
+
+                    if let (Some(instrumentation), Some((item_count, item_size))) =
+                        (self.overflow_instrumentation.as_ref(), item_sizing)
+                    {
+                        instrumentation.increment_overflowed_event_count_and_byte_size(
+                            item_count as u64,
+                            item_size as u64,
+                        );
+                    }
                 }
             }
         };
@@ -248,9 +368,20 @@ impl<T: Bufferable> BufferSender<T> {
                     );
                 }
             }
+
+            if !dropped_oldest.is_empty() {
+                let (dropped_count, dropped_size) =
+                    dropped_oldest.iter().fold((0, 0), |(count, size), item| {
+                        (count + item.event_count(), size + item.size_of())
+                    });
+                instrumentation.increment_dropped_oldest_event_count_and_byte_size(
+                    dropped_count as u64,
+                    dropped_size as u64,
+                );
+            }
         }
 
-        Ok(())
+        Ok(buffered && !was_dropped)
     }
 
     #[async_recursion]
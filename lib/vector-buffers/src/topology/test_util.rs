@@ -1,4 +1,8 @@
-use std::{error, fmt, num::NonZeroUsize};
+use std::{
+    error, fmt,
+    num::NonZeroUsize,
+    sync::{atomic::AtomicUsize, Arc},
+};
 
 use bytes::{Buf, BufMut};
 use vector_common::byte_size_of::ByteSizeOf;
@@ -113,8 +117,9 @@ pub(crate) async fn build_buffer(
                 handle.clone(),
             )
             .await;
-            base_sender.switch_to_overflow(overflow_sender);
-            base_receiver.switch_to_overflow(overflow_receiver);
+            let overflow_pending = Arc::new(AtomicUsize::new(0));
+            base_sender.switch_to_overflow(overflow_sender, Arc::clone(&overflow_pending));
+            base_receiver.switch_to_overflow(overflow_receiver, overflow_pending);
 
             (base_sender, base_receiver)
         }
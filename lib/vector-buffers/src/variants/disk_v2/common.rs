@@ -7,6 +7,7 @@ use crc32fast::Hasher;
 use snafu::Snafu;
 
 use super::{
+    encryption::{RecordCipher, ENCRYPTION_OVERHEAD},
     io::{Filesystem, ProductionFilesystem},
     ledger::LEDGER_LEN,
     record::RECORD_HEADER_LEN,
@@ -44,6 +45,12 @@ pub const MAX_FILE_ID: u16 = u16::MAX;
 #[cfg(test)]
 pub const MAX_FILE_ID: u16 = 6;
 
+// When a maximum age is configured, the writer rolls to a new data file once the current data file
+// is older than this fraction of the maximum age. As data files are expired as a whole, based on
+// when they were created, this bounds how much earlier than the maximum age a record can be
+// expired.
+pub const DATA_FILE_MAX_AGE_DIVISOR: u32 = 10;
+
 // The alignment used by the record serializer.
 const SERIALIZER_ALIGNMENT: usize = 16;
 const MAX_ALIGNABLE_AMOUNT: usize = usize::MAX - SERIALIZER_ALIGNMENT;
@@ -67,6 +74,17 @@ pub(crate) const fn align16(amount: usize) -> usize {
     ((amount + SERIALIZER_ALIGNMENT - 1) / SERIALIZER_ALIGNMENT) * SERIALIZER_ALIGNMENT
 }
 
+/// Gets how many data files ahead of the data file `from` the data file `to` is.
+///
+/// Data file IDs wrap around at `MAX_FILE_ID`, so this accounts for `to` having wrapped around when `from` has not.
+pub(crate) const fn data_file_id_distance(from: u16, to: u16) -> u16 {
+    if to >= from {
+        to - from
+    } else {
+        MAX_FILE_ID - from + to
+    }
+}
+
 /// Gets the maximum possible data file size given the type-level numerical limits and buffer invariants.
 fn get_maximum_data_file_size() -> u64 {
     let ledger_len: u64 = LEDGER_LEN
@@ -153,6 +171,24 @@ pub struct DiskBufferConfig<FS> {
     /// amount of data written since the last flush would be lost.
     pub(crate) flush_interval: Duration,
 
+    /// Maximum age of records in the buffer.
+    ///
+    /// Records are expired by the reader, instead of being read, once they are older than this
+    /// amount. Expiration happens a data file at a time, based on when the data file was created.
+    pub(crate) max_age: Option<Duration>,
+
+    /// Maximum number of events that the buffer can hold.
+    ///
+    /// This is a limit in addition to `max_buffer_size`, such that the buffer is full when either
+    /// limit is reached.
+    pub(crate) max_events: Option<u64>,
+
+    /// Cipher used to encrypt and decrypt record payloads.
+    ///
+    /// When set, records are encrypted before being written to data files, and previously-written
+    /// records, whether encrypted or not, can still be read.
+    pub(crate) encryption: Option<RecordCipher>,
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -173,6 +209,9 @@ where
     pub(crate) max_record_size: Option<usize>,
     pub(crate) write_buffer_size: Option<usize>,
     pub(crate) flush_interval: Option<Duration>,
+    pub(crate) max_age: Option<Duration>,
+    pub(crate) max_events: Option<u64>,
+    pub(crate) encryption: Option<RecordCipher>,
     pub(crate) filesystem: FS,
 }
 
@@ -188,6 +227,9 @@ impl DiskBufferConfigBuilder {
             max_record_size: None,
             write_buffer_size: None,
             flush_interval: None,
+            max_age: None,
+            max_events: None,
+            encryption: None,
             filesystem: ProductionFilesystem,
         }
     }
@@ -268,6 +310,40 @@ where
         self
     }
 
+    /// Sets the maximum age of records in the buffer.
+    ///
+    /// Records are expired by the reader, instead of being read, once they are older than this
+    /// amount. Expiration happens a data file at a time, based on when the data file was created,
+    /// and so records may be expired slightly before reaching the maximum age.
+    ///
+    /// Defaults to no limit.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Sets the maximum number of events that the buffer can hold.
+    ///
+    /// This is a limit in addition to the maximum buffer size, such that the buffer is full when
+    /// either limit is reached.
+    ///
+    /// Defaults to no limit.
+    pub fn max_events(mut self, amount: u64) -> Self {
+        self.max_events = Some(amount);
+        self
+    }
+
+    /// Sets the cipher used to encrypt record payloads.
+    ///
+    /// Records are encrypted with the cipher's current key, and can be decrypted with either the
+    /// current key or any of its previous keys.
+    ///
+    /// Defaults to no encryption.
+    pub fn encryption(mut self, cipher: RecordCipher) -> Self {
+        self.encryption = Some(cipher);
+        self
+    }
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -287,6 +363,9 @@ where
             max_record_size: self.max_record_size,
             write_buffer_size: self.write_buffer_size,
             flush_interval: self.flush_interval,
+            max_age: self.max_age,
+            max_events: self.max_events,
+            encryption: self.encryption,
             filesystem,
         }
     }
@@ -301,6 +380,9 @@ where
         let max_record_size = self.max_record_size.unwrap_or(DEFAULT_MAX_RECORD_SIZE);
         let write_buffer_size = self.write_buffer_size.unwrap_or(DEFAULT_WRITE_BUFFER_SIZE);
         let flush_interval = self.flush_interval.unwrap_or(DEFAULT_FLUSH_INTERVAL);
+        let max_age = self.max_age;
+        let max_events = self.max_events;
+        let encryption = self.encryption;
         let filesystem = self.filesystem;
 
         // Validate the input parameters.
@@ -358,6 +440,17 @@ where
             });
         }
 
+        if encryption.is_some() && max_record_size <= MINIMUM_MAX_RECORD_SIZE + ENCRYPTION_OVERHEAD
+        {
+            return Err(BuildError::InvalidParameter {
+                param_name: "max_record_size",
+                reason: format!(
+                    "must be greater than {} bytes when encryption is enabled",
+                    MINIMUM_MAX_RECORD_SIZE + ENCRYPTION_OVERHEAD
+                ),
+            });
+        }
+
         if write_buffer_size == 0 {
             return Err(BuildError::InvalidParameter {
                 param_name: "write_buffer_size",
@@ -365,6 +458,20 @@ where
             });
         }
 
+        if max_age.is_some_and(|max_age| max_age.is_zero()) {
+            return Err(BuildError::InvalidParameter {
+                param_name: "max_age",
+                reason: "cannot be zero".to_string(),
+            });
+        }
+
+        if max_events == Some(0) {
+            return Err(BuildError::InvalidParameter {
+                param_name: "max_events",
+                reason: "cannot be zero".to_string(),
+            });
+        }
+
         // Users configure the `max_size` of their disk buffers, which translates to the `max_buffer_size` field here,
         // and represents the maximum desired size of a disk buffer in terms of on-disk usage. In order to meet this
         // request, we do a few things internally and also enforce a lower bound on `max_buffer_size` to ensure we can
@@ -412,6 +519,9 @@ where
             max_record_size,
             write_buffer_size,
             flush_interval,
+            max_age,
+            max_events,
+            encryption,
             filesystem,
         })
     }
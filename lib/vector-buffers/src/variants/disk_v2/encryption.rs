@@ -0,0 +1,257 @@
+use std::fmt;
+
+use chacha20poly1305::{aead::AeadInPlace, KeyInit, Tag, XChaCha20Poly1305, XNonce};
+use snafu::Snafu;
+
+/// Length, in bytes, of an encryption key.
+pub const KEY_LEN: usize = 32;
+
+const NONCE_LEN: usize = 24;
+const TAG_LEN: usize = 16;
+
+/// Number of bytes that encrypting a record payload adds to it.
+///
+/// Each encrypted payload is stored as the nonce, followed by the ciphertext, followed by the
+/// authentication tag.
+pub const ENCRYPTION_OVERHEAD: usize = NONCE_LEN + TAG_LEN;
+
+/// Record metadata flag that marks the record payload as being encrypted.
+///
+/// This is the highest bit of the record metadata, which is never used by `Encodable::Metadata`,
+/// so that it can be stripped before the metadata is handed to the decoder. Versions of Vector that
+/// don't know about this flag will consider encrypted records as incompatible, rather than trying
+/// to decode the ciphertext.
+pub const ENCRYPTED_RECORD_FLAG: u32 = 1 << 31;
+
+/// Error that occurred when parsing an encryption key.
+#[derive(Debug, Snafu)]
+pub enum EncryptionKeyError {
+    #[snafu(display(
+        "encryption key must be {} hex characters long, but was {} characters long",
+        KEY_LEN * 2,
+        len
+    ))]
+    InvalidLength { len: usize },
+
+    #[snafu(display("encryption key must only contain hex characters"))]
+    InvalidCharacter,
+}
+
+/// Error that occurred when decrypting a record payload.
+#[derive(Debug, Snafu)]
+pub enum DecryptionError {
+    #[snafu(display("encrypted payload too short ({} bytes)", len))]
+    TooShort { len: usize },
+
+    #[snafu(display("payload could not be authenticated with any configured key"))]
+    Unauthenticated,
+}
+
+/// A key used to encrypt data files.
+#[derive(Clone)]
+pub struct EncryptionKey([u8; KEY_LEN]);
+
+impl EncryptionKey {
+    /// Parses an encryption key from its hex-encoded form.
+    ///
+    /// # Errors
+    ///
+    /// If the key is not exactly 64 hex characters long, an error variant will be returned.
+    pub fn from_hex(hex: &str) -> Result<Self, EncryptionKeyError> {
+        let hex = hex.trim().as_bytes();
+        if hex.len() != KEY_LEN * 2 {
+            return Err(EncryptionKeyError::InvalidLength { len: hex.len() });
+        }
+
+        let mut key = [0u8; KEY_LEN];
+        for (byte, pair) in key.iter_mut().zip(hex.chunks_exact(2)) {
+            let hi = hex_value(pair[0]).ok_or(EncryptionKeyError::InvalidCharacter)?;
+            let lo = hex_value(pair[1]).ok_or(EncryptionKeyError::InvalidCharacter)?;
+            *byte = (hi << 4) | lo;
+        }
+
+        Ok(Self(key))
+    }
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EncryptionKey(**REDACTED**)")
+    }
+}
+
+const fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Authenticated encryption of record payloads.
+///
+/// Payloads are encrypted with XChaCha20-Poly1305, using a random nonce per record, and the record
+/// ID and metadata as associated data, so that an encrypted payload can't be moved to another
+/// record without failing authentication.
+///
+/// New records are always encrypted with the current key. Previous keys are only ever used to
+/// decrypt records, which allows rotating the key while data files written with the previous key
+/// are still in the buffer.
+#[derive(Clone)]
+pub struct RecordCipher {
+    current: XChaCha20Poly1305,
+    previous: Vec<XChaCha20Poly1305>,
+}
+
+impl RecordCipher {
+    /// Creates a new [`RecordCipher`] from the current key and any previous keys.
+    pub fn new(current: &EncryptionKey, previous: &[EncryptionKey]) -> Self {
+        Self {
+            current: XChaCha20Poly1305::new(&current.0.into()),
+            previous: previous
+                .iter()
+                .map(|key| XChaCha20Poly1305::new(&key.0.into()))
+                .collect(),
+        }
+    }
+
+    /// Encrypts `payload` with the current key, writing the encrypted payload to `out`.
+    ///
+    /// # Panics
+    ///
+    /// If the payload is too large to be encrypted, this method will panic. Payloads are limited by
+    /// the maximum record size, which is far below that limit.
+    pub fn encrypt(&self, id: u64, metadata: u32, payload: &[u8], out: &mut Vec<u8>) {
+        let nonce: [u8; NONCE_LEN] = rand::random();
+        let associated_data = associated_data(id, metadata);
+
+        out.clear();
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(payload);
+        let tag = self
+            .current
+            .encrypt_in_place_detached(
+                XNonce::from_slice(&nonce),
+                &associated_data,
+                &mut out[NONCE_LEN..],
+            )
+            .expect("payload should never exceed the maximum encryptable length");
+        out.extend_from_slice(&tag);
+    }
+
+    /// Decrypts `payload`, writing the decrypted payload to `out`.
+    ///
+    /// The current key is tried first, followed by each of the previous keys, in order.
+    ///
+    /// # Errors
+    ///
+    /// If the payload can't be authenticated with any of the configured keys, whether because it
+    /// was encrypted with an unknown key or because it was modified, an error variant will be
+    /// returned.
+    pub fn decrypt(
+        &self,
+        id: u64,
+        metadata: u32,
+        payload: &[u8],
+        out: &mut Vec<u8>,
+    ) -> Result<(), DecryptionError> {
+        if payload.len() < ENCRYPTION_OVERHEAD {
+            return Err(DecryptionError::TooShort { len: payload.len() });
+        }
+
+        let (nonce, rest) = payload.split_at(NONCE_LEN);
+        let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+        let nonce = XNonce::from_slice(nonce);
+        let tag = Tag::from_slice(tag);
+        let associated_data = associated_data(id, metadata);
+
+        for cipher in std::iter::once(&self.current).chain(&self.previous) {
+            out.clear();
+            out.extend_from_slice(ciphertext);
+            if cipher
+                .decrypt_in_place_detached(nonce, &associated_data, out, tag)
+                .is_ok()
+            {
+                return Ok(());
+            }
+        }
+
+        out.clear();
+        Err(DecryptionError::Unauthenticated)
+    }
+}
+
+impl fmt::Debug for RecordCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordCipher")
+            .field("previous_keys", &self.previous.len())
+            .finish_non_exhaustive()
+    }
+}
+
+fn associated_data(id: u64, metadata: u32) -> [u8; 12] {
+    let mut buf = [0u8; 12];
+    buf[..8].copy_from_slice(&id.to_be_bytes());
+    buf[8..].copy_from_slice(&metadata.to_be_bytes());
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EncryptionKey, EncryptionKeyError, RecordCipher, ENCRYPTION_OVERHEAD};
+
+    const KEY_A: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+    const KEY_B: &str = "F0F1F2F3F4F5F6F7F8F9FAFBFCFDFEFFE0E1E2E3E4E5E6E7E8E9EAEBECEDEEEF";
+
+    #[test]
+    fn key_from_hex() {
+        assert!(EncryptionKey::from_hex(KEY_A).is_ok());
+        assert!(EncryptionKey::from_hex(KEY_B).is_ok());
+        assert!(matches!(
+            EncryptionKey::from_hex("abcd"),
+            Err(EncryptionKeyError::InvalidLength { len: 4 })
+        ));
+        assert!(matches!(
+            EncryptionKey::from_hex(&KEY_A.replace('0', "z")),
+            Err(EncryptionKeyError::InvalidCharacter)
+        ));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let key = EncryptionKey::from_hex(KEY_A).unwrap();
+        let cipher = RecordCipher::new(&key, &[]);
+
+        let mut encrypted = Vec::new();
+        cipher.encrypt(42, 7, b"hello world", &mut encrypted);
+        assert_eq!(encrypted.len(), b"hello world".len() + ENCRYPTION_OVERHEAD);
+
+        let mut decrypted = Vec::new();
+        cipher.decrypt(42, 7, &encrypted, &mut decrypted).unwrap();
+        assert_eq!(decrypted, b"hello world");
+
+        // The record ID and metadata are authenticated along with the payload.
+        assert!(cipher.decrypt(43, 7, &encrypted, &mut decrypted).is_err());
+        assert!(cipher.decrypt(42, 8, &encrypted, &mut decrypted).is_err());
+    }
+
+    #[test]
+    fn previous_keys_can_decrypt() {
+        let key_a = EncryptionKey::from_hex(KEY_A).unwrap();
+        let key_b = EncryptionKey::from_hex(KEY_B).unwrap();
+
+        let mut encrypted = Vec::new();
+        RecordCipher::new(&key_a, &[]).encrypt(1, 0, b"payload", &mut encrypted);
+
+        let mut decrypted = Vec::new();
+        let rotated = RecordCipher::new(&key_b, &[key_a]);
+        rotated.decrypt(1, 0, &encrypted, &mut decrypted).unwrap();
+        assert_eq!(decrypted, b"payload");
+
+        let without_previous = RecordCipher::new(&key_b, &[]);
+        assert!(without_previous
+            .decrypt(1, 0, &encrypted, &mut decrypted)
+            .is_err());
+    }
+}
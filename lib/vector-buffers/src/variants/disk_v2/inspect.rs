@@ -0,0 +1,390 @@
+//! Offline inspection of disk buffers.
+//!
+//! This allows looking at the ledger and data files of a disk buffer without opening it as a
+//! buffer, which would otherwise create missing files, validate and repair the writer state, and
+//! delete data files as records are acknowledged. Records are only ever read, which makes it safe
+//! to use against a buffer that needs to be salvaged.
+//!
+//! The buffer must not be in use by a running Vector process while it is being inspected.
+use std::{
+    fs::File,
+    io::{self, BufReader, ErrorKind, Read},
+    path::{Path, PathBuf},
+};
+
+use crc32fast::Hasher;
+use fslock::LockFile;
+use rkyv::AlignedVec;
+use snafu::{ResultExt, Snafu};
+
+use super::{
+    common::{create_crc32c_hasher, MAX_FILE_ID},
+    encryption::{RecordCipher, ENCRYPTED_RECORD_FLAG},
+    ledger::{get_data_file_path, LedgerState},
+    reader::{decode_record_payload, ReaderError},
+    record::{try_as_record_archive, ArchivedRecord, RecordStatus},
+    ser::try_as_archive,
+};
+use crate::Bufferable;
+
+/// Error that occurred while inspecting a disk buffer.
+#[derive(Debug, Snafu)]
+pub enum InspectError {
+    /// The given directory does not contain a disk buffer.
+    #[snafu(display("no disk buffer ledger found at {}", path.display()))]
+    MissingLedger { path: PathBuf },
+
+    /// The buffer is locked by another process.
+    #[snafu(display(
+        "failed to lock buffer.lock; is another Vector process running and using this buffer?"
+    ))]
+    LedgerLockAlreadyHeld,
+
+    /// A general I/O error occurred.
+    #[snafu(display("I/O error: {}", source))]
+    Io { source: io::Error },
+
+    /// The ledger state was unable to be deserialized.
+    #[snafu(display("failed to deserialize ledger from buffer: {}", reason))]
+    FailedToDeserialize { reason: String },
+}
+
+/// Point-in-time copy of the ledger state of a disk buffer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LedgerSnapshot {
+    /// Next record ID the writer will use.
+    pub writer_next_record_id: u64,
+
+    /// Data file ID the writer is currently writing to.
+    pub writer_current_data_file_id: u16,
+
+    /// Data file ID the reader is currently reading from.
+    pub reader_current_data_file_id: u16,
+
+    /// Last record ID that was read and acknowledged.
+    pub reader_last_record_id: u64,
+}
+
+impl LedgerSnapshot {
+    /// Gets the number of events that were written to the buffer but not yet acknowledged.
+    pub fn unacknowledged_events(&self) -> u64 {
+        self.writer_next_record_id
+            .wrapping_sub(self.reader_last_record_id)
+            .wrapping_sub(1)
+    }
+
+    /// Gets the IDs of the data files that may still hold records, in the order they are read.
+    pub fn data_file_ids(&self) -> impl Iterator<Item = u16> {
+        let mut file_id = self.reader_current_data_file_id;
+        let last_file_id = self.writer_current_data_file_id;
+        let mut done = false;
+        std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            let current = file_id;
+            done = current == last_file_id;
+            file_id = (file_id + 1) % MAX_FILE_ID;
+            Some(current)
+        })
+    }
+}
+
+/// Summary of a single data file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DataFileReport {
+    /// Data file ID.
+    pub file_id: u16,
+
+    /// Path to the data file.
+    pub path: PathBuf,
+
+    /// Size of the data file, in bytes.
+    pub size: u64,
+
+    /// Number of valid records in the data file.
+    pub records: u64,
+
+    /// Number of events in the data file, based on the record IDs.
+    pub events: u64,
+
+    /// Number of valid records that have not yet been acknowledged.
+    pub unacknowledged_records: u64,
+
+    /// ID of the first valid record in the data file.
+    pub first_record_id: Option<u64>,
+
+    /// ID of the last valid record in the data file.
+    pub last_record_id: Option<u64>,
+
+    /// Error that stopped the data file from being read any further, if any.
+    ///
+    /// The reader skips the remainder of a data file after such an error, so any records after it
+    /// are lost.
+    pub error: Option<String>,
+}
+
+/// A record read from a data file.
+pub struct InspectedRecord<'a> {
+    file_id: u16,
+    record: &'a ArchivedRecord<'a>,
+    acknowledged: bool,
+}
+
+impl InspectedRecord<'_> {
+    /// Gets the ID of the data file this record was read from.
+    pub fn file_id(&self) -> u16 {
+        self.file_id
+    }
+
+    /// Gets the ID of this record.
+    pub fn id(&self) -> u64 {
+        self.record.id()
+    }
+
+    /// Whether or not this record has already been acknowledged.
+    ///
+    /// Acknowledged records were already processed by the sink, and will not be read again.
+    pub fn is_acknowledged(&self) -> bool {
+        self.acknowledged
+    }
+
+    /// Whether or not the payload of this record is encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        self.record.metadata() & ENCRYPTED_RECORD_FLAG != 0
+    }
+
+    /// Decodes this record, decrypting it with the given cipher if it is encrypted.
+    ///
+    /// # Errors
+    ///
+    /// If the record can't be decrypted or decoded as `T`, an error variant will be returned.
+    pub fn decode<T: Bufferable>(
+        &self,
+        cipher: Option<&RecordCipher>,
+    ) -> Result<T, ReaderError<T>> {
+        decode_record_payload(self.record, cipher, &mut Vec::new())
+    }
+}
+
+/// Read-only view of a disk buffer.
+pub struct BufferInspector {
+    data_dir: PathBuf,
+    ledger: LedgerSnapshot,
+    _lock: LockFile,
+}
+
+impl BufferInspector {
+    /// Opens the disk buffer in the given directory for inspection.
+    ///
+    /// The buffer lock is held until the inspector is dropped, so that Vector can't start using the
+    /// buffer in the meantime.
+    ///
+    /// # Errors
+    ///
+    /// If the directory does not contain a disk buffer, the buffer is in use by another process, or
+    /// the ledger can't be read, an error variant will be returned.
+    pub fn open(data_dir: &Path) -> Result<Self, InspectError> {
+        let ledger_path = data_dir.join("buffer.db");
+        if !ledger_path.is_file() {
+            return Err(InspectError::MissingLedger { path: ledger_path });
+        }
+
+        let mut lock = LockFile::open(&data_dir.join("buffer.lock")).context(IoSnafu)?;
+        if !lock.try_lock().context(IoSnafu)? {
+            return Err(InspectError::LedgerLockAlreadyHeld);
+        }
+
+        let mut buf = AlignedVec::new();
+        buf.extend_from_slice(&std::fs::read(&ledger_path).context(IoSnafu)?);
+        if buf.is_empty() {
+            return Err(InspectError::FailedToDeserialize {
+                reason: "ledger file is empty".to_string(),
+            });
+        }
+        let state =
+            try_as_archive::<LedgerState>(&buf).map_err(|e| InspectError::FailedToDeserialize {
+                reason: e.into_inner(),
+            })?;
+
+        let ledger = LedgerSnapshot {
+            writer_next_record_id: state.get_next_writer_record_id(),
+            writer_current_data_file_id: state.get_current_writer_file_id(),
+            reader_current_data_file_id: state.get_current_reader_file_id(),
+            reader_last_record_id: state.get_last_reader_record_id(),
+        };
+
+        Ok(Self {
+            data_dir: data_dir.to_path_buf(),
+            ledger,
+            _lock: lock,
+        })
+    }
+
+    /// Gets the ledger state of the buffer.
+    pub fn ledger(&self) -> &LedgerSnapshot {
+        &self.ledger
+    }
+
+    /// Reads every data file in the buffer, validating the checksum of every record.
+    ///
+    /// Data files are read in the same order as the buffer reader would read them, and each valid
+    /// record is passed to `visit`, including records that were already acknowledged. Data files
+    /// that no longer exist are skipped.
+    ///
+    /// # Errors
+    ///
+    /// If a data file can't be opened, or `visit` returns an error, an error will be returned.
+    /// Invalid records are not considered an error, and are instead reported in the summary of the
+    /// data file they were found in.
+    pub fn scan<E, F>(&self, mut visit: F) -> Result<Vec<DataFileReport>, E>
+    where
+        E: From<InspectError>,
+        F: FnMut(&InspectedRecord<'_>) -> Result<(), E>,
+    {
+        let checksummer = create_crc32c_hasher();
+        let mut buf = AlignedVec::new();
+        let mut reports: Vec<DataFileReport> = Vec::new();
+        let mut last_record_id = None;
+
+        for file_id in self.ledger.data_file_ids() {
+            let path = get_data_file_path(&self.data_dir, file_id);
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(InspectError::Io { source: e }.into()),
+            };
+            let size = file.metadata().context(IoSnafu)?.len();
+            let mut reader = BufReader::new(file);
+            let mut report = DataFileReport {
+                file_id,
+                path,
+                size,
+                ..DataFileReport::default()
+            };
+
+            loop {
+                let record_len = match read_length_delimiter(&mut reader) {
+                    Ok(Some(record_len)) => record_len,
+                    Ok(None) => break,
+                    Err(e) => {
+                        report.error = Some(e);
+                        break;
+                    }
+                };
+
+                if let Err(e) = read_record(&mut reader, record_len, &mut buf) {
+                    report.error = Some(e);
+                    break;
+                }
+
+                let record = match check_record(&buf, &checksummer) {
+                    Ok(record) => record,
+                    Err(e) => {
+                        report.error = Some(e);
+                        break;
+                    }
+                };
+
+                // Records contain as many events as the difference between their ID and the ID
+                // of the next record, so the previous record can only be accounted for now.
+                let id = record.id();
+                if let Some(previous_id) = last_record_id.replace(id) {
+                    let events = id.wrapping_sub(previous_id);
+                    if report.records > 0 {
+                        report.events += events;
+                    } else if let Some(previous) =
+                        reports.iter_mut().rev().find(|report| report.records > 0)
+                    {
+                        previous.events += events;
+                    }
+                }
+
+                let acknowledged = id <= self.ledger.reader_last_record_id;
+                report.records += 1;
+                if !acknowledged {
+                    report.unacknowledged_records += 1;
+                }
+                report.first_record_id.get_or_insert(id);
+                report.last_record_id = Some(id);
+
+                visit(&InspectedRecord {
+                    file_id,
+                    record,
+                    acknowledged,
+                })?;
+            }
+
+            reports.push(report);
+        }
+
+        if let Some(previous_id) = last_record_id {
+            if let Some(last) = reports.iter_mut().rev().find(|report| report.records > 0) {
+                last.events += self.ledger.writer_next_record_id.wrapping_sub(previous_id);
+            }
+        }
+
+        Ok(reports)
+    }
+}
+
+fn read_length_delimiter<R: Read>(reader: &mut R) -> Result<Option<usize>, String> {
+    let mut length = [0u8; 8];
+    let mut filled = 0;
+    while filled < length.len() {
+        match reader.read(&mut length[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    match filled {
+        0 => Ok(None),
+        8 => usize::try_from(u64::from_be_bytes(length))
+            .map(Some)
+            .map_err(|_| "record length exceeds usize".to_string()),
+        _ => Err("partial write detected in record length delimiter".to_string()),
+    }
+}
+
+fn read_record<R: Read>(
+    reader: &mut R,
+    record_len: usize,
+    buf: &mut AlignedVec,
+) -> Result<(), String> {
+    if record_len == 0 {
+        return Err("record length was zero".to_string());
+    }
+
+    // Read in chunks, rather than allocating the whole record up front, as a corrupted length
+    // delimiter can claim a record length far beyond the size of the data file.
+    buf.clear();
+    let mut chunk = [0u8; 8192];
+    while buf.len() < record_len {
+        let needed = std::cmp::min(record_len - buf.len(), chunk.len());
+        match reader.read(&mut chunk[..needed]) {
+            Ok(0) => return Err("partial write detected in record".to_string()),
+            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    Ok(())
+}
+
+fn check_record<'a>(buf: &'a [u8], checksummer: &Hasher) -> Result<&'a ArchivedRecord<'a>, String> {
+    let record = try_as_record_archive(buf)
+        .map_err(|e| format!("failed to deserialize record: {}", e.into_inner()))?;
+    match record.verify_checksum(checksummer) {
+        RecordStatus::Valid { .. } => Ok(record),
+        RecordStatus::Corrupted { calculated, actual } => Err(format!(
+            "record checksum did not match (calculated {calculated:#010x}, actual {actual:#010x})"
+        )),
+        RecordStatus::FailedDeserialization(e) => {
+            Err(format!("failed to deserialize record: {}", e.into_inner()))
+        }
+    }
+}
@@ -1,8 +1,9 @@
-use std::{io, path::Path};
+use std::{io, path::Path, time::SystemTime};
 
 use tokio::{
     fs::OpenOptions,
     io::{AsyncRead, AsyncWrite},
+    time::Instant,
 };
 
 #[cfg(unix)]
@@ -11,6 +12,7 @@ const FILE_MODE_OWNER_RW_GROUP_RO: u32 = 0o640;
 /// File metadata.
 pub struct Metadata {
     pub(crate) len: u64,
+    pub(crate) created: Option<SystemTime>,
 }
 
 impl Metadata {
@@ -18,6 +20,21 @@ impl Metadata {
     pub fn len(&self) -> u64 {
         self.len
     }
+
+    /// Gets the creation time of the file, if available.
+    ///
+    /// On platforms, or filesystems, that do not track the creation time of files, this falls back
+    /// to the last modification time of the file.
+    pub fn created(&self) -> Option<SystemTime> {
+        self.created
+    }
+
+    /// Gets the creation time of the file as an [`Instant`], so that it can be compared with the
+    /// creation times the writer tracks for the data files it creates.
+    pub fn created_instant(&self) -> Option<Instant> {
+        let age = self.created?.elapsed().unwrap_or_default();
+        Instant::now().checked_sub(age)
+    }
 }
 
 /// Generalized interface for opening and deleting files from a filesystem.
@@ -218,6 +235,7 @@ impl AsyncFile for tokio::fs::File {
         let metadata = self.metadata().await?;
         Ok(Metadata {
             len: metadata.len(),
+            created: metadata.created().or_else(|_| metadata.modified()).ok(),
         })
     }
 
@@ -1,9 +1,10 @@
 use std::{
+    collections::HashMap,
     fmt, io, mem,
-    path::PathBuf,
-    sync::atomic::{AtomicBool, AtomicU16, AtomicU64, Ordering},
-    sync::Arc,
-    time::Instant,
+    path::{Path, PathBuf},
+    sync::atomic::{fence, AtomicBool, AtomicU16, AtomicU64, Ordering},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 use bytecheck::CheckBytes;
@@ -18,7 +19,9 @@ use vector_common::finalizer::OrderedFinalizer;
 
 use super::{
     backed_archive::BackedArchive,
-    common::{align16, DiskBufferConfig, MAX_FILE_ID},
+    common::{
+        align16, data_file_id_distance, DiskBufferConfig, DATA_FILE_MAX_AGE_DIVISOR, MAX_FILE_ID,
+    },
     io::{AsyncFile, WritableMemoryMap},
     ser::SerializeError,
     Filesystem,
@@ -72,6 +75,11 @@ pub enum LedgerLoadCreateError {
     FailedToSerialize { reason: String },
 }
 
+/// Gets the path of the data file with the given file ID, within the given buffer directory.
+pub(super) fn get_data_file_path(data_dir: &Path, file_id: u16) -> PathBuf {
+    data_dir.join(format!("buffer-data-{file_id}.dat"))
+}
+
 /// Ledger state.
 ///
 /// Stores the relevant information related to both the reader and writer.  Gets serialized and
@@ -119,7 +127,7 @@ impl Default for LedgerState {
 }
 
 impl ArchivedLedgerState {
-    fn get_current_writer_file_id(&self) -> u16 {
+    pub(super) fn get_current_writer_file_id(&self) -> u16 {
         self.writer_current_data_file.load(Ordering::Acquire)
     }
 
@@ -141,7 +149,7 @@ impl ArchivedLedgerState {
         previous.wrapping_add(amount)
     }
 
-    fn get_current_reader_file_id(&self) -> u16 {
+    pub(super) fn get_current_reader_file_id(&self) -> u16 {
         self.reader_current_data_file.load(Ordering::Acquire)
     }
 
@@ -227,6 +235,16 @@ where
     unacked_reader_file_id_offset: AtomicU16,
     // Last flush of all unflushed files: ledger, data file, etc.
     last_flush: AtomicCell<Instant>,
+    // The data file currently being dropped, either by the writer to make room for newer records,
+    // or because it expired, if any.
+    dropping_data_file_id: AtomicCell<Option<u16>>,
+    // Number of events in dropped data files that the reader will encounter as record ID gaps.
+    dropped_data_file_events: AtomicU64,
+    // Number of events written to each data file, by data file ID, for accounting for the events
+    // in a data file if it gets dropped.
+    data_file_event_counts: Mutex<HashMap<u16, u64>>,
+    // When each data file opened by the writer was created, by data file path.
+    data_files_created: Mutex<HashMap<PathBuf, tokio::time::Instant>>,
     // Tracks usage data about the buffer.
     usage_handle: BufferUsageHandle,
 }
@@ -347,9 +365,7 @@ where
 
     /// Gets the data file path for an arbitrary file ID.
     pub fn get_data_file_path(&self, file_id: u16) -> PathBuf {
-        self.config
-            .data_dir
-            .join(format!("buffer-data-{file_id}.dat"))
+        get_data_file_path(&self.config.data_dir, file_id)
     }
 
     /// Waits for a signal from the reader that progress has been made.
@@ -395,6 +411,38 @@ where
             .increment_sent_event_count_and_byte_size(event_count, total_record_size);
     }
 
+    /// Tracks when the data file at the given path was created.
+    ///
+    /// The writer tracks this for every data file it opens, so that the reader expires records
+    /// using the same creation time, and clock, as the writer uses to decide when to roll to a new
+    /// data file. Since data file paths are reused once their IDs wrap around, this only ever holds
+    /// an entry per data file ID.
+    pub fn track_data_file_created(&self, path: &Path, created: tokio::time::Instant) {
+        self.data_files_created
+            .lock()
+            .expect("lock should not be poisoned")
+            .insert(path.to_path_buf(), created);
+    }
+
+    /// Gets when the data file at the given path was created, if it was opened by the writer.
+    pub fn get_data_file_created(&self, path: &Path) -> Option<tokio::time::Instant> {
+        self.data_files_created
+            .lock()
+            .expect("lock should not be poisoned")
+            .get(path)
+            .copied()
+    }
+
+    /// Tracks the statistics of multiple expired reads.
+    ///
+    /// Expired records are acknowledged like any other record, but are dropped instead of being
+    /// sent onwards.
+    pub fn track_expired_reads(&self, event_count: u64, total_record_size: u64) {
+        self.decrement_total_buffer_size(total_record_size);
+        self.usage_handle
+            .increment_expired_event_count_and_byte_size(event_count, total_record_size);
+    }
+
     /// Marks the writer as finished.
     ///
     /// If the writer was not yet marked done, `false` is returned.  Otherwise, `true` is returned,
@@ -434,6 +482,14 @@ where
         );
     }
 
+    /// Gets the file ID offset of the reader past the acknowledged reader file ID.
+    ///
+    /// This is zero when every data file the reader has moved past has also been acknowledged, and
+    /// deleted.
+    pub fn get_unacked_reader_file_id_offset(&self) -> u16 {
+        self.unacked_reader_file_id_offset.load(Ordering::Acquire)
+    }
+
     /// Increments the acknowledged reader file ID.
     ///
     /// As records may be read and stored for a small period of time (batching in a sink, etc), we
@@ -526,6 +582,185 @@ where
             );
     }
 
+    /// Tracks that the given number of events were written to the given data file.
+    pub fn track_data_file_events(&self, file_id: u16, event_count: u64) {
+        *self
+            .data_file_event_counts
+            .lock()
+            .expect("lock should not be poisoned")
+            .entry(file_id)
+            .or_default() += event_count;
+    }
+
+    /// Forgets the events written to the given data file, as the writer is starting it over.
+    pub fn reset_data_file_events(&self, file_id: u16) {
+        self.data_file_event_counts
+            .lock()
+            .expect("lock should not be poisoned")
+            .remove(&file_id);
+    }
+
+    /// Attempts to claim the given data file so that it can be dropped.
+    ///
+    /// A data file can only be dropped if the reader has not yet moved on to it, the writer is
+    /// no longer writing to it, and no other data file is being dropped. If the data file can be
+    /// dropped, `true` is returned, and the caller must call [`finish_dropping_data_file`] once it
+    /// has been deleted.
+    ///
+    /// The reader checks [`is_data_file_being_dropped`] after moving on to a new data file, and
+    /// before opening it, so between the two, either the dropper sees that the reader has moved on
+    /// to the data file, or the reader sees that the data file is being dropped, and waits.
+    pub fn try_begin_dropping_data_file(&self, file_id: u16) -> bool {
+        if self
+            .dropping_data_file_id
+            .compare_exchange(None, Some(file_id))
+            .is_err()
+        {
+            return false;
+        }
+        fence(Ordering::SeqCst);
+
+        let (reader_file_id, writer_file_id) = self.get_current_reader_writer_file_id();
+        let file_distance = data_file_id_distance(reader_file_id, file_id);
+        let droppable = file_distance > 0
+            && file_distance < data_file_id_distance(reader_file_id, writer_file_id);
+        if !droppable {
+            self.dropping_data_file_id.store(None);
+        }
+
+        droppable
+    }
+
+    /// Marks the data file claimed by [`try_begin_dropping_data_file`] as no longer being dropped.
+    ///
+    /// This also notifies the reader, in case it was waiting to open the data file.
+    pub fn finish_dropping_data_file(&self) {
+        self.dropping_data_file_id.store(None);
+        self.notify_writer_waiters();
+    }
+
+    /// Returns `true` if the given data file is currently being dropped.
+    pub fn is_data_file_being_dropped(&self, file_id: u16) -> bool {
+        fence(Ordering::SeqCst);
+        self.dropping_data_file_id.load() == Some(file_id)
+    }
+
+    /// Deletes the data file with the given ID, claimed by [`try_begin_dropping_data_file`].
+    ///
+    /// Returns the size of the data file, and the number of events written to it, if known, or
+    /// `None` if the data file did not exist.
+    pub async fn delete_dropped_data_file(
+        &self,
+        file_id: u16,
+    ) -> io::Result<Option<(u64, Option<u64>)>> {
+        let data_file_path = self.get_data_file_path(file_id);
+        let data_file = match self.filesystem().open_file_readable(&data_file_path).await {
+            Ok(data_file) => data_file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let data_file_size = data_file.metadata().await?.len();
+        drop(data_file);
+
+        self.filesystem().delete_file(&data_file_path).await?;
+
+        let event_count = self
+            .data_file_event_counts
+            .lock()
+            .expect("lock should not be poisoned")
+            .remove(&file_id);
+        Ok(Some((data_file_size, event_count)))
+    }
+
+    /// Tracks the statistics of a data file dropped by the writer to make room for newer records.
+    ///
+    /// When the number of events in the data file is known, they're accounted for here, and will
+    /// be subtracted from the record ID gap that the reader later encounters, so that they aren't
+    /// reported twice. Data files written before the process started have no known event count, and
+    /// so their events are reported by the reader as usual.
+    pub fn track_dropped_data_file(&self, event_count: Option<u64>, data_file_size: u64) {
+        self.decrement_total_buffer_size(data_file_size);
+
+        let event_count = event_count.unwrap_or(0);
+        self.dropped_data_file_events
+            .fetch_add(event_count, Ordering::AcqRel);
+        self.usage_handle
+            .increment_dropped_oldest_event_count_and_byte_size(event_count, data_file_size);
+    }
+
+    /// Tracks the statistics of a data file dropped because its records expired.
+    ///
+    /// Like [`track_dropped_data_file`], the events of the data file, when known, are subtracted
+    /// from the record ID gap that the reader later encounters.
+    pub fn track_expired_data_file(&self, event_count: Option<u64>, data_file_size: u64) {
+        self.decrement_total_buffer_size(data_file_size);
+
+        let event_count = event_count.unwrap_or(0);
+        self.dropped_data_file_events
+            .fetch_add(event_count, Ordering::AcqRel);
+        self.usage_handle
+            .increment_expired_event_count_and_byte_size(event_count, data_file_size);
+    }
+
+    /// Consumes up to `count` events previously dropped along with their data files.
+    ///
+    /// The number of events consumed is returned, which will be smaller than `count` if not that
+    /// many events were dropped.
+    pub fn consume_dropped_data_file_events(&self, count: u64) -> u64 {
+        self.dropped_data_file_events
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                Some(n.saturating_sub(count))
+            })
+            .map_or(0, |n| n.min(count))
+    }
+
+    /// Drops the data files whose records have expired, other than the ones the reader and the
+    /// writer are on.
+    ///
+    /// Data files are created in order, so this stops at the first data file that hasn't expired.
+    pub async fn drop_expired_data_files(&self, max_age: Duration) -> io::Result<()> {
+        let (reader_file_id, writer_file_id) = self.get_current_reader_writer_file_id();
+
+        let mut file_id = (reader_file_id + 1) % MAX_FILE_ID;
+        while file_id != writer_file_id {
+            let data_file_path = self.get_data_file_path(file_id);
+            let created = match self.get_data_file_created(&data_file_path) {
+                Some(created) => Some(created),
+                None => match self.filesystem().open_file_readable(&data_file_path).await {
+                    Ok(data_file) => data_file.metadata().await?.created_instant(),
+                    // Already dropped to make room for newer records.
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                        file_id = (file_id + 1) % MAX_FILE_ID;
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                },
+            };
+            if !created.is_some_and(|created| created.elapsed() >= max_age) {
+                break;
+            }
+
+            if self.try_begin_dropping_data_file(file_id) {
+                let result = self.delete_dropped_data_file(file_id).await;
+                self.finish_dropping_data_file();
+
+                if let Some((data_file_size, event_count)) = result? {
+                    self.track_expired_data_file(event_count, data_file_size);
+                    self.flush()?;
+
+                    debug!(
+                        data_file_path = data_file_path.to_string_lossy().as_ref(),
+                        data_file_size, event_count, "Dropped expired data file."
+                    );
+                }
+            }
+
+            file_id = (file_id + 1) % MAX_FILE_ID;
+        }
+
+        Ok(())
+    }
+
     pub fn track_dropped_events(&self, count: u64) {
         // We don't know how many bytes are represented by dropped events because we never actually had a chance to read
         // them, so we have to use a byte size of 0 here.
@@ -646,6 +881,10 @@ where
             pending_acks: AtomicU64::new(0),
             unacked_reader_file_id_offset: AtomicU16::new(0),
             last_flush: AtomicCell::new(Instant::now()),
+            dropping_data_file_id: AtomicCell::new(None),
+            dropped_data_file_events: AtomicU64::new(0),
+            data_file_event_counts: Mutex::new(HashMap::new()),
+            data_files_created: Mutex::new(HashMap::new()),
             usage_handle,
         };
         ledger.update_buffer_size().await?;
@@ -700,13 +939,47 @@ where
         Ok(())
     }
 
+    /// Spawns a task dropping expired data files, if a maximum age is configured, so that they're
+    /// deleted even while the reader isn't making progress, such as when the sink is down.
+    ///
+    /// The task stops once the buffer is dropped.
+    pub(super) fn spawn_expired_data_file_sweep(self: &Arc<Self>) {
+        let Some(max_age) = self.config().max_age else {
+            return;
+        };
+        let ledger = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let period = (max_age / DATA_FILE_MAX_AGE_DIVISOR).max(Duration::from_millis(1));
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let Some(ledger) = ledger.upgrade() else {
+                    break;
+                };
+                if let Err(error) = ledger.drop_expired_data_files(max_age).await {
+                    error!(%error, "Failed to drop expired data files.");
+                }
+            }
+        });
+    }
+
+    /// Spawns a task tracking the acknowledgements of records handed out by the reader.
+    ///
+    /// The task doesn't keep the buffer open, since only the reader applies those acknowledgements
+    /// to the buffer: records acknowledged after the buffer is dropped are read again once it's
+    /// reopened.
     #[must_use]
     pub(super) fn spawn_finalizer(self: Arc<Self>) -> OrderedFinalizer<u64> {
         let (finalizer, mut stream) = OrderedFinalizer::new(None);
+        let ledger = Arc::downgrade(&self);
         tokio::spawn(async move {
             while let Some((_status, amount)) = stream.next().await {
-                self.increment_pending_acks(amount);
-                self.notify_writer_waiters();
+                let Some(ledger) = ledger.upgrade() else {
+                    break;
+                };
+                ledger.increment_pending_acks(amount);
+                ledger.notify_writer_waiters();
             }
         });
         finalizer
@@ -732,6 +1005,7 @@ where
             )
             .field("writer_done", &self.writer_done.load(Ordering::Acquire))
             .field("last_flush", &self.last_flush.load())
+            .field("dropping_data_file_id", &self.dropping_data_file_id.load())
             .finish_non_exhaustive()
     }
 }
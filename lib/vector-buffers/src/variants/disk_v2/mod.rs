@@ -176,19 +176,24 @@ use std::{
     num::NonZeroU64,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use async_trait::async_trait;
 use snafu::{ResultExt, Snafu};
+use tokio::sync::Mutex;
 use vector_common::finalization::Finalizable;
 
 mod backed_archive;
 mod common;
+mod encryption;
+pub mod inspect;
 mod io;
 mod ledger;
 mod reader;
 mod record;
 mod ser;
+mod shared;
 mod writer;
 
 #[cfg(test)]
@@ -197,9 +202,11 @@ mod tests;
 use self::ledger::Ledger;
 pub use self::{
     common::{DiskBufferConfig, DiskBufferConfigBuilder},
+    encryption::{EncryptionKey, EncryptionKeyError, RecordCipher},
     io::{Filesystem, ProductionFilesystem},
     ledger::LedgerLoadCreateError,
     reader::{BufferReader, ReaderError},
+    shared::SharedBufferReader,
     writer::{BufferWriter, WriterError},
 };
 use crate::{
@@ -268,6 +275,7 @@ where
             .context(ReaderSeekFailedSnafu)?;
 
         ledger.synchronize_buffer_usage();
+        ledger.spawn_expired_data_file_sweep();
 
         Ok((writer, reader, ledger))
     }
@@ -301,6 +309,10 @@ pub struct DiskV2Buffer {
     id: String,
     data_dir: PathBuf,
     max_size: NonZeroU64,
+    max_events: Option<NonZeroU64>,
+    max_age: Option<Duration>,
+    encryption: Option<RecordCipher>,
+    shared_group: Option<String>,
 }
 
 impl DiskV2Buffer {
@@ -309,8 +321,82 @@ impl DiskV2Buffer {
             id,
             data_dir,
             max_size,
+            max_events: None,
+            max_age: None,
+            encryption: None,
+            shared_group: None,
         }
     }
+
+    /// Sets the maximum number of events the buffer can hold.
+    #[must_use]
+    pub fn with_max_events(mut self, max_events: Option<NonZeroU64>) -> Self {
+        self.max_events = max_events;
+        self
+    }
+
+    /// Sets the maximum age of records in the buffer, after which they are expired.
+    #[must_use]
+    pub fn with_max_age(mut self, max_age: Option<Duration>) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Sets the cipher used to encrypt records written to the buffer.
+    #[must_use]
+    pub fn with_encryption(mut self, encryption: Option<RecordCipher>) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
+    /// Sets the name of the shared disk buffer to read from.
+    ///
+    /// Shared disk buffers are stored separately from the buffers of individual sinks.
+    #[must_use]
+    pub fn with_shared_group(mut self, shared_group: Option<String>) -> Self {
+        self.shared_group = shared_group;
+        self
+    }
+
+    fn buffer_path(&self) -> PathBuf {
+        match &self.shared_group {
+            Some(group) => get_shared_disk_v2_data_dir_path(&self.data_dir, group),
+            None => get_disk_v2_data_dir_path(&self.data_dir, &self.id),
+        }
+    }
+
+    /// Converts this buffer into a sender and a receiver for each of the given readers, which all
+    /// read every record written to the buffer, while the senders all write to the same writer, so
+    /// each record must only be sent through one of them.
+    ///
+    /// Each reader tracks the records it has acknowledged separately, so that it can pick up where
+    /// it left off after a restart, while records are only removed from the buffer once every
+    /// reader has acknowledged them.
+    pub(crate) async fn into_shared_buffer_parts<T>(
+        self,
+        reader_ids: &[String],
+        usage_handle: BufferUsageHandle,
+    ) -> Result<Vec<(SenderAdapter<T>, ReceiverAdapter<T>)>, Box<dyn Error + Send + Sync>>
+    where
+        T: Bufferable + Clone,
+    {
+        let (writer, reader) = build_disk_v2_buffer(
+            usage_handle,
+            self.buffer_path(),
+            self.max_size,
+            self.max_events,
+            self.max_age,
+            self.encryption,
+        )
+        .await?;
+        let readers = shared::split_reader(reader, reader_ids).await?;
+        let writer = Arc::new(Mutex::new(writer));
+
+        Ok(readers
+            .into_iter()
+            .map(|reader| (SenderAdapter::DiskV2(Arc::clone(&writer)), reader.into()))
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -328,9 +414,11 @@ where
     ) -> Result<(SenderAdapter<T>, ReceiverAdapter<T>), Box<dyn Error + Send + Sync>> {
         let (writer, reader) = build_disk_v2_buffer(
             usage_handle,
-            &self.data_dir,
-            self.id.as_str(),
+            self.buffer_path(),
             self.max_size,
+            self.max_events,
+            self.max_age,
+            self.encryption,
         )
         .await?;
 
@@ -340,9 +428,11 @@ where
 
 async fn build_disk_v2_buffer<T>(
     usage_handle: BufferUsageHandle,
-    data_dir: &Path,
-    id: &str,
+    buffer_path: PathBuf,
     max_size: NonZeroU64,
+    max_events: Option<NonZeroU64>,
+    max_age: Option<Duration>,
+    encryption: Option<RecordCipher>,
 ) -> Result<
     (
         BufferWriter<T, ProductionFilesystem>,
@@ -353,12 +443,24 @@ async fn build_disk_v2_buffer<T>(
 where
     T: Bufferable + Clone,
 {
-    usage_handle.set_buffer_limits(Some(max_size.get()), None);
+    let max_events = max_events.map(NonZeroU64::get);
+    usage_handle.set_buffer_limits(
+        Some(max_size.get()),
+        max_events.map(|n| usize::try_from(n).unwrap_or(usize::MAX)),
+    );
 
-    let buffer_path = get_disk_v2_data_dir_path(data_dir, id);
-    let config = DiskBufferConfigBuilder::from_path(buffer_path)
-        .max_buffer_size(max_size.get())
-        .build()?;
+    let mut builder =
+        DiskBufferConfigBuilder::from_path(buffer_path).max_buffer_size(max_size.get());
+    if let Some(max_events) = max_events {
+        builder = builder.max_events(max_events);
+    }
+    if let Some(max_age) = max_age {
+        builder = builder.max_age(max_age);
+    }
+    if let Some(cipher) = encryption {
+        builder = builder.encryption(cipher);
+    }
+    let config = builder.build()?;
     Buffer::from_config(config, usage_handle)
         .await
         .map_err(Into::into)
@@ -367,3 +469,7 @@ where
 pub(crate) fn get_disk_v2_data_dir_path(base_dir: &Path, buffer_id: &str) -> PathBuf {
     base_dir.join("buffer").join("v2").join(buffer_id)
 }
+
+pub(crate) fn get_shared_disk_v2_data_dir_path(base_dir: &Path, group: &str) -> PathBuf {
+    base_dir.join("buffer").join("v2-shared").join(group)
+}
@@ -1,5 +1,7 @@
 use std::{
-    cmp, fmt,
+    cmp,
+    collections::HashSet,
+    fmt,
     io::{self, ErrorKind},
     marker::PhantomData,
     num::NonZeroU64,
@@ -10,11 +12,15 @@ use std::{
 use crc32fast::Hasher;
 use rkyv::{archived_root, AlignedVec};
 use snafu::{ResultExt, Snafu};
-use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    time::Instant,
+};
 use vector_common::{finalization::BatchNotifier, finalizer::OrderedFinalizer};
 
 use super::{
-    common::create_crc32c_hasher,
+    common::{create_crc32c_hasher, DiskBufferConfig},
+    encryption::{RecordCipher, ENCRYPTED_RECORD_FLAG},
     ledger::Ledger,
     record::{validate_record_archive, ArchivedRecord, Record, RecordStatus},
     Filesystem,
@@ -108,6 +114,17 @@ where
     #[snafu(display("record version not compatible: {}", reason))]
     Incompatible { reason: String },
 
+    /// The record could not be decrypted.
+    ///
+    /// This can occur when the record was encrypted with a key that is no longer configured, either
+    /// as the current key or as a previous key, or when no key is configured at all.  As the
+    /// authenticity of the record is also verified during decryption, this can also indicate that
+    /// the record was modified on disk.
+    ///
+    /// This is effectively the same class of error as an invalid checksum/failed deserialization.
+    #[snafu(display("failed to decrypt record: {}", reason))]
+    Decryption { reason: String },
+
     /// The reader detected that a data file contains a partially-written record.
     ///
     /// Records should never be partially written to a data file (we don't split records across data
@@ -134,6 +151,7 @@ where
             self,
             ReaderError::Checksum { .. }
                 | ReaderError::Deserialization { .. }
+                | ReaderError::Decryption { .. }
                 | ReaderError::PartialWrite
         )
     }
@@ -145,6 +163,7 @@ where
             ReaderError::Checksum { .. } => "checksum_mismatch",
             ReaderError::Decode { .. } => "decode_failed",
             ReaderError::Incompatible { .. } => "incompatible_record_version",
+            ReaderError::Decryption { .. } => "decryption_failed",
             ReaderError::PartialWrite => "partial_write",
             ReaderError::EmptyRecord => "empty_record",
         }
@@ -160,6 +179,7 @@ where
             | ReaderError::Checksum { .. }
             | ReaderError::Decode { .. }
             | ReaderError::Incompatible { .. }
+            | ReaderError::Decryption { .. }
             | ReaderError::PartialWrite => Some(BufferReadError { error_code, error }),
         }
     }
@@ -189,6 +209,9 @@ impl<T: Bufferable> PartialEq for ReaderError<T> {
             (Self::Incompatible { reason: l_reason }, Self::Incompatible { reason: r_reason }) => {
                 l_reason == r_reason
             }
+            (Self::Decryption { reason: l_reason }, Self::Decryption { reason: r_reason }) => {
+                l_reason == r_reason
+            }
             _ => core::mem::discriminant(self) == core::mem::discriminant(other),
         }
     }
@@ -199,6 +222,8 @@ pub(super) struct RecordReader<R, T> {
     reader: BufReader<R>,
     aligned_buf: AlignedVec,
     checksummer: Hasher,
+    cipher: Option<RecordCipher>,
+    decrypt_buf: Vec<u8>,
     current_record_id: u64,
     _t: PhantomData<T>,
 }
@@ -217,11 +242,20 @@ where
             reader: BufReader::with_capacity(256 * 1024, reader),
             aligned_buf: AlignedVec::new(),
             checksummer: create_crc32c_hasher(),
+            cipher: None,
+            decrypt_buf: Vec::new(),
             current_record_id: 0,
             _t: PhantomData,
         }
     }
 
+    /// Sets the cipher used to decrypt encrypted records.
+    #[must_use]
+    pub fn with_encryption(mut self, cipher: Option<RecordCipher>) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
     #[cfg_attr(test, instrument(skip(self), level = "trace"))]
     async fn read_length_delimiter(
         &mut self,
@@ -374,7 +408,7 @@ where
         // - `try_next_record` does all the archive checks, checksum validation, etc
         let record = unsafe { archived_root::<Record<'_>>(&self.aligned_buf) };
 
-        decode_record_payload(record)
+        decode_record_payload(record, self.cipher.as_ref(), &mut self.decrypt_buf)
     }
 }
 
@@ -387,6 +421,7 @@ where
             .field("reader", &self.reader)
             .field("aligned_buf", &self.aligned_buf)
             .field("checksummer", &self.checksummer)
+            .field("cipher", &self.cipher)
             .field("current_record_id", &self.current_record_id)
             .finish()
     }
@@ -406,6 +441,8 @@ where
     data_file_record_count: u64,
     data_file_marked_record_count: u64,
     ready_to_read: bool,
+    data_file_expires_at: Option<Instant>,
+    expired_record_ids: HashSet<u64>,
     record_acks: OrderedAcknowledgements<u64, u64>,
     data_file_acks: OrderedAcknowledgements<u64, (PathBuf, u64)>,
     finalizer: OrderedFinalizer<u64>,
@@ -432,6 +469,8 @@ where
             data_file_record_count: 0,
             data_file_marked_record_count: 0,
             ready_to_read: false,
+            data_file_expires_at: None,
+            expired_record_ids: HashSet::new(),
             record_acks: OrderedAcknowledgements::from_acked(next_expected_record_id),
             data_file_acks: OrderedAcknowledgements::from_acked(0),
             finalizer,
@@ -439,10 +478,27 @@ where
         }
     }
 
+    /// Gets the configuration of the buffer this reader is reading from.
+    pub(super) fn config(&self) -> &DiskBufferConfig<FS> {
+        self.ledger.config()
+    }
+
+    /// Gets the ID of the last event in the most recently read record.
+    pub(super) fn last_record_id(&self) -> u64 {
+        self.last_reader_record_id
+    }
+
     fn reset(&mut self) {
         self.reader = None;
         self.bytes_read = 0;
         self.data_file_start_record_id = None;
+        self.data_file_expires_at = None;
+    }
+
+    /// Whether or not the records in the current data file have expired.
+    fn is_data_file_expired(&self) -> bool {
+        self.data_file_expires_at
+            .is_some_and(|expires_at| Instant::now() >= expires_at)
     }
 
     fn track_read(&mut self, record_id: u64, record_bytes: u64, event_count: NonZeroU64) {
@@ -511,11 +567,22 @@ where
         // occur at all, so we're relying on this method to correct the buffer size for us.  This is
         // why `bytes_read` is optional: when it's specified, we calculate a delta for handling
         // partial-read scenarios, otherwise, we just use the entire data file size as is.
-        let data_file = self
+        //
+        // The data file may have already been dropped by the writer to make room for newer records,
+        // in which case it has already been accounted for, and there's nothing left to delete.
+        let data_file = match self
             .ledger
             .filesystem()
             .open_file_readable(&data_file_path)
-            .await?;
+            .await
+        {
+            Ok(data_file) => data_file,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                self.ledger.increment_acked_reader_file_id();
+                return self.ledger.flush();
+            }
+            Err(e) => return Err(e),
+        };
         let metadata = data_file.metadata().await?;
 
         let decrease_amount = bytes_read.map_or_else(
@@ -578,12 +645,14 @@ where
         let mut events_acknowledged: u64 = 0;
         let mut events_skipped: u64 = 0;
         let mut bytes_acknowledged: u64 = 0;
+        let mut events_expired: u64 = 0;
+        let mut bytes_expired: u64 = 0;
 
         let consumed_acks = self.ledger.consume_pending_acks();
         if consumed_acks > 0 {
             self.record_acks.add_acknowledgements(consumed_acks);
 
-            while let Some(EligibleMarker { len, data, .. }) =
+            while let Some(EligibleMarker { id, len, data }) =
                 self.record_acks.get_next_eligible_marker()
             {
                 had_eligible_records = true;
@@ -614,14 +683,27 @@ where
                         bytes_acknowledged = bytes_acknowledged.checked_add(record_bytes).expect(
                             "acknowledging more than 2^64 bytes at a time is obviously a bug",
                         );
+
+                        // Expired records are acknowledged like any other record, as soon as
+                        // they're read, so we account for them separately.
+                        if self.expired_record_ids.remove(&id) {
+                            events_expired += len;
+                            bytes_expired += record_bytes;
+                        }
                     }
                 }
             }
 
             // We successfully processed at least one record, so update our buffer and ledger accounting.
             if had_eligible_records {
-                self.ledger
-                    .track_reads(events_acknowledged, bytes_acknowledged);
+                self.ledger.track_reads(
+                    events_acknowledged - events_expired,
+                    bytes_acknowledged - bytes_expired,
+                );
+                if events_expired > 0 {
+                    self.ledger
+                        .track_expired_reads(events_expired, bytes_expired);
+                }
 
                 // We need to account for skipped events, too, so that our "last reader record ID"
                 // value stays correct as we process these gap markers.
@@ -635,8 +717,14 @@ where
             }
 
             // If any events were skipped, do our logging/metrics for that.
-            if events_skipped > 0 {
-                self.ledger.track_dropped_events(events_skipped);
+            //
+            // Events in data files dropped to make room for newer records, or because they expired,
+            // show up as gaps, too, but they've already been accounted for when they were dropped.
+            let events_dropped_with_data_files =
+                self.ledger.consume_dropped_data_file_events(events_skipped);
+            if events_skipped > events_dropped_with_data_files {
+                self.ledger
+                    .track_dropped_events(events_skipped - events_dropped_with_data_files);
             }
         }
 
@@ -754,6 +842,14 @@ where
         // implies a data file existing.
         loop {
             let (reader_file_id, writer_file_id) = self.ledger.get_current_reader_writer_file_id();
+
+            // If this data file is in the middle of being dropped, wait for that to finish, at which
+            // point we'll find the data file missing.
+            if self.ledger.is_data_file_being_dropped(reader_file_id) {
+                self.ledger.wait_for_writer().await;
+                continue;
+            }
+
             let data_file_path = self.ledger.get_current_reader_data_file_path();
             let data_file = match self
                 .ledger
@@ -770,6 +866,15 @@ where
                                 "Data file does not yet exist. Waiting for writer to create."
                             );
                             self.ledger.wait_for_writer().await;
+                        } else if self.ledger.get_unacked_reader_file_id_offset() > 0 {
+                            // This data file was dropped, to make room for newer records or because
+                            // it expired, but we still have data files pending acknowledgement, so
+                            // we roll past it like any other data file to keep them in order.
+                            debug!(
+                                data_file_path = data_file_path.to_string_lossy().as_ref(),
+                                "Data file was dropped. Skipping."
+                            );
+                            self.roll_to_next_data_file();
                         } else {
                             self.ledger.increment_acked_reader_file_id();
                         }
@@ -785,7 +890,23 @@ where
                 "Opened data file for reading."
             );
 
-            self.reader = Some(RecordReader::new(data_file));
+            // Records are expired a data file at a time, based on when the data file was created,
+            // since the writer limits how long it writes to any given data file. We use the
+            // creation time tracked by the writer, and only fall back to the metadata of data files
+            // the writer hasn't opened, such as those left over from a previous run.
+            if let Some(max_age) = self.ledger.config().max_age {
+                let created = match self.ledger.get_data_file_created(&data_file_path) {
+                    Some(created) => Some(created),
+                    None => data_file.metadata().await?.created_instant(),
+                };
+                self.data_file_expires_at =
+                    created.and_then(|created| created.checked_add(max_age));
+            }
+
+            self.reader = Some(
+                RecordReader::new(data_file)
+                    .with_encryption(self.ledger.config().encryption.clone()),
+            );
             return Ok(());
         }
     }
@@ -851,7 +972,9 @@ where
                     let record = try_as_record_archive(data_file_mmap.as_ref())
                         .expect("record was already validated");
 
-                    let Ok(item) = decode_record_payload::<T>(record) else {
+                    let cipher = self.ledger.config().encryption.as_ref();
+                    let Ok(item) = decode_record_payload::<T>(record, cipher, &mut Vec::new())
+                    else {
                         // If there's an error decoding the item, just fall back to the slow path,
                         // because this file might actually be where we left off, so we don't want
                         // to incorrectly skip ahead or anything.
@@ -957,78 +1080,156 @@ where
     pub async fn next(&mut self) -> Result<Option<T>, ReaderError<T>> {
         let mut force_check_pending_data_files = false;
 
-        let token = loop {
-            // Handle any pending acknowledgements first.
-            self.handle_pending_acknowledgements(force_check_pending_data_files)
-                .await
-                .context(IoSnafu)?;
-            force_check_pending_data_files = false;
+        loop {
+            let token = loop {
+                // Handle any pending acknowledgements first.
+                self.handle_pending_acknowledgements(force_check_pending_data_files)
+                    .await
+                    .context(IoSnafu)?;
+                force_check_pending_data_files = false;
+
+                // If the writer has marked themselves as done, and the buffer has been emptied, then
+                // we're done and can return.  We have to look at something besides simply the writer
+                // being marked as done to know if we're actually done or not, and "buffer size" is better
+                // than "total records" because we update buffer size when handling acknowledgements,
+                // whether it's an individual ack or an entire file being deleted.
+                //
+                // If we used "total records", we could end up stuck in cases where we skipped
+                // corrupted records, but hadn't yet had a "good" record that we could read, since the
+                // "we skipped records due to corruption" logic requires performing valid read to
+                // detect, and calculate a valid delta from.
+                if self.ledger.is_writer_done() {
+                    let total_buffer_size = self.ledger.get_total_buffer_size();
+                    if total_buffer_size == 0 {
+                        return Ok(None);
+                    }
+                }
 
-            // If the writer has marked themselves as done, and the buffer has been emptied, then
-            // we're done and can return.  We have to look at something besides simply the writer
-            // being marked as done to know if we're actually done or not, and "buffer size" is better
-            // than "total records" because we update buffer size when handling acknowledgements,
-            // whether it's an individual ack or an entire file being deleted.
-            //
-            // If we used "total records", we could end up stuck in cases where we skipped
-            // corrupted records, but hadn't yet had a "good" record that we could read, since the
-            // "we skipped records due to corruption" logic requires performing valid read to
-            // detect, and calculate a valid delta from.
-            if self.ledger.is_writer_done() {
-                let total_buffer_size = self.ledger.get_total_buffer_size();
-                if total_buffer_size == 0 {
-                    return Ok(None);
+                self.ensure_ready_for_read().await.context(IoSnafu)?;
+
+                let reader = self
+                    .reader
+                    .as_mut()
+                    .expect("reader should exist after `ensure_ready_for_read`");
+
+                let (reader_file_id, writer_file_id) =
+                    self.ledger.get_current_reader_writer_file_id();
+
+                // Essentially: is the writer still writing to this data file or not, and are we
+                // actually ready to read (aka initialized)?
+                //
+                // This is a necessary invariant to understand if the record reader should actually keep
+                // waiting for data, or if a data file had a partial write/missing data and should be
+                // skipped. In particular, not only does this matter for deadlocking during shutdown due
+                // to improper writer behavior/flushing, but it also matters during initialization in
+                // case where the current data file had a partial write.
+                let is_finalized = (reader_file_id != writer_file_id) || !self.ready_to_read;
+
+                // Try reading a record, which if successful, gives us a token to actually read/get a
+                // reference to the record.  This is a slightly-tricky song-and-dance due to rustc not
+                // yet fully understanding mutable borrows when conditional control flow is involved.
+                match reader.try_next_record(is_finalized).await {
+                    // Not even enough data to read a length delimiter, so we need to wait for the
+                    // writer to signal us that there's some actual data to read.
+                    Ok(None) => {}
+                    // We got a valid record, so keep the token.
+                    Ok(Some(token)) => break token,
+                    // A length-delimited payload was read, but we failed to deserialize it as a valid
+                    // record, or we deserialized it and the checksum was invalid.  Either way, we're not
+                    // sure the rest of the data file is even valid, so roll to the next file.
+                    //
+                    // TODO: Explore the concept of putting a data file into a "one more attempt to read
+                    // a valid record" state, almost like a semi-open circuit breaker.  There's a
+                    // possibility that the length delimiter we got is valid, and all the data was
+                    // written for the record, but the data was invalid... and that if we just kept
+                    // reading, we might actually encounter a valid record.
+                    //
+                    // Theoretically, based on both the validation done by `rkyv` and the checksum, it
+                    // should be incredibly unlikely to read a valid record after getting a
+                    // corrupted record if there was missing data or more invalid data.  We use
+                    // checksumming to assert errors within a given chunk of the payload, so one payload
+                    // being corrupted doesn't always, in fact, mean that other records after it are
+                    // corrupted too.
+                    Err(e) => {
+                        // Invalid checksums and deserialization failures can't really be acted upon by
+                        // the caller, but they might be expecting a read-after-write behavior, so we
+                        // return the error to them after ensuring that we roll to the next file first.
+                        if e.is_bad_read() {
+                            self.roll_to_next_data_file();
+                        }
+
+                        return Err(e);
+                    }
+                };
+
+                // Fundamentally, when `try_read_record` returns `None`, there's three possible
+                // scenarios:
+                //
+                // 1. we are entirely caught up to the writer
+                // 2. we've hit the end of the data file and need to go to the next one
+                // 3. the writer has closed/dropped/finished/etc
+                //
+                // When we're at this point, we check the reader/writer file IDs.  If the file IDs are
+                // not identical, we now know the writer has moved on.  Crucially, since we always flush
+                // our writes before waking up, including before moving to a new file, then we know that
+                // if the reader/writer were not identical at the start the loop, and `try_read_record`
+                // returned `None`, that we have hit the actual end of the reader's current data file,
+                // and need to move on.
+                //
+                // If the file IDs were identical, it would imply that reader is still on the writer's
+                // current data file. We then "wait" for the writer to wake us up. It may lead to the
+                // same thing -- `try_read_record` returning `None` with an identical reader/writer file
+                // ID -- but that's OK, because it would mean we were actually waiting for the writer to
+                // make progress now.  If the wake-up was valid, due to writer progress, then, well...
+                // we'd actually be able to read data.
+                //
+                // The case of "the writer has closed/dropped/finished/etc" is handled at the top of the
+                // loop, because otherwise we could get stuck waiting for the writer after an empty
+                // `try_read_record` attempt when the writer is done and we're at the end of the file,
+                // etc.
+                if self.ready_to_read {
+                    if reader_file_id != writer_file_id {
+                        debug!(
+                            reader_file_id,
+                            writer_file_id, "Reached the end of current data file."
+                        );
+
+                        self.roll_to_next_data_file();
+                        force_check_pending_data_files = true;
+                        continue;
+                    }
+
+                    self.ledger.wait_for_writer().await;
+                } else {
+                    debug!(
+                        bytes_read = self.bytes_read,
+                        "Current data file has no more data."
+                    );
+
+                    if reader_file_id == writer_file_id {
+                        // We're currently just seeking to where we left off the last time this buffer was
+                        // running, which might mean there's no records for us to read at all because we
+                        // were already caught up.  All we can do is signal to `seek_to_next_record` that
+                        // we're caught up.
+                        return Ok(None);
+                    }
                 }
-            }
+            };
 
-            self.ensure_ready_for_read().await.context(IoSnafu)?;
+            // We got a read token, so our record is present in the reader, and now we can actually read
+            // it out and return it.
+            let record_id = token.record_id();
+            let record_bytes = token.record_bytes() as u64;
 
             let reader = self
                 .reader
                 .as_mut()
                 .expect("reader should exist after `ensure_ready_for_read`");
-
-            let (reader_file_id, writer_file_id) = self.ledger.get_current_reader_writer_file_id();
-
-            // Essentially: is the writer still writing to this data file or not, and are we
-            // actually ready to read (aka initialized)?
-            //
-            // This is a necessary invariant to understand if the record reader should actually keep
-            // waiting for data, or if a data file had a partial write/missing data and should be
-            // skipped. In particular, not only does this matter for deadlocking during shutdown due
-            // to improper writer behavior/flushing, but it also matters during initialization in
-            // case where the current data file had a partial write.
-            let is_finalized = (reader_file_id != writer_file_id) || !self.ready_to_read;
-
-            // Try reading a record, which if successful, gives us a token to actually read/get a
-            // reference to the record.  This is a slightly-tricky song-and-dance due to rustc not
-            // yet fully understanding mutable borrows when conditional control flow is involved.
-            match reader.try_next_record(is_finalized).await {
-                // Not even enough data to read a length delimiter, so we need to wait for the
-                // writer to signal us that there's some actual data to read.
-                Ok(None) => {}
-                // We got a valid record, so keep the token.
-                Ok(Some(token)) => break token,
-                // A length-delimited payload was read, but we failed to deserialize it as a valid
-                // record, or we deserialized it and the checksum was invalid.  Either way, we're not
-                // sure the rest of the data file is even valid, so roll to the next file.
-                //
-                // TODO: Explore the concept of putting a data file into a "one more attempt to read
-                // a valid record" state, almost like a semi-open circuit breaker.  There's a
-                // possibility that the length delimiter we got is valid, and all the data was
-                // written for the record, but the data was invalid... and that if we just kept
-                // reading, we might actually encounter a valid record.
-                //
-                // Theoretically, based on both the validation done by `rkyv` and the checksum, it
-                // should be incredibly unlikely to read a valid record after getting a
-                // corrupted record if there was missing data or more invalid data.  We use
-                // checksumming to assert errors within a given chunk of the payload, so one payload
-                // being corrupted doesn't always, in fact, mean that other records after it are
-                // corrupted too.
+            let mut record = match reader.read_record(token) {
+                Ok(record) => record,
                 Err(e) => {
-                    // Invalid checksums and deserialization failures can't really be acted upon by
-                    // the caller, but they might be expecting a read-after-write behavior, so we
-                    // return the error to them after ensuring that we roll to the next file first.
+                    // Records that can't be decrypted are handled like corrupted records, since
+                    // every other record in the data file was likely encrypted with the same key.
                     if e.is_bad_read() {
                         self.roll_to_next_data_file();
                     }
@@ -1037,106 +1238,69 @@ where
                 }
             };
 
-            // Fundamentally, when `try_read_record` returns `None`, there's three possible
-            // scenarios:
-            //
-            // 1. we are entirely caught up to the writer
-            // 2. we've hit the end of the data file and need to go to the next one
-            // 3. the writer has closed/dropped/finished/etc
-            //
-            // When we're at this point, we check the reader/writer file IDs.  If the file IDs are
-            // not identical, we now know the writer has moved on.  Crucially, since we always flush
-            // our writes before waking up, including before moving to a new file, then we know that
-            // if the reader/writer were not identical at the start the loop, and `try_read_record`
-            // returned `None`, that we have hit the actual end of the reader's current data file,
-            // and need to move on.
-            //
-            // If the file IDs were identical, it would imply that reader is still on the writer's
-            // current data file. We then "wait" for the writer to wake us up. It may lead to the
-            // same thing -- `try_read_record` returning `None` with an identical reader/writer file
-            // ID -- but that's OK, because it would mean we were actually waiting for the writer to
-            // make progress now.  If the wake-up was valid, due to writer progress, then, well...
-            // we'd actually be able to read data.
-            //
-            // The case of "the writer has closed/dropped/finished/etc" is handled at the top of the
-            // loop, because otherwise we could get stuck waiting for the writer after an empty
-            // `try_read_record` attempt when the writer is done and we're at the end of the file,
-            // etc.
-            if self.ready_to_read {
-                if reader_file_id != writer_file_id {
-                    debug!(
-                        reader_file_id,
-                        writer_file_id, "Reached the end of current data file."
-                    );
-
-                    self.roll_to_next_data_file();
-                    force_check_pending_data_files = true;
-                    continue;
-                }
+            let record_events: u64 = record
+                .event_count()
+                .try_into()
+                .expect("Event count for a record cannot exceed 2^64 events.");
+            let record_events = record_events
+                .try_into()
+                .map_err(|_| ReaderError::EmptyRecord)?;
+            self.track_read(record_id, record_bytes, record_events);
+
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            record.add_batch_notifier(batch);
+            self.finalizer.add(record_events.get(), receiver);
+
+            // If the record has expired, we drop it, which acknowledges it right away, and move on to
+            // the next record.  We track expired records separately so they can be reported as such
+            // once their acknowledgement is processed.
+            if self.ready_to_read && self.is_data_file_expired() {
+                drop(record);
+                self.expired_record_ids.insert(record_id);
 
-                self.ledger.wait_for_writer().await;
-            } else {
-                debug!(
-                    bytes_read = self.bytes_read,
-                    "Current data file has no more data."
+                trace!(
+                    record_id,
+                    record_events,
+                    record_bytes,
+                    data_file_id = self.ledger.get_current_reader_file_id(),
+                    "Expired record."
                 );
 
-                if reader_file_id == writer_file_id {
-                    // We're currently just seeking to where we left off the last time this buffer was
-                    // running, which might mean there's no records for us to read at all because we
-                    // were already caught up.  All we can do is signal to `seek_to_next_record` that
-                    // we're caught up.
-                    return Ok(None);
-                }
+                continue;
             }
-        };
 
-        // We got a read token, so our record is present in the reader, and now we can actually read
-        // it out and return it.
-        let record_id = token.record_id();
-        let record_bytes = token.record_bytes() as u64;
-
-        let reader = self
-            .reader
-            .as_mut()
-            .expect("reader should exist after `ensure_ready_for_read`");
-        let mut record = reader.read_record(token)?;
-
-        let record_events: u64 = record
-            .event_count()
-            .try_into()
-            .expect("Event count for a record cannot exceed 2^64 events.");
-        let record_events = record_events
-            .try_into()
-            .map_err(|_| ReaderError::EmptyRecord)?;
-        self.track_read(record_id, record_bytes, record_events);
-
-        let (batch, receiver) = BatchNotifier::new_with_receiver();
-        record.add_batch_notifier(batch);
-        self.finalizer.add(record_events.get(), receiver);
+            if self.ready_to_read {
+                trace!(
+                    record_id,
+                    record_events,
+                    record_bytes,
+                    data_file_id = self.ledger.get_current_reader_file_id(),
+                    "Read record."
+                );
+            }
 
-        if self.ready_to_read {
-            trace!(
-                record_id,
-                record_events,
-                record_bytes,
-                data_file_id = self.ledger.get_current_reader_file_id(),
-                "Read record."
-            );
+            Ok(Some(record))
         }
-
-        Ok(Some(record))
     }
 }
 
 pub(crate) fn decode_record_payload<T: Bufferable>(
     record: &ArchivedRecord<'_>,
+    cipher: Option<&RecordCipher>,
+    decrypt_buf: &mut Vec<u8>,
 ) -> Result<T, ReaderError<T>> {
+    // Encrypted records are flagged as such in the raw record metadata, which we strip off before
+    // handing the metadata to `T`, as the payload is decrypted before it gets decoded.
+    let raw_metadata = record.metadata();
+    let is_encrypted = raw_metadata & ENCRYPTED_RECORD_FLAG != 0;
+
     // Try and convert the raw record metadata into the true metadata type used by `T`, and then
     // also verify that `T` is able to decode records with the metadata used for this record in particular.
-    let metadata = T::Metadata::from_u32(record.metadata()).ok_or(ReaderError::Incompatible {
-        reason: format!("invalid metadata for {}", std::any::type_name::<T>()),
-    })?;
+    let metadata = T::Metadata::from_u32(raw_metadata & !ENCRYPTED_RECORD_FLAG).ok_or(
+        ReaderError::Incompatible {
+            reason: format!("invalid metadata for {}", std::any::type_name::<T>()),
+        },
+    )?;
 
     if !T::can_decode(metadata) {
         return Err(ReaderError::Incompatible {
@@ -1147,6 +1311,20 @@ pub(crate) fn decode_record_payload<T: Bufferable>(
         });
     }
 
+    let payload = if is_encrypted {
+        let cipher = cipher.ok_or_else(|| ReaderError::Decryption {
+            reason: "record is encrypted, but no encryption key is configured".to_string(),
+        })?;
+        cipher
+            .decrypt(record.id(), raw_metadata, record.payload(), decrypt_buf)
+            .map_err(|e| ReaderError::Decryption {
+                reason: e.to_string(),
+            })?;
+        decrypt_buf.as_slice()
+    } else {
+        record.payload()
+    };
+
     // Now we can finally try decoding.
-    T::decode(metadata, record.payload()).context(DecodeSnafu)
+    T::decode(metadata, payload).context(DecodeSnafu)
 }
@@ -0,0 +1,259 @@
+//! Shared disk buffers.
+//!
+//! A shared disk buffer is written to by a single writer, like any other disk buffer, but is read
+//! by multiple readers, each of which is sent every record in the buffer.  This allows fanning out
+//! events to multiple sinks while only writing them to disk once.
+//!
+//! Under the hood, a single [`BufferReader`] reads each record from disk, and hands a copy of it to
+//! every reader.  Since the copies share the finalizers of the original record, a record is only
+//! acknowledged in the buffer itself -- and its data file eventually deleted -- once every reader
+//! has acknowledged it.
+//!
+//! Each reader also tracks the ID of the last record it has acknowledged, in its own position file
+//! next to the ledger, so that records acknowledged by some readers but not others are only sent
+//! again to the readers that had not yet acknowledged them when the buffer is reopened.
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use futures::{future::join_all, StreamExt};
+use tokio::{fs, io::AsyncWriteExt, select, sync::mpsc, time::interval};
+use vector_common::{
+    finalization::BatchNotifier, finalizer::OrderedFinalizer, internal_event::emit,
+};
+
+use super::{BufferReader, Filesystem};
+use crate::{spawn_named, Bufferable};
+
+/// The number of records that can be waiting to be read by a reader of a shared buffer.
+///
+/// Readers can read ahead of each other by this many records, after which the buffer waits for the
+/// slowest reader to catch up before reading any further.  Records are not removed from disk until
+/// every reader has acknowledged them, regardless of this limit.
+const SHARED_READER_QUEUE_LEN: usize = 128;
+
+/// Gets the path of the position file for the given reader, within the given buffer directory.
+pub(super) fn get_reader_position_path(data_dir: &Path, reader_id: &str) -> PathBuf {
+    data_dir.join(format!("buffer-reader-{reader_id}.db"))
+}
+
+/// Reads records from a shared buffer.
+#[derive(Debug)]
+pub struct SharedBufferReader<T> {
+    rx: mpsc::Receiver<T>,
+}
+
+impl<T> SharedBufferReader<T>
+where
+    T: Bufferable,
+{
+    /// Reads a record.
+    ///
+    /// If the writer is closed and every record in the buffer has been read, `None` is returned.
+    /// Otherwise, reads the next record or waits until the next record is available.
+    pub async fn next(&mut self) -> Option<T> {
+        self.rx.recv().await
+    }
+}
+
+/// The position of a reader in a shared buffer.
+///
+/// This is the ID of the last event in the last record that the reader has acknowledged, which is
+/// persisted to disk as a big-endian integer.
+struct ReaderPosition {
+    path: PathBuf,
+}
+
+impl ReaderPosition {
+    async fn load(&self) -> io::Result<u64> {
+        match fs::read(&self.path).await {
+            Ok(buf) => <[u8; 8]>::try_from(buf.as_slice())
+                .map(u64::from_be_bytes)
+                .map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "reader position file {} is corrupted",
+                            self.path.to_string_lossy()
+                        ),
+                    )
+                }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(0),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn store(&self, last_record_id: u64) -> io::Result<()> {
+        // Write to a temporary file first, and then move it over the existing position file, so that
+        // the position file is never left partially written.
+        let temp_path = self.path.with_extension("db.tmp");
+        let mut file = fs::File::create(&temp_path).await?;
+        file.write_all(&last_record_id.to_be_bytes()).await?;
+        file.sync_all().await?;
+        drop(file);
+
+        fs::rename(&temp_path, &self.path).await
+    }
+}
+
+struct SharedReader<T> {
+    id: String,
+    tx: mpsc::Sender<T>,
+    finalizer: OrderedFinalizer<u64>,
+    last_acked_record_id: u64,
+}
+
+/// Splits the given buffer reader into a reader for each of the given reader IDs.
+///
+/// # Errors
+///
+/// If the position of any reader cannot be loaded, an error is returned.
+pub(super) async fn split_reader<T, FS>(
+    reader: BufferReader<T, FS>,
+    reader_ids: &[String],
+) -> io::Result<Vec<SharedBufferReader<T>>>
+where
+    T: Bufferable + Clone,
+    FS: Filesystem + 'static,
+    FS::File: Unpin,
+{
+    let data_dir = reader.config().data_dir.clone();
+    let flush_interval = reader.config().flush_interval;
+
+    let mut readers = Vec::with_capacity(reader_ids.len());
+    let mut shared_readers = Vec::with_capacity(reader_ids.len());
+    for id in reader_ids {
+        let position = ReaderPosition {
+            path: get_reader_position_path(&data_dir, id),
+        };
+        let last_acked_record_id = position.load().await?;
+
+        let (tx, rx) = mpsc::channel(SHARED_READER_QUEUE_LEN);
+        readers.push(SharedBufferReader { rx });
+        shared_readers.push(SharedReader {
+            id: id.clone(),
+            tx,
+            finalizer: spawn_position_tracker(position, last_acked_record_id, flush_interval),
+            last_acked_record_id,
+        });
+    }
+
+    spawn_named(
+        run_shared_reader(reader, shared_readers),
+        "shared disk buffer reader",
+    );
+
+    Ok(readers)
+}
+
+/// Tracks the acknowledgements of a reader, periodically persisting its position.
+fn spawn_position_tracker(
+    position: ReaderPosition,
+    mut last_acked_record_id: u64,
+    flush_interval: Duration,
+) -> OrderedFinalizer<u64> {
+    let (finalizer, mut stream) = OrderedFinalizer::new(None);
+    let tracker = async move {
+        let mut flush = interval(flush_interval);
+        let mut dirty = false;
+        loop {
+            select! {
+                acked = stream.next() => match acked {
+                    Some((_status, record_id)) => {
+                        last_acked_record_id = record_id;
+                        dirty = true;
+                    }
+                    None => break,
+                },
+                _ = flush.tick(), if dirty => {
+                    if let Err(error) = position.store(last_acked_record_id).await {
+                        error!(
+                            message = "Failed to persist position of shared disk buffer reader.",
+                            %error,
+                        );
+                    }
+                    dirty = false;
+                }
+            }
+        }
+
+        if dirty {
+            if let Err(error) = position.store(last_acked_record_id).await {
+                error!(
+                    message = "Failed to persist position of shared disk buffer reader.",
+                    %error,
+                );
+            }
+        }
+    };
+    spawn_named(tracker, "shared disk buffer position tracker");
+    finalizer
+}
+
+async fn run_shared_reader<T, FS>(
+    mut reader: BufferReader<T, FS>,
+    shared_readers: Vec<SharedReader<T>>,
+) where
+    T: Bufferable + Clone,
+    FS: Filesystem,
+    FS::File: Unpin,
+{
+    loop {
+        // Once every reader is closed, there's no one left to read records for, and the buffer
+        // should be closed too, even if it's waiting for more records to be written.
+        let all_closed = join_all(
+            shared_readers
+                .iter()
+                .map(|shared_reader| shared_reader.tx.closed()),
+        );
+        let next = select! {
+            next = reader.next() => next,
+            () = all_closed => break,
+        };
+
+        let record = match next {
+            Ok(Some(record)) => record,
+            Ok(None) => break,
+            Err(e) => match e.as_recoverable_error() {
+                Some(re) => {
+                    emit(re);
+                    continue;
+                }
+                None => {
+                    error!(
+                        message = "Shared disk buffer reader encountered an unrecoverable error.",
+                        error = %e,
+                    );
+                    break;
+                }
+            },
+        };
+        let record_id = reader.last_record_id();
+
+        for shared_reader in &shared_readers {
+            // Readers which already acknowledged this record before the buffer was reopened don't
+            // get it again.
+            if record_id <= shared_reader.last_acked_record_id {
+                continue;
+            }
+
+            // We only attach the reader's own batch notifier once we know the record will be
+            // delivered, as otherwise dropping it would count as the reader acknowledging it.
+            let Ok(permit) = shared_reader.tx.reserve().await else {
+                debug!(
+                    reader_id = shared_reader.id.as_str(),
+                    "Shared disk buffer reader closed."
+                );
+                continue;
+            };
+
+            let mut item = record.clone();
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            item.add_batch_notifier(batch);
+            shared_reader.finalizer.add(record_id, receiver);
+            permit.send(item);
+        }
+    }
+}
@@ -83,3 +83,40 @@ async fn ack_wakes_reader() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn outstanding_acks_dont_keep_ledger_open() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let data_dir = &data_dir;
+            let load_ledger = || async move {
+                let config = DiskBufferConfigBuilder::from_path(data_dir.clone())
+                    .build()
+                    .expect("creating buffer should not fail");
+                Ledger::load_or_create(config, BufferUsageHandle::noop()).await
+            };
+
+            // Create a standalone ledger, and hand out a record that never gets acknowledged.
+            let ledger = Arc::new(
+                load_ledger()
+                    .await
+                    .expect("ledger should not fail to load/create"),
+            );
+            let finalizer = Arc::clone(&ledger).spawn_finalizer();
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            finalizer.add(1, receiver);
+
+            // Once the ledger itself is dropped, the buffer can be opened again, even though the
+            // record is still outstanding.
+            drop(ledger);
+            load_ledger()
+                .await
+                .expect("ledger should not fail to load after being dropped");
+
+            drop(batch);
+        }
+    })
+    .await;
+}
@@ -0,0 +1,173 @@
+use super::{create_buffer_v2_with_encryption, read_next, read_next_some};
+use crate::{
+    assert_buffer_is_empty,
+    test::{acknowledge, install_tracing_helpers, with_temp_dir, SizedRecord},
+    variants::disk_v2::{
+        encryption::{EncryptionKey, RecordCipher},
+        ReaderError,
+    },
+};
+
+const KEY_A: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+const KEY_B: &str = "f0f1f2f3f4f5f6f7f8f9fafbfcfdfeffe0e1e2e3e4e5e6e7e8e9eaebecedeeef";
+
+fn cipher(current: &str, previous: &[&str]) -> RecordCipher {
+    let current = EncryptionKey::from_hex(current).expect("key should be valid");
+    let previous = previous
+        .iter()
+        .map(|key| EncryptionKey::from_hex(key).expect("key should be valid"))
+        .collect::<Vec<_>>();
+    RecordCipher::new(&current, &previous)
+}
+
+#[tokio::test]
+async fn encrypted_records_are_not_written_in_plaintext() {
+    let _a = install_tracing_helpers();
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, mut reader, ledger) =
+                create_buffer_v2_with_encryption(data_dir, Some(cipher(KEY_A, &[]))).await;
+            assert_buffer_is_empty!(ledger);
+
+            // `SizedRecord` encodes its payload as a run of identical bytes, which is trivial to
+            // spot in the data file if it isn't encrypted.
+            let record = SizedRecord::new(256);
+            writer
+                .write_record(record.clone())
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+
+            let data_file = std::fs::read(ledger.get_current_writer_data_file_path())
+                .expect("data file should exist");
+            assert!(!data_file.windows(64).any(|w| w.iter().all(|b| *b == 0x42)));
+
+            let read_record = read_next_some(&mut reader).await;
+            assert_eq!(read_record, record);
+            acknowledge(read_record).await;
+
+            assert_eq!(read_next(&mut reader).await, None);
+            assert_buffer_is_empty!(ledger);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn records_remain_readable_after_key_rotation() {
+    let _a = install_tracing_helpers();
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Write a record with the original key, and then reopen the buffer after rotating to a
+            // new key, keeping the original key as a previous key.
+            let (mut writer, _, ledger) =
+                create_buffer_v2_with_encryption(data_dir.clone(), Some(cipher(KEY_A, &[]))).await;
+
+            let first_record = SizedRecord::new(92);
+            writer
+                .write_record(first_record.clone())
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            drop(writer);
+            drop(ledger);
+
+            let (mut writer, mut reader, ledger) =
+                create_buffer_v2_with_encryption(data_dir, Some(cipher(KEY_B, &[KEY_A]))).await;
+
+            let second_record = SizedRecord::new(96);
+            writer
+                .write_record(second_record.clone())
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+
+            for expected in [first_record, second_record] {
+                let record = read_next_some(&mut reader).await;
+                assert_eq!(record, expected);
+                acknowledge(record).await;
+            }
+
+            assert_eq!(read_next(&mut reader).await, None);
+            assert_buffer_is_empty!(ledger);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn unencrypted_records_remain_readable_after_enabling_encryption() {
+    let _a = install_tracing_helpers();
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, _, ledger) =
+                create_buffer_v2_with_encryption(data_dir.clone(), None).await;
+
+            let record = SizedRecord::new(128);
+            writer
+                .write_record(record.clone())
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            drop(writer);
+            drop(ledger);
+
+            let (writer, mut reader, ledger) = create_buffer_v2_with_encryption::<_, SizedRecord>(
+                data_dir,
+                Some(cipher(KEY_A, &[])),
+            )
+            .await;
+            writer.close();
+
+            let read_record = read_next_some(&mut reader).await;
+            assert_eq!(read_record, record);
+            acknowledge(read_record).await;
+
+            assert_eq!(read_next(&mut reader).await, None);
+            assert_buffer_is_empty!(ledger);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn records_encrypted_with_unknown_key_cannot_be_read() {
+    let _a = install_tracing_helpers();
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, _, ledger) =
+                create_buffer_v2_with_encryption(data_dir.clone(), Some(cipher(KEY_A, &[]))).await;
+
+            writer
+                .write_record(SizedRecord::new(64))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            drop(writer);
+            drop(ledger);
+
+            // Reopen the buffer with a different key, and without the original key as a previous
+            // key, which leaves no way to decrypt the record that was written.
+            let (writer, mut reader, _ledger) = create_buffer_v2_with_encryption::<_, SizedRecord>(
+                data_dir,
+                Some(cipher(KEY_B, &[])),
+            )
+            .await;
+            writer.close();
+
+            let result = reader.next().await;
+            assert!(matches!(result, Err(ReaderError::Decryption { .. })));
+        }
+    })
+    .await;
+}
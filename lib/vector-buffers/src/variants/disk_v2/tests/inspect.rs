@@ -0,0 +1,151 @@
+use std::io::{Seek, SeekFrom, Write};
+
+use super::{create_default_buffer_v2, read_next_some};
+use crate::{
+    test::{acknowledge, install_tracing_helpers, with_temp_dir, MultiEventRecord},
+    variants::disk_v2::inspect::{BufferInspector, DataFileReport, InspectError, InspectedRecord},
+};
+
+fn collect_records(
+    inspector: &BufferInspector,
+) -> (Vec<DataFileReport>, Vec<(u64, bool, MultiEventRecord)>) {
+    let mut records = Vec::new();
+    let reports = inspector
+        .scan::<InspectError, _>(|record: &InspectedRecord<'_>| {
+            let decoded = record
+                .decode::<MultiEventRecord>(None)
+                .expect("record should decode");
+            records.push((record.id(), record.is_acknowledged(), decoded));
+            Ok(())
+        })
+        .expect("scan should not fail");
+    (reports, records)
+}
+
+#[tokio::test]
+async fn inspect_reports_ledger_state_and_records() {
+    let _a = install_tracing_helpers();
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, mut reader, ledger) = create_default_buffer_v2(data_dir.clone()).await;
+
+            let expected = (0..3).map(|_| MultiEventRecord::new(2)).collect::<Vec<_>>();
+            for record in expected.iter().cloned() {
+                writer
+                    .write_record(record)
+                    .await
+                    .expect("write should not fail");
+            }
+            writer.flush().await.expect("flush should not fail");
+
+            // Acknowledge the first record, and read the second record so that the acknowledgement
+            // is processed, but leave the second record unacknowledged.
+            let first = read_next_some(&mut reader).await;
+            acknowledge(first).await;
+            let _second = read_next_some(&mut reader).await;
+
+            // The buffer is still open, so it can't be inspected yet.
+            assert!(matches!(
+                BufferInspector::open(&data_dir),
+                Err(InspectError::LedgerLockAlreadyHeld)
+            ));
+
+            drop(writer);
+            drop(reader);
+            drop(ledger);
+
+            let inspector = BufferInspector::open(&data_dir).expect("buffer should open");
+            let snapshot = inspector.ledger();
+            assert_eq!(snapshot.writer_next_record_id, 7);
+            assert_eq!(snapshot.reader_last_record_id, 2);
+            assert_eq!(snapshot.unacknowledged_events(), 4);
+
+            let (reports, records) = collect_records(&inspector);
+            assert_eq!(reports.len(), 1);
+            assert_eq!(reports[0].records, 3);
+            assert_eq!(reports[0].events, 6);
+            assert_eq!(reports[0].unacknowledged_records, 2);
+            assert_eq!(reports[0].first_record_id, Some(1));
+            assert_eq!(reports[0].last_record_id, Some(5));
+            assert_eq!(reports[0].error, None);
+
+            let ids = records
+                .iter()
+                .map(|(id, acknowledged, _)| (*id, *acknowledged))
+                .collect::<Vec<_>>();
+            assert_eq!(ids, vec![(1, true), (3, false), (5, false)]);
+            let decoded = records
+                .into_iter()
+                .map(|(_, _, record)| record)
+                .collect::<Vec<_>>();
+            assert_eq!(decoded, expected);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn inspect_reports_corrupted_records() {
+    let _a = install_tracing_helpers();
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, reader, ledger) = create_default_buffer_v2(data_dir.clone()).await;
+
+            for _ in 0..3 {
+                writer
+                    .write_record(MultiEventRecord::new(1))
+                    .await
+                    .expect("write should not fail");
+            }
+            writer.flush().await.expect("flush should not fail");
+
+            let data_file_path = ledger.get_current_writer_data_file_path();
+            drop(writer);
+            drop(reader);
+            drop(ledger);
+
+            // Mangle the tail end of the data file, which is where the last record lives.
+            let mut data_file = std::fs::OpenOptions::new()
+                .write(true)
+                .open(&data_file_path)
+                .expect("data file should open");
+            data_file
+                .seek(SeekFrom::End(-4))
+                .expect("seek should not fail");
+            data_file
+                .write_all(&[0xFF; 4])
+                .expect("write should not fail");
+            drop(data_file);
+
+            let inspector = BufferInspector::open(&data_dir).expect("buffer should open");
+            let (reports, records) = collect_records(&inspector);
+            assert_eq!(reports.len(), 1);
+            assert_eq!(reports[0].records, 2);
+            assert!(reports[0].error.is_some());
+            assert_eq!(records.len(), 2);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn inspect_requires_existing_buffer() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            assert!(matches!(
+                BufferInspector::open(&data_dir),
+                Err(InspectError::MissingLedger { .. })
+            ));
+
+            // Inspecting must not create the buffer as a side effect.
+            assert!(!data_dir.join("buffer.db").exists());
+        }
+    })
+    .await;
+}
@@ -2,6 +2,7 @@ use std::{
     io::{self, Cursor},
     path::Path,
     sync::Arc,
+    time::Duration,
 };
 
 use tokio::{
@@ -10,6 +11,7 @@ use tokio::{
 };
 
 use super::{
+    encryption::RecordCipher,
     io::{AsyncFile, Metadata, ProductionFilesystem, ReadableMemoryMap, WritableMemoryMap},
     ledger::LEDGER_LEN,
     record::RECORD_HEADER_LEN,
@@ -24,16 +26,22 @@ type FilesystemUnderTest = ProductionFilesystem;
 
 mod acknowledgements;
 mod basic;
+mod encryption;
 mod initialization;
+mod inspect;
 mod invariants;
 mod known_errors;
 mod model;
 mod record;
+mod shared;
 mod size_limits;
 
 impl AsyncFile for DuplexStream {
     async fn metadata(&self) -> io::Result<Metadata> {
-        Ok(Metadata { len: 0 })
+        Ok(Metadata {
+            len: 0,
+            created: None,
+        })
     }
 
     async fn sync_all(&self) -> io::Result<()> {
@@ -43,7 +51,10 @@ impl AsyncFile for DuplexStream {
 
 impl AsyncFile for Cursor<Vec<u8>> {
     async fn metadata(&self) -> io::Result<Metadata> {
-        Ok(Metadata { len: 0 })
+        Ok(Metadata {
+            len: 0,
+            created: None,
+        })
     }
 
     async fn sync_all(&self) -> io::Result<()> {
@@ -336,6 +347,79 @@ where
         .expect("should not fail to create buffer")
 }
 
+/// Creates a disk v2 buffer with the specified maximum number of events.
+pub(crate) async fn create_buffer_v2_with_max_events<P, R>(
+    data_dir: P,
+    max_events: u64,
+) -> (
+    BufferWriter<R, FilesystemUnderTest>,
+    BufferReader<R, FilesystemUnderTest>,
+    Arc<Ledger<FilesystemUnderTest>>,
+)
+where
+    P: AsRef<Path>,
+    R: Bufferable,
+{
+    let config = DiskBufferConfigBuilder::from_path(data_dir)
+        .max_events(max_events)
+        .build()
+        .expect("creating buffer should not fail");
+    let usage_handle = BufferUsageHandle::noop();
+
+    Buffer::from_config_inner(config, usage_handle)
+        .await
+        .expect("should not fail to create buffer")
+}
+
+/// Creates a disk v2 buffer with the specified maximum record age.
+pub(crate) async fn create_buffer_v2_with_max_age<P, R>(
+    data_dir: P,
+    max_age: Duration,
+) -> (
+    BufferWriter<R, FilesystemUnderTest>,
+    BufferReader<R, FilesystemUnderTest>,
+    Arc<Ledger<FilesystemUnderTest>>,
+)
+where
+    P: AsRef<Path>,
+    R: Bufferable,
+{
+    let config = DiskBufferConfigBuilder::from_path(data_dir)
+        .max_age(max_age)
+        .build()
+        .expect("creating buffer should not fail");
+    let usage_handle = BufferUsageHandle::noop();
+
+    Buffer::from_config_inner(config, usage_handle)
+        .await
+        .expect("should not fail to create buffer")
+}
+
+/// Creates a disk v2 buffer that encrypts records with the given cipher, if any.
+pub(crate) async fn create_buffer_v2_with_encryption<P, R>(
+    data_dir: P,
+    cipher: Option<RecordCipher>,
+) -> (
+    BufferWriter<R, FilesystemUnderTest>,
+    BufferReader<R, FilesystemUnderTest>,
+    Arc<Ledger<FilesystemUnderTest>>,
+)
+where
+    P: AsRef<Path>,
+    R: Bufferable,
+{
+    let mut builder = DiskBufferConfigBuilder::from_path(data_dir);
+    if let Some(cipher) = cipher {
+        builder = builder.encryption(cipher);
+    }
+    let config = builder.build().expect("creating buffer should not fail");
+    let usage_handle = BufferUsageHandle::noop();
+
+    Buffer::from_config_inner(config, usage_handle)
+        .await
+        .expect("should not fail to create buffer")
+}
+
 /// Creates a disk v2 buffer with the specified write buffer size.
 pub(crate) async fn create_buffer_v2_with_write_buffer_size<P, R>(
     data_dir: P,
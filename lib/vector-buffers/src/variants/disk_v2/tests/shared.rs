@@ -0,0 +1,145 @@
+use std::time::Duration;
+
+use super::create_default_buffer_v2;
+use crate::{
+    assert_buffer_is_empty,
+    test::{acknowledge, install_tracing_helpers, with_temp_dir, MultiEventRecord},
+    variants::disk_v2::{
+        shared::{get_reader_position_path, split_reader},
+        SharedBufferReader,
+    },
+};
+
+async fn read_next_some(reader: &mut SharedBufferReader<MultiEventRecord>) -> MultiEventRecord {
+    reader.next().await.expect("read should produce a record")
+}
+
+fn reader_ids(ids: &[&str]) -> Vec<String> {
+    ids.iter().map(ToString::to_string).collect()
+}
+
+#[tokio::test]
+async fn shared_readers_each_read_every_record() {
+    let _a = install_tracing_helpers();
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, reader, ledger) = create_default_buffer_v2(data_dir).await;
+            let mut readers = split_reader(reader, &reader_ids(&["a", "b"]))
+                .await
+                .expect("readers should be created");
+
+            let expected = [MultiEventRecord::new(2), MultiEventRecord::new(3)];
+            for record in expected.iter().cloned() {
+                writer
+                    .write_record(record)
+                    .await
+                    .expect("write should not fail");
+            }
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+
+            // The first reader acknowledging its records isn't enough for them to be removed from
+            // the buffer, as the second reader hasn't read them yet.
+            for expected_record in &expected {
+                let record = read_next_some(&mut readers[0]).await;
+                assert_eq!(&record, expected_record);
+                acknowledge(record).await;
+            }
+            assert_eq!(ledger.get_total_records(), 5);
+
+            for expected_record in &expected {
+                let record = read_next_some(&mut readers[1]).await;
+                assert_eq!(&record, expected_record);
+                acknowledge(record).await;
+            }
+
+            for reader in &mut readers {
+                assert_eq!(reader.next().await, None);
+            }
+            assert_buffer_is_empty!(ledger);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn shared_readers_resume_from_their_own_position() {
+    let _a = install_tracing_helpers();
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, reader, ledger) = create_default_buffer_v2(data_dir.clone()).await;
+
+            let expected = (0..3).map(|_| MultiEventRecord::new(1)).collect::<Vec<_>>();
+            for record in expected.iter().cloned() {
+                writer
+                    .write_record(record)
+                    .await
+                    .expect("write should not fail");
+            }
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+
+            // The second reader already acknowledged the first two records, with IDs 1 and 2, the
+            // last time the buffer was open.
+            let position_path = get_reader_position_path(&data_dir, "b");
+            std::fs::write(&position_path, 2u64.to_be_bytes()).expect("position should be written");
+
+            let mut readers = split_reader(reader, &reader_ids(&["a", "b"]))
+                .await
+                .expect("readers should be created");
+
+            for _ in 0..3 {
+                let record = read_next_some(&mut readers[0]).await;
+                acknowledge(record).await;
+            }
+
+            let record = read_next_some(&mut readers[1]).await;
+            assert_eq!(record, expected[2]);
+            acknowledge(record).await;
+
+            for reader in &mut readers {
+                assert_eq!(reader.next().await, None);
+            }
+            assert_buffer_is_empty!(ledger);
+
+            // Both readers have now acknowledged every record, which is persisted once they've
+            // finished reading.
+            for id in ["a", "b"] {
+                let position_path = get_reader_position_path(&data_dir, id);
+                let mut position = Vec::new();
+                for _ in 0..100 {
+                    position = std::fs::read(&position_path).unwrap_or_default();
+                    if position == 3u64.to_be_bytes() {
+                        break;
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+                assert_eq!(position, 3u64.to_be_bytes());
+            }
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn shared_reader_rejects_corrupted_position() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (_writer, reader, _ledger) =
+                create_default_buffer_v2::<_, MultiEventRecord>(data_dir.clone()).await;
+
+            std::fs::write(get_reader_position_path(&data_dir, "a"), [0xFF; 3])
+                .expect("position should be written");
+
+            let result = split_reader(reader, &reader_ids(&["a"])).await;
+            assert!(result.is_err());
+        }
+    })
+    .await;
+}
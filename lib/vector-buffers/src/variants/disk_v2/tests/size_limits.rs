@@ -5,7 +5,8 @@ use tokio_test::{assert_pending, task::spawn};
 use tracing::Instrument;
 
 use super::{
-    create_buffer_v2_with_data_file_count_limit, create_buffer_v2_with_max_data_file_size,
+    create_buffer_v2_with_data_file_count_limit, create_buffer_v2_with_max_age,
+    create_buffer_v2_with_max_data_file_size, create_buffer_v2_with_max_events,
     create_buffer_v2_with_max_record_size, read_next, read_next_some,
 };
 use crate::{
@@ -425,6 +426,233 @@ async fn writer_try_write_returns_when_buffer_is_full() {
     .await;
 }
 
+#[tokio::test]
+async fn writer_try_write_returns_when_max_events_is_reached() {
+    let _a = install_tracing_helpers();
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Create our buffer with a limit of two events, which is far below what the byte-based
+            // limits would allow.
+            let (mut writer, mut reader, ledger) =
+                create_buffer_v2_with_max_events(data_dir, 2).await;
+
+            assert_buffer_is_empty!(ledger);
+
+            for _ in 0..2 {
+                let write_result = writer
+                    .try_write_record(SizedRecord::new(64))
+                    .await
+                    .expect("write should not fail");
+                assert_eq!(write_result, None);
+            }
+            writer.flush().await.expect("flush should not fail");
+            assert_buffer_records!(ledger, 2);
+
+            // The buffer is now full by event count, so the next write should hand back our record.
+            let third_record = SizedRecord::new(64);
+            let write_result = writer
+                .try_write_record(third_record.clone())
+                .await
+                .expect("write should not fail");
+            assert_eq!(write_result, Some(third_record.clone()));
+
+            // Once we read and acknowledge a record, there's room for another event again.
+            let record = read_next_some(&mut reader).await;
+            acknowledge(record).await;
+            let _ = read_next_some(&mut reader).await;
+            assert_buffer_records!(ledger, 1);
+
+            let write_result = writer
+                .try_write_record(third_record)
+                .await
+                .expect("write should not fail");
+            assert_eq!(write_result, None);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn reader_expires_records_older_than_max_age() {
+    let _a = install_tracing_helpers();
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let max_age = Duration::from_secs(60);
+            let (mut writer, mut reader, ledger) =
+                create_buffer_v2_with_max_age(data_dir, max_age).await;
+
+            assert_buffer_is_empty!(ledger);
+
+            // Write a record, and then advance time far enough for it to expire. The next write
+            // then goes to a new data file, since the current one is now too old to write to.
+            let expired_record = SizedRecord::new(32);
+            writer
+                .write_record(expired_record)
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+
+            tokio::time::pause();
+            tokio::time::advance(max_age * 2).await;
+
+            let fresh_record = SizedRecord::new(64);
+            writer
+                .write_record(fresh_record.clone())
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+
+            assert_buffer_records!(ledger, 2);
+
+            // Only the record that hasn't expired yet should be read back.
+            let record = read_next_some(&mut reader).await;
+            assert_eq!(record, fresh_record);
+            acknowledge(record).await;
+
+            let final_empty_read = read_next(&mut reader).await;
+            assert_eq!(final_empty_read, None);
+            assert_buffer_is_empty!(ledger);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn expired_data_files_are_dropped_while_reader_is_stalled() {
+    let _a = install_tracing_helpers();
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Data files are dropped in the background, on a timer, so this runs in real time.
+            let max_age = Duration::from_secs(3);
+            let (mut writer, mut reader, ledger) =
+                create_buffer_v2_with_max_age(data_dir, max_age).await;
+
+            assert_buffer_is_empty!(ledger);
+
+            // Write a record, and read it without acknowledging it, as a stalled sink would.
+            let stalled_record = SizedRecord::new(32);
+            let stalled_bytes = writer
+                .write_record(stalled_record.clone())
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            let record = read_next_some(&mut reader).await;
+            assert_eq!(record, stalled_record);
+
+            // Each of the next records goes to a new data file, as the current one is too old to
+            // write to by then. The first of them expires well before the second one does.
+            tokio::time::sleep(max_age / 6).await;
+            let expired_record = SizedRecord::new(48);
+            writer
+                .write_record(expired_record)
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+
+            tokio::time::sleep(max_age * 9 / 10).await;
+            let fresh_record = SizedRecord::new(64);
+            let fresh_bytes = writer
+                .write_record(fresh_record.clone())
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            assert_reader_writer_v2_file_positions!(ledger, 0, 2);
+            assert_buffer_records!(ledger, 3);
+
+            // The data file holding the expired record is dropped even though the reader hasn't
+            // moved past the first data file.
+            let remaining_bytes = (stalled_bytes + fresh_bytes) as u64;
+            timeout(max_age, async {
+                while ledger.get_total_buffer_size() != remaining_bytes {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                }
+            })
+            .await
+            .expect("expired data file should be dropped");
+            assert!(!ledger.get_data_file_path(1).exists());
+
+            // Once the reader makes progress again, it skips over the dropped data file.
+            acknowledge(record).await;
+            writer.close();
+
+            let record = read_next_some(&mut reader).await;
+            assert_eq!(record, fresh_record);
+            acknowledge(record).await;
+
+            let final_empty_read = read_next(&mut reader).await;
+            assert_eq!(final_empty_read, None);
+            assert_buffer_is_empty!(ledger);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn writer_drops_oldest_data_file_when_buffer_is_full() {
+    let _a = install_tracing_helpers();
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Create our buffer such that each record fills an entire data file, and only three
+            // records fit in the buffer at a time.
+            //
+            // The sizes are different so that we can assert that we got back the expected record at
+            // each read we perform.
+            let records = (93..=96).map(SizedRecord::new).collect::<Vec<_>>();
+
+            let max_data_file_size = get_minimum_data_file_size_for_record_payload(&records[3]);
+            let (mut writer, mut reader, ledger) =
+                create_buffer_v2_with_data_file_count_limit(data_dir, max_data_file_size, 4).await;
+
+            assert_buffer_is_empty!(ledger);
+
+            // Fill up the buffer, which leaves us with three data files.
+            let mut bytes_written = 0;
+            for record in records.iter().take(3) {
+                bytes_written += writer
+                    .write_record(record.clone())
+                    .await
+                    .expect("write should not fail");
+                writer.flush().await.expect("flush should not fail");
+            }
+            assert_reader_writer_v2_file_positions!(ledger, 0, 2);
+            assert_eq!(ledger.get_total_buffer_size(), bytes_written as u64);
+
+            // The next write should drop the oldest data file that the reader isn't on, which is
+            // the second data file, and then succeed.
+            let write_result = writer
+                .try_write_record_dropping_oldest(records[3].clone())
+                .await
+                .expect("write should not fail");
+            assert_eq!(write_result, None);
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+
+            assert_reader_writer_v2_file_positions!(ledger, 0, 3);
+
+            // Now read all of the remaining records, which should skip over the dropped data file.
+            for expected in [&records[0], &records[2], &records[3]] {
+                let record = read_next_some(&mut reader).await;
+                assert_eq!(&record, expected);
+                acknowledge(record).await;
+            }
+
+            let final_empty_read = read_next(&mut reader).await;
+            assert_eq!(final_empty_read, None);
+            assert_eq!(ledger.get_total_buffer_size(), 0);
+        }
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn writer_can_validate_last_write_when_buffer_is_full() {
     let _a = install_tracing_helpers();
@@ -21,10 +21,14 @@ use rkyv::{
     AlignedVec, Infallible,
 };
 use snafu::{ResultExt, Snafu};
-use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::{
+    io::{AsyncWrite, AsyncWriteExt},
+    time::Instant,
+};
 
 use super::{
-    common::{create_crc32c_hasher, DiskBufferConfig},
+    common::{create_crc32c_hasher, DiskBufferConfig, DATA_FILE_MAX_AGE_DIVISOR, MAX_FILE_ID},
+    encryption::{RecordCipher, ENCRYPTED_RECORD_FLAG, ENCRYPTION_OVERHEAD},
     io::Filesystem,
     ledger::Ledger,
     record::{validate_record_archive, Record, RecordStatus},
@@ -33,7 +37,7 @@ use crate::{
     encoding::{AsMetadata, Encodable},
     variants::disk_v2::{
         io::AsyncFile,
-        reader::decode_record_payload,
+        reader::{decode_record_payload, ReaderError},
         record::{try_as_record_archive, RECORD_HEADER_LEN},
     },
     Bufferable,
@@ -363,6 +367,8 @@ pub(super) struct RecordWriter<W, T> {
     ser_buf: AlignedVec,
     ser_scratch: AlignedVec,
     checksummer: Hasher,
+    cipher: Option<RecordCipher>,
+    encrypt_buf: Vec<u8>,
     max_record_size: usize,
     current_data_file_size: u64,
     max_data_file_size: u64,
@@ -413,6 +419,8 @@ where
             ser_buf: AlignedVec::with_capacity(16_384),
             ser_scratch: AlignedVec::with_capacity(16_384),
             checksummer: create_crc32c_hasher(),
+            cipher: None,
+            encrypt_buf: Vec::new(),
             max_record_size,
             current_data_file_size,
             max_data_file_size,
@@ -420,6 +428,19 @@ where
         }
     }
 
+    /// Sets the cipher used to encrypt records.
+    ///
+    /// As encryption adds a fixed amount of overhead to each record, the maximum size of an encoded
+    /// record is reduced by that amount when encryption is enabled.
+    #[must_use]
+    pub fn with_encryption(mut self, cipher: Option<RecordCipher>) -> Self {
+        if cipher.is_some() {
+            self.max_record_size -= ENCRYPTION_OVERHEAD;
+        }
+        self.cipher = cipher;
+        self
+    }
+
     /// Gets a reference to the underlying writer.
     #[cfg(test)]
     pub fn get_ref(&self) -> &W {
@@ -480,9 +501,18 @@ where
             });
         }
 
-        let metadata = T::get_metadata().into_u32();
-        let wrapped_record =
-            Record::with_checksum(id, metadata, &self.encode_buf, &self.checksummer);
+        // When encryption is enabled, we encrypt the encoded record and flag it as encrypted in the
+        // record metadata, so that the reader knows to decrypt it before decoding it.
+        let mut metadata = T::get_metadata().into_u32();
+        let payload = match &self.cipher {
+            Some(cipher) => {
+                metadata |= ENCRYPTED_RECORD_FLAG;
+                cipher.encrypt(id, metadata, &self.encode_buf, &mut self.encrypt_buf);
+                self.encrypt_buf.as_slice()
+            }
+            None => self.encode_buf.as_slice(),
+        };
+        let wrapped_record = Record::with_checksum(id, metadata, payload, &self.checksummer);
 
         // Push 8 dummy bytes where our length delimiter will sit.  We'll fix this up after
         // serialization.  Notably, `AlignedSerializer` will report the serializer position as
@@ -720,6 +750,7 @@ where
     next_record_id: u64,
     unflushed_events: u64,
     data_file_size: u64,
+    data_file_created: Option<Instant>,
     unflushed_bytes: u64,
     data_file_full: bool,
     skip_to_next: bool,
@@ -742,6 +773,7 @@ where
             config,
             writer: None,
             data_file_size: 0,
+            data_file_created: None,
             data_file_full: false,
             unflushed_bytes: 0,
             skip_to_next: false,
@@ -757,6 +789,11 @@ where
     }
 
     fn track_write(&mut self, event_count: usize, record_size: u64) {
+        // We also track how many events each data file holds, which is needed to account for the
+        // events in a data file if it gets dropped.
+        self.ledger
+            .track_data_file_events(self.ledger.get_current_writer_file_id(), event_count as u64);
+
         self.data_file_size += record_size;
         self.unflushed_events += event_count as u64;
         self.unflushed_bytes += record_size;
@@ -790,12 +827,34 @@ where
         !self.data_file_full && self.data_file_size < self.config.max_data_file_size
     }
 
-    fn can_write_record(&self, amount: usize) -> bool {
+    fn can_write_record(&self, amount: usize, event_count: usize) -> bool {
         let total_buffer_size = self.ledger.get_total_buffer_size() + self.unflushed_bytes;
         let potential_write_len =
             u64::try_from(amount).expect("Vector only supports 64-bit architectures.");
+        let total_events = self.ledger.get_total_records() + self.unflushed_events;
+        // We always allow writing a record to an empty buffer, even if it has more events than the
+        // limit, as it could otherwise never be written.
+        let within_max_events = self.config.max_events.map_or(true, |max_events| {
+            total_events == 0 || total_events + event_count as u64 <= max_events
+        });
+
+        self.can_write()
+            && within_max_events
+            && total_buffer_size + potential_write_len <= self.config.max_buffer_size
+    }
 
-        self.can_write() && total_buffer_size + potential_write_len <= self.config.max_buffer_size
+    /// Whether or not the current data file is old enough that we should roll to the next one.
+    ///
+    /// Data files are expired as a whole once they're older than the configured maximum age, so we
+    /// don't keep writing to a data file for too long, as otherwise the newer records in it would
+    /// be expired far earlier than they should be.
+    fn is_data_file_too_old(&self) -> bool {
+        match (self.config.max_age, self.data_file_created) {
+            (Some(max_age), Some(created)) => {
+                created.elapsed() >= max_age / DATA_FILE_MAX_AGE_DIVISOR
+            }
+            _ => false,
+        }
     }
 
     #[instrument(skip(self), level = "debug")]
@@ -807,6 +866,7 @@ where
     fn reset(&mut self) {
         self.writer = None;
         self.data_file_size = 0;
+        self.data_file_created = None;
         self.data_file_full = false;
     }
 
@@ -882,63 +942,74 @@ where
                 // next writer record ID should be.
                 let record = try_as_record_archive(data_file_mmap.as_ref())
                     .expect("record was already validated");
-                let item = decode_record_payload::<T>(record).map_err(|e| {
-                    WriterError::FailedToValidate {
-                        reason: e.to_string(),
+                let cipher = self.config.encryption.as_ref();
+                match decode_record_payload::<T>(record, cipher, &mut Vec::new()) {
+                    // If the record can't be decrypted, most likely because the encryption key was
+                    // changed without keeping the old key as a previous key, we can't tell where the
+                    // writer should be, so we skip to the next data file, just like with corruption.
+                    Err(ReaderError::Decryption { reason }) => {
+                        error!(?reason, "Last written record could not be decrypted.");
+                        true
                     }
-                })?;
-
-                // Since we have a valid record, checksum and all, see if the writer record ID
-                // in the ledger lines up with the record ID we have here.  Specifically, the record
-                // ID plus the number of events in the record should be the next record ID that gets used.
-                let ledger_next = self.ledger.state().get_next_writer_record_id();
-                let record_events =
-                    u64::try_from(item.event_count()).expect("event count should never exceed u64");
-                let record_next = last_record_id.wrapping_add(record_events);
-
-                match ledger_next.cmp(&record_next) {
-                    Ordering::Equal => {
-                        // We're exactly where the ledger thinks we should be, so nothing to do.
-                        debug!(
-                            ledger_next,
-                            last_record_id,
-                            record_events,
-                            "Synchronized with ledger. Writer ready."
-                        );
-                        false
+                    Err(e) => {
+                        return Err(WriterError::FailedToValidate {
+                            reason: e.to_string(),
+                        })
                     }
-                    Ordering::Greater => {
-                        // Our last write is behind where the ledger thinks we should be, so we
-                        // likely missed flushing some records, or partially flushed the data file.
-                        // Better roll over to be safe.
-                        error!(
+                    Ok(item) => {
+                        // Since we have a valid record, checksum and all, see if the writer record ID
+                        // in the ledger lines up with the record ID we have here.  Specifically, the record
+                        // ID plus the number of events in the record should be the next record ID that gets used.
+                        let ledger_next = self.ledger.state().get_next_writer_record_id();
+                        let record_events = u64::try_from(item.event_count())
+                            .expect("event count should never exceed u64");
+                        let record_next = last_record_id.wrapping_add(record_events);
+
+                        match ledger_next.cmp(&record_next) {
+                            Ordering::Equal => {
+                                // We're exactly where the ledger thinks we should be, so nothing to do.
+                                debug!(
+                                    ledger_next,
+                                    last_record_id,
+                                    record_events,
+                                    "Synchronized with ledger. Writer ready."
+                                );
+                                false
+                            }
+                            Ordering::Greater => {
+                                // Our last write is behind where the ledger thinks we should be, so we
+                                // likely missed flushing some records, or partially flushed the data file.
+                                // Better roll over to be safe.
+                                error!(
                             ledger_next, last_record_id, record_events,
                             "Last record written to data file is behind expected position. Events have likely been lost.");
-                        true
-                    }
-                    Ordering::Less => {
-                        // We're actually _ahead_ of the ledger, which is to say we wrote a valid
-                        // record to the data file, but never incremented our "writer next record
-                        // ID" field.  Given that record IDs are monotonic, it's safe to forward
-                        // ourselves to make the "writer next record ID" in the ledger match the
-                        // reality of the data file.  If there were somehow gaps in the data file,
-                        // the reader will detect it, and this way, we avoid duplicate record IDs.
-                        debug!(
+                                true
+                            }
+                            Ordering::Less => {
+                                // We're actually _ahead_ of the ledger, which is to say we wrote a valid
+                                // record to the data file, but never incremented our "writer next record
+                                // ID" field.  Given that record IDs are monotonic, it's safe to forward
+                                // ourselves to make the "writer next record ID" in the ledger match the
+                                // reality of the data file.  If there were somehow gaps in the data file,
+                                // the reader will detect it, and this way, we avoid duplicate record IDs.
+                                debug!(
                             ledger_next,
                             last_record_id,
                             record_events,
                             new_ledger_next = record_next,
                             "Ledger desynchronized from data files. Fast forwarding ledger state."
                         );
-                        let ledger_record_delta = record_next - ledger_next;
-                        let next_record_id = self
-                            .ledger
-                            .state()
-                            .increment_next_writer_record_id(ledger_record_delta);
-                        self.next_record_id = next_record_id;
-                        self.unflushed_events = 0;
-
-                        false
+                                let ledger_record_delta = record_next - ledger_next;
+                                let next_record_id = self
+                                    .ledger
+                                    .state()
+                                    .increment_next_writer_record_id(ledger_record_delta);
+                                self.next_record_id = next_record_id;
+                                self.unflushed_events = 0;
+
+                                false
+                            }
+                        }
                     }
                 }
             }
@@ -990,7 +1061,12 @@ where
     fn is_buffer_full(&self) -> bool {
         let total_buffer_size = self.ledger.get_total_buffer_size() + self.unflushed_bytes;
         let max_buffer_size = self.config.max_buffer_size;
+        let total_events = self.ledger.get_total_records() + self.unflushed_events;
         total_buffer_size >= max_buffer_size
+            || self
+                .config
+                .max_events
+                .is_some_and(|max_events| total_events >= max_events)
     }
 
     /// Ensures this writer is ready to attempt writer the next record.
@@ -1023,7 +1099,7 @@ where
         // need to skip to the next file, we honor that here.
         let mut should_open_next = self.should_skip();
         if self.writer.is_some() {
-            if self.can_write() {
+            if self.can_write() && !self.is_data_file_too_old() {
                 return Ok(());
             }
 
@@ -1117,22 +1193,40 @@ where
                     "Opened data file for writing."
                 );
 
+                // Data files we create are tracked as created now. Otherwise, this is the data file
+                // we left off writing to, and we fall back to its metadata.
+                let data_file_created = if data_file_size == 0 {
+                    Some(Instant::now())
+                } else {
+                    data_file.metadata().await?.created_instant()
+                };
+                if let Some(created) = data_file_created {
+                    self.ledger
+                        .track_data_file_created(&data_file_path, created);
+                }
+
                 // Make sure the file is flushed to disk, especially if we just created it.
                 data_file.sync_all().await?;
 
-                self.writer = Some(RecordWriter::new(
-                    data_file,
-                    data_file_size,
-                    self.config.write_buffer_size,
-                    self.config.max_data_file_size,
-                    self.config.max_record_size,
-                ));
+                self.writer = Some(
+                    RecordWriter::new(
+                        data_file,
+                        data_file_size,
+                        self.config.write_buffer_size,
+                        self.config.max_data_file_size,
+                        self.config.max_record_size,
+                    )
+                    .with_encryption(self.config.encryption.clone()),
+                );
                 self.data_file_size = data_file_size;
+                self.data_file_created = data_file_created;
 
                 // If we opened the "next" data file, we need to increment the current writer
                 // file ID now to signal that the writer has moved on.
                 if should_open_next {
                     self.ledger.state().increment_writer_file_id();
+                    self.ledger
+                        .reset_data_file_events(self.ledger.get_current_writer_file_id());
                     self.ledger.notify_writer_waiters();
 
                     debug!(
@@ -1232,7 +1326,7 @@ where
         // internal buffers, as we haven't yet flushed it, and we return it to the caller.
         //
         // Otherwise, we proceed with flushing like we normally would.
-        let can_write_record = self.can_write_record(token.serialized_len());
+        let can_write_record = self.can_write_record(token.serialized_len(), record_events.get());
         let writer = self
             .writer
             .as_mut()
@@ -1277,6 +1371,75 @@ where
         Ok(Ok(bytes_written))
     }
 
+    /// Attempts to write a record, dropping the oldest data files in the buffer to make room for it.
+    ///
+    /// Data files are dropped, oldest first, until the record can be written. Only data files which
+    /// the reader has not yet moved on to, and which the writer is no longer writing to, can be
+    /// dropped, so if the buffer is still full after dropping all of them, the original record will
+    /// be returned. Otherwise, a write will be executed, which will run to completion, and `None`
+    /// will be returned.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurred while writing the record, or while dropping a data file, an error
+    /// variant will be returned describing the error.
+    #[instrument(skip_all, level = "debug")]
+    pub async fn try_write_record_dropping_oldest(
+        &mut self,
+        mut record: T,
+    ) -> Result<Option<T>, WriterError<T>> {
+        loop {
+            match self.try_write_record_inner(record).await? {
+                Ok(_) => return Ok(None),
+                Err(old_record) => {
+                    record = old_record;
+                    if !self.drop_oldest_data_file().await.context(IoSnafu)? {
+                        return Ok(Some(record));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Drops the oldest data file that is neither being read nor written.
+    ///
+    /// Returns `true` if a data file was dropped, or `false` if there was no data file that could be
+    /// dropped.
+    async fn drop_oldest_data_file(&mut self) -> io::Result<bool> {
+        let (reader_file_id, writer_file_id) = self.ledger.get_current_reader_writer_file_id();
+
+        let mut file_id = (reader_file_id + 1) % MAX_FILE_ID;
+        while file_id != writer_file_id {
+            if self.ledger.try_begin_dropping_data_file(file_id) {
+                let result = self.ledger.delete_dropped_data_file(file_id).await;
+                self.ledger.finish_dropping_data_file();
+
+                if let Some((data_file_size, event_count)) = result? {
+                    self.ledger
+                        .track_dropped_data_file(event_count, data_file_size);
+                    self.ledger.flush()?;
+
+                    debug!(
+                        data_file_path = self
+                            .ledger
+                            .get_data_file_path(file_id)
+                            .to_string_lossy()
+                            .as_ref(),
+                        data_file_size,
+                        event_count,
+                        "Dropped oldest data file to make room for newer records."
+                    );
+
+                    return Ok(true);
+                }
+            }
+
+            file_id = (file_id + 1) % MAX_FILE_ID;
+        }
+
+        Ok(false)
+    }
+
     /// Writes a record.
     ///
     /// If the record was written successfully, the number of bytes written to the data file will be
@@ -0,0 +1,86 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, RwLock},
+};
+
+use async_graphql::{Context, Object};
+use once_cell::sync::Lazy;
+
+use crate::{
+    config::{self, ComponentKey, Config, ConfigSnapshot, DiffReport},
+    signal::{SignalTo, SignalTx},
+};
+
+const INVARIANT: &str = "Couldn't acquire lock on Vector config. Please report this.";
+
+/// Snapshot of the running configuration, used as the baseline for dry-run reloads.
+static RUNNING_CONFIG: Lazy<Arc<RwLock<ConfigSnapshot>>> =
+    Lazy::new(|| Arc::new(RwLock::new(ConfigSnapshot::default())));
+
+#[derive(Default)]
+pub struct ConfigQuery;
+
+#[Object]
+impl ConfigQuery {
+    /// Compares the given configuration, as JSON, against the running configuration without
+    /// applying it, with the given components rebuilt as `reloadComponents` would. Returns a
+    /// JSON-encoded report of the components, buffers, and connections a reload would affect.
+    async fn config_diff(
+        &self,
+        config: String,
+        #[graphql(default)] component_ids: Vec<String>,
+    ) -> async_graphql::Result<String> {
+        let new = config::load_from_str(&config, config::Format::Json)
+            .map_err(|errors| errors.join("\n"))?;
+        let reloaded = component_ids
+            .into_iter()
+            .map(ComponentKey::from)
+            .collect::<HashSet<_>>();
+        let report = DiffReport::new(
+            &RUNNING_CONFIG.read().expect(INVARIANT),
+            &ConfigSnapshot::new(&new),
+            &reloaded,
+        );
+
+        Ok(serde_json::to_string(&report)?)
+    }
+}
+
+#[derive(Default)]
+pub struct ConfigMutation;
+
+#[Object]
+impl ConfigMutation {
+    /// Reloads the configuration from disk, rebuilding the given components even if their
+    /// configuration didn't change. Returns once the reload has been requested; its outcome is
+    /// reported in Vector's logs. Requires `api.mutations` to be enabled.
+    async fn reload_components(
+        &self,
+        ctx: &Context<'_>,
+        component_ids: Vec<String>,
+    ) -> async_graphql::Result<bool> {
+        let keys = component_ids
+            .into_iter()
+            .map(ComponentKey::from)
+            .collect::<HashSet<_>>();
+
+        {
+            let running = RUNNING_CONFIG.read().expect(INVARIANT);
+            if let Some(key) = keys.iter().find(|key| !running.contains(key)) {
+                return Err(format!("Component \"{}\" isn't running.", key).into());
+            }
+        }
+
+        ctx.data_opt::<SignalTx>()
+            .ok_or("Mutations are disabled. Set `api.mutations` to `true` to enable them.")?
+            .send(SignalTo::ReloadComponents(keys))
+            .map_err(|_| "Vector is shutting down.")?;
+
+        Ok(true)
+    }
+}
+
+/// Update the running configuration snapshot.
+pub fn update_config(config: &Config) {
+    *RUNNING_CONFIG.write().expect(INVARIANT) = ConfigSnapshot::new(config);
+}
@@ -104,7 +104,7 @@ static INTROSPECTION_QUERY: &str = indoc! {r#"
 
 #[tokio::main]
 async fn main() {
-    let schema = build_schema().finish();
+    let schema = build_schema(None).finish();
     let res = schema.execute(INTROSPECTION_QUERY).await;
     let json = serde_json::to_string_pretty(&res).unwrap();
 
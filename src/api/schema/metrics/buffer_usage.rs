@@ -0,0 +1,59 @@
+use async_graphql::Object;
+
+use crate::config::ComponentKey;
+
+pub struct ComponentBufferUsage {
+    component_key: ComponentKey,
+    buffer_events: f64,
+    buffer_byte_size: f64,
+    max_events: Option<f64>,
+    max_byte_size: Option<f64>,
+}
+
+impl ComponentBufferUsage {
+    /// Returns a new `ComponentBufferUsage` struct, which is a GraphQL type. Values are summed
+    /// across all stages of the component's buffer.
+    pub const fn new(
+        component_key: ComponentKey,
+        buffer_events: f64,
+        buffer_byte_size: f64,
+        max_events: Option<f64>,
+        max_byte_size: Option<f64>,
+    ) -> Self {
+        Self {
+            component_key,
+            buffer_events,
+            buffer_byte_size,
+            max_events,
+            max_byte_size,
+        }
+    }
+}
+
+#[Object]
+impl ComponentBufferUsage {
+    /// Component id
+    async fn component_id(&self) -> &str {
+        self.component_key.id()
+    }
+
+    /// Number of events currently held in the buffer
+    async fn buffer_events(&self) -> f64 {
+        self.buffer_events
+    }
+
+    /// Number of bytes currently held in the buffer
+    async fn buffer_byte_size(&self) -> f64 {
+        self.buffer_byte_size
+    }
+
+    /// Configured maximum number of events, if the buffer is bounded by event count
+    async fn max_events(&self) -> Option<f64> {
+        self.max_events
+    }
+
+    /// Configured maximum number of bytes, if the buffer is bounded by size
+    async fn max_byte_size(&self) -> Option<f64> {
+        self.max_byte_size
+    }
+}
@@ -69,3 +69,59 @@ impl ComponentErrorsTotal {
         ErrorsTotal::new(self.metric.clone())
     }
 }
+
+pub struct ErrorTypeTotal {
+    error_type: String,
+    errors_total: f64,
+}
+
+impl ErrorTypeTotal {
+    pub const fn new(error_type: String, errors_total: f64) -> Self {
+        Self {
+            error_type,
+            errors_total,
+        }
+    }
+}
+
+#[Object]
+impl ErrorTypeTotal {
+    /// Error type, as reported by the `error_type` tag
+    async fn error_type(&self) -> &str {
+        &self.error_type
+    }
+
+    /// Total error count for this error type
+    async fn errors_total(&self) -> f64 {
+        self.errors_total
+    }
+}
+
+pub struct ComponentErrorsByType {
+    component_key: ComponentKey,
+    error_types: Vec<ErrorTypeTotal>,
+}
+
+impl ComponentErrorsByType {
+    /// Returns a new `ComponentErrorsByType` struct, which is a GraphQL type. The
+    /// component id is hoisted for clear field resolution in the resulting payload
+    pub const fn new(component_key: ComponentKey, error_types: Vec<ErrorTypeTotal>) -> Self {
+        Self {
+            component_key,
+            error_types,
+        }
+    }
+}
+
+#[Object]
+impl ComponentErrorsByType {
+    /// Component id
+    async fn component_id(&self) -> &str {
+        self.component_key.id()
+    }
+
+    /// Error totals, split by `error_type`
+    async fn error_types(&self) -> &Vec<ErrorTypeTotal> {
+        &self.error_types
+    }
+}
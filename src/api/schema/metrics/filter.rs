@@ -369,6 +369,129 @@ pub fn component_sent_events_total_throughputs_with_outputs(
         .skip(1)
 }
 
+/// Returns a stream of error totals for each component, split by the `error_type` tag of
+/// metrics ending in `_errors_total`. Components are only included when one of their
+/// error totals has increased since the previous sample.
+pub fn component_errors_totals_by_error_type(
+    interval: i32,
+) -> impl Stream<Item = Vec<(ComponentKey, Vec<(String, f64)>)>> {
+    let mut cache = BTreeMap::new();
+
+    component_to_filtered_metrics(interval, &|m| m.name().ends_with("_errors_total")).map(
+        move |map| {
+            map.into_iter()
+                .filter_map(|(id, metrics)| {
+                    let by_type = metrics.into_iter().fold(
+                        BTreeMap::new(),
+                        |mut map: BTreeMap<String, f64>, m| {
+                            if let MetricValue::Counter { value } = m.value() {
+                                let error_type = m
+                                    .tag_value("error_type")
+                                    .unwrap_or_else(|| "unknown".to_string());
+                                *map.entry(error_type).or_default() += value;
+                            }
+                            map
+                        },
+                    );
+
+                    let mut changed = false;
+                    for (error_type, value) in by_type.iter() {
+                        changed |= cache
+                            .insert(format!("{}.{}", id, error_type), *value)
+                            .unwrap_or(0.00)
+                            < *value;
+                    }
+
+                    changed.then(|| (ComponentKey::from(id), by_type.into_iter().collect()))
+                })
+                .collect()
+        },
+    )
+}
+
+/// Current buffer usage for a component, summed across all buffer stages.
+pub struct BufferUsage {
+    pub events: f64,
+    pub byte_size: f64,
+    pub max_events: Option<f64>,
+    pub max_byte_size: Option<f64>,
+}
+
+/// Returns a stream of buffer usage for each component with a buffer, sampled over
+/// `interval` milliseconds.
+pub fn component_buffer_usages(
+    interval: i32,
+) -> impl Stream<Item = Vec<(ComponentKey, BufferUsage)>> {
+    component_to_filtered_metrics(interval, &|m| m.name().starts_with("buffer_")).map(|map| {
+        map.into_iter()
+            .filter_map(|(id, metrics)| {
+                let gauge = |name: &str| {
+                    metrics
+                        .iter()
+                        .filter(|m| m.name() == name)
+                        .filter_map(|m| match m.value() {
+                            MetricValue::Gauge { value } => Some(*value),
+                            _ => None,
+                        })
+                        .reduce(|a, b| a + b)
+                };
+
+                let events = gauge("buffer_events");
+                let byte_size = gauge("buffer_byte_size");
+                if events.is_none() && byte_size.is_none() {
+                    return None;
+                }
+
+                Some((
+                    ComponentKey::from(id),
+                    BufferUsage {
+                        events: events.unwrap_or(0.00),
+                        byte_size: byte_size.unwrap_or(0.00),
+                        max_events: gauge("buffer_max_event_size"),
+                        max_byte_size: gauge("buffer_max_byte_size"),
+                    },
+                ))
+            })
+            .collect()
+    })
+}
+
+/// Returns a stream of the number of HTTP requests completed and their mean round-trip time,
+/// in seconds, between `interval` samples for each component, derived from the
+/// `http_client_rtt_seconds` histogram. Components with no completed requests within the
+/// interval are omitted.
+pub fn component_request_latencies(
+    interval: i32,
+) -> impl Stream<Item = Vec<(ComponentKey, i64, f64)>> {
+    let mut cache = BTreeMap::new();
+
+    component_to_filtered_metrics(interval, &|m| m.name() == "http_client_rtt_seconds")
+        .map(move |map| {
+            map.into_iter()
+                .filter_map(|(id, metrics)| {
+                    let (count, sum) =
+                        metrics
+                            .iter()
+                            .fold((0, 0.00), |(count, sum), m| match m.value() {
+                                MetricValue::AggregatedHistogram {
+                                    count: c, sum: s, ..
+                                } => (count + c, sum + s),
+                                _ => (count, sum),
+                            });
+
+                    let (last_count, last_sum) =
+                        cache.insert(id.clone(), (count, sum)).unwrap_or((0, 0.00));
+                    let requests = count.checked_sub(last_count).filter(|n| *n > 0)?;
+                    let mean = (sum - last_sum) / requests as f64;
+
+                    Some((ComponentKey::from(id), requests as i64, mean))
+                })
+                .collect()
+        })
+        // Ignore the first, since we only care about sampling between `interval`
+        .skip(1)
+}
+
 /// Returns a map of Component ID to list of metrics where metrics have been
 /// filtered by `filter_fn`
 fn component_to_filtered_metrics(
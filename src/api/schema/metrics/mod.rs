@@ -1,9 +1,11 @@
 mod allocated_bytes;
+mod buffer_usage;
 mod errors;
 pub mod filter;
 mod output;
 mod received_bytes;
 mod received_events;
+mod request_latency;
 mod sent_bytes;
 mod sent_events;
 mod sink;
@@ -16,8 +18,9 @@ mod host;
 
 pub use allocated_bytes::{AllocatedBytes, ComponentAllocatedBytes};
 use async_graphql::{Interface, Subscription};
+pub use buffer_usage::ComponentBufferUsage;
 use chrono::{DateTime, Utc};
-pub use errors::{ComponentErrorsTotal, ErrorsTotal};
+pub use errors::{ComponentErrorsByType, ComponentErrorsTotal, ErrorTypeTotal, ErrorsTotal};
 pub use filter::*;
 pub use output::*;
 pub use received_bytes::{
@@ -26,6 +29,7 @@ pub use received_bytes::{
 pub use received_events::{
     ComponentReceivedEventsThroughput, ComponentReceivedEventsTotal, ReceivedEventsTotal,
 };
+pub use request_latency::ComponentRequestLatency;
 pub use sent_bytes::{ComponentSentBytesThroughput, ComponentSentBytesTotal, SentBytesTotal};
 pub use sent_events::{ComponentSentEventsThroughput, ComponentSentEventsTotal, SentEventsTotal};
 pub use sink::{IntoSinkMetrics, SinkMetrics};
@@ -270,6 +274,58 @@ impl MetricsSubscription {
             .map(|m| m.into_iter().map(ComponentErrorsTotal::new).collect())
     }
 
+    /// Component error metrics over `interval`, split by `error_type`.
+    async fn component_errors_by_type(
+        &self,
+        #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentErrorsByType>> {
+        component_errors_totals_by_error_type(interval).map(|m| {
+            m.into_iter()
+                .map(|(key, error_types)| {
+                    ComponentErrorsByType::new(
+                        key,
+                        error_types
+                            .into_iter()
+                            .map(|(error_type, total)| ErrorTypeTotal::new(error_type, total))
+                            .collect(),
+                    )
+                })
+                .collect()
+        })
+    }
+
+    /// Component buffer usage metrics over `interval`.
+    async fn component_buffer_usages(
+        &self,
+        #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentBufferUsage>> {
+        component_buffer_usages(interval).map(|m| {
+            m.into_iter()
+                .map(|(key, usage)| {
+                    ComponentBufferUsage::new(
+                        key,
+                        usage.events,
+                        usage.byte_size,
+                        usage.max_events,
+                        usage.max_byte_size,
+                    )
+                })
+                .collect()
+        })
+    }
+
+    /// Component HTTP request latency metrics over `interval`.
+    async fn component_request_latencies(
+        &self,
+        #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentRequestLatency>> {
+        component_request_latencies(interval).map(|m| {
+            m.into_iter()
+                .map(|(key, requests, mean)| ComponentRequestLatency::new(key, requests, mean))
+                .collect()
+        })
+    }
+
     /// All metrics.
     async fn metrics(
         &self,
@@ -0,0 +1,39 @@
+use async_graphql::Object;
+
+use crate::config::ComponentKey;
+
+pub struct ComponentRequestLatency {
+    component_key: ComponentKey,
+    requests: i64,
+    mean_rtt_seconds: f64,
+}
+
+impl ComponentRequestLatency {
+    /// Returns a new `ComponentRequestLatency` struct, which is a GraphQL type. The
+    /// component id is hoisted for clear field resolution in the resulting payload
+    pub const fn new(component_key: ComponentKey, requests: i64, mean_rtt_seconds: f64) -> Self {
+        Self {
+            component_key,
+            requests,
+            mean_rtt_seconds,
+        }
+    }
+}
+
+#[Object]
+impl ComponentRequestLatency {
+    /// Component id
+    async fn component_id(&self) -> &str {
+        self.component_key.id()
+    }
+
+    /// Number of requests completed within the interval
+    async fn requests(&self) -> i64 {
+        self.requests
+    }
+
+    /// Mean request round-trip time within the interval, in seconds
+    async fn mean_rtt_seconds(&self) -> f64 {
+        self.mean_rtt_seconds
+    }
+}
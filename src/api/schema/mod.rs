@@ -1,4 +1,5 @@
 pub mod components;
+pub mod config;
 pub mod events;
 pub mod filter;
 mod health;
@@ -7,16 +8,22 @@ mod metrics;
 mod relay;
 pub mod sort;
 
-use async_graphql::{EmptyMutation, MergedObject, MergedSubscription, Schema, SchemaBuilder};
+use async_graphql::{MergedObject, MergedSubscription, Schema, SchemaBuilder};
+
+use crate::signal::SignalTx;
 
 #[derive(MergedObject, Default)]
 pub struct Query(
     health::HealthQuery,
     components::ComponentsQuery,
+    config::ConfigQuery,
     #[cfg(feature = "sources-host_metrics")] metrics::MetricsQuery,
     meta::MetaQuery,
 );
 
+#[derive(MergedObject, Default)]
+pub struct Mutation(config::ConfigMutation);
+
 #[derive(MergedSubscription, Default)]
 pub struct Subscription(
     health::HealthSubscription,
@@ -25,7 +32,16 @@ pub struct Subscription(
     events::EventsSubscription,
 );
 
-/// Build a new GraphQL schema, comprised of Query, Mutation and Subscription types
-pub fn build_schema() -> SchemaBuilder<Query, EmptyMutation, Subscription> {
-    Schema::build(Query::default(), EmptyMutation, Subscription::default())
+/// Build a new GraphQL schema, comprised of Query, Mutation and Subscription types. Mutations are
+/// rejected unless a `SignalTx` is given to send their signals to.
+pub fn build_schema(signal_tx: Option<SignalTx>) -> SchemaBuilder<Query, Mutation, Subscription> {
+    let builder = Schema::build(
+        Query::default(),
+        Mutation::default(),
+        Subscription::default(),
+    );
+    match signal_tx {
+        Some(signal_tx) => builder.data(signal_tx),
+        None => builder,
+    }
 }
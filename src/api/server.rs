@@ -21,6 +21,7 @@ use crate::{
     config::{self, api},
     http::build_http_trace_layer,
     internal_events::{SocketBindError, SocketMode},
+    signal::SignalTx,
     topology,
 };
 
@@ -36,9 +37,10 @@ impl Server {
         config: &config::Config,
         watch_rx: topology::WatchRx,
         running: Arc<AtomicBool>,
+        signal_tx: SignalTx,
         handle: &Handle,
     ) -> crate::Result<Self> {
-        let routes = make_routes(config.api, watch_rx, running);
+        let routes = make_routes(config.api, watch_rx, running, signal_tx);
 
         let (_shutdown, rx) = oneshot::channel();
         // warp uses `tokio::spawn` and so needs us to enter the runtime context.
@@ -75,6 +77,7 @@ impl Server {
 
         // Update component schema with the config before starting the server.
         schema::components::update_config(config);
+        schema::config::update_config(config);
 
         // Spawn the server in the background.
         handle.spawn(server);
@@ -91,7 +94,8 @@ impl Server {
     /// directly involve `self`, it provides a neater API to expose an internal implementation
     /// detail than exposing the function of the sub-mod directly.
     pub fn update_config(&self, config: &config::Config) {
-        schema::components::update_config(config)
+        schema::components::update_config(config);
+        schema::config::update_config(config);
     }
 }
 
@@ -99,6 +103,7 @@ fn make_routes(
     api: api::Options,
     watch_tx: topology::WatchRx,
     running: Arc<AtomicBool>,
+    signal_tx: SignalTx,
 ) -> BoxedFilter<(impl Reply,)> {
     // Routes...
 
@@ -114,12 +119,14 @@ fn make_routes(
     // GraphQL subscription handler. Creates a Warp WebSocket handler and for each connection,
     // parses the required headers for GraphQL and builds per-connection context based on the
     // provided `WatchTx` channel sender. This allows GraphQL resolvers to subscribe to
-    // topology changes.
+    // topology changes. The `SignalTx` used by mutations is only provided when they're enabled.
+    let signal_tx = api.mutations.then_some(signal_tx);
+    let subscription_signal_tx = signal_tx.clone();
     let graphql_subscription_handler =
         warp::ws()
             .and(graphql_protocol())
             .map(move |ws: Ws, protocol: WebSocketProtocols| {
-                let schema = schema::build_schema().finish();
+                let schema = schema::build_schema(subscription_signal_tx.clone()).finish();
                 let watch_tx = watch_tx.clone();
 
                 let reply = ws.on_upgrade(move |socket| {
@@ -142,9 +149,10 @@ fn make_routes(
     // a subscription and if so, an attempt will be made to upgrade the connection to WebSockets.
     // All other queries will fall back to the default HTTP handler.
     let graphql_handler = if api.graphql {
+        let query_schema = schema::build_schema(signal_tx).finish();
         warp::path("graphql")
             .and(graphql_subscription_handler.or(
-                async_graphql_warp::graphql(schema::build_schema().finish()).and_then(
+                async_graphql_warp::graphql(query_schema).and_then(
                     |(schema, request): (Schema<_, _, _>, Request)| async move {
                         Ok::<_, Infallible>(GraphQLResponse::from(schema.execute(request).await))
                     },
@@ -397,3 +397,49 @@ async fn integration_test_tap_multiple_outputs() {
 
     assert!(default_output_found && dropped_output_found);
 }
+
+#[tokio::test]
+async fn reload_components_mutation() {
+    use std::collections::HashSet;
+
+    use crate::signal::SignalTo;
+
+    let mut config = Config::builder();
+    config.add_source("in", DemoLogsConfig::default());
+    config.add_sink(
+        "out",
+        &["in"],
+        BlackholeConfig {
+            print_interval_secs: 1,
+            rate: None,
+        },
+    );
+    super::schema::config::update_config(&config.build().unwrap());
+
+    let reload = |id: &str| {
+        format!(
+            r#"mutation {{ reloadComponents(componentIds: ["{}"]) }}"#,
+            id
+        )
+    };
+
+    // Mutations are rejected unless they're enabled.
+    let schema = build_schema(None).finish();
+    let response = schema.execute(reload("out")).await;
+    assert!(response.errors[0].message.contains("api.mutations"));
+
+    let (signal_tx, mut signal_rx) = tokio::sync::broadcast::channel(1);
+    let schema = build_schema(Some(signal_tx)).finish();
+
+    let response = schema.execute(reload("missing")).await;
+    assert!(response.errors[0].message.contains("isn't running"));
+
+    let response = schema.execute(reload("out")).await;
+    assert!(response.errors.is_empty());
+    match signal_rx.recv().await.unwrap() {
+        SignalTo::ReloadComponents(keys) => {
+            assert_eq!(keys, HashSet::from([ComponentKey::from("out")]))
+        }
+        _ => panic!("unexpected signal"),
+    }
+}
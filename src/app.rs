@@ -1,5 +1,7 @@
 #![allow(missing_docs)]
-use std::{num::NonZeroUsize, path::PathBuf, process::ExitStatus, time::Duration};
+use std::{
+    collections::HashSet, num::NonZeroUsize, path::PathBuf, process::ExitStatus, time::Duration,
+};
 
 use exitcode::ExitCode;
 use futures::StreamExt;
@@ -10,10 +12,10 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use crate::extra_context::ExtraContext;
 #[cfg(feature = "api")]
-use crate::{api, internal_events::ApiStarted};
+use crate::{api, internal_events::ApiStarted, signal::SignalTx};
 use crate::{
     cli::{handle_config_errors, LogFormat, Opts, RootOpts},
-    config::{self, Config, ConfigPath},
+    config::{self, ComponentKey, Config, ConfigPath},
     heartbeat,
     internal_events::{VectorConfigLoadError, VectorQuit, VectorStarted, VectorStopped},
     signal::{SignalHandler, SignalPair, SignalRx, SignalTo},
@@ -112,12 +114,13 @@ impl ApplicationConfig {
 
     /// Configure the API server, if applicable
     #[cfg(feature = "api")]
-    pub fn setup_api(&self, handle: &Handle) -> Option<api::Server> {
+    pub fn setup_api(&self, handle: &Handle, signal_tx: SignalTx) -> Option<api::Server> {
         if self.api.enabled {
             match api::Server::start(
                 self.topology.config(),
                 self.topology.watch(),
                 std::sync::Arc::clone(&self.topology.running),
+                signal_tx,
                 handle,
             ) {
                 Ok(api_server) => {
@@ -232,7 +235,9 @@ impl Application {
 
         let topology_controller = SharedTopologyController::new(TopologyController {
             #[cfg(feature = "api")]
-            api_server: config.setup_api(handle),
+            api_server: config.setup_api(handle, signals.handler.clone_tx()),
+            #[cfg(feature = "api")]
+            signal_tx: signals.handler.clone_tx(),
             topology: config.topology,
             config_paths: config.config_paths.clone(),
             require_healthy: root_opts.require_healthy,
@@ -320,25 +325,28 @@ async fn handle_signal(
     match signal {
         Ok(SignalTo::ReloadFromConfigBuilder(config_builder)) => {
             let topology_controller = topology_controller.lock().await;
-            reload_config_from_result(topology_controller, config_builder.build()).await
+            reload_config_from_result(topology_controller, config_builder.build(), &HashSet::new())
+                .await
         }
         Ok(SignalTo::ReloadFromDisk) => {
-            let mut topology_controller = topology_controller.lock().await;
-
-            // Reload paths
-            if let Some(paths) = config::process_paths(config_paths) {
-                topology_controller.config_paths = paths;
-            }
-
-            // Reload config
-            let new_config = config::load_from_paths_with_provider_and_secrets(
-                &topology_controller.config_paths,
+            reload_config_from_disk(
+                topology_controller,
+                config_paths,
                 signal_handler,
                 allow_empty_config,
+                &HashSet::new(),
             )
-            .await;
-
-            reload_config_from_result(topology_controller, new_config).await
+            .await
+        }
+        Ok(SignalTo::ReloadComponents(components)) => {
+            reload_config_from_disk(
+                topology_controller,
+                config_paths,
+                signal_handler,
+                allow_empty_config,
+                &components,
+            )
+            .await
         }
         Err(RecvError::Lagged(amt)) => {
             warn!("Overflow, dropped {} signals.", amt);
@@ -349,12 +357,41 @@ async fn handle_signal(
     }
 }
 
+async fn reload_config_from_disk(
+    topology_controller: &SharedTopologyController,
+    config_paths: &[ConfigPath],
+    signal_handler: &mut SignalHandler,
+    allow_empty_config: bool,
+    components_to_reload: &HashSet<ComponentKey>,
+) -> Option<SignalTo> {
+    let mut topology_controller = topology_controller.lock().await;
+
+    // Reload paths
+    if let Some(paths) = config::process_paths(config_paths) {
+        topology_controller.config_paths = paths;
+    }
+
+    // Reload config
+    let new_config = config::load_from_paths_with_provider_and_secrets(
+        &topology_controller.config_paths,
+        signal_handler,
+        allow_empty_config,
+    )
+    .await;
+
+    reload_config_from_result(topology_controller, new_config, components_to_reload).await
+}
+
 async fn reload_config_from_result(
     mut topology_controller: MutexGuard<'_, TopologyController>,
     config: Result<Config, Vec<String>>,
+    components_to_reload: &HashSet<ComponentKey>,
 ) -> Option<SignalTo> {
     match config {
-        Ok(new_config) => match topology_controller.reload(new_config).await {
+        Ok(new_config) => match topology_controller
+            .reload(new_config, components_to_reload)
+            .await
+        {
             ReloadOutcome::FatalError(error) => Some(SignalTo::Shutdown(Some(error))),
             _ => None,
         },
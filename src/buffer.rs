@@ -0,0 +1,384 @@
+#![allow(missing_docs)]
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+use bytes::BytesMut;
+use clap::Parser;
+use colored::*;
+use serde::Serialize;
+use tokio_util::codec::Encoder as _;
+use vector_lib::{
+    buffers::{
+        inspect::{BufferInspector, DataFileReport, InspectError, InspectedRecord, LedgerSnapshot},
+        EncryptionKey, RecordCipher,
+    },
+    codecs::NativeJsonSerializer,
+    event::{EventArray, EventContainer},
+};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    #[command(subcommand)]
+    sub_command: SubCommand,
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+enum SubCommand {
+    /// Report the ledger state and data files of a disk buffer, validating the checksum of every
+    /// record.
+    Inspect(InspectOpts),
+
+    /// Export the events in a disk buffer as native JSON, one event per line.
+    Export(ExportOpts),
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+struct BufferOpts {
+    /// The ID of the sink whose buffer to read, or the name of the shared buffer with `--shared`.
+    component_id: String,
+
+    /// The data directory of the Vector instance that owns the buffer.
+    #[arg(long, default_value = "/var/lib/vector/")]
+    data_dir: PathBuf,
+
+    /// Read the shared disk buffer with the given name, rather than the buffer of a sink.
+    #[arg(long)]
+    shared: bool,
+}
+
+impl BufferOpts {
+    fn buffer_path(&self) -> PathBuf {
+        self.data_dir
+            .join("buffer")
+            .join(if self.shared { "v2-shared" } else { "v2" })
+            .join(&self.component_id)
+    }
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+struct InspectOpts {
+    #[command(flatten)]
+    buffer: BufferOpts,
+
+    /// Format the report in an encoding scheme.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+struct ExportOpts {
+    #[command(flatten)]
+    buffer: BufferOpts,
+
+    /// The file to write the events to. Defaults to stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Also export events that were already acknowledged by the sink, but are still on disk.
+    #[arg(long)]
+    include_acknowledged: bool,
+
+    /// The key used to encrypt the buffer, as 64 hex characters.
+    #[arg(long, env = "VECTOR_BUFFER_ENCRYPTION_KEY", hide_env_values = true)]
+    encryption_key: Option<String>,
+
+    /// Previous keys used to encrypt the buffer, as 64 hex characters.
+    #[arg(long = "previous-encryption-key", requires = "encryption_key")]
+    previous_encryption_keys: Vec<String>,
+}
+
+impl ExportOpts {
+    fn cipher(&self) -> Result<Option<RecordCipher>, String> {
+        let Some(key) = &self.encryption_key else {
+            return Ok(None);
+        };
+        let parse = |key: &str| {
+            EncryptionKey::from_hex(key).map_err(|error| format!("Invalid encryption key: {error}"))
+        };
+
+        let current = parse(key)?;
+        let previous = self
+            .previous_encryption_keys
+            .iter()
+            .map(|key| parse(key))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Some(RecordCipher::new(&current, &previous)))
+    }
+}
+
+#[derive(Serialize)]
+struct InspectReport {
+    path: PathBuf,
+    writer_next_record_id: u64,
+    writer_current_data_file_id: u16,
+    reader_current_data_file_id: u16,
+    reader_last_record_id: u64,
+    unacknowledged_events: u64,
+    data_files: Vec<DataFileSummary>,
+}
+
+#[derive(Serialize)]
+struct DataFileSummary {
+    file_id: u16,
+    path: PathBuf,
+    size: u64,
+    records: u64,
+    events: u64,
+    unacknowledged_records: u64,
+    first_record_id: Option<u64>,
+    last_record_id: Option<u64>,
+    error: Option<String>,
+}
+
+impl InspectReport {
+    fn new(path: PathBuf, ledger: &LedgerSnapshot, data_files: Vec<DataFileReport>) -> Self {
+        Self {
+            path,
+            writer_next_record_id: ledger.writer_next_record_id,
+            writer_current_data_file_id: ledger.writer_current_data_file_id,
+            reader_current_data_file_id: ledger.reader_current_data_file_id,
+            reader_last_record_id: ledger.reader_last_record_id,
+            unacknowledged_events: ledger.unacknowledged_events(),
+            data_files: data_files
+                .into_iter()
+                .map(|report| DataFileSummary {
+                    file_id: report.file_id,
+                    path: report.path,
+                    size: report.size,
+                    records: report.records,
+                    events: report.events,
+                    unacknowledged_records: report.unacknowledged_records,
+                    first_record_id: report.first_record_id,
+                    last_record_id: report.last_record_id,
+                    error: report.error,
+                })
+                .collect(),
+        }
+    }
+
+    fn has_errors(&self) -> bool {
+        self.data_files.iter().any(|file| file.error.is_some())
+    }
+
+    #[allow(clippy::print_stdout)]
+    fn print_text(&self) {
+        println!("Buffer: {}", self.path.display());
+        println!(
+            "Writer: data file {}, next record ID {}",
+            self.writer_current_data_file_id, self.writer_next_record_id
+        );
+        println!(
+            "Reader: data file {}, last record ID {}",
+            self.reader_current_data_file_id, self.reader_last_record_id
+        );
+        println!("Unacknowledged events: {}", self.unacknowledged_events);
+
+        println!("\nData files:");
+        if self.data_files.is_empty() {
+            println!("- none");
+        }
+        for file in &self.data_files {
+            let record_ids = match (file.first_record_id, file.last_record_id) {
+                (Some(first), Some(last)) => format!("record IDs {first}..={last}"),
+                _ => "no records".to_string(),
+            };
+            println!(
+                "- {} ({} bytes): {} records ({} unacknowledged), {} events, {}",
+                file.path.display(),
+                file.size,
+                file.records,
+                file.unacknowledged_records,
+                file.events,
+                record_ids,
+            );
+            if let Some(error) = &file.error {
+                println!("  {}", format!("error: {error}").red());
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ExportError {
+    Inspect(InspectError),
+    Io(io::Error),
+}
+
+impl From<InspectError> for ExportError {
+    fn from(error: InspectError) -> Self {
+        Self::Inspect(error)
+    }
+}
+
+impl From<io::Error> for ExportError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl std::fmt::Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Inspect(error) => error.fmt(f),
+            Self::Io(error) => write!(f, "Failed to write events: {error}"),
+        }
+    }
+}
+
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match &opts.sub_command {
+        SubCommand::Inspect(opts) => inspect(opts),
+        SubCommand::Export(opts) => export(opts),
+    }
+}
+
+#[allow(clippy::print_stderr)]
+fn report_error(error: impl std::fmt::Display) {
+    eprintln!("{}", error.to_string().red());
+}
+
+fn open(opts: &BufferOpts) -> Result<BufferInspector, exitcode::ExitCode> {
+    BufferInspector::open(&opts.buffer_path()).map_err(|error| {
+        report_error(&error);
+        match error {
+            InspectError::MissingLedger { .. } => exitcode::NOINPUT,
+            InspectError::LedgerLockAlreadyHeld => exitcode::TEMPFAIL,
+            InspectError::Io { .. } => exitcode::IOERR,
+            InspectError::FailedToDeserialize { .. } => exitcode::DATAERR,
+        }
+    })
+}
+
+fn inspect(opts: &InspectOpts) -> exitcode::ExitCode {
+    let inspector = match open(&opts.buffer) {
+        Ok(inspector) => inspector,
+        Err(code) => return code,
+    };
+
+    let data_files = match inspector.scan::<InspectError, _>(|_| Ok(())) {
+        Ok(data_files) => data_files,
+        Err(error) => {
+            report_error(error);
+            return exitcode::IOERR;
+        }
+    };
+
+    let report = InspectReport::new(opts.buffer.buffer_path(), inspector.ledger(), data_files);
+    match opts.format {
+        Format::Text => report.print_text(),
+        #[allow(clippy::print_stdout)]
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+    }
+
+    if report.has_errors() {
+        exitcode::DATAERR
+    } else {
+        exitcode::OK
+    }
+}
+
+fn export(opts: &ExportOpts) -> exitcode::ExitCode {
+    let cipher = match opts.cipher() {
+        Ok(cipher) => cipher,
+        Err(error) => {
+            report_error(error);
+            return exitcode::USAGE;
+        }
+    };
+
+    let inspector = match open(&opts.buffer) {
+        Ok(inspector) => inspector,
+        Err(code) => return code,
+    };
+
+    let output: Box<dyn Write> = match &opts.output {
+        Some(path) => match File::create(path) {
+            Ok(file) => Box::new(file),
+            Err(error) => {
+                report_error(format!("Failed to create {}: {error}", path.display()));
+                return exitcode::CANTCREAT;
+            }
+        },
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut output = BufWriter::new(output);
+
+    let mut serializer = NativeJsonSerializer;
+    let mut buf = BytesMut::new();
+    let mut exported_events = 0usize;
+    let mut skipped_records = 0usize;
+    let result = inspector.scan::<ExportError, _>(|record: &InspectedRecord<'_>| {
+        if record.is_acknowledged() && !opts.include_acknowledged {
+            return Ok(());
+        }
+
+        let events = match record.decode::<EventArray>(cipher.as_ref()) {
+            Ok(events) => events,
+            Err(error) => {
+                report_error(format!(
+                    "Skipping record {} in data file {}: {error}",
+                    record.id(),
+                    record.file_id()
+                ));
+                skipped_records += 1;
+                return Ok(());
+            }
+        };
+
+        for event in events.into_events() {
+            buf.clear();
+            serializer
+                .encode(event, &mut buf)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+            output.write_all(&buf)?;
+            output.write_all(b"\n")?;
+            exported_events += 1;
+        }
+        Ok(())
+    });
+
+    let data_files = match result.and_then(|data_files| {
+        output.flush()?;
+        Ok(data_files)
+    }) {
+        Ok(data_files) => data_files,
+        Err(error) => {
+            report_error(error);
+            return exitcode::IOERR;
+        }
+    };
+
+    for file in &data_files {
+        if let Some(error) = &file.error {
+            report_error(format!(
+                "Stopped reading data file {} early: {error}",
+                file.path.display()
+            ));
+        }
+    }
+
+    #[allow(clippy::print_stderr)]
+    {
+        eprintln!("Exported {exported_events} events.");
+    }
+
+    if skipped_records > 0 || data_files.iter().any(|file| file.error.is_some()) {
+        exitcode::DATAERR
+    } else {
+        exitcode::OK
+    }
+}
@@ -10,7 +10,9 @@ use crate::service;
 use crate::tap;
 #[cfg(feature = "api-client")]
 use crate::top;
-use crate::{config, convert_config, generate, get_version, graph, list, unit_test, validate};
+use crate::{
+    buffer, config, convert_config, generate, get_version, graph, list, unit_test, validate,
+};
 use crate::{generate_schema, signal};
 
 #[derive(Parser, Debug)]
@@ -33,6 +35,7 @@ impl Opts {
     pub const fn log_level(&self) -> &'static str {
         let (quiet_level, verbose_level) = match self.sub_command {
             Some(SubCommand::Validate(_))
+            | Some(SubCommand::Buffer(_))
             | Some(SubCommand::Graph(_))
             | Some(SubCommand::Generate(_))
             | Some(SubCommand::ConvertConfig(_))
@@ -276,7 +279,8 @@ pub enum SubCommand {
     /// For guidance on how to write unit tests check out <https://vector.dev/guides/level-up/unit-testing/>.
     Test(unit_test::Opts),
 
-    /// Output the topology as visual representation using the DOT language which can be rendered by GraphViz
+    /// Output the topology as visual representation using the DOT language which can be rendered by GraphViz,
+    /// as a Mermaid flowchart, or as JSON, optionally annotated with live metrics of a running instance
     Graph(graph::Opts),
 
     /// Display topology and metrics in the console, for a local or remote Vector instance
@@ -287,6 +291,9 @@ pub enum SubCommand {
     #[cfg(feature = "api-client")]
     Tap(tap::Opts),
 
+    /// Inspect or export the contents of a disk buffer, while Vector is stopped.
+    Buffer(buffer::Opts),
+
     /// Manage the vector service.
     #[cfg(windows)]
     Service(service::Opts),
@@ -302,11 +309,12 @@ impl SubCommand {
         color: bool,
     ) -> exitcode::ExitCode {
         match self {
-            Self::Config(c) => config::cmd(c),
+            Self::Buffer(b) => buffer::cmd(b),
+            Self::Config(c) => config::cmd(c).await,
             Self::ConvertConfig(opts) => convert_config::cmd(opts),
             Self::Generate(g) => generate::cmd(g),
             Self::GenerateSchema => generate_schema::cmd(),
-            Self::Graph(g) => graph::cmd(g),
+            Self::Graph(g) => graph::cmd(g).await,
             Self::List(l) => list::cmd(l),
             #[cfg(windows)]
             Self::Service(s) => service::cmd(s),
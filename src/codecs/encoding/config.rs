@@ -1,4 +1,8 @@
 use crate::codecs::Transformer;
+#[cfg(feature = "codecs-parquet")]
+use crate::codecs::{Encoder, EncoderKind};
+#[cfg(feature = "codecs-parquet")]
+use vector_lib::codecs::encoding::BatchSerializerConfig;
 use vector_lib::codecs::{
     encoding::{Framer, FramingConfig, Serializer, SerializerConfig},
     CharacterDelimitedEncoder, LengthDelimitedEncoder, NewlineDelimitedEncoder,
@@ -104,7 +108,7 @@ impl EncodingConfigWithFraming {
                 SinkType::StreamBased => NewlineDelimitedEncoder::default().into(),
                 SinkType::MessageBased => CharacterDelimitedEncoder::new(b',').into(),
             },
-            (None, Serializer::Avro(_) | Serializer::Native(_)) => {
+            (None, Serializer::Avro(_) | Serializer::Native(_) | Serializer::SchemaRegistry(_)) => {
                 LengthDelimitedEncoder::default().into()
             }
             (None, Serializer::Gelf(_)) => {
@@ -129,6 +133,25 @@ impl EncodingConfigWithFraming {
 
         Ok((framer, serializer))
     }
+
+    /// Build the `EncoderKind` for this config, or for the given batch encoding instead if one is
+    /// set.
+    ///
+    /// The transformer of this config applies either way.
+    #[cfg(feature = "codecs-parquet")]
+    pub fn build_encoder_kind(
+        &self,
+        batch_encoding: Option<&BatchSerializerConfig>,
+        sink_type: SinkType,
+    ) -> crate::Result<EncoderKind> {
+        match batch_encoding {
+            Some(batch_encoding) => Ok(EncoderKind::Batch(batch_encoding.build()?)),
+            None => {
+                let (framer, serializer) = self.build(sink_type)?;
+                Ok(Encoder::<Framer>::new(framer, serializer).into())
+            }
+        }
+    }
 }
 
 /// The way a sink processes outgoing events.
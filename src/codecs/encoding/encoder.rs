@@ -1,5 +1,7 @@
 use bytes::BytesMut;
 use tokio_util::codec::Encoder as _;
+#[cfg(feature = "codecs-parquet")]
+use vector_lib::codecs::encoding::BatchSerializer;
 use vector_lib::codecs::{
     encoding::{Error, Framer, Serializer},
     CharacterDelimitedEncoder, NewlineDelimitedEncoder, TextSerializerConfig,
@@ -113,7 +115,10 @@ impl Encoder<Framer> {
                 Serializer::Gelf(_) | Serializer::Json(_) | Serializer::NativeJson(_),
                 Framer::CharacterDelimited(CharacterDelimitedEncoder { delimiter: b',' }),
             ) => "application/json",
-            (Serializer::Native(_), _) | (Serializer::Protobuf(_), _) => "application/octet-stream",
+            (
+                Serializer::Native(_) | Serializer::Protobuf(_) | Serializer::SchemaRegistry(_),
+                _,
+            ) => "application/octet-stream",
             (
                 Serializer::Avro(_)
                 | Serializer::Csv(_)
@@ -181,6 +186,43 @@ impl tokio_util::codec::Encoder<Event> for Encoder<()> {
     }
 }
 
+/// An encoder for sinks that write each batch of events as a single object, which either encodes
+/// the events of the batch one at a time, or the whole batch at once.
+#[derive(Debug, Clone)]
+pub enum EncoderKind {
+    /// Encodes the events one at a time, framing them within the batch.
+    Framed(Box<Encoder<Framer>>),
+    /// Encodes the whole batch at once.
+    #[cfg(feature = "codecs-parquet")]
+    Batch(BatchSerializer),
+}
+
+impl EncoderKind {
+    /// Get the HTTP content type.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            EncoderKind::Framed(encoder) => encoder.content_type(),
+            #[cfg(feature = "codecs-parquet")]
+            EncoderKind::Batch(serializer) => serializer.content_type(),
+        }
+    }
+
+    /// Get the extension of the files holding the encoded batches, if the encoding determines it.
+    pub const fn file_extension(&self) -> Option<&'static str> {
+        match self {
+            EncoderKind::Framed(_) => None,
+            #[cfg(feature = "codecs-parquet")]
+            EncoderKind::Batch(serializer) => Some(serializer.file_extension()),
+        }
+    }
+}
+
+impl From<Encoder<Framer>> for EncoderKind {
+    fn from(encoder: Encoder<Framer>) -> Self {
+        Self::Framed(Box::new(encoder))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::BufMut;
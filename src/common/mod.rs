@@ -17,3 +17,9 @@ pub(crate) mod sqs;
 
 #[cfg(any(feature = "sources-aws_s3", feature = "sinks-aws_s3"))]
 pub(crate) mod s3;
+
+#[cfg(any(feature = "sources-mqtt", feature = "sinks-mqtt"))]
+pub(crate) mod mqtt;
+
+#[cfg(any(feature = "sources-websocket", feature = "sinks-websocket"))]
+pub(crate) mod websocket;
@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use rand::Rng;
+use rumqttc::{MqttOptions, QoS, TlsConfiguration, Transport};
+use snafu::{ResultExt, Snafu};
+use vector_lib::configurable::configurable_component;
+use vector_lib::tls::{MaybeTlsSettings, TlsEnableableConfig, TlsError};
+
+use crate::template::TemplateParseError;
+
+/// Common MQTT connection configuration, shared by the `mqtt` source and sink.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+pub struct MqttCommonConfig {
+    /// MQTT server address (The broker’s domain name or IP address).
+    #[configurable(metadata(docs::examples = "mqtt.example.com", docs::examples = "127.0.0.1"))]
+    #[derivative(Default(value = "\"localhost\".into()"))]
+    pub host: String,
+
+    /// TCP port of the MQTT server to connect to.
+    #[serde(default = "default_port")]
+    #[derivative(Default(value = "default_port()"))]
+    pub port: u16,
+
+    /// MQTT username.
+    pub user: Option<String>,
+
+    /// MQTT password.
+    pub password: Option<String>,
+
+    /// MQTT client ID.
+    ///
+    /// If not set, a random client ID is generated on every start. Persistent sessions can only
+    /// be resumed with a fixed client ID, so the `mqtt` source requires one unless
+    /// `clean_session` is enabled.
+    pub client_id: Option<String>,
+
+    /// Connection keep-alive interval.
+    #[serde(default = "default_keep_alive")]
+    #[derivative(Default(value = "default_keep_alive()"))]
+    pub keep_alive: u16,
+
+    /// If set to true, the MQTT session is cleaned on login.
+    ///
+    /// Otherwise the broker keeps the session, including subscriptions and messages queued
+    /// while disconnected, across reconnects of the same client ID.
+    #[serde(default = "default_clean_session")]
+    #[derivative(Default(value = "default_clean_session()"))]
+    pub clean_session: bool,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+}
+
+const fn default_port() -> u16 {
+    1883
+}
+
+const fn default_keep_alive() -> u16 {
+    60
+}
+
+const fn default_clean_session() -> bool {
+    false
+}
+
+/// Supported Quality of Service types for MQTT.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+#[allow(clippy::enum_variant_names)]
+pub enum MqttQoS {
+    /// AtLeastOnce.
+    #[derivative(Default)]
+    AtLeastOnce,
+
+    /// AtMostOnce.
+    AtMostOnce,
+
+    /// ExactlyOnce.
+    ExactlyOnce,
+}
+
+impl From<MqttQoS> for QoS {
+    fn from(value: MqttQoS) -> Self {
+        match value {
+            MqttQoS::AtLeastOnce => QoS::AtLeastOnce,
+            MqttQoS::AtMostOnce => QoS::AtMostOnce,
+            MqttQoS::ExactlyOnce => QoS::ExactlyOnce,
+        }
+    }
+}
+
+impl From<MqttQoS> for rumqttc::v5::mqttbytes::QoS {
+    fn from(value: MqttQoS) -> Self {
+        match value {
+            MqttQoS::AtLeastOnce => Self::AtLeastOnce,
+            MqttQoS::AtMostOnce => Self::AtMostOnce,
+            MqttQoS::ExactlyOnce => Self::ExactlyOnce,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Snafu)]
+pub enum ConfigurationError {
+    #[snafu(display("Client ID is not allowed to be empty."))]
+    EmptyClientId,
+    #[snafu(display("Username and password must be either both provided or both missing."))]
+    InvalidCredentials,
+    #[snafu(display("At least one topic filter must be provided."))]
+    EmptyTopics,
+    #[snafu(display("Invalid topic filter: {:?}.", filter))]
+    InvalidTopicFilter { filter: String },
+    #[snafu(display("Publish properties require the `v5` protocol version."))]
+    PropertiesRequireV5,
+    #[snafu(display("A client ID is required unless `clean_session` is enabled."))]
+    PersistentSessionWithoutClientId,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum MqttError {
+    #[snafu(display("invalid topic template: {}", source))]
+    TopicTemplate { source: TemplateParseError },
+    #[snafu(display("TLS error: {}", source))]
+    Tls { source: TlsError },
+    #[snafu(display("MQTT configuration error: {}", source))]
+    Configuration { source: ConfigurationError },
+}
+
+impl MqttCommonConfig {
+    /// Builds the options to connect to the broker with, generating a client ID starting with
+    /// `client_id_prefix` if none is configured.
+    pub fn build_options(&self, client_id_prefix: &str) -> Result<MqttOptions, MqttError> {
+        let client_id = self.client_id(client_id_prefix)?;
+        let mut options = MqttOptions::new(&client_id, &self.host, self.port);
+        options.set_keep_alive(Duration::from_secs(self.keep_alive.into()));
+        options.set_clean_session(self.clean_session);
+        if let Some((user, password)) = self.credentials()? {
+            options.set_credentials(user, password);
+        }
+        if let Some(transport) = self.transport()? {
+            options.set_transport(transport);
+        }
+        Ok(options)
+    }
+
+    /// Builds the options to connect to the broker with over MQTT 5, generating a client ID
+    /// starting with `client_id_prefix` if none is configured.
+    ///
+    /// The `clean_session` setting maps to the clean start flag.
+    pub fn build_options_v5(
+        &self,
+        client_id_prefix: &str,
+    ) -> Result<rumqttc::v5::MqttOptions, MqttError> {
+        let client_id = self.client_id(client_id_prefix)?;
+        let mut options = rumqttc::v5::MqttOptions::new(&client_id, &self.host, self.port);
+        options.set_keep_alive(Duration::from_secs(self.keep_alive.into()));
+        options.set_clean_start(self.clean_session);
+        if let Some((user, password)) = self.credentials()? {
+            options.set_credentials(user, password);
+        }
+        if let Some(transport) = self.transport()? {
+            options.set_transport(transport);
+        }
+        Ok(options)
+    }
+
+    fn client_id(&self, client_id_prefix: &str) -> Result<String, MqttError> {
+        let client_id = self.client_id.clone().unwrap_or_else(|| {
+            let hash = rand::thread_rng()
+                .sample_iter(&rand_distr::Alphanumeric)
+                .take(6)
+                .map(char::from)
+                .collect::<String>();
+            format!("{client_id_prefix}{hash}")
+        });
+
+        if client_id.is_empty() {
+            return Err(ConfigurationError::EmptyClientId).context(ConfigurationSnafu);
+        }
+        Ok(client_id)
+    }
+
+    fn credentials(&self) -> Result<Option<(&str, &str)>, MqttError> {
+        match (&self.user, &self.password) {
+            (Some(user), Some(password)) => Ok(Some((user, password))),
+            (None, None) => Ok(None),
+            _ => Err(MqttError::Configuration {
+                source: ConfigurationError::InvalidCredentials,
+            }),
+        }
+    }
+
+    fn transport(&self) -> Result<Option<Transport>, MqttError> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false).context(TlsSnafu)?;
+        Ok(tls.tls().map(|tls| {
+            let ca = tls.authorities_pem().flatten().collect();
+            let client_auth = None;
+            let alpn = Some(vec!["mqtt".into()]);
+            Transport::Tls(TlsConfiguration::Simple {
+                ca,
+                client_auth,
+                alpn,
+            })
+        }))
+    }
+}
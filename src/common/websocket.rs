@@ -0,0 +1,209 @@
+use std::{
+    io,
+    net::SocketAddr,
+    num::NonZeroU64,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use snafu::{ResultExt, Snafu};
+use tokio::{net::TcpStream, time};
+use tokio_tungstenite::{
+    client_async_with_config,
+    tungstenite::{
+        client::{uri_mode, IntoClientRequest},
+        error::{Error as WsError, ProtocolError, UrlError},
+        handshake::client::Request as WsRequest,
+        protocol::WebSocketConfig,
+        stream::Mode as UriMode,
+    },
+    WebSocketStream as WsStream,
+};
+use vector_lib::emit;
+
+use crate::{
+    dns,
+    http::Auth,
+    internal_events::{WsConnectionEstablished, WsConnectionFailedError},
+    sinks::util::retries::ExponentialBackoff,
+    tls::{MaybeTlsSettings, MaybeTlsStream, TlsError},
+};
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum WebSocketError {
+    #[snafu(display("Creating WebSocket client failed: {}", source))]
+    CreateFailed { source: WsError },
+    #[snafu(display("Connect error: {}", source))]
+    ConnectError { source: TlsError },
+    #[snafu(display("Unable to resolve DNS: {}", source))]
+    DnsError { source: dns::DnsError },
+    #[snafu(display("No addresses returned."))]
+    NoAddresses,
+}
+
+#[derive(Clone)]
+pub struct WebSocketConnector {
+    uri: String,
+    host: String,
+    port: u16,
+    tls: MaybeTlsSettings,
+    auth: Option<Auth>,
+}
+
+impl WebSocketConnector {
+    pub fn new(
+        uri: String,
+        tls: MaybeTlsSettings,
+        auth: Option<Auth>,
+    ) -> Result<Self, WebSocketError> {
+        let request = (&uri).into_client_request().context(CreateFailedSnafu)?;
+        let (host, port) = Self::extract_host_and_port(&request).context(CreateFailedSnafu)?;
+
+        Ok(Self {
+            uri,
+            host,
+            port,
+            tls,
+            auth,
+        })
+    }
+
+    fn extract_host_and_port(request: &WsRequest) -> Result<(String, u16), WsError> {
+        let host = request
+            .uri()
+            .host()
+            .ok_or(WsError::Url(UrlError::NoHostName))?
+            .to_string();
+        let mode = uri_mode(request.uri())?;
+        let port = request.uri().port_u16().unwrap_or(match mode {
+            UriMode::Tls => 443,
+            UriMode::Plain => 80,
+        });
+
+        Ok((host, port))
+    }
+
+    pub(crate) const fn fresh_backoff() -> ExponentialBackoff {
+        ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(60))
+    }
+
+    async fn tls_connect(&self) -> Result<MaybeTlsStream<TcpStream>, WebSocketError> {
+        let ip = dns::Resolver
+            .lookup_ip(self.host.clone())
+            .await
+            .context(DnsSnafu)?
+            .next()
+            .ok_or(WebSocketError::NoAddresses)?;
+
+        let addr = SocketAddr::new(ip, self.port);
+        self.tls
+            .connect(&self.host, &addr)
+            .await
+            .context(ConnectSnafu)
+    }
+
+    async fn connect(&self) -> Result<WsStream<MaybeTlsStream<TcpStream>>, WebSocketError> {
+        let mut request = (&self.uri)
+            .into_client_request()
+            .context(CreateFailedSnafu)?;
+
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let maybe_tls = self.tls_connect().await?;
+
+        let ws_config = WebSocketConfig::default();
+
+        let (ws_stream, _response) = client_async_with_config(request, maybe_tls, Some(ws_config))
+            .await
+            .context(CreateFailedSnafu)?;
+
+        Ok(ws_stream)
+    }
+
+    /// Connects to the server, retrying with an exponential backoff until it succeeds.
+    pub async fn connect_backoff(&self) -> WsStream<MaybeTlsStream<TcpStream>> {
+        self.connect_with_backoff(&mut Self::fresh_backoff()).await
+    }
+
+    /// Connects to the server, retrying with the given backoff until it succeeds.
+    ///
+    /// The backoff is left as it is once connected, so that callers can keep backing off when
+    /// the connection turns out to be unusable.
+    pub async fn connect_with_backoff(
+        &self,
+        backoff: &mut ExponentialBackoff,
+    ) -> WsStream<MaybeTlsStream<TcpStream>> {
+        loop {
+            match self.connect().await {
+                Ok(ws_stream) => {
+                    emit!(WsConnectionEstablished {});
+                    return ws_stream;
+                }
+                Err(error) => {
+                    emit!(WsConnectionFailedError {
+                        error: Box::new(error)
+                    });
+                    time::sleep(backoff.next().unwrap()).await;
+                }
+            }
+        }
+    }
+
+    pub async fn healthcheck(&self) -> crate::Result<()> {
+        self.connect().await.map(|_| ()).map_err(Into::into)
+    }
+}
+
+pub struct PingInterval {
+    interval: Option<time::Interval>,
+}
+
+impl PingInterval {
+    pub fn new(period: Option<u64>) -> Self {
+        Self {
+            interval: period.map(|period| time::interval(Duration::from_secs(period))),
+        }
+    }
+
+    fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<time::Instant> {
+        match self.interval.as_mut() {
+            Some(interval) => interval.poll_tick(cx),
+            None => Poll::Pending,
+        }
+    }
+
+    pub async fn tick(&mut self) -> time::Instant {
+        std::future::poll_fn(|cx| self.poll_tick(cx)).await
+    }
+}
+
+/// Fails with a timeout error if no pong has been received from the peer within `ping_timeout`.
+pub fn check_received_pong_time(
+    ping_timeout: Option<NonZeroU64>,
+    last_pong: Instant,
+) -> Result<(), WsError> {
+    if let Some(ping_timeout) = ping_timeout {
+        if last_pong.elapsed() > Duration::from_secs(ping_timeout.into()) {
+            return Err(WsError::Io(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "Pong not received in time",
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+pub const fn is_closed(error: &WsError) -> bool {
+    matches!(
+        error,
+        WsError::ConnectionClosed
+            | WsError::AlreadyClosed
+            | WsError::Protocol(ProtocolError::ResetWithoutClosingHandshake)
+    )
+}
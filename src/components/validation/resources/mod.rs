@@ -170,6 +170,8 @@ fn deserializer_config_to_serializer(config: &DeserializerConfig) -> encoding::S
         DeserializerConfig::Gelf { .. } => SerializerConfig::Gelf,
         DeserializerConfig::Avro { avro } => SerializerConfig::Avro { avro: avro.into() },
         DeserializerConfig::Vrl { .. } => unimplemented!(),
+        // There's no subject to encode events under.
+        DeserializerConfig::SchemaRegistry(_) => unimplemented!(),
     };
 
     serializer_config
@@ -221,6 +223,11 @@ fn serializer_config_to_deserializer(
             })
         }
         SerializerConfig::RawMessage | SerializerConfig::Text(_) => DeserializerConfig::Bytes,
+        SerializerConfig::SchemaRegistry(config) => DeserializerConfig::SchemaRegistry(
+            vector_lib::codecs::decoding::SchemaRegistryDeserializerConfig {
+                schema_registry: config.schema_registry.registry.clone(),
+            },
+        ),
     };
 
     deserializer_config.build()
@@ -23,6 +23,12 @@ pub struct Options {
     /// Whether or not the GraphQL endpoint is enabled
     #[serde(default = "default_graphql", skip_serializing_if = "is_true")]
     pub graphql: bool,
+
+    /// Whether or not GraphQL mutations, such as reloading components, are allowed.
+    ///
+    /// The API has no authentication, so anyone who can reach its address can use them.
+    #[serde(default = "default_mutations")]
+    pub mutations: bool,
 }
 
 impl Default for Options {
@@ -32,6 +38,7 @@ impl Default for Options {
             playground: default_playground(),
             address: default_address(),
             graphql: default_graphql(),
+            mutations: default_mutations(),
         }
     }
 }
@@ -68,6 +75,10 @@ const fn default_graphql() -> bool {
     true
 }
 
+const fn default_mutations() -> bool {
+    false
+}
+
 impl Options {
     pub fn merge(&mut self, other: Self) -> Result<(), String> {
         // Merge options
@@ -94,6 +105,7 @@ impl Options {
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
             graphql: self.graphql & other.graphql,
+            mutations: self.mutations | other.mutations,
         };
 
         *self = options;
@@ -108,6 +120,7 @@ fn bool_merge() {
         address: None,
         playground: false,
         graphql: false,
+        mutations: true,
     };
 
     a.merge(Options::default()).unwrap();
@@ -118,7 +131,8 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
-            graphql: false
+            graphql: false,
+            mutations: true,
         }
     );
 }
@@ -131,6 +145,7 @@ fn bind_merge() {
         address: Some(address),
         playground: true,
         graphql: true,
+        mutations: false,
     };
 
     a.merge(Options::default()).unwrap();
@@ -142,6 +157,7 @@ fn bind_merge() {
             address: Some(address),
             playground: true,
             graphql: true,
+            mutations: false,
         }
     );
 }
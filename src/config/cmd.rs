@@ -1,15 +1,27 @@
-use std::path::PathBuf;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use serde_json::Value;
-
-use super::{load_builder_from_paths, load_source_from_paths, process_paths, ConfigBuilder};
+#[cfg(feature = "api-client")]
+use url::Url;
+
+use super::{
+    load_builder_from_paths, load_from_paths, load_source_from_paths,
+    loading::{load_builder_from_paths_with_secrets, load_secret_backends_from_paths},
+    process_paths, Config, ConfigBuilder, ConfigSnapshot, DiffReport,
+};
 use crate::cli::handle_config_errors;
 use crate::config;
 
 #[derive(Parser, Debug, Clone)]
 #[command(rename_all = "kebab-case")]
 pub struct Opts {
+    #[command(subcommand)]
+    sub_command: Option<SubCommand>,
+
     /// Pretty print JSON
     #[arg(short, long)]
     pretty: bool,
@@ -57,6 +69,66 @@ pub struct Opts {
     pub config_dirs: Vec<PathBuf>,
 }
 
+#[derive(Subcommand, Debug, Clone)]
+enum SubCommand {
+    /// Show how reloading from one configuration to another would affect a running topology.
+    Diff(DiffOpts),
+
+    /// Ask a running Vector instance to reload its configuration from disk, rebuilding the given
+    /// components even if their configuration didn't change.
+    #[cfg(feature = "api-client")]
+    Reload(ReloadOpts),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(rename_all = "kebab-case")]
+struct DiffOpts {
+    /// The old and the new configuration, each either a file or a directory of files. With
+    /// `--dry-run`, only the new configuration is given.
+    #[arg(required = true, num_args = 1..=2)]
+    paths: Vec<PathBuf>,
+
+    /// Output the report as JSON.
+    #[arg(long)]
+    json: bool,
+
+    /// Compare the configuration against the one running in a Vector instance, using its API,
+    /// instead of against another local configuration. Nothing is reloaded.
+    #[cfg(feature = "api-client")]
+    #[arg(long)]
+    dry_run: bool,
+
+    /// GraphQL API server endpoint used by `--dry-run`.
+    #[cfg(feature = "api-client")]
+    #[arg(short, long)]
+    url: Option<Url>,
+}
+
+#[cfg(feature = "api-client")]
+#[derive(Parser, Debug, Clone)]
+#[command(rename_all = "kebab-case")]
+struct ReloadOpts {
+    /// IDs of the components to rebuild (comma-separated).
+    #[arg(required = true, value_delimiter(','))]
+    components: Vec<String>,
+
+    /// GraphQL API server endpoint of the running Vector instance.
+    #[arg(short, long)]
+    url: Option<Url>,
+}
+
+impl DiffOpts {
+    #[cfg(feature = "api-client")]
+    const fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    #[cfg(not(feature = "api-client"))]
+    const fn dry_run(&self) -> bool {
+        false
+    }
+}
+
 impl Opts {
     fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
         config::merge_path_lists(vec![
@@ -162,7 +234,14 @@ fn serialize_to_json(
 /// The purpose of this func is to combine user configuration after processing all paths,
 /// Pipelines expansions, etc. The JSON result of this serialization can itself be used as a config,
 /// which also makes it useful for version control or treating as a singular unit of configuration.
-pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match &opts.sub_command {
+        Some(SubCommand::Diff(diff_opts)) => return diff(diff_opts).await,
+        #[cfg(feature = "api-client")]
+        Some(SubCommand::Reload(reload_opts)) => return reload(reload_opts).await,
+        None => {}
+    }
+
     let paths = opts.paths_with_formats();
     // Start by serializing to a `ConfigBuilder`. This will leverage validation in config
     // builder fields which we'll use to error out if required.
@@ -190,9 +269,171 @@ pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
     exitcode::OK
 }
 
+fn config_path(path: &Path) -> config::ConfigPath {
+    if path.is_dir() {
+        config::ConfigPath::Dir(path.to_path_buf())
+    } else {
+        config::ConfigPath::File(path.to_path_buf(), None)
+    }
+}
+
+/// Function used by the `vector config diff` subcommand for previewing the effect of a reload.
+async fn diff(opts: &DiffOpts) -> exitcode::ExitCode {
+    let expected_paths = if opts.dry_run() { 1 } else { 2 };
+    if opts.paths.len() != expected_paths {
+        #[allow(clippy::print_stderr)]
+        {
+            eprintln!(
+                "Expected {} configuration path(s), got {}.",
+                expected_paths,
+                opts.paths.len()
+            );
+        }
+        return exitcode::USAGE;
+    }
+
+    let mut configs = Vec::with_capacity(expected_paths);
+    for path in &opts.paths {
+        let paths = match process_paths(&[config_path(path)]) {
+            Some(paths) => paths,
+            None => return exitcode::CONFIG,
+        };
+        configs.push(paths);
+    }
+
+    #[cfg(feature = "api-client")]
+    if opts.dry_run {
+        return match dry_run(opts, &configs[0]).await {
+            Ok(report) => print_report(opts, &report),
+            Err(code) => code,
+        };
+    }
+
+    let mut snapshots = Vec::with_capacity(2);
+    for paths in &configs {
+        match load_with_secrets(paths) {
+            Ok(config) => snapshots.push(ConfigSnapshot::new(&config)),
+            Err(errs) => return handle_config_errors(errs),
+        }
+    }
+
+    print_report(
+        opts,
+        &DiffReport::new(&snapshots[0], &snapshots[1], &HashSet::new()),
+    )
+}
+
+/// Loads a configuration with its secrets resolved, so that a rotated secret shows up as a change
+/// instead of comparing identical `SECRET[...]` placeholders.
+fn load_with_secrets(paths: &[config::ConfigPath]) -> Result<Config, Vec<String>> {
+    let mut secrets_backends_loader = load_secret_backends_from_paths(paths)?;
+    if !secrets_backends_loader.has_secrets_to_retrieve() {
+        return load_from_paths(paths);
+    }
+
+    // Keep the sender alive, so that backends don't mistake a closed channel for a shutdown.
+    let (_signal_tx, mut signal_rx) = tokio::sync::broadcast::channel(1);
+    let resolved_secrets = secrets_backends_loader
+        .retrieve(&mut signal_rx)
+        .map_err(|error| vec![error])?;
+    let (config, build_warnings) =
+        load_builder_from_paths_with_secrets(paths, resolved_secrets)?.build_with_warnings()?;
+
+    for warning in build_warnings {
+        warn!("{}", warning);
+    }
+
+    Ok(config)
+}
+
+fn print_report(opts: &DiffOpts, report: &DiffReport) -> exitcode::ExitCode {
+    #[allow(clippy::print_stdout)]
+    if opts.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(report).expect("report should be serializable")
+        );
+    } else {
+        print!("{}", report);
+    }
+
+    exitcode::OK
+}
+
+/// Asks a running Vector instance to compare the given configuration against its own.
+#[cfg(feature = "api-client")]
+#[allow(clippy::print_stderr)]
+async fn dry_run(
+    opts: &DiffOpts,
+    paths: &[config::ConfigPath],
+) -> Result<DiffReport, exitcode::ExitCode> {
+    use vector_api_client::{gql::ConfigQueryExt, Client};
+
+    // Make sure the configuration is valid locally before sending it.
+    let builder = load_builder_from_paths(paths).map_err(handle_config_errors)?;
+    let source = load_source_from_paths(paths).map_err(handle_config_errors)?;
+    let json =
+        serialize_to_json(source, &builder, false, false).expect("config should be serializable");
+
+    let url = opts
+        .url
+        .clone()
+        .unwrap_or_else(config::api::default_graphql_url);
+    let client = Client::new(url.clone());
+
+    let response = client.config_diff_query(json).await.map_err(|error| {
+        eprintln!("Vector API server isn't reachable ({}): {}", url, error);
+        exitcode::UNAVAILABLE
+    })?;
+
+    match (response.data, response.errors) {
+        (Some(data), _) => Ok(serde_json::from_str(&data.config_diff)
+            .expect("API server should return a valid report")),
+        (None, errors) => {
+            for error in errors.unwrap_or_default() {
+                eprintln!("{}", error.message);
+            }
+            Err(exitcode::CONFIG)
+        }
+    }
+}
+
+/// Function used by the `vector config reload` subcommand for rebuilding individual components of
+/// a running Vector instance.
+#[cfg(feature = "api-client")]
+#[allow(clippy::print_stderr)]
+async fn reload(opts: &ReloadOpts) -> exitcode::ExitCode {
+    use vector_api_client::{gql::ConfigMutationExt, Client};
+
+    let url = opts
+        .url
+        .clone()
+        .unwrap_or_else(config::api::default_graphql_url);
+    let client = Client::new(url.clone());
+
+    match client
+        .reload_components_mutation(opts.components.clone())
+        .await
+    {
+        Ok(response) => match response.errors {
+            Some(errors) if !errors.is_empty() => {
+                for error in errors {
+                    eprintln!("{}", error.message);
+                }
+                exitcode::CONFIG
+            }
+            _ => exitcode::OK,
+        },
+        Err(error) => {
+            eprintln!("Vector API server isn't reachable ({}): {}", url, error);
+            exitcode::UNAVAILABLE
+        }
+    }
+}
+
 #[cfg(all(test, feature = "sources", feature = "transforms", feature = "sinks"))]
 mod tests {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
 
     use proptest::{num, prelude::*, sample};
     use rand::{
@@ -204,14 +445,14 @@ mod tests {
         SinkDescription, SourceDescription, TransformDescription,
     };
 
-    use crate::config::Format;
+    use crate::config::{ConfigPath, Format};
     use crate::{
         config::{cmd::serialize_to_json, vars, ConfigBuilder},
         generate,
         generate::{generate_example, TransformInputsStrategy},
     };
 
-    use super::merge_json;
+    use super::{load_with_secrets, merge_json, ConfigSnapshot, DiffReport};
 
     #[test]
     fn test_array_override() {
@@ -386,4 +627,43 @@ mod tests {
             assert!(serde_json::from_str::<ConfigBuilder>(json.as_ref()).is_ok());
         }
     }
+
+    #[test]
+    fn diff_resolves_secrets() {
+        let dir = crate::test_util::temp_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let load = |name: &str, secret: &str| {
+            let path = dir.join(format!("{}.toml", name));
+            std::fs::write(
+                &path,
+                indoc::formatdoc!(
+                    r#"
+                        [secret.backend]
+                        type = "test"
+                        replacement = "{}"
+
+                        [sources.in]
+                        type = "test_basic"
+
+                        [sinks.out]
+                        type = "test_basic"
+                        inputs = ["in"]
+                        data = "SECRET[backend.data]"
+                    "#,
+                    secret
+                ),
+            )
+            .unwrap();
+            ConfigSnapshot::new(&load_with_secrets(&[ConfigPath::File(path, None)]).unwrap())
+        };
+
+        let report = DiffReport::new(
+            &load("old", "before"),
+            &load("new", "rotated"),
+            &HashSet::new(),
+        );
+        assert_eq!(report.sinks.rebuilt, vec!["out"]);
+        assert!(report.sinks.unknown.is_empty());
+    }
 }
@@ -31,6 +31,10 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(type_errors);
     }
 
+    if let Err(buffer_errors) = validation::check_buffers(&builder) {
+        errors.extend(buffer_errors);
+    }
+
     if let Err(output_errors) = validation::check_outputs(&builder) {
         errors.extend(output_errors);
     }
@@ -106,6 +110,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         };
 
         config.propagate_acknowledgements()?;
+        validation::check_offset_links(&config)?;
 
         let warnings = validation::warnings(&config);
 
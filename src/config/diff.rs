@@ -1,8 +1,12 @@
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
 
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 
-use super::{ComponentKey, Config};
+use super::{ComponentKey, Config, OutputId, COLLECTOR};
 
 #[derive(Debug)]
 pub struct ConfigDiff {
@@ -18,14 +22,63 @@ impl ConfigDiff {
     }
 
     pub fn new(old: &Config, new: &Config) -> Self {
-        ConfigDiff {
+        Self::from_snapshots(&ConfigSnapshot::new(old), &ConfigSnapshot::new(new))
+    }
+
+    /// Computes the difference between two configuration snapshots. This is what both a reload
+    /// and the dry-run report of one are based on, so that the two can't disagree.
+    pub fn from_snapshots(old: &ConfigSnapshot, new: &ConfigSnapshot) -> Self {
+        let mut sinks = Difference::new(&old.sinks, &new.sinks);
+        sinks.split_rewired(&old.sinks, &new.sinks);
+
+        let mut diff = ConfigDiff {
             sources: Difference::new(&old.sources, &new.sources),
             transforms: Difference::new(&old.transforms, &new.transforms),
-            sinks: Difference::new(&old.sinks, &new.sinks),
+            sinks,
             enrichment_tables: Difference::new(&old.enrichment_tables, &new.enrichment_tables),
+        };
+        diff.rebuild_offset_links(&new.offset_links);
+        diff.sinks
+            .rebuild_shared_buffers(&old.shared_buffers, &new.shared_buffers);
+        diff
+    }
+
+    /// Marks each source handing off committing the offsets of its events as changed along with
+    /// the sink committing them, whenever either of them is added or rebuilt, or the sink is
+    /// rewired, as they're linked when they're built.
+    ///
+    /// The map holds the sink committing the offsets of each such source, by source.
+    fn rebuild_offset_links(&mut self, links: &HashMap<ComponentKey, ComponentKey>) {
+        for (source, sink) in links {
+            if self.sources.contains_new(source)
+                || self.sinks.contains_new(sink)
+                || self.sinks.is_rewired(sink)
+            {
+                self.sources.mark_changed(source);
+                self.sinks.mark_changed(sink);
+            }
         }
     }
 
+    /// Marks the given components as changed, so that they're rebuilt even if their configuration
+    /// didn't change. Components which aren't part of the new configuration are ignored.
+    pub fn with_reloaded_components(
+        mut self,
+        new: &ConfigSnapshot,
+        keys: &HashSet<ComponentKey>,
+    ) -> Self {
+        for key in keys {
+            if new.sources.contains_key(key) {
+                self.sources.mark_changed(key);
+            } else if new.transforms.contains_key(key) {
+                self.transforms.mark_changed(key);
+            } else if new.sinks.contains_key(key) {
+                self.sinks.mark_changed(key);
+            }
+        }
+        self
+    }
+
     /// Swaps removed with added in Differences.
     pub fn flip(mut self) -> Self {
         self.sources.flip();
@@ -39,6 +92,13 @@ impl ConfigDiff {
         self.sources.contains(key) || self.transforms.contains(key) || self.sinks.contains(key)
     }
 
+    /// Checks whether or not the given component is added or changed.
+    pub fn contains_new(&self, key: &ComponentKey) -> bool {
+        self.sources.contains_new(key)
+            || self.transforms.contains_new(key)
+            || self.sinks.contains_new(key)
+    }
+
     /// Checks whether or not the given component is changed.
     pub fn is_changed(&self, key: &ComponentKey) -> bool {
         self.sources.is_changed(key)
@@ -59,6 +119,11 @@ pub struct Difference {
     pub to_remove: HashSet<ComponentKey>,
     pub to_change: HashSet<ComponentKey>,
     pub to_add: HashSet<ComponentKey>,
+    /// Components whose configuration only changed in their `inputs`. These keep running, along
+    /// with their buffers and connections, and are only reconnected to their new inputs. Only
+    /// sinks are currently rewired, as the schema definitions transforms are built with depend on
+    /// their inputs.
+    pub to_rewire: HashSet<ComponentKey>,
 }
 
 impl Difference {
@@ -92,6 +157,65 @@ impl Difference {
             to_remove,
             to_change,
             to_add,
+            to_rewire: HashSet::new(),
+        }
+    }
+
+    /// Moves changed components whose configuration differs only in their `inputs` over to
+    /// `to_rewire`.
+    fn split_rewired<C>(&mut self, old: &IndexMap<ComponentKey, C>, new: &IndexMap<ComponentKey, C>)
+    where
+        C: serde::Serialize,
+    {
+        let without_inputs = |component: &C| {
+            let mut value = serde_json::to_value(component).unwrap();
+            if let Some(object) = value.as_object_mut() {
+                object.remove("inputs");
+            }
+            value
+        };
+
+        let rewired = self
+            .to_change
+            .iter()
+            .filter(|&key| without_inputs(&old[key]) == without_inputs(&new[key]))
+            .cloned()
+            .collect::<Vec<_>>();
+        for key in rewired {
+            self.to_change.remove(&key);
+            self.to_rewire.insert(key);
+        }
+    }
+
+    /// Marks every sink reading from a shared disk buffer as changed when any other sink reading
+    /// from it is added, removed, or changed, as the buffer is rebuilt along with that sink.
+    ///
+    /// Both maps hold the name of the shared disk buffer of each sink which has one.
+    fn rebuild_shared_buffers(
+        &mut self,
+        old: &HashMap<ComponentKey, String>,
+        new: &HashMap<ComponentKey, String>,
+    ) {
+        let rebuilt = old
+            .iter()
+            .filter(|(key, _)| self.is_removed(key) || self.is_changed(key))
+            .chain(
+                new.iter()
+                    .filter(|(key, _)| self.is_added(key) || self.is_changed(key)),
+            )
+            .map(|(_, group)| group.clone())
+            .collect::<HashSet<_>>();
+        for (key, group) in new {
+            if rebuilt.contains(group) {
+                self.mark_changed(key);
+            }
+        }
+    }
+
+    fn mark_changed(&mut self, key: &ComponentKey) {
+        if !self.to_add.contains(key) {
+            self.to_rewire.remove(key);
+            self.to_change.insert(key.clone());
         }
     }
 
@@ -120,6 +244,11 @@ impl Difference {
         self.to_change.contains(key)
     }
 
+    /// Checks whether or not the given component is only being reconnected to new inputs.
+    pub fn is_rewired(&self, key: &ComponentKey) -> bool {
+        self.to_rewire.contains(key)
+    }
+
     /// Checks whether the given component is present as an addition.
     pub fn is_added(&self, id: &ComponentKey) -> bool {
         self.to_add.contains(id)
@@ -142,3 +271,612 @@ impl Difference {
         self.to_change.iter().chain(self.to_remove.iter())
     }
 }
+
+/// Gets the name of the shared disk buffer of each sink which has one.
+fn shared_buffers(config: &Config) -> HashMap<ComponentKey, String> {
+    config
+        .sinks()
+        .filter_map(|(key, sink)| Some((key.clone(), sink.buffer.shared_group()?.to_string())))
+        .collect()
+}
+
+/// Snapshot of the parts of a [`Config`] that determine how a reload affects the running
+/// topology. Unlike [`Config`], a snapshot can be cloned and retained after the config itself has
+/// been consumed by the topology.
+#[derive(Clone, Debug, Default)]
+pub struct ConfigSnapshot {
+    global: serde_json::Value,
+    sources: IndexMap<ComponentKey, serde_json::Value>,
+    transforms: IndexMap<ComponentKey, serde_json::Value>,
+    sinks: IndexMap<ComponentKey, serde_json::Value>,
+    enrichment_tables: IndexMap<ComponentKey, serde_json::Value>,
+    buffers: IndexMap<ComponentKey, serde_json::Value>,
+    shared_buffers: HashMap<ComponentKey, String>,
+    offset_links: HashMap<ComponentKey, ComponentKey>,
+    connections: HashSet<(OutputId, ComponentKey)>,
+    /// Components whose configuration still contains `SECRET[...]` placeholders, and so can't be
+    /// compared against a configuration with resolved secrets.
+    secret_backed: HashSet<ComponentKey>,
+}
+
+impl ConfigSnapshot {
+    pub fn new(config: &Config) -> Self {
+        fn to_values<C: serde::Serialize>(
+            components: &IndexMap<ComponentKey, C>,
+        ) -> IndexMap<ComponentKey, serde_json::Value> {
+            components
+                .iter()
+                .map(|(key, c)| (key.clone(), serde_json::to_value(c).unwrap()))
+                .collect()
+        }
+
+        let connections = config
+            .transforms()
+            .flat_map(|(key, t)| t.inputs.iter().map(move |i| (i.clone(), key.clone())))
+            .chain(
+                config
+                    .sinks()
+                    .flat_map(|(key, s)| s.inputs.iter().map(move |i| (i.clone(), key.clone()))),
+            )
+            .collect();
+
+        let sources = to_values(&config.sources);
+        let transforms = to_values(&config.transforms);
+        let sinks = to_values(&config.sinks);
+        let enrichment_tables = to_values(&config.enrichment_tables);
+        let secret_backed = [&sources, &transforms, &sinks, &enrichment_tables]
+            .into_iter()
+            .flatten()
+            .filter(|(_, value)| COLLECTOR.is_match(&value.to_string()))
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        Self {
+            global: serde_json::to_value(&config.global).unwrap(),
+            sources,
+            transforms,
+            sinks,
+            enrichment_tables,
+            buffers: config
+                .sinks()
+                .map(|(key, sink)| (key.clone(), serde_json::to_value(&sink.buffer).unwrap()))
+                .collect(),
+            shared_buffers: shared_buffers(config),
+            offset_links: config.offset_links(),
+            connections,
+            secret_backed,
+        }
+    }
+
+    /// Checks whether the given source, transform, or sink is part of the configuration.
+    pub fn contains(&self, key: &ComponentKey) -> bool {
+        self.sources.contains_key(key)
+            || self.transforms.contains_key(key)
+            || self.sinks.contains_key(key)
+    }
+}
+
+/// Components added, removed, or rebuilt by a reload, sorted by component ID.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub rebuilt: Vec<String>,
+    /// Components which keep running and are only reconnected to their new inputs.
+    #[serde(default)]
+    pub rewired: Vec<String>,
+    /// Components referencing secrets that weren't resolved for the comparison, which may or may
+    /// not be rebuilt.
+    #[serde(default)]
+    pub unknown: Vec<String>,
+}
+
+impl From<&Difference> for ComponentChanges {
+    fn from(diff: &Difference) -> Self {
+        let sorted = |keys: &HashSet<ComponentKey>| {
+            let mut keys = keys.iter().map(ToString::to_string).collect::<Vec<_>>();
+            keys.sort();
+            keys
+        };
+
+        Self {
+            added: sorted(&diff.to_add),
+            removed: sorted(&diff.to_remove),
+            rebuilt: sorted(&diff.to_change),
+            rewired: sorted(&diff.to_rewire),
+            unknown: Vec::new(),
+        }
+    }
+}
+
+impl ComponentChanges {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.rebuilt.is_empty()
+            && self.rewired.is_empty()
+            && self.unknown.is_empty()
+    }
+
+    /// Flags the existing components whose new configuration references unresolved secrets as
+    /// unknown, since comparing placeholders tells nothing about whether the secrets changed.
+    fn flag_secret_backed(
+        mut self,
+        old: &IndexMap<ComponentKey, serde_json::Value>,
+        secret_backed: &HashSet<ComponentKey>,
+    ) -> Self {
+        let mut unknown = secret_backed
+            .iter()
+            .filter(|key| old.contains_key(*key))
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        unknown.sort();
+
+        self.rebuilt.retain(|key| !unknown.contains(key));
+        self.rewired.retain(|key| !unknown.contains(key));
+        self.unknown = unknown;
+        self
+    }
+}
+
+/// What happens to the buffer of a removed or rebuilt sink.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BufferAction {
+    /// The buffer configuration is unchanged, so the buffer and its contents are handed over to
+    /// the rebuilt sink.
+    Reused,
+    /// The buffer configuration changed, so a new buffer is built for the rebuilt sink.
+    Rebuilt,
+    /// The sink is removed, along with its buffer.
+    Dropped,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BufferChange {
+    pub sink: String,
+    pub action: BufferAction,
+}
+
+/// How a reload affects a connection between a component output and a downstream component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionAction {
+    /// The connection is new.
+    Added,
+    /// The connection no longer exists.
+    Removed,
+    /// The connection exists before and after the reload, but events stop flowing through it
+    /// while either end is rebuilt.
+    Interrupted,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionChange {
+    pub from: String,
+    pub to: String,
+    pub action: ConnectionAction,
+}
+
+/// Human and machine readable summary of how reloading from one configuration to another would
+/// affect the running topology.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffReport {
+    /// Whether the global options changed, in which case a reload is rejected and Vector must be
+    /// restarted instead.
+    pub global_changed: bool,
+    pub sources: ComponentChanges,
+    pub transforms: ComponentChanges,
+    pub sinks: ComponentChanges,
+    pub enrichment_tables: ComponentChanges,
+    pub buffers: Vec<BufferChange>,
+    pub connections: Vec<ConnectionChange>,
+}
+
+impl DiffReport {
+    /// Reports how reloading from `old` to `new` affects the running topology, with the given
+    /// components rebuilt even if their configuration is unchanged, as `reloadComponents` does.
+    pub fn new(
+        old: &ConfigSnapshot,
+        new: &ConfigSnapshot,
+        reloaded: &HashSet<ComponentKey>,
+    ) -> Self {
+        let diff = ConfigDiff::from_snapshots(old, new).with_reloaded_components(new, reloaded);
+
+        // Mirrors `RunningTopology::shutdown_diff`, which hands the buffer of a changed sink over
+        // to its replacement only if the buffer configuration is unchanged, and the buffer isn't
+        // shared with other sinks.
+        let mut buffers = diff
+            .sinks
+            .to_change
+            .iter()
+            .map(|key| BufferChange {
+                sink: key.to_string(),
+                action: if old.buffers.get(key) == new.buffers.get(key)
+                    && !old.shared_buffers.contains_key(key)
+                {
+                    BufferAction::Reused
+                } else {
+                    BufferAction::Rebuilt
+                },
+            })
+            .chain(diff.sinks.to_remove.iter().map(|key| BufferChange {
+                sink: key.to_string(),
+                action: BufferAction::Dropped,
+            }))
+            .collect::<Vec<_>>();
+        buffers.sort_by(|a, b| a.sink.cmp(&b.sink));
+
+        let mut connections = old
+            .connections
+            .difference(&new.connections)
+            .map(|c| (c, ConnectionAction::Removed))
+            .chain(
+                new.connections
+                    .difference(&old.connections)
+                    .map(|c| (c, ConnectionAction::Added)),
+            )
+            .chain(
+                old.connections
+                    .intersection(&new.connections)
+                    .filter(|(from, to)| {
+                        diff.contains_new(&from.component) || diff.contains_new(to)
+                    })
+                    .map(|c| (c, ConnectionAction::Interrupted)),
+            )
+            .map(|((from, to), action)| ConnectionChange {
+                from: from.to_string(),
+                to: to.to_string(),
+                action,
+            })
+            .collect::<Vec<_>>();
+        connections.sort_by(|a, b| (a.action, &a.from, &a.to).cmp(&(b.action, &b.from, &b.to)));
+
+        Self {
+            global_changed: old.global != new.global,
+            sources: ComponentChanges::from(&diff.sources)
+                .flag_secret_backed(&old.sources, &new.secret_backed),
+            transforms: ComponentChanges::from(&diff.transforms)
+                .flag_secret_backed(&old.transforms, &new.secret_backed),
+            sinks: ComponentChanges::from(&diff.sinks)
+                .flag_secret_backed(&old.sinks, &new.secret_backed),
+            enrichment_tables: ComponentChanges::from(&diff.enrichment_tables)
+                .flag_secret_backed(&old.enrichment_tables, &new.secret_backed),
+            buffers,
+            connections,
+        }
+    }
+
+    /// Checks whether the reload would change anything at all.
+    pub fn is_empty(&self) -> bool {
+        !self.global_changed
+            && self.sources.is_empty()
+            && self.transforms.is_empty()
+            && self.sinks.is_empty()
+            && self.enrichment_tables.is_empty()
+    }
+}
+
+impl fmt::Display for DiffReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes.");
+        }
+
+        if self.global_changed {
+            writeln!(
+                f,
+                "Global options changed: the reload would be rejected and Vector must be restarted."
+            )?;
+        }
+
+        for (title, changes) in [
+            ("Sources", &self.sources),
+            ("Transforms", &self.transforms),
+            ("Sinks", &self.sinks),
+            ("Enrichment tables", &self.enrichment_tables),
+        ] {
+            if changes.is_empty() {
+                continue;
+            }
+            writeln!(f, "{}:", title)?;
+            for key in &changes.added {
+                writeln!(f, "  + {} (added)", key)?;
+            }
+            for key in &changes.removed {
+                writeln!(f, "  - {} (removed)", key)?;
+            }
+            for key in &changes.rebuilt {
+                writeln!(f, "  ~ {} (rebuilt)", key)?;
+            }
+            for key in &changes.rewired {
+                writeln!(f, "  ~ {} (inputs rewired)", key)?;
+            }
+            for key in &changes.unknown {
+                writeln!(f, "  ? {} (unknown, references unresolved secrets)", key)?;
+            }
+        }
+
+        if !self.buffers.is_empty() {
+            writeln!(f, "Buffers:")?;
+            for change in &self.buffers {
+                let action = match change.action {
+                    BufferAction::Reused => "reused",
+                    BufferAction::Rebuilt => "rebuilt, buffer configuration changed",
+                    BufferAction::Dropped => "dropped, sink removed",
+                };
+                writeln!(f, "  {} ({})", change.sink, action)?;
+            }
+        }
+
+        if !self.connections.is_empty() {
+            writeln!(f, "Connections:")?;
+            for change in &self.connections {
+                let (symbol, action) = match change.action {
+                    ConnectionAction::Added => ("+", "added"),
+                    ConnectionAction::Removed => ("-", "removed"),
+                    ConnectionAction::Interrupted => ("~", "interrupted during reload"),
+                };
+                writeln!(
+                    f,
+                    "  {} {} -> {} ({})",
+                    symbol, change.from, change.to, action
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::config::{load_from_str, Format};
+
+    fn report(old: &str, new: &str) -> DiffReport {
+        let old = load_from_str(old, Format::Toml).unwrap();
+        let new = load_from_str(new, Format::Toml).unwrap();
+        DiffReport::new(
+            &ConfigSnapshot::new(&old),
+            &ConfigSnapshot::new(&new),
+            &HashSet::new(),
+        )
+    }
+
+    #[test]
+    fn reports_changed_components_and_connections() {
+        let report = report(
+            indoc! {r#"
+                [sources.in]
+                type = "test_basic"
+
+                [transforms.parse]
+                type = "test_basic"
+                inputs = ["in"]
+                suffix = "foo"
+                increase = 1.0
+
+                [sinks.out]
+                type = "test_basic"
+                inputs = ["parse"]
+            "#},
+            indoc! {r#"
+                [sources.in]
+                type = "test_basic"
+
+                [transforms.parse]
+                type = "test_basic"
+                inputs = ["in"]
+                suffix = "bar"
+                increase = 1.0
+
+                [sinks.out]
+                type = "test_basic"
+                inputs = ["parse"]
+
+                [sinks.archive]
+                type = "test_basic"
+                inputs = ["in"]
+            "#},
+        );
+
+        assert!(!report.global_changed);
+        assert!(report.sources.is_empty());
+        assert_eq!(report.transforms.rebuilt, vec!["parse"]);
+        assert_eq!(report.sinks.added, vec!["archive"]);
+        assert!(report.buffers.is_empty());
+        assert_eq!(
+            report
+                .connections
+                .iter()
+                .map(|c| (c.from.as_str(), c.to.as_str(), c.action))
+                .collect::<Vec<_>>(),
+            vec![
+                ("in", "archive", ConnectionAction::Added),
+                ("in", "parse", ConnectionAction::Interrupted),
+                ("parse", "out", ConnectionAction::Interrupted),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_rewired_sinks() {
+        let report = report(
+            indoc! {r#"
+                [sources.in1]
+                type = "test_basic"
+
+                [sources.in2]
+                type = "test_basic"
+
+                [sinks.out]
+                type = "test_basic"
+                inputs = ["in1"]
+            "#},
+            indoc! {r#"
+                [sources.in1]
+                type = "test_basic"
+
+                [sources.in2]
+                type = "test_basic"
+
+                [sinks.out]
+                type = "test_basic"
+                inputs = ["in1", "in2"]
+            "#},
+        );
+
+        assert!(report.sinks.rebuilt.is_empty());
+        assert_eq!(report.sinks.rewired, vec!["out"]);
+        assert!(report.buffers.is_empty());
+        assert_eq!(
+            report.connections,
+            vec![ConnectionChange {
+                from: "in2".into(),
+                to: "out".into(),
+                action: ConnectionAction::Added
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_buffer_reuse() {
+        let report = report(
+            indoc! {r#"
+                [sources.in]
+                type = "test_basic"
+
+                [sinks.reused]
+                type = "test_basic"
+                inputs = ["in"]
+
+                [sinks.rebuilt]
+                type = "test_basic"
+                inputs = ["in"]
+
+                [sinks.dropped]
+                type = "test_basic"
+                inputs = ["in"]
+            "#},
+            indoc! {r#"
+                [sources.in]
+                type = "test_basic"
+
+                [sinks.reused]
+                type = "test_basic"
+                inputs = ["in"]
+                data = "changed"
+
+                [sinks.rebuilt]
+                type = "test_basic"
+                inputs = ["in"]
+                buffer.max_events = 1000
+            "#},
+        );
+
+        assert_eq!(report.sinks.removed, vec!["dropped"]);
+        assert_eq!(report.sinks.rebuilt, vec!["rebuilt", "reused"]);
+        assert_eq!(
+            report.buffers,
+            vec![
+                BufferChange {
+                    sink: "dropped".into(),
+                    action: BufferAction::Dropped
+                },
+                BufferChange {
+                    sink: "rebuilt".into(),
+                    action: BufferAction::Rebuilt
+                },
+                BufferChange {
+                    sink: "reused".into(),
+                    action: BufferAction::Reused
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_secret_backed_components_as_unknown() {
+        let report = report(
+            indoc! {r#"
+                [sources.in]
+                type = "test_basic"
+
+                [sinks.out]
+                type = "test_basic"
+                inputs = ["in"]
+                data = "resolved"
+
+                [sinks.added]
+                type = "test_basic"
+                inputs = ["in"]
+            "#},
+            indoc! {r#"
+                [sources.in]
+                type = "test_basic"
+
+                [sinks.out]
+                type = "test_basic"
+                inputs = ["in"]
+                data = "SECRET[backend.data]"
+
+                [sinks.added]
+                type = "test_basic"
+                inputs = ["in"]
+
+                [sinks.new]
+                type = "test_basic"
+                inputs = ["in"]
+                data = "SECRET[backend.data]"
+            "#},
+        );
+
+        assert!(report.sinks.rebuilt.is_empty());
+        assert_eq!(report.sinks.unknown, vec!["out"]);
+        assert_eq!(report.sinks.added, vec!["new"]);
+    }
+
+    #[test]
+    fn reports_reloaded_components() {
+        let config = indoc! {r#"
+            [sources.in]
+            type = "test_basic"
+
+            [transforms.parse]
+            type = "test_basic"
+            inputs = ["in"]
+            suffix = "foo"
+            increase = 1.0
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["parse"]
+        "#};
+        let config = ConfigSnapshot::new(&load_from_str(config, Format::Toml).unwrap());
+        let report = DiffReport::new(
+            &config,
+            &config,
+            &[ComponentKey::from("parse"), ComponentKey::from("missing")]
+                .into_iter()
+                .collect(),
+        );
+
+        assert_eq!(report.transforms.rebuilt, vec!["parse"]);
+        assert!(report.sources.is_empty());
+        assert!(report.sinks.is_empty());
+        assert_eq!(
+            report
+                .connections
+                .iter()
+                .map(|c| (c.from.as_str(), c.to.as_str(), c.action))
+                .collect::<Vec<_>>(),
+            vec![
+                ("in", "parse", ConnectionAction::Interrupted),
+                ("parse", "out", ConnectionAction::Interrupted),
+            ]
+        );
+    }
+}
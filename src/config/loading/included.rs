@@ -0,0 +1,48 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use toml::value::Table;
+
+use super::{prepare_input, ComponentHint, Loader, Process};
+
+/// Collects the paths of the files included by config files, so they can be watched along with
+/// the config files themselves.
+pub struct IncludedPathsLoader {
+    paths: Vec<PathBuf>,
+}
+
+impl IncludedPathsLoader {
+    pub fn new() -> Self {
+        Self { paths: Vec::new() }
+    }
+}
+
+impl Process for IncludedPathsLoader {
+    /// Prepares input by interpolating environment variables, as included paths may contain them.
+    fn prepare<R: Read>(&mut self, input: R) -> Result<String, Vec<String>> {
+        prepare_input(input)
+    }
+
+    fn included(&mut self, path: &Path) {
+        // Files that don't exist can't be watched, and fail to load anyway.
+        if path.is_file() {
+            self.paths.push(path.to_path_buf());
+        }
+    }
+
+    /// The loaded config is discarded, only the included paths are kept.
+    fn merge(&mut self, _table: Table, _hint: Option<ComponentHint>) -> Result<(), Vec<String>> {
+        Ok(())
+    }
+}
+
+impl Loader<Vec<PathBuf>> for IncludedPathsLoader {
+    /// Returns the included paths, without duplicates.
+    fn take(mut self) -> Vec<PathBuf> {
+        self.paths.sort();
+        self.paths.dedup();
+        self.paths
+    }
+}
@@ -198,6 +198,98 @@ pub(super) mod process {
             Ok(result)
         }
 
+        /// Resolves the `include` directive of a config file's `table`, loaded from `path`. The
+        /// included files, relative to `path`, are loaded and overlaid on top of each other in
+        /// order, and `table` is then overlaid on top of the result. Included files may
+        /// themselves include other files.
+        fn resolve_includes(
+            &mut self,
+            path: &Path,
+            mut table: Table,
+            stack: &mut Vec<PathBuf>,
+        ) -> Result<Table, Vec<String>> {
+            let includes = match table.remove(INCLUDE_KEY) {
+                None => Vec::new(),
+                Some(Value::String(include)) => vec![include],
+                Some(Value::Array(includes)) => includes
+                    .into_iter()
+                    .map(|include| match include {
+                        Value::String(include) => Ok(include),
+                        _ => Err(vec![format!(
+                            "`{}` in {:?} must only contain paths.",
+                            INCLUDE_KEY, path
+                        )]),
+                    })
+                    .collect::<Result<_, _>>()?,
+                Some(_) => {
+                    return Err(vec![format!(
+                        "`{}` in {:?} must be a path or a list of paths.",
+                        INCLUDE_KEY, path
+                    )])
+                }
+            };
+
+            stack.push(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+
+            let mut errors = Vec::new();
+            let mut result = Table::new();
+            for include in includes {
+                let include_path = path
+                    .parent()
+                    .unwrap_or_else(|| Path::new("."))
+                    .join(include);
+                let canonical = include_path
+                    .canonicalize()
+                    .unwrap_or_else(|_| include_path.clone());
+                if stack.contains(&canonical) {
+                    errors.push(format!(
+                        "Config file {:?} is included recursively from {:?}.",
+                        include_path, path
+                    ));
+                    continue;
+                }
+
+                let format = match Format::from_path(&include_path) {
+                    Ok(format) => format,
+                    Err(_) => {
+                        errors.push(format!(
+                            "Could not detect the format of included config file {:?}.",
+                            include_path
+                        ));
+                        continue;
+                    }
+                };
+
+                self.included(&include_path);
+                match self.load_file(&include_path, format) {
+                    Ok(Some((_, included))) => {
+                        match self.resolve_includes(&include_path, included, stack) {
+                            Ok(included) => overlay(&mut result, included),
+                            Err(errs) => errors.extend(errs),
+                        }
+                    }
+                    Ok(None) => errors.push(format!(
+                        "Could not load included config file {:?}.",
+                        include_path
+                    )),
+                    Err(errs) => errors.extend(errs),
+                }
+            }
+
+            stack.pop();
+
+            if errors.is_empty() {
+                overlay(&mut result, table);
+                Ok(result)
+            } else {
+                Err(errors)
+            }
+        }
+
+        /// Called with the path of each file included by another config file, before it's
+        /// loaded.
+        fn included(&mut self, _path: &Path) {}
+
         /// Merge a provided TOML `Table` in an implementation-specific way. Contains an
         /// optional component hint, which may affect how components are merged. Takes a `&mut self`
         /// with the intention of merging an inner value that can be `take`n by a `Loader`.
@@ -218,6 +310,7 @@ where
     /// Returns a vector of non-fatal warnings on success, or a vector of error strings on failure.
     fn load_from_file(&mut self, path: &Path, format: Format) -> Result<(), Vec<String>> {
         if let Some((_, table)) = self.load_file(path, format)? {
+            let table = self.resolve_includes(path, table, &mut Vec::new())?;
             self.merge(table, None)?;
             Ok(())
         } else {
@@ -242,17 +335,44 @@ where
             .map(|hint| (hint.join_path(path), hint));
 
         // Get files from the root of the folder. These represent top-level config settings,
-        // and need to merged down first to represent a more 'complete' config.
+        // and need to merged down first to represent a more 'complete' config. Like files loaded
+        // with `load_from_file`, they may include other files. Component files in the
+        // sub-folders can't, since `include` is a regular option of some components.
         let mut root = Table::new();
-        let table = self.load_dir(path, false)?;
-
-        // Discard the named part of the path, since these don't form any component names.
-        for (_, value) in table {
-            // All files should contain key/value pairs.
-            if let Value::Table(table) = value {
-                merge_into_table(&mut root, table).map_err(|e| vec![e.to_string()])?;
+        let mut errors = Vec::new();
+        for entry in read_dir(path)? {
+            let entry = match entry {
+                Ok(entry) => entry.path(),
+                Err(err) => {
+                    errors.push(format!(
+                        "Could not read entry in config dir: {:?}, {}.",
+                        path, err
+                    ));
+                    continue;
+                }
+            };
+            // If the file doesn't contain a known extension, skip it.
+            let format = match Format::from_path(&entry) {
+                Ok(format) if entry.is_file() => format,
+                _ => continue,
+            };
+
+            // Discard the named part of the path, since these don't form any component names.
+            let table = match self.load_file(&entry, format) {
+                Ok(Some((_, table))) => self.resolve_includes(&entry, table, &mut Vec::new()),
+                Ok(None) => continue,
+                Err(errs) => Err(errs),
+            };
+            match table.and_then(|table| {
+                merge_into_table(&mut root, table).map_err(|e| vec![e.to_string()])
+            }) {
+                Ok(()) => {}
+                Err(errs) => errors.extend(errs),
             }
         }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
 
         // Merge the 'root' config value first.
         self.merge(root, None)?;
@@ -274,6 +394,45 @@ where
     }
 }
 
+/// Top-level key of a config file listing other config files to include.
+const INCLUDE_KEY: &str = "include";
+
+/// Key marking a table in an overlay as deleted, e.g. `[sinks.debug]` with `"$delete" = true`.
+const DELETE_KEY: &str = "$delete";
+
+/// Overlays `patch` onto `base`. Tables are merged by key, so components can be patched by
+/// referring to them by ID, while any other value in `patch` replaces the one in `base`. Tables
+/// marked with `DELETE_KEY` remove the corresponding key from `base` instead.
+fn overlay(base: &mut Table, patch: Table) {
+    for (key, value) in patch {
+        match value {
+            Value::Table(table) if is_deletion_marker(&table) => {
+                base.remove(&key);
+            }
+            Value::Table(table) => match base.get_mut(&key) {
+                Some(Value::Table(existing)) => overlay(existing, table),
+                _ => {
+                    // Overlaying onto an empty table strips any deletion markers that are nested
+                    // in a table that doesn't exist in `base`.
+                    let mut inserted = Table::new();
+                    overlay(&mut inserted, table);
+                    base.insert(key, Value::Table(inserted));
+                }
+            },
+            value => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn is_deletion_marker(table: &Table) -> bool {
+    table
+        .get(DELETE_KEY)
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
 /// Merge two TOML `Value`s, returning a new `Value`.
 fn merge_values(value: toml::Value, other: toml::Value) -> Result<toml::Value, Vec<String>> {
     serde_toml_merge::merge(value, other).map_err(|e| vec![e.to_string()])
@@ -1,4 +1,5 @@
 mod config_builder;
+mod included;
 mod loader;
 mod secret;
 mod source;
@@ -13,6 +14,7 @@ use std::{
 
 use config_builder::ConfigBuilderLoader;
 use glob::glob;
+use included::IncludedPathsLoader;
 use loader::process::Process;
 pub use loader::*;
 pub use secret::*;
@@ -238,6 +240,28 @@ pub fn load_secret_backends_from_paths(
     loader_from_paths(SecretBackendLoader::new(), config_paths)
 }
 
+/// Uses `IncludedPathsLoader` to collect the files included by the config files in `ConfigPaths`.
+///
+/// Config files that fail to load are skipped, as the errors are reported when loading the config
+/// itself, but the files they include before the failure are still returned.
+pub fn load_included_paths(config_paths: &[ConfigPath]) -> Vec<PathBuf> {
+    let mut loader = IncludedPathsLoader::new();
+
+    for config_path in config_paths {
+        _ = match config_path {
+            ConfigPath::File(path, format_hint) => loader.load_from_file(
+                path,
+                format_hint
+                    .or_else(move || Format::from_path(&path).ok())
+                    .unwrap_or_default(),
+            ),
+            ConfigPath::Dir(path) => loader.load_from_dir(path),
+        };
+    }
+
+    loader.take()
+}
+
 pub fn load_from_str(input: &str, format: Format) -> Result<Config, Vec<String>> {
     let builder = load_from_inputs(std::iter::once((input.as_bytes(), format)))?;
     let (config, build_warnings) = builder.build_with_warnings()?;
@@ -325,7 +349,7 @@ fn default_config_paths() -> Vec<ConfigPath> {
 mod tests {
     use std::path::PathBuf;
 
-    use super::load_builder_from_paths;
+    use super::{load_builder_from_paths, load_included_paths, load_source_from_paths};
     use crate::config::{ComponentKey, ConfigPath};
 
     #[test]
@@ -387,4 +411,76 @@ mod tests {
         let configs = vec![ConfigPath::Dir(path)];
         load_builder_from_paths(&configs).unwrap();
     }
+
+    #[test]
+    fn load_file_with_include_overlay() {
+        let path = PathBuf::from(".")
+            .join("tests")
+            .join("config-include")
+            .join("overlay.yaml");
+        let configs = vec![ConfigPath::File(path, None)];
+
+        let builder = load_builder_from_paths(&configs).unwrap();
+        assert!(builder.sources.contains_key(&ComponentKey::from("source0")));
+        assert!(builder.sinks.contains_key(&ComponentKey::from("sink0")));
+        assert!(!builder.sinks.contains_key(&ComponentKey::from("debug")));
+
+        let source = load_source_from_paths(&configs).unwrap();
+        let sink = source["sinks"]["sink0"].as_table().unwrap();
+        assert_eq!(sink["target"].as_str(), Some("stderr"));
+        assert_eq!(sink["inputs"][0].as_str(), Some("transform0"));
+        assert_eq!(
+            source["transforms"]["transform0"]["rate"].as_integer(),
+            Some(100)
+        );
+        assert!(!source.contains_key("include"));
+    }
+
+    #[test]
+    fn load_file_with_recursive_include() {
+        let path = PathBuf::from(".")
+            .join("tests")
+            .join("config-include")
+            .join("cycle_a.toml");
+        let configs = vec![ConfigPath::File(path, None)];
+        let errors = load_builder_from_paths(&configs).unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|error| error.contains("is included recursively")));
+    }
+
+    #[test]
+    fn load_directory_with_include() {
+        let path = PathBuf::from(".")
+            .join("tests")
+            .join("config-include")
+            .join("dir");
+        let configs = vec![ConfigPath::Dir(path)];
+
+        let builder = load_builder_from_paths(&configs).unwrap();
+        assert!(builder.sources.contains_key(&ComponentKey::from("source0")));
+        assert!(builder.sinks.contains_key(&ComponentKey::from("sink0")));
+        assert!(!builder.sinks.contains_key(&ComponentKey::from("debug")));
+    }
+
+    #[test]
+    fn load_included_paths_of_files_and_directories() {
+        let base = PathBuf::from(".").join("tests").join("config-include");
+
+        let configs = vec![ConfigPath::File(base.join("overlay.yaml"), None)];
+        assert_eq!(load_included_paths(&configs), vec![base.join("base.toml")]);
+
+        let configs = vec![ConfigPath::Dir(base.join("dir"))];
+        assert_eq!(
+            load_included_paths(&configs),
+            vec![base.join("dir").join("../base.toml")]
+        );
+
+        // The files included before a recursive include are still returned.
+        let configs = vec![ConfigPath::File(base.join("cycle_a.toml"), None)];
+        assert_eq!(
+            load_included_paths(&configs),
+            vec![base.join("cycle_b.toml")]
+        );
+    }
 }
@@ -31,6 +31,7 @@ pub mod format;
 mod graph;
 mod id;
 mod loading;
+mod offsets;
 pub mod provider;
 pub mod schema;
 mod secret;
@@ -44,15 +45,19 @@ pub mod watcher;
 
 pub use builder::ConfigBuilder;
 pub use cmd::{cmd, Opts};
-pub use diff::ConfigDiff;
+pub use diff::{
+    BufferAction, BufferChange, ComponentChanges, ConfigDiff, ConfigSnapshot, ConnectionAction,
+    ConnectionChange, DiffReport,
+};
 pub use enrichment_table::{EnrichmentTableConfig, EnrichmentTableOuter};
 pub use format::{Format, FormatHint};
 pub use id::{ComponentKey, Inputs};
 pub use loading::{
     load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider_and_secrets,
-    load_from_str, load_source_from_paths, merge_path_lists, process_paths, COLLECTOR,
-    CONFIG_PATHS,
+    load_from_str, load_included_paths, load_source_from_paths, merge_path_lists, process_paths,
+    COLLECTOR, CONFIG_PATHS,
 };
+pub use offsets::OffsetsLink;
 pub use provider::ProviderConfig;
 pub use secret::SecretBackend;
 pub use sink::{BoxedSink, SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter};
@@ -150,6 +155,39 @@ impl Config {
         self.sinks.get(id)
     }
 
+    /// Returns the key under which the given component is connected to the fanouts of its inputs.
+    ///
+    /// Sinks reading from the same shared disk buffer are all sent the same events, so only the
+    /// first of them is connected, under a key for the buffer itself, and writes those events to
+    /// the buffer for all of them. The others aren't connected at all, so `None` is returned.
+    pub fn fanout_key(&self, id: &ComponentKey) -> Option<ComponentKey> {
+        let Some(group) = self.sink(id).and_then(|sink| sink.buffer.shared_group()) else {
+            return Some(id.clone());
+        };
+        let first = self
+            .sinks()
+            .filter(|(_, sink)| sink.buffer.shared_group() == Some(group))
+            .map(|(key, _)| key)
+            .min_by_key(|key| key.to_string())?;
+
+        // Component IDs can't contain periods, so this never clashes with an actual component.
+        (first == id).then(|| ComponentKey::from(format!("shared_disk_buffer.{}", group)))
+    }
+
+    /// Returns the inputs connected under the given fanout key, as returned by [`Self::fanout_key`].
+    pub fn inputs_for_fanout_key(&self, fanout_key: &ComponentKey) -> Option<&[OutputId]> {
+        match self.inputs_for_node(fanout_key) {
+            Some(inputs) if self.fanout_key(fanout_key).as_ref() == Some(fanout_key) => {
+                Some(inputs)
+            }
+            _ => self
+                .sinks()
+                .filter(|(_, sink)| sink.buffer.shared_group().is_some())
+                .find(|(key, _)| self.fanout_key(key).as_ref() == Some(fanout_key))
+                .map(|(_, sink)| &sink.inputs[..]),
+        }
+    }
+
     pub fn inputs_for_node(&self, id: &ComponentKey) -> Option<&[OutputId]> {
         self.transforms
             .get(id)
@@ -249,6 +287,10 @@ pub enum Resource {
     SystemFdOffset(usize),
     Fd(u32),
     DiskBuffer(String),
+    /// A disk buffer shared by the sinks in the given group.
+    ///
+    /// Unlike other resources, this may be claimed by multiple sinks of the same configuration.
+    SharedDiskBuffer(String),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Copy)]
@@ -327,6 +369,7 @@ impl Display for Resource {
             Resource::SystemFdOffset(offset) => write!(fmt, "systemd {}th socket", offset + 1),
             Resource::Fd(fd) => write!(fmt, "file descriptor: {}", fd),
             Resource::DiskBuffer(name) => write!(fmt, "disk buffer {:?}", name),
+            Resource::SharedDiskBuffer(group) => write!(fmt, "shared disk buffer {:?}", group),
         }
     }
 }
@@ -611,6 +654,184 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn bad_buffer_stages() {
+        let err = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+
+            [[sinks.out.buffer]]
+            type = "memory"
+            max_events = 10
+
+            [[sinks.out.buffer]]
+            type = "memory"
+            max_events = 10
+            when_full = "overflow"
+
+            [sinks.out2]
+            type = "test_basic"
+            inputs = ["in"]
+
+            [[sinks.out2.buffer]]
+            type = "disk"
+            max_size = 268435488
+            when_full = "overflow"
+
+            [[sinks.out2.buffer]]
+            type = "disk"
+            max_size = 268435488
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            vec![
+                "Sink \"out\": buffer stage 0 must use `when_full: overflow`.",
+                "Sink \"out\": the last buffer stage cannot use `when_full: overflow`.",
+                "Sink \"out2\": buffers can only have a single disk stage.",
+            ],
+            err,
+        );
+    }
+
+    #[tokio::test]
+    async fn bad_shared_buffers() {
+        let err = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sources.in2]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+
+            [sinks.out.buffer]
+            type = "disk"
+            max_size = 268435488
+            shared_group = "fanout"
+
+            [sinks.out2]
+            type = "test_basic"
+            inputs = ["in2"]
+
+            [sinks.out2.buffer]
+            type = "disk"
+            max_size = 536870912
+            shared_group = "fanout"
+
+            [sinks.out3]
+            type = "test_basic"
+            inputs = ["in"]
+
+            [[sinks.out3.buffer]]
+            type = "memory"
+            max_events = 10
+            when_full = "overflow"
+
+            [[sinks.out3.buffer]]
+            type = "disk"
+            max_size = 268435488
+            shared_group = "other"
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            vec![
+                "Sink \"out3\": a shared disk buffer must be the only stage of the buffer.",
+                "Sinks sharing disk buffer \"fanout\" must have the same buffer configuration.",
+                "Sinks sharing disk buffer \"fanout\" must have the same inputs.",
+            ],
+            err,
+        );
+    }
+
+    #[cfg(all(feature = "sources-kafka", feature = "sinks-kafka"))]
+    #[tokio::test]
+    async fn bad_offset_links() {
+        let err = load(
+            r#"
+            [sources.in]
+            type = "kafka"
+            bootstrap_servers = "localhost:9092"
+            group_id = "group"
+            topics = ["topic"]
+            exactly_once = true
+
+            [sources.in2]
+            type = "kafka"
+            bootstrap_servers = "localhost:9092"
+            group_id = "group2"
+            topics = ["topic"]
+            exactly_once = true
+
+            [sinks.out]
+            type = "kafka"
+            inputs = ["in", "in2"]
+            bootstrap_servers = "localhost:9092"
+            topic = "topic"
+            encoding.codec = "json"
+            transaction.transactional_id = "vector-1"
+
+            [sinks.out2]
+            type = "kafka"
+            inputs = ["in2"]
+            bootstrap_servers = "localhost:9092"
+            topic = "topic"
+            encoding.codec = "json"
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            vec![
+                "Source \"in2\": the offsets of its events are committed by the sink they are sent to, so it must be the input of a single sink committing them, such as a `kafka` sink with a `transaction`, and of no other component."
+            ],
+            err,
+        );
+    }
+
+    #[tokio::test]
+    async fn bad_source_buffers() {
+        let err = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sources.in.buffer]
+            type = "disk"
+            max_size = 268435488
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            vec!["Source \"in\": this source does not support buffering."],
+            err,
+        );
+    }
+
     #[tokio::test]
     async fn duplicate_name() {
         let err = load(
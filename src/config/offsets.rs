@@ -0,0 +1,77 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use super::{ComponentKey, Config};
+
+/// A link between a source handing off committing the offsets of the events it emits, and the sink
+/// committing them, such as a `kafka` source with `exactly_once` enabled and a transactional `kafka`
+/// sink.
+///
+/// Through the link, the source provides the sink with whatever it needs to commit the offsets,
+/// such as the consumer group metadata of a Kafka consumer. Linked components are always built
+/// together, so a new link is created whenever either of them is rebuilt.
+#[derive(Clone, Default)]
+pub struct OffsetsLink(Arc<Mutex<Option<Arc<dyn Any + Send + Sync>>>>);
+
+impl OffsetsLink {
+    /// Provides the sink with the given value, replacing any value provided before.
+    pub fn provide<T: Any + Send + Sync>(&self, value: T) {
+        *self.0.lock().expect("offsets link lock poisoned") = Some(Arc::new(value));
+    }
+
+    /// Gets the value provided by the source, if it provided one of the given type yet.
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let value = self.0.lock().expect("offsets link lock poisoned").clone()?;
+        value.downcast().ok()
+    }
+}
+
+impl fmt::Debug for OffsetsLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OffsetsLink").finish_non_exhaustive()
+    }
+}
+
+impl Config {
+    /// Gets the sink committing the offsets of the events of each source handing off committing
+    /// them, by source.
+    ///
+    /// Validation ensures that each of these sources sends its events to a single sink committing
+    /// their offsets.
+    pub fn offset_links(&self) -> HashMap<ComponentKey, ComponentKey> {
+        self.sources()
+            .filter(|(_, source)| source.inner.hands_off_offsets())
+            .filter_map(|(source_key, _)| {
+                self.sinks()
+                    .find(|(_, sink)| {
+                        sink.inputs
+                            .iter()
+                            .any(|input| &input.component == source_key)
+                    })
+                    .map(|(sink_key, _)| (source_key.clone(), sink_key.clone()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_link_provides_value() {
+        let link = OffsetsLink::default();
+        assert!(link.get::<u32>().is_none());
+
+        link.clone().provide(1u32);
+        assert_eq!(link.get::<u32>().as_deref(), Some(&1));
+        assert!(link.get::<String>().is_none());
+
+        link.provide(2u32);
+        assert_eq!(link.get::<u32>().as_deref(), Some(&2));
+    }
+}
@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, collections::HashMap};
 
 use async_trait::async_trait;
 use dyn_clone::DynClone;
@@ -14,7 +14,7 @@ use vector_lib::{
     sink::VectorSink,
 };
 
-use super::{id::Inputs, schema, ComponentKey, ProxyConfig, Resource};
+use super::{id::Inputs, schema, ComponentKey, OffsetsLink, ProxyConfig, Resource};
 use crate::extra_context::ExtraContext;
 use crate::sinks::{util::UriSerde, Healthcheck};
 
@@ -103,7 +103,10 @@ where
         for stage in self.buffer.stages() {
             match stage {
                 BufferType::Memory { .. } => {}
-                BufferType::DiskV2 { .. } => resources.push(Resource::DiskBuffer(id.to_string())),
+                BufferType::DiskV2 { .. } => match stage.shared_group() {
+                    Some(group) => resources.push(Resource::SharedDiskBuffer(group.to_string())),
+                    None => resources.push(Resource::DiskBuffer(id.to_string())),
+                },
             }
         }
         resources
@@ -226,6 +229,12 @@ pub trait SinkConfig: DynClone + NamedComponent + core::fmt::Debug + Send + Sync
 
     /// Gets the acknowledgements configuration for this sink.
     fn acknowledgements(&self) -> &AcknowledgementsConfig;
+
+    /// Whether or not this sink commits the offsets of the events it writes, for the sources
+    /// handing off committing them.
+    fn commits_source_offsets(&self) -> bool {
+        false
+    }
 }
 
 dyn_clone::clone_trait_object!(SinkConfig);
@@ -242,6 +251,10 @@ pub struct SinkContext {
     /// Extra context data provided by the running app and shared across all components. This can be
     /// used to pass shared settings or other data from outside the components.
     pub extra_context: ExtraContext,
+
+    /// The links to the sources handing off committing the offsets of their events to this sink,
+    /// by source.
+    pub offsets_links: HashMap<ComponentKey, OffsetsLink>,
 }
 
 impl Default for SinkContext {
@@ -254,6 +267,7 @@ impl Default for SinkContext {
             app_name: crate::get_app_name().to_string(),
             app_name_slug: crate::get_slugified_app_name(),
             extra_context: Default::default(),
+            offsets_links: HashMap::new(),
         }
     }
 }
@@ -9,6 +9,7 @@ use vector_lib::configurable::{
     configurable_component, Configurable, GenerateError, Metadata, NamedComponent,
 };
 use vector_lib::{
+    buffers::BufferConfig,
     config::{
         AcknowledgementsConfig, GlobalOptions, LogNamespace, SourceAcknowledgementsConfig,
         SourceOutput,
@@ -16,7 +17,7 @@ use vector_lib::{
     source::Source,
 };
 
-use super::{schema, ComponentKey, ProxyConfig, Resource};
+use super::{schema, ComponentKey, OffsetsLink, ProxyConfig, Resource};
 use crate::{extra_context::ExtraContext, shutdown::ShutdownSignal, SourceSender};
 
 pub type BoxedSource = Box<dyn SourceConfig>;
@@ -54,6 +55,14 @@ pub struct SourceOuter {
     #[serde(default, skip_serializing_if = "vector_lib::serde::is_default")]
     pub proxy: ProxyConfig,
 
+    /// Configures a disk buffer for the events emitted by the source.
+    ///
+    /// Events are written to the buffer as soon as the source has decoded them, and acknowledged
+    /// to the client once they're persisted in it, rather than once they've been processed by the
+    /// rest of the topology. Only supported by some push-based sources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buffer: Option<BufferConfig>,
+
     #[serde(default, skip)]
     pub sink_acknowledgements: bool,
 
@@ -63,9 +72,18 @@ pub struct SourceOuter {
 }
 
 impl SourceOuter {
+    pub fn resources(&self, id: &ComponentKey) -> Vec<Resource> {
+        let mut resources = self.inner.resources();
+        if self.buffer.is_some() {
+            resources.push(Resource::DiskBuffer(id.to_string()));
+        }
+        resources
+    }
+
     pub(crate) fn new<I: Into<BoxedSource>>(inner: I) -> Self {
         Self {
             proxy: Default::default(),
+            buffer: None,
             sink_acknowledgements: false,
             inner: inner.into(),
         }
@@ -113,6 +131,23 @@ pub trait SourceConfig: DynClone + NamedComponent + core::fmt::Debug + Send + Sy
     /// well as emit contextual warnings when end-to-end acknowledgements are enabled, but the
     /// topology as configured does not actually support the use of end-to-end acknowledgements.
     fn can_acknowledge(&self) -> bool;
+
+    /// Whether or not this source supports buffering the events it emits on disk.
+    ///
+    /// This is meant for push-based sources, which can acknowledge their clients once the events
+    /// they've received are persisted in the buffer, rather than once they've been fully processed.
+    fn can_buffer(&self) -> bool {
+        false
+    }
+
+    /// Whether or not the offsets of the events this source emits are committed by the sink they're
+    /// sent to, rather than by the source itself.
+    ///
+    /// Such a source must only send its events to a single sink committing their offsets, which is
+    /// linked to the source when they're built, through [`SourceContext::offsets_link`].
+    fn hands_off_offsets(&self) -> bool {
+        false
+    }
 }
 
 dyn_clone::clone_trait_object!(SourceConfig);
@@ -135,6 +170,10 @@ pub struct SourceContext {
     /// Extra context data provided by the running app and shared across all components. This can be
     /// used to pass shared settings or other data from outside the components.
     pub extra_context: ExtraContext,
+
+    /// The link to the sink committing the offsets of the events this source emits, if the source
+    /// hands off committing them.
+    pub offsets_link: Option<OffsetsLink>,
 }
 
 impl SourceContext {
@@ -156,6 +195,7 @@ impl SourceContext {
                 schema_definitions: HashMap::default(),
                 schema: Default::default(),
                 extra_context: Default::default(),
+                offsets_link: None,
             },
             shutdown,
         )
@@ -176,6 +216,7 @@ impl SourceContext {
             schema_definitions: schema_definitions.unwrap_or_default(),
             schema: Default::default(),
             extra_context: Default::default(),
+            offsets_link: None,
         }
     }
 
@@ -2,8 +2,14 @@ use crate::config::schema;
 use futures_util::{stream, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use heim::{disk::Partition, units::information::byte};
 use indexmap::IndexMap;
-use std::{collections::HashMap, path::PathBuf};
-use vector_lib::{buffers::config::DiskUsage, internal_event::DEFAULT_OUTPUT};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::PathBuf,
+};
+use vector_lib::{
+    buffers::{config::DiskUsage, BufferType, WhenFull},
+    internal_event::DEFAULT_OUTPUT,
+};
 
 use super::{
     builder::ConfigBuilder, transform::get_transform_output_ids, ComponentKey, Config, OutputId,
@@ -122,17 +128,119 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+/// Checks that the stages of chained sink buffers can form a valid buffer topology: every stage
+/// but the last must overflow to the next one, and only a single stage can be stored on disk.
+///
+/// Sinks sharing a disk buffer must also agree on how the buffer is configured, and what is written
+/// to it. Sources can only be buffered on disk, and only if they support it.
+pub fn check_buffers(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = vec![];
+    let mut shared_groups = BTreeMap::<&str, Vec<_>>::new();
+
+    for (key, source) in &config.sources {
+        let Some(buffer) = &source.buffer else {
+            continue;
+        };
+        if !source.inner.can_buffer() {
+            errors.push(format!(
+                "Source \"{}\": this source does not support buffering.",
+                key
+            ));
+        } else if !matches!(
+            buffer.stages(),
+            [BufferType::DiskV2 {
+                shared_group: None,
+                ..
+            }]
+        ) {
+            errors.push(format!(
+                "Source \"{}\": source buffers must be a single, unshared, disk buffer.",
+                key
+            ));
+        }
+    }
+
+    for (key, sink) in &config.sinks {
+        let stages = sink.buffer.stages();
+        for (idx, stage) in stages.iter().enumerate() {
+            let is_last = idx + 1 == stages.len();
+            match (stage.when_full(), is_last) {
+                (WhenFull::Overflow, true) => errors.push(format!(
+                    "Sink \"{}\": the last buffer stage cannot use `when_full: overflow`.",
+                    key
+                )),
+                (WhenFull::Block | WhenFull::DropNewest | WhenFull::DropOldest, false) => errors
+                    .push(format!(
+                        "Sink \"{}\": buffer stage {} must use `when_full: overflow`.",
+                        key, idx
+                    )),
+                _ => {}
+            }
+        }
+
+        let disk_stages = stages
+            .iter()
+            .filter(|stage| matches!(stage, BufferType::DiskV2 { .. }))
+            .count();
+        if disk_stages > 1 {
+            errors.push(format!(
+                "Sink \"{}\": buffers can only have a single disk stage.",
+                key
+            ));
+        }
+
+        if let Some(group) = sink.buffer.shared_group() {
+            if stages.len() > 1 {
+                errors.push(format!(
+                    "Sink \"{}\": a shared disk buffer must be the only stage of the buffer.",
+                    key
+                ));
+            }
+            shared_groups.entry(group).or_default().push(sink);
+        }
+    }
+
+    for (group, sinks) in shared_groups {
+        let (first, rest) = sinks.split_first().expect("groups are never empty");
+        if rest.iter().any(|sink| sink.buffer != first.buffer) {
+            errors.push(format!(
+                "Sinks sharing disk buffer \"{}\" must have the same buffer configuration.",
+                group
+            ));
+        }
+
+        let inputs = first.inputs.iter().collect::<HashSet<_>>();
+        if rest
+            .iter()
+            .any(|sink| sink.inputs.iter().collect::<HashSet<_>>() != inputs)
+        {
+            errors.push(format!(
+                "Sinks sharing disk buffer \"{}\" must have the same inputs.",
+                group
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     let source_resources = config
         .sources
         .iter()
-        .map(|(id, config)| (id, config.inner.resources()));
+        .map(|(id, config)| (id, config.resources(id)));
     let sink_resources = config
         .sinks
         .iter()
         .map(|(id, config)| (id, config.resources(id)));
 
-    let conflicting_components = Resource::conflicts(source_resources.chain(sink_resources));
+    // Sinks sharing a disk buffer are expected to claim it together.
+    let mut conflicting_components = Resource::conflicts(source_resources.chain(sink_resources));
+    conflicting_components.retain(|resource, _| !matches!(resource, Resource::SharedDiskBuffer(_)));
 
     if conflicting_components.is_empty() {
         Ok(())
@@ -194,10 +302,46 @@ pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+/// Checks that each source handing off committing the offsets of the events it emits only sends
+/// them to a single sink committing their offsets, as the offsets of events sent anywhere else
+/// would never be committed.
+pub fn check_offset_links(config: &Config) -> Result<(), Vec<String>> {
+    let errors = config
+        .sources()
+        .filter(|(_, source)| source.inner.hands_off_offsets())
+        .filter_map(|(key, _)| {
+            let reads_source =
+                |inputs: &[OutputId]| inputs.iter().any(|input| &input.component == key);
+            let transforms = config
+                .transforms()
+                .filter(|(_, transform)| reads_source(&transform.inputs))
+                .count();
+            let sinks = config
+                .sinks()
+                .filter(|(_, sink)| reads_source(&sink.inputs))
+                .collect::<Vec<_>>();
+
+            match sinks.as_slice() {
+                [(_, sink)] if transforms == 0 && sink.inner.commits_source_offsets() => None,
+                _ => Some(format!(
+                    "Source \"{}\": the offsets of its events are committed by the sink they are sent to, so it must be the input of a single sink committing them, such as a `kafka` sink with a `transaction`, and of no other component.",
+                    key
+                )),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 pub async fn check_buffer_preconditions(config: &Config) -> Result<(), Vec<String>> {
     // We need to assert that Vector's data directory is located on a mountpoint that has enough
-    // capacity to allow all sinks with disk buffers configured to be able to use up to their
-    // maximum configured size without overrunning the total capacity.
+    // capacity to allow all sources and sinks with disk buffers configured to be able to use up to
+    // their maximum configured size without overrunning the total capacity.
     //
     // More subtly, we need to make sure we properly map a given buffer's data directory to the
     // appropriate mountpoint, as it is technically possible that individual buffers could be on
@@ -207,15 +351,34 @@ pub async fn check_buffer_preconditions(config: &Config) -> Result<(), Vec<Strin
     // don't always know the upper bound of that usage i.e. file checkpoint state.
 
     // Grab all configured disk buffers, and if none are present, simply return early.
+    //
+    // Sources have a separate buffer for each of their outputs, while sinks sharing a disk buffer
+    // all report the same buffer, which only counts once.
     let global_data_dir = config.global.data_dir.clone();
-    let configured_disk_buffers = config
-        .sinks()
-        .flat_map(|(id, sink)| {
-            sink.buffer
+    let mut seen_data_dirs = HashSet::new();
+    let source_buffers = config
+        .sources()
+        .filter_map(|(id, source)| Some((id, source, source.buffer.as_ref()?)))
+        .flat_map(|(id, source, buffer)| {
+            let outputs = source.inner.outputs(config.schema.log_namespace());
+            outputs.into_iter().map(move |output| {
+                let output_id = OutputId {
+                    component: id.clone(),
+                    port: output.port,
+                };
+                (ComponentKey::from(output_id.to_string()), buffer)
+            })
+        });
+    let configured_disk_buffers = source_buffers
+        .chain(config.sinks().map(|(id, sink)| (id.clone(), &sink.buffer)))
+        .flat_map(|(id, buffer)| {
+            let data_dir = global_data_dir.clone();
+            buffer
                 .stages()
                 .iter()
-                .filter_map(|stage| stage.disk_usage(global_data_dir.clone(), id))
+                .filter_map(move |stage| stage.disk_usage(data_dir.clone(), &id))
         })
+        .filter(|usage| seen_data_dirs.insert(usage.data_dir().to_path_buf()))
         .collect::<Vec<_>>();
 
     if configured_disk_buffers.is_empty() {
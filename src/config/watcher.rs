@@ -8,6 +8,8 @@ use std::{
 #[cfg(unix)]
 use notify::{recommended_watcher, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 
+#[cfg(unix)]
+use crate::config::{self, ConfigPath};
 use crate::Error;
 
 /// Per notify own documentation, it's advised to have delay of more than 30 sec,
@@ -124,14 +126,32 @@ fn create_watcher(
     Ok((watcher, receiver))
 }
 
+/// Watches the config paths, along with the files they include. The included files are resolved
+/// again whenever the paths are re-added, so files newly included by a changed config are
+/// watched as well.
 #[cfg(unix)]
 fn add_paths(watcher: &mut RecommendedWatcher, config_paths: &[PathBuf]) -> Result<(), Error> {
-    for path in config_paths {
+    for path in config_paths.iter().chain(&included_paths(config_paths)) {
         watcher.watch(path, RecursiveMode::NonRecursive)?;
     }
     Ok(())
 }
 
+#[cfg(unix)]
+fn included_paths(config_paths: &[PathBuf]) -> Vec<PathBuf> {
+    let config_paths = config_paths
+        .iter()
+        .map(|path| {
+            if path.is_dir() {
+                ConfigPath::Dir(path.clone())
+            } else {
+                ConfigPath::File(path.clone(), None)
+            }
+        })
+        .collect::<Vec<_>>();
+    config::load_included_paths(&config_paths)
+}
+
 #[cfg(all(test, unix, not(target_os = "macos")))] // https://github.com/vectordotdev/vector/issues/5000
 mod tests {
     use std::{fs::File, io::Write, time::Duration};
@@ -199,4 +219,24 @@ mod tests {
             panic!("Test timed out");
         }
     }
+
+    #[tokio::test]
+    async fn included_file_update() {
+        trace_init();
+
+        let delay = Duration::from_secs(3);
+        let dir = temp_dir().to_path_buf();
+        let file_path = dir.join("vector.toml");
+        let included_path = dir.join("included.toml");
+
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(&file_path, "include = [\"included.toml\"]\n").unwrap();
+        let mut file = File::create(&included_path).unwrap();
+
+        spawn_thread(&[file_path], delay).unwrap();
+
+        if !test(&mut file, delay * 5).await {
+            panic!("Test timed out");
+        }
+    }
 }
@@ -1,9 +1,25 @@
+use std::collections::HashMap;
 use std::fmt::Write as _;
 use std::path::PathBuf;
 
 use clap::Parser;
+use serde::Serialize;
+#[cfg(feature = "api-client")]
+use url::Url;
 
-use crate::config;
+use crate::config::{self, ComponentKey, OutputId};
+
+/// Output format of the rendered topology.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// DOT language, which can be rendered by GraphViz
+    #[default]
+    Dot,
+    /// Mermaid flowchart, which can be embedded in Markdown documents
+    Mermaid,
+    /// JSON object of nodes and edges
+    Json,
+}
 
 #[derive(Parser, Debug)]
 #[command(rename_all = "kebab-case")]
@@ -45,6 +61,22 @@ pub struct Opts {
         value_delimiter(',')
     )]
     pub config_dirs: Vec<PathBuf>,
+
+    /// Format to render the topology in.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Dot)]
+    format: OutputFormat,
+
+    /// GraphQL API server endpoint of a running Vector instance. When provided, edges are
+    /// annotated with their current throughput in events per second, and components that
+    /// reported errors while sampling are highlighted.
+    #[cfg(feature = "api-client")]
+    #[arg(short, long)]
+    url: Option<Url>,
+
+    /// Interval to sample live metrics over, in milliseconds. Only used together with `--url`.
+    #[cfg(feature = "api-client")]
+    #[arg(short, long, default_value = "1000")]
+    interval: u32,
 }
 
 impl Opts {
@@ -65,7 +97,324 @@ impl Opts {
     }
 }
 
-pub(crate) fn cmd(opts: &Opts) -> exitcode::ExitCode {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum NodeKind {
+    Source,
+    Transform,
+    Sink,
+}
+
+#[derive(Debug, Serialize)]
+struct Node {
+    id: String,
+    kind: NodeKind,
+    #[serde(rename = "type")]
+    component_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    errored: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+struct Edge {
+    from: String,
+    port: Option<String>,
+    to: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    events_per_sec: Option<f64>,
+}
+
+/// Topology of a configuration, optionally annotated with metrics of a running instance.
+#[derive(Debug, Default, Serialize)]
+struct Graph {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+/// Metrics sampled from a running instance, used to annotate the graph.
+#[cfg(any(feature = "api-client", test))]
+#[derive(Debug, Default)]
+struct LiveMetrics {
+    /// Events per second, by component and output port. The default output has no port.
+    throughputs: HashMap<(String, Option<String>), f64>,
+    /// Components whose error totals increased while sampling.
+    errored: Vec<String>,
+}
+
+impl Graph {
+    fn from_config(config: &config::Config) -> Self {
+        let mut graph = Self::default();
+
+        for (id, source) in config.sources() {
+            graph.add_node(id, NodeKind::Source, source.inner.get_component_name());
+        }
+
+        for (id, transform) in config.transforms() {
+            graph.add_node(
+                id,
+                NodeKind::Transform,
+                transform.inner.get_component_name(),
+            );
+            graph.add_edges(id, transform.inputs.iter());
+        }
+
+        for (id, sink) in config.sinks() {
+            graph.add_node(id, NodeKind::Sink, sink.inner.get_component_name());
+            graph.add_edges(id, sink.inputs.iter());
+        }
+
+        graph
+    }
+
+    fn add_node(&mut self, id: &ComponentKey, kind: NodeKind, component_type: &str) {
+        self.nodes.push(Node {
+            id: id.to_string(),
+            kind,
+            component_type: component_type.to_string(),
+            errored: None,
+        });
+    }
+
+    fn add_edges<'a>(&mut self, id: &ComponentKey, inputs: impl Iterator<Item = &'a OutputId>) {
+        for input in inputs {
+            self.edges.push(Edge {
+                from: input.component.to_string(),
+                port: input.port.clone(),
+                to: id.to_string(),
+                events_per_sec: None,
+            });
+        }
+    }
+
+    /// Annotates nodes and edges with live metrics.
+    #[cfg(any(feature = "api-client", test))]
+    fn annotate(&mut self, metrics: &LiveMetrics) {
+        for node in self.nodes.iter_mut() {
+            node.errored = Some(metrics.errored.contains(&node.id));
+        }
+
+        for edge in self.edges.iter_mut() {
+            let key = (edge.from.clone(), edge.port.clone());
+            edge.events_per_sec = Some(metrics.throughputs.get(&key).copied().unwrap_or(0.0));
+        }
+    }
+
+    fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Dot => self.render_dot(),
+            OutputFormat::Mermaid => self.render_mermaid(),
+            OutputFormat::Json => {
+                serde_json::to_string_pretty(self).expect("serializing graph never fails")
+            }
+        }
+    }
+
+    fn render_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+
+        for node in &self.nodes {
+            let shape = match node.kind {
+                NodeKind::Source => "trapezium",
+                NodeKind::Transform => "diamond",
+                NodeKind::Sink => "invtrapezium",
+            };
+            if node.errored == Some(true) {
+                writeln!(dot, "  \"{}\" [shape={} color=red]", node.id, shape)
+            } else {
+                writeln!(dot, "  \"{}\" [shape={}]", node.id, shape)
+            }
+            .expect("write to String never fails");
+        }
+
+        for edge in &self.edges {
+            match edge_label(edge) {
+                Some(label) => writeln!(
+                    dot,
+                    "  \"{}\" -> \"{}\" [label=\"{}\"]",
+                    edge.from, edge.to, label
+                ),
+                None => writeln!(dot, "  \"{}\" -> \"{}\"", edge.from, edge.to),
+            }
+            .expect("write to String never fails");
+        }
+
+        dot += "}";
+        dot
+    }
+
+    fn render_mermaid(&self) -> String {
+        // Component IDs may contain characters which aren't valid in Mermaid node IDs, so nodes
+        // are referenced by index and the component ID is used as the label.
+        let index = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id.as_str(), i))
+            .collect::<HashMap<_, _>>();
+
+        let mut mermaid = String::from("flowchart LR\n");
+
+        for (i, node) in self.nodes.iter().enumerate() {
+            let (open, close) = match node.kind {
+                NodeKind::Source => ("[/", "\\]"),
+                NodeKind::Transform => ("{", "}"),
+                NodeKind::Sink => ("[\\", "/]"),
+            };
+            writeln!(
+                mermaid,
+                "  n{}{}\"{}\"{}",
+                i,
+                open,
+                escape_mermaid(&node.id),
+                close
+            )
+            .expect("write to String never fails");
+        }
+
+        for edge in &self.edges {
+            // Inputs which don't refer to a component in this config can't be rendered
+            let (Some(from), Some(to)) =
+                (index.get(edge.from.as_str()), index.get(edge.to.as_str()))
+            else {
+                continue;
+            };
+            match edge_label(edge) {
+                Some(label) => writeln!(
+                    mermaid,
+                    "  n{} -->|\"{}\"| n{}",
+                    from,
+                    escape_mermaid(&label),
+                    to
+                ),
+                None => writeln!(mermaid, "  n{} --> n{}", from, to),
+            }
+            .expect("write to String never fails");
+        }
+
+        let errored = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.errored == Some(true))
+            .map(|(i, _)| format!("n{}", i))
+            .collect::<Vec<_>>();
+        if !errored.is_empty() {
+            mermaid += "  classDef errored stroke:#f00,stroke-width:2px\n";
+            writeln!(mermaid, "  class {} errored", errored.join(","))
+                .expect("write to String never fails");
+        }
+
+        mermaid
+    }
+}
+
+/// Returns the label of an edge, made up of the output port and throughput, if any.
+fn edge_label(edge: &Edge) -> Option<String> {
+    let throughput = edge.events_per_sec.map(|v| format!("{:.1} events/s", v));
+    match (&edge.port, throughput) {
+        (Some(port), Some(throughput)) => Some(format!("{} ({})", port, throughput)),
+        (Some(port), None) => Some(port.clone()),
+        (None, throughput) => throughput,
+    }
+}
+
+/// Escapes double quotes, which would otherwise terminate a Mermaid label.
+fn escape_mermaid(s: &str) -> String {
+    s.replace('"', "#quot;")
+}
+
+/// Samples throughput and errors of each component from a running instance over `interval`
+/// milliseconds.
+#[cfg(feature = "api-client")]
+async fn sample_live_metrics(url: Url, interval: u32) -> Result<LiveMetrics, String> {
+    use std::time::Duration;
+
+    use tokio_stream::StreamExt;
+    use vector_lib::api_client::{connect_subscription_client, gql::MetricsSubscriptionExt};
+
+    let mut ws_url = url;
+    ws_url
+        .set_scheme(match ws_url.scheme() {
+            "https" => "wss",
+            _ => "ws",
+        })
+        .expect("Couldn't build WebSocket URL. Please report.");
+
+    let client = connect_subscription_client(ws_url)
+        .await
+        .map_err(|error| format!("Couldn't connect to API via WebSockets: {}", error))?;
+
+    let interval = interval as i64;
+    let throughputs = client.component_sent_events_throughputs_subscription(interval);
+    let errors = client.component_errors_totals_subscription(interval);
+    tokio::pin!(throughputs);
+    tokio::pin!(errors);
+
+    // Throughputs are only reported after two samples, and errors are compared across two samples,
+    // so allow a few intervals to pass before giving up.
+    let timeout = tokio::time::sleep(Duration::from_millis(interval as u64 * 5));
+    tokio::pin!(timeout);
+
+    let per_sec = 1000.0 / interval as f64;
+    let mut metrics = LiveMetrics::default();
+    let mut throughputs_sampled = false;
+    // Error totals from the first sample, which components are compared against so that only
+    // those with errors during the interval are flagged, rather than any that ever errored.
+    let mut first_error_totals: Option<HashMap<String, f64>> = None;
+    let mut errors_sampled = false;
+    while !(throughputs_sampled && errors_sampled) {
+        tokio::select! {
+            _ = &mut timeout => return Err("Timed out waiting for metrics.".to_string()),
+            res = errors.next(), if !errors_sampled => {
+                let Some(Some(res)) = res else {
+                    return Err("API connection closed while sampling metrics.".to_string());
+                };
+                let Some(d) = res.data else { continue };
+                let totals = d
+                    .component_errors_totals
+                    .into_iter()
+                    .map(|c| (c.component_id, c.metric.errors_total));
+                match &first_error_totals {
+                    None => first_error_totals = Some(totals.collect()),
+                    Some(first) => {
+                        metrics.errored.extend(
+                            totals
+                                .filter(|(id, total)| {
+                                    *total > first.get(id).copied().unwrap_or(0.0)
+                                })
+                                .map(|(id, _)| id),
+                        );
+                        errors_sampled = true;
+                    }
+                }
+            }
+            res = throughputs.next(), if !throughputs_sampled => {
+                let Some(Some(res)) = res else {
+                    return Err("API connection closed while sampling metrics.".to_string());
+                };
+                let Some(d) = res.data else { continue };
+                for c in d.component_sent_events_throughputs {
+                    for (output_id, throughput) in c.outputs() {
+                        let port = (output_id != vector_lib::internal_event::DEFAULT_OUTPUT)
+                            .then_some(output_id);
+                        metrics
+                            .throughputs
+                            .insert((c.component_id.clone(), port), throughput as f64 * per_sec);
+                    }
+                    metrics
+                        .throughputs
+                        .entry((c.component_id, None))
+                        .or_insert(c.throughput as f64 * per_sec);
+                }
+                throughputs_sampled = true;
+            }
+        }
+    }
+
+    Ok(metrics)
+}
+
+pub(crate) async fn cmd(opts: &Opts) -> exitcode::ExitCode {
     let paths = opts.paths_with_formats();
     let paths = match config::process_paths(&paths) {
         Some(paths) => paths,
@@ -83,54 +432,136 @@ pub(crate) fn cmd(opts: &Opts) -> exitcode::ExitCode {
         }
     };
 
-    let mut dot = String::from("digraph {\n");
+    #[allow(unused_mut)]
+    let mut graph = Graph::from_config(&config);
 
-    for (id, _source) in config.sources() {
-        writeln!(dot, "  \"{}\" [shape=trapezium]", id).expect("write to String never fails");
+    #[cfg(feature = "api-client")]
+    if let Some(url) = opts.url.clone() {
+        match sample_live_metrics(url.clone(), opts.interval).await {
+            Ok(metrics) => graph.annotate(&metrics),
+            Err(error) => {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!("Vector API server isn't reachable ({}): {}", url, error);
+                }
+                return exitcode::UNAVAILABLE;
+            }
+        }
     }
 
-    for (id, transform) in config.transforms() {
-        writeln!(dot, "  \"{}\" [shape=diamond]", id).expect("write to String never fails");
+    #[allow(clippy::print_stdout)]
+    {
+        println!("{}", graph.render(opts.format));
+    }
 
-        for input in transform.inputs.iter() {
-            if let Some(port) = &input.port {
-                writeln!(
-                    dot,
-                    "  \"{}\" -> \"{}\" [label=\"{}\"]",
-                    input.component, id, port
-                )
-                .expect("write to String never fails");
-            } else {
-                writeln!(dot, "  \"{}\" -> \"{}\"", input, id)
-                    .expect("write to String never fails");
-            }
+    exitcode::OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> Graph {
+        let mut graph = Graph::default();
+        graph.add_node(&ComponentKey::from("in"), NodeKind::Source, "demo_logs");
+        graph.add_node(&ComponentKey::from("router"), NodeKind::Transform, "route");
+        graph.add_node(&ComponentKey::from("out"), NodeKind::Sink, "console");
+        graph.add_edges(
+            &ComponentKey::from("router"),
+            [OutputId::from(ComponentKey::from("in"))].iter(),
+        );
+        graph.add_edges(
+            &ComponentKey::from("out"),
+            [OutputId {
+                component: ComponentKey::from("router"),
+                port: Some("errors".to_string()),
+            }]
+            .iter(),
+        );
+        graph
+    }
+
+    fn live_metrics() -> LiveMetrics {
+        LiveMetrics {
+            throughputs: HashMap::from([
+                (("in".to_string(), None), 10.0),
+                (("router".to_string(), Some("errors".to_string())), 2.5),
+            ]),
+            errored: vec!["out".to_string()],
         }
     }
 
-    for (id, sink) in config.sinks() {
-        writeln!(dot, "  \"{}\" [shape=invtrapezium]", id).expect("write to String never fails");
+    #[test]
+    fn render_dot() {
+        assert_eq!(
+            graph().render(OutputFormat::Dot),
+            indoc::indoc! {r#"
+                digraph {
+                  "in" [shape=trapezium]
+                  "router" [shape=diamond]
+                  "out" [shape=invtrapezium]
+                  "in" -> "router"
+                  "router" -> "out" [label="errors"]
+                }"#}
+        );
+    }
 
-        for input in &sink.inputs {
-            if let Some(port) = &input.port {
-                writeln!(
-                    dot,
-                    "  \"{}\" -> \"{}\" [label=\"{}\"]",
-                    input.component, id, port
-                )
-                .expect("write to String never fails");
-            } else {
-                writeln!(dot, "  \"{}\" -> \"{}\"", input, id)
-                    .expect("write to String never fails");
-            }
-        }
+    #[test]
+    fn render_dot_annotated() {
+        let mut graph = graph();
+        graph.annotate(&live_metrics());
+
+        assert_eq!(
+            graph.render(OutputFormat::Dot),
+            indoc::indoc! {r#"
+                digraph {
+                  "in" [shape=trapezium]
+                  "router" [shape=diamond]
+                  "out" [shape=invtrapezium color=red]
+                  "in" -> "router" [label="10.0 events/s"]
+                  "router" -> "out" [label="errors (2.5 events/s)"]
+                }"#}
+        );
     }
 
-    dot += "}";
+    #[test]
+    fn render_mermaid_annotated() {
+        let mut graph = graph();
+        graph.annotate(&live_metrics());
 
-    #[allow(clippy::print_stdout)]
-    {
-        println!("{}", dot);
+        assert_eq!(
+            graph.render(OutputFormat::Mermaid),
+            indoc::indoc! {r#"
+                flowchart LR
+                  n0[/"in"\]
+                  n1{"router"}
+                  n2[\"out"/]
+                  n0 -->|"10.0 events/s"| n1
+                  n1 -->|"errors (2.5 events/s)"| n2
+                  classDef errored stroke:#f00,stroke-width:2px
+                  class n2 errored
+            "#}
+        );
     }
 
-    exitcode::OK
+    #[test]
+    fn render_json() {
+        let json: serde_json::Value =
+            serde_json::from_str(&graph().render(OutputFormat::Json)).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "nodes": [
+                    { "id": "in", "kind": "source", "type": "demo_logs" },
+                    { "id": "router", "kind": "transform", "type": "route" },
+                    { "id": "out", "kind": "sink", "type": "console" },
+                ],
+                "edges": [
+                    { "from": "in", "port": null, "to": "router" },
+                    { "from": "router", "port": "errors", "to": "out" },
+                ],
+            })
+        );
+    }
 }
@@ -88,3 +88,28 @@ pub mod source {
         }
     }
 }
+
+#[cfg(feature = "sinks-amqp")]
+pub mod sink {
+    use vector_lib::internal_event::InternalEvent;
+    use vector_lib::internal_event::{error_stage, error_type};
+
+    #[derive(Debug)]
+    pub struct AmqpInvalidPropertyError<'a> {
+        pub property: &'a str,
+        pub value: &'a str,
+    }
+
+    impl InternalEvent for AmqpInvalidPropertyError<'_> {
+        fn emit(self) {
+            warn!(
+                message = "Rendered message property is not a valid number; leaving it unset.",
+                property = %self.property,
+                value = %self.value,
+                error_type = error_type::TEMPLATE_FAILED,
+                stage = error_stage::PROCESSING,
+                internal_log_rate_limit = true,
+            );
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use std::fmt::Display;
+
+use metrics::counter;
+use vector_lib::internal_event::{error_stage, error_type};
+use vector_lib::internal_event::{ComponentEventsDropped, InternalEvent, UNINTENTIONAL};
+
+#[derive(Debug)]
+pub struct ClickhouseUnmappableFieldError<'a, E> {
+    pub column: &'a str,
+    pub error: E,
+}
+
+impl<E: Display> InternalEvent for ClickhouseUnmappableFieldError<'_, E> {
+    fn emit(self) {
+        let reason = "Event field can't be encoded into its column.";
+
+        error!(
+            message = reason,
+            column = %self.column,
+            error = %self.error,
+            error_code = "unmappable_field",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "unmappable_field",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -151,13 +151,175 @@ impl InternalEvent for KafkaStatisticsReceived<'_> {
         if self.expose_lag_metrics {
             for (topic_id, topic) in &self.statistics.topics {
                 for (partition_id, partition) in &topic.partitions {
-                    gauge!("kafka_consumer_lag", partition.consumer_lag as f64, "topic_id" => topic_id.clone(), "partition_id" => partition_id.to_string());
+                    emit!(KafkaConsumerLag {
+                        topic: topic_id,
+                        partition: *partition_id,
+                        lag: partition.consumer_lag,
+                    });
                 }
             }
         }
     }
 }
 
+#[derive(Debug)]
+pub struct KafkaConsumerLag<'a> {
+    pub topic: &'a str,
+    pub partition: i32,
+    pub lag: i64,
+}
+
+impl InternalEvent for KafkaConsumerLag<'_> {
+    fn emit(self) {
+        trace!(
+            message = "Consumer lag updated.",
+            topic = self.topic,
+            partition = %self.partition,
+            lag = %self.lag,
+        );
+        gauge!(
+            "kafka_consumer_lag", self.lag as f64,
+            "topic_id" => self.topic.to_string(),
+            "partition_id" => self.partition.to_string(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaPartitionAssigned<'a> {
+    pub topic: &'a str,
+    pub partition: i32,
+}
+
+impl InternalEvent for KafkaPartitionAssigned<'_> {
+    fn emit(self) {
+        info!(
+            message = "Partition assigned.",
+            topic = self.topic,
+            partition = %self.partition,
+        );
+        counter!(
+            "kafka_partition_assignments_total", 1,
+            "topic" => self.topic.to_string(),
+            "partition" => self.partition.to_string(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaPartitionRevoked<'a> {
+    pub topic: &'a str,
+    pub partition: i32,
+}
+
+impl InternalEvent for KafkaPartitionRevoked<'_> {
+    fn emit(self) {
+        info!(
+            message = "Partition revoked.",
+            topic = self.topic,
+            partition = %self.partition,
+        );
+        counter!(
+            "kafka_partition_revocations_total", 1,
+            "topic" => self.topic.to_string(),
+            "partition" => self.partition.to_string(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaPartitionSeek<'a> {
+    pub topic: &'a str,
+    pub partition: i32,
+    pub offset: rdkafka::Offset,
+}
+
+impl InternalEvent for KafkaPartitionSeek<'_> {
+    fn emit(self) {
+        info!(
+            message = "Moved partition to the configured start position.",
+            topic = self.topic,
+            partition = %self.partition,
+            offset = ?self.offset,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaSeekError<'a> {
+    pub topic: &'a str,
+    pub partition: i32,
+    pub error: rdkafka::error::KafkaError,
+}
+
+impl InternalEvent for KafkaSeekError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Unable to move partition to the configured start position.",
+            topic = self.topic,
+            partition = %self.partition,
+            error = %self.error,
+            error_code = "kafka_seek",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_seek",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaPartitionRewound<'a> {
+    pub topic: &'a str,
+    pub partition: i32,
+    pub offset: i64,
+}
+
+impl InternalEvent for KafkaPartitionRewound<'_> {
+    fn emit(self) {
+        warn!(
+            message = "Rewound partition to the first message of an aborted transaction.",
+            topic = self.topic,
+            partition = %self.partition,
+            offset = %self.offset,
+            internal_log_rate_limit = true,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaRewindError<'a> {
+    pub topic: &'a str,
+    pub partition: i32,
+    pub error: rdkafka::error::KafkaError,
+}
+
+impl InternalEvent for KafkaRewindError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Unable to rewind partition to the first message of an aborted transaction.",
+            topic = self.topic,
+            partition = %self.partition,
+            error = %self.error,
+            error_code = "kafka_rewind",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_rewind",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
 pub struct KafkaHeaderExtractionError<'a> {
     pub header_field: &'a OwnedTargetPath,
 }
@@ -180,3 +342,20 @@ impl InternalEvent for KafkaHeaderExtractionError<'_> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct KafkaInvalidPartitionError<'a> {
+    pub value: &'a str,
+}
+
+impl InternalEvent for KafkaInvalidPartitionError<'_> {
+    fn emit(self) {
+        warn!(
+            message = "Rendered partition is not a valid partition number; using the partitioner instead.",
+            value = %self.value,
+            error_type = error_type::TEMPLATE_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+    }
+}
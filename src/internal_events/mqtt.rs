@@ -1,16 +1,17 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 
 use metrics::counter;
-use rumqttc::ConnectionError;
+use rumqttc::{ClientError, ConnectionError};
 use vector_lib::internal_event::InternalEvent;
 use vector_lib::internal_event::{error_stage, error_type};
 
+/// The `error` is either a `rumqttc::ConnectionError` or its MQTT 5 counterpart.
 #[derive(Debug)]
-pub struct MqttConnectionError {
-    pub error: ConnectionError,
+pub struct MqttConnectionError<E> {
+    pub error: E,
 }
 
-impl InternalEvent for MqttConnectionError {
+impl<E: Display> InternalEvent for MqttConnectionError<E> {
     fn emit(self) {
         error!(
             message = "MQTT connection error.",
@@ -32,3 +33,130 @@ impl InternalEvent for MqttConnectionError {
         Some("MqttConnectionError")
     }
 }
+
+#[derive(Debug)]
+pub struct MqttReceiveError {
+    pub error: ConnectionError,
+}
+
+impl InternalEvent for MqttReceiveError {
+    fn emit(self) {
+        error!(
+            message = "MQTT connection error.",
+            error = %self.error,
+            error_code = "mqtt_connection_error",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "mqtt_connection_error",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("MqttReceiveError")
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttSubscribeError<'a> {
+    pub topic: &'a str,
+}
+
+impl InternalEvent for MqttSubscribeError<'_> {
+    fn emit(self) {
+        error!(
+            message = "MQTT broker rejected the subscription.",
+            topic = %self.topic,
+            error_code = "mqtt_subscribe_rejected",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "mqtt_subscribe_rejected",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("MqttSubscribeError")
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttAckError {
+    pub error: ClientError,
+}
+
+impl InternalEvent for MqttAckError {
+    fn emit(self) {
+        error!(
+            message = "Unable to ack.",
+            error = %self.error,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("MqttAckError")
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttRedeliveryReconnect<'a> {
+    pub topic: &'a str,
+}
+
+impl InternalEvent for MqttRedeliveryReconnect<'_> {
+    fn emit(self) {
+        warn!(
+            message = "Events of a message were not delivered, reconnecting for the broker to deliver it again.",
+            topic = %self.topic,
+            internal_log_rate_limit = true,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("MqttRedeliveryReconnect")
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttUndeliveredAck<'a> {
+    pub topic: &'a str,
+}
+
+impl InternalEvent for MqttUndeliveredAck<'_> {
+    fn emit(self) {
+        error!(
+            message = "Events of a message were not delivered, acknowledging it anyway as the broker can't deliver it again without a persistent session.",
+            topic = %self.topic,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("MqttUndeliveredAck")
+    }
+}
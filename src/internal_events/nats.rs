@@ -0,0 +1,59 @@
+use std::fmt::Debug;
+
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct NatsReceiveError {
+    pub error: async_nats::Error,
+}
+
+impl InternalEvent for NatsReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Failed to receive JetStream message.",
+            error = %self.error,
+            error_code = "nats_receive_error",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "nats_receive_error",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("NatsReceiveError")
+    }
+}
+
+#[derive(Debug)]
+pub struct NatsAckError {
+    pub error: async_nats::Error,
+}
+
+impl InternalEvent for NatsAckError {
+    fn emit(self) {
+        error!(
+            message = "Unable to ack.",
+            error = %self.error,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("NatsAckError")
+    }
+}
@@ -61,6 +61,7 @@ pub enum PulsarErrorEventType {
     Read,
     Ack,
     NAck,
+    Schema,
 }
 
 #[cfg(feature = "sources-pulsar")]
@@ -92,6 +93,13 @@ registered_event!(
             "error_type" => error_type::READER_FAILED,
             "stage" => error_stage::RECEIVING,
         ),
+
+        schema_errors: Counter = register_counter!(
+            "component_errors_total",
+            "error_code" => "decoding_schema",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        ),
     }
 
     fn emit(&self,error:PulsarErrorEventData) {
@@ -132,6 +140,18 @@ registered_event!(
 
                 self.nack_errors.increment(1_u64);
             }
+            PulsarErrorEventType::Schema => {
+                error!(
+                    message = "Failed to decode message with its schema, falling back to the configured decoding.",
+                    error = error.msg,
+                    error_code = "decoding_schema",
+                    error_type = error_type::PARSER_FAILED,
+                    stage = error_stage::PROCESSING,
+                    internal_log_rate_limit = true,
+                );
+
+                self.schema_errors.increment(1_u64);
+            }
         }
     }
 );
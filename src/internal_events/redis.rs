@@ -33,3 +33,59 @@ impl InternalEvent for RedisReceiveEventError {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct RedisAckError {
+    pub error: redis::RedisError,
+}
+
+impl InternalEvent for RedisAckError {
+    fn emit(self) {
+        error!(
+            message = "Unable to acknowledge message.",
+            error = %self.error,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("RedisAckError")
+    }
+}
+
+#[derive(Debug)]
+pub struct RedisStreamFieldMissingError<'a> {
+    pub id: &'a str,
+    pub field: &'a str,
+}
+
+impl InternalEvent for RedisStreamFieldMissingError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Stream entry is missing the message field; dropping it.",
+            id = %self.id,
+            field = %self.field,
+            error_code = "missing_field",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "missing_field",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("RedisStreamFieldMissingError")
+    }
+}
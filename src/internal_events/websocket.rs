@@ -1,5 +1,6 @@
 use std::error::Error;
 use std::fmt::Debug;
+use std::net::SocketAddr;
 
 use metrics::counter;
 use vector_lib::internal_event::InternalEvent;
@@ -89,3 +90,61 @@ impl InternalEvent for WsConnectionError {
         Some("WsConnectionError")
     }
 }
+
+#[derive(Debug)]
+pub struct WsReceiveError {
+    pub error: tokio_tungstenite::tungstenite::Error,
+}
+
+impl InternalEvent for WsReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Error receiving WebSocket message.",
+            error = %self.error,
+            error_code = "ws_receive_error",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "ws_receive_error",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsReceiveError")
+    }
+}
+
+#[derive(Debug)]
+pub struct WsAcceptError {
+    pub error: Box<dyn Error>,
+    pub peer_addr: Option<SocketAddr>,
+}
+
+impl InternalEvent for WsAcceptError {
+    fn emit(self) {
+        error!(
+            message = "Failed to accept WebSocket connection.",
+            error = %self.error,
+            peer_addr = ?self.peer_addr,
+            error_code = "ws_accept_failed",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "ws_accept_failed",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsAcceptError")
+    }
+}
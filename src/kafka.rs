@@ -1,7 +1,13 @@
 #![allow(missing_docs)]
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
-use rdkafka::{consumer::ConsumerContext, ClientConfig, ClientContext, Statistics};
+use rdkafka::{
+    consumer::{ConsumerContext, ConsumerGroupMetadata},
+    ClientConfig, ClientContext, Statistics,
+};
 use snafu::Snafu;
 use tracing::Span;
 use vector_lib::configurable::configurable_component;
@@ -185,3 +191,54 @@ impl ClientContext for KafkaStatisticsContext {
 }
 
 impl ConsumerContext for KafkaStatisticsContext {}
+
+/// Looks up the current group metadata of the consumer of a `kafka` source with exactly-once
+/// delivery enabled, which the source provides to the transactional `kafka` sink committing its
+/// offsets through their [`OffsetsLink`](crate::config::OffsetsLink).
+#[derive(Clone)]
+pub(crate) struct GroupMetadataLookup(Arc<dyn Fn() -> Option<ConsumerGroupMetadata> + Send + Sync>);
+
+impl GroupMetadataLookup {
+    pub(crate) fn new(
+        lookup: impl Fn() -> Option<ConsumerGroupMetadata> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(lookup))
+    }
+
+    /// Returns the current group metadata of the consumer, to be passed along with its offsets to a
+    /// producer transaction, unless the consumer is gone.
+    pub(crate) fn group_metadata(&self) -> Option<ConsumerGroupMetadata> {
+        (self.0)()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rdkafka::consumer::{BaseConsumer, Consumer};
+
+    use super::*;
+    use crate::config::OffsetsLink;
+
+    #[test]
+    fn group_metadata_lookup_through_link() {
+        let consumer: Arc<BaseConsumer> = Arc::new(
+            ClientConfig::new()
+                .set("bootstrap.servers", "localhost:9092")
+                .set("group.id", "group-metadata-lookup")
+                .create()
+                .unwrap(),
+        );
+        let link = OffsetsLink::default();
+        assert!(link.get::<GroupMetadataLookup>().is_none());
+
+        let weak = Arc::downgrade(&consumer);
+        link.provide(GroupMetadataLookup::new(move || {
+            weak.upgrade().and_then(|consumer| consumer.group_metadata())
+        }));
+        let lookup = link.get::<GroupMetadataLookup>().unwrap();
+        assert!(lookup.group_metadata().is_some());
+
+        drop(consumer);
+        assert!(lookup.group_metadata().is_none());
+    }
+}
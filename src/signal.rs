@@ -1,5 +1,7 @@
 #![allow(missing_docs)]
 
+use std::collections::HashSet;
+
 use snafu::Snafu;
 use tokio::{runtime::Runtime, sync::broadcast};
 use tokio_stream::{Stream, StreamExt};
@@ -18,6 +20,9 @@ pub enum SignalTo {
     ReloadFromConfigBuilder(ConfigBuilder),
     /// Signal to reload config from the filesystem.
     ReloadFromDisk,
+    /// Signal to reload config from the filesystem, rebuilding the given components even if their
+    /// configuration didn't change.
+    ReloadComponents(HashSet<ComponentKey>),
     /// Signal to shutdown process.
     Shutdown(Option<ShutdownError>),
     /// Shutdown process immediately.
@@ -1,7 +1,10 @@
 //! Configuration functionality for the `AMQP` sink.
-use crate::{amqp::AmqpConfig, sinks::prelude::*};
-use lapin::{types::ShortString, BasicProperties};
-use std::sync::Arc;
+use crate::{amqp::AmqpConfig, internal_events::sink::AmqpInvalidPropertyError, sinks::prelude::*};
+use lapin::{
+    types::{AMQPValue, FieldTable, LongString, ShortString},
+    BasicProperties,
+};
+use std::{collections::HashMap, sync::Arc};
 use vector_lib::codecs::TextSerializerConfig;
 
 use super::sink::AmqpSink;
@@ -18,11 +21,53 @@ pub struct AmqpPropertiesConfig {
     pub(crate) content_encoding: Option<String>,
 
     /// Expiration for AMQP messages (in milliseconds)
-    pub(crate) expiration_ms: Option<u64>,
+    ///
+    /// This can be either a number, or a template that renders to one.
+    #[configurable(metadata(docs::examples = 60000, docs::examples = "{{ ttl_ms }}"))]
+    pub(crate) expiration_ms: Option<AmqpNumberTemplate>,
+
+    /// Priority for the AMQP messages, between 0 and 255.
+    ///
+    /// This can be either a number, or a template that renders to one. The queues must be
+    /// declared with a maximum priority for brokers to take it into account.
+    #[configurable(metadata(docs::examples = 5, docs::examples = "{{ priority }}"))]
+    pub(crate) priority: Option<AmqpNumberTemplate>,
+
+    /// Headers for the AMQP messages.
+    ///
+    /// The values are sent as strings, and can be templated.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "An AMQP message header."))]
+    #[configurable(metadata(docs::examples = "headers_examples()"))]
+    pub(crate) headers: HashMap<String, Template>,
+}
+
+/// A number, either fixed or rendered from a template.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(untagged)]
+pub enum AmqpNumberTemplate {
+    /// A fixed number.
+    Fixed(u64),
+
+    /// A template that renders to a number.
+    Template(Template),
+}
+
+fn headers_examples() -> HashMap<String, String> {
+    let mut example = HashMap::new();
+    example.insert("tenant".to_string(), "{{ tenant }}".to_string());
+    example.insert("source".to_string(), "vector".to_string());
+
+    example
 }
 
 impl AmqpPropertiesConfig {
-    pub(super) fn build(&self) -> BasicProperties {
+    /// Builds the properties of the message for the event.
+    ///
+    /// Templated properties that fail to render, or that don't render to a valid number, are left
+    /// unset rather than dropping the event.
+    pub(super) fn build(&self, event: &Event) -> BasicProperties {
         let mut prop = BasicProperties::default();
         if let Some(content_type) = &self.content_type {
             prop = prop.with_content_type(ShortString::from(content_type.clone()));
@@ -31,12 +76,70 @@ impl AmqpPropertiesConfig {
             prop = prop.with_content_encoding(ShortString::from(content_encoding.clone()));
         }
         if let Some(expiration_ms) = &self.expiration_ms {
-            prop = prop.with_expiration(ShortString::from(expiration_ms.to_string()));
+            if let Some(expiration_ms) = expiration_ms.render(event, "properties.expiration_ms") {
+                prop = prop.with_expiration(ShortString::from(expiration_ms.to_string()));
+            }
+        }
+        if let Some(priority) = &self.priority {
+            if let Some(priority) = priority.render(event, "properties.priority") {
+                match u8::try_from(priority) {
+                    Ok(priority) => prop = prop.with_priority(priority),
+                    Err(_) => emit!(AmqpInvalidPropertyError {
+                        property: "properties.priority",
+                        value: &priority.to_string(),
+                    }),
+                }
+            }
+        }
+        if !self.headers.is_empty() {
+            let mut headers = FieldTable::default();
+            for (key, template) in &self.headers {
+                if let Some(value) = render_template(template, event, "properties.headers") {
+                    headers.insert(
+                        ShortString::from(key.clone()),
+                        AMQPValue::LongString(LongString::from(value)),
+                    );
+                }
+            }
+            prop = prop.with_headers(headers);
         }
         prop
     }
 }
 
+impl AmqpNumberTemplate {
+    fn render(&self, event: &Event, property: &'static str) -> Option<u64> {
+        match self {
+            Self::Fixed(number) => Some(*number),
+            Self::Template(template) => {
+                let value = render_template(template, event, property)?;
+                value
+                    .parse()
+                    .map_err(|_| {
+                        emit!(AmqpInvalidPropertyError {
+                            property,
+                            value: &value,
+                        })
+                    })
+                    .ok()
+            }
+        }
+    }
+}
+
+fn render_template(template: &Template, event: &Event, property: &'static str) -> Option<String> {
+    template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(property),
+                drop_event: false,
+            })
+        })
+        .ok()
+}
+
 /// Configuration for the `amqp` sink.
 ///
 /// Supports AMQP version 0.9.1
@@ -131,3 +234,35 @@ pub(super) async fn healthcheck(channel: Arc<lapin::Channel>) -> crate::Result<(
 pub fn generate_config() {
     crate::test_util::test_generate_config::<AmqpSinkConfig>();
 }
+
+#[test]
+fn build_properties() {
+    use vector_lib::event::LogEvent;
+
+    let config: AmqpPropertiesConfig = toml::from_str(
+        r#"
+        expiration_ms = 60000
+        priority = "{{ priority }}"
+        headers.tenant = "{{ tenant }}"
+        headers.missing = "{{ missing }}"
+        "#,
+    )
+    .unwrap();
+
+    let mut log = LogEvent::from("message");
+    log.insert("priority", 5);
+    log.insert("tenant", "acme");
+    let properties = config.build(&log.into());
+
+    assert_eq!(
+        properties.expiration().as_ref().map(ShortString::as_str),
+        Some("60000")
+    );
+    assert_eq!(*properties.priority(), Some(5));
+    let headers = properties.headers().as_ref().unwrap().inner();
+    assert_eq!(headers.len(), 1);
+    assert_eq!(
+        headers.get(&ShortString::from("tenant")),
+        Some(&AMQPValue::LongString(LongString::from("acme")))
+    );
+}
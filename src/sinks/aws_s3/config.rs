@@ -1,7 +1,7 @@
 use aws_sdk_s3::Client as S3Client;
 use tower::ServiceBuilder;
 use vector_lib::codecs::{
-    encoding::{Framer, FramingConfig},
+    encoding::{BatchSerializerConfig, FramingConfig},
     TextSerializerConfig,
 };
 use vector_lib::configurable::configurable_component;
@@ -11,7 +11,7 @@ use vector_lib::TimeZone;
 use super::sink::S3RequestOptions;
 use crate::{
     aws::{AwsAuthentication, RegionOrEndpoint},
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType},
+    codecs::{EncodingConfigWithFraming, SinkType},
     config::{AcknowledgementsConfig, GenerateConfig, Input, ProxyConfig, SinkConfig, SinkContext},
     sinks::{
         s3_common::{
@@ -22,8 +22,8 @@ use crate::{
             sink::S3Sink,
         },
         util::{
-            timezone_to_offset, BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression,
-            ServiceBuilderExt, TowerRequestConfig,
+            encoding::object_compression, timezone_to_offset, BatchConfig,
+            BulkSizeBasedDefaultBatchSettings, Compression, ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck,
     },
@@ -90,7 +90,8 @@ pub struct S3SinkConfig {
 
     /// The filename extension to use in the object key.
     ///
-    /// This overrides setting the extension based on the configured `compression`.
+    /// This overrides setting the extension based on the configured `compression`, or on the
+    /// configured `batch_encoding`.
     #[configurable(metadata(docs::examples = "json"))]
     pub filename_extension: Option<String>,
 
@@ -103,6 +104,10 @@ pub struct S3SinkConfig {
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_encoding: Option<BatchSerializerConfig>,
+
     /// Compression configuration.
     ///
     /// All compression algorithms use the default compression level unless otherwise specified.
@@ -160,6 +165,7 @@ impl GenerateConfig for S3SinkConfig {
             options: S3Options::default(),
             region: RegionOrEndpoint::default(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
@@ -183,7 +189,11 @@ impl SinkConfig for S3SinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type())
+        let input_type = match &self.batch_encoding {
+            Some(batch_encoding) => batch_encoding.input_type(),
+            None => self.encoding.config().1.input_type(),
+        };
+        Input::new(input_type)
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -227,17 +237,23 @@ impl S3SinkConfig {
         let partitioner = S3KeyPartitioner::new(key_prefix, ssekms_key_id);
 
         let transformer = self.encoding.transformer();
-        let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let encoder = self
+            .encoding
+            .build_encoder_kind(self.batch_encoding.as_ref(), SinkType::MessageBased)?;
+
+        let (compression, extension) = object_compression(&encoder, self.compression);
 
         let request_options = S3RequestOptions {
             bucket: self.bucket.clone(),
             api_options: self.options.clone(),
-            filename_extension: self.filename_extension.clone(),
+            filename_extension: self
+                .filename_extension
+                .clone()
+                .unwrap_or_else(|| extension.into()),
             filename_time_format: self.filename_time_format.clone(),
             filename_append_uuid: self.filename_append_uuid,
             encoder: (transformer, encoder),
-            compression: self.compression,
+            compression,
             filename_tz_offset: offset,
         };
 
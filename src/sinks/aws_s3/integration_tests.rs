@@ -1,7 +1,7 @@
 #![cfg(all(test, feature = "aws-s3-integration-tests"))]
 
 use std::{
-    io::{BufRead, BufReader},
+    io::{BufRead, BufReader, Read},
     time::Duration,
 };
 
@@ -19,7 +19,10 @@ use flate2::read::MultiGzDecoder;
 use futures::{stream, Stream};
 use similar_asserts::assert_eq;
 use tokio_stream::StreamExt;
-use vector_lib::codecs::{encoding::FramingConfig, TextSerializerConfig};
+use vector_lib::codecs::{
+    encoding::{FramingConfig, ParquetSerializerConfig},
+    TextSerializerConfig,
+};
 use vector_lib::{
     config::proxy::ProxyConfig,
     event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, EventArray, LogEvent},
@@ -300,6 +303,53 @@ async fn s3_zstd() {
 // doesn't enforce the required Content-MD5 header on the request for
 // buckets with object lock enabled
 // https://github.com/localstack/localstack/issues/4166
+#[tokio::test]
+async fn s3_parquet() {
+    // Batch codecs compress their output themselves, so the default gzip compression isn't applied,
+    // and objects are named after the codec.
+    let cx = SinkContext::default();
+
+    let bucket = uuid::Uuid::new_v4().to_string();
+
+    create_bucket(&bucket, false).await;
+
+    let batch_size = 1_000;
+    let batch_multiplier = 3;
+    let config = S3SinkConfig {
+        batch_encoding: Some(ParquetSerializerConfig::default().into()),
+        compression: Compression::gzip_default(),
+        filename_time_format: "%s%f".into(),
+        ..config(&bucket, batch_size)
+    };
+
+    let prefix = config.key_prefix.clone();
+    let service = config.create_service(&cx.globals.proxy).await.unwrap();
+    let sink = config.build_processor(service, cx).unwrap();
+
+    let (_lines, events, receiver) = make_events_batch(100, batch_size * batch_multiplier);
+    run_and_assert_sink_compliance(sink, events, &AWS_SINK_TAGS).await;
+    assert_eq!(receiver.await, BatchStatus::Delivered);
+
+    let keys = get_keys(&bucket, prefix).await;
+    assert_eq!(keys.len(), batch_multiplier);
+
+    let mut key_stream = stream::iter(keys);
+    while let Some(key) = key_stream.next().await {
+        assert!(key.ends_with(".parquet"));
+
+        let obj = get_object(&bucket, key).await;
+        assert_eq!(obj.content_encoding, None);
+
+        let mut body = Vec::new();
+        get_object_output_body(obj)
+            .await
+            .read_to_end(&mut body)
+            .unwrap();
+        assert!(body.starts_with(b"PAR1"));
+        assert!(body.ends_with(b"PAR1"));
+    }
+}
+
 #[tokio::test]
 async fn s3_insert_message_into_object_lock() {
     let cx = SinkContext::default();
@@ -431,6 +481,7 @@ async fn s3_flush_on_exhaustion() {
             options: S3Options::default(),
             region: RegionOrEndpoint::with_both("us-east-1", s3_address()),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            batch_encoding: None,
             compression: Compression::None,
             batch,
             request: TowerRequestConfig::default(),
@@ -514,6 +565,7 @@ fn config(bucket: &str, batch_size: usize) -> S3SinkConfig {
         options: S3Options::default(),
         region: RegionOrEndpoint::with_both("us-east-1", s3_address()),
         encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+        batch_encoding: None,
         compression: Compression::None,
         batch,
         request: TowerRequestConfig::default(),
@@ -3,12 +3,11 @@ use std::io;
 use bytes::Bytes;
 use chrono::{FixedOffset, Utc};
 use uuid::Uuid;
-use vector_lib::codecs::encoding::Framer;
 use vector_lib::event::Finalizable;
 use vector_lib::request_metadata::RequestMetadata;
 
 use crate::{
-    codecs::{Encoder, Transformer},
+    codecs::{EncoderKind, Transformer},
     event::Event,
     sinks::{
         s3_common::{
@@ -28,9 +27,9 @@ pub struct S3RequestOptions {
     pub bucket: String,
     pub filename_time_format: String,
     pub filename_append_uuid: bool,
-    pub filename_extension: Option<String>,
+    pub filename_extension: String,
     pub api_options: S3Options,
-    pub encoder: (Transformer, Encoder<Framer>),
+    pub encoder: (Transformer, EncoderKind),
     pub compression: Compression,
     pub filename_tz_offset: Option<FixedOffset>,
 }
@@ -38,7 +37,7 @@ pub struct S3RequestOptions {
 impl RequestBuilder<(S3PartitionKey, Vec<Event>)> for S3RequestOptions {
     type Metadata = S3Metadata;
     type Events = Vec<Event>;
-    type Encoder = (Transformer, Encoder<Framer>);
+    type Encoder = (Transformer, EncoderKind);
     type Payload = Bytes;
     type Request = S3Request;
     type Error = io::Error; // TODO: this is ugly.
@@ -95,13 +94,7 @@ impl RequestBuilder<(S3PartitionKey, Vec<Event>)> for S3RequestOptions {
         let mut s3_options = self.api_options.clone();
         s3_options.ssekms_key_id = ssekms_key_id;
 
-        let extension = self
-            .filename_extension
-            .as_ref()
-            .cloned()
-            .unwrap_or_else(|| self.compression.extension().into());
-
-        s3metadata.s3_key = format_s3_key(&s3metadata.s3_key, &filename, &extension);
+        s3metadata.s3_key = format_s3_key(&s3metadata.s3_key, &filename, &self.filename_extension);
 
         S3Request {
             body: payload.into_payload(),
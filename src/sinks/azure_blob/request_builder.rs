@@ -1,12 +1,11 @@
 use bytes::Bytes;
 use chrono::Utc;
 use uuid::Uuid;
-use vector_lib::codecs::encoding::Framer;
 use vector_lib::request_metadata::RequestMetadata;
 use vector_lib::EstimatedJsonEncodedSizeOf;
 
 use crate::{
-    codecs::{Encoder, Transformer},
+    codecs::{EncoderKind, Transformer},
     event::{Event, Finalizable},
     sinks::{
         azure_common::config::{AzureBlobMetadata, AzureBlobRequest},
@@ -22,14 +21,15 @@ pub struct AzureBlobRequestOptions {
     pub container_name: String,
     pub blob_time_format: String,
     pub blob_append_uuid: bool,
-    pub encoder: (Transformer, Encoder<Framer>),
+    pub blob_extension: &'static str,
+    pub encoder: (Transformer, EncoderKind),
     pub compression: Compression,
 }
 
 impl RequestBuilder<(String, Vec<Event>)> for AzureBlobRequestOptions {
     type Metadata = AzureBlobMetadata;
     type Events = Vec<Event>;
-    type Encoder = (Transformer, Encoder<Framer>);
+    type Encoder = (Transformer, EncoderKind);
     type Payload = Bytes;
     type Request = AzureBlobRequest;
     type Error = std::io::Error;
@@ -74,10 +74,9 @@ impl RequestBuilder<(String, Vec<Event>)> for AzureBlobRequestOptions {
                 .unwrap_or_else(|| formatted_ts.to_string())
         };
 
-        let extension = self.compression.extension();
         azure_metadata.partition_key = format!(
             "{}{}.{}",
-            azure_metadata.partition_key, blob_name, extension
+            azure_metadata.partition_key, blob_name, self.blob_extension
         );
 
         let blob_data = payload.into_payload();
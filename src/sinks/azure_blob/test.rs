@@ -24,6 +24,7 @@ fn default_config(encoding: EncodingConfigWithFraming) -> AzureBlobSinkConfig {
         blob_time_format: Default::default(),
         blob_append_uuid: Default::default(),
         encoding,
+        batch_encoding: None,
         compression: Compression::gzip_default(),
         batch: Default::default(),
         request: Default::default(),
@@ -59,12 +60,14 @@ fn azure_blob_build_request_without_compression() {
         container_name,
         blob_time_format,
         blob_append_uuid,
+        blob_extension: compression.extension(),
         encoder: (
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::default().into(),
                 TextSerializerConfig::default().build().into(),
-            ),
+            )
+            .into(),
         ),
         compression,
     };
@@ -107,12 +110,14 @@ fn azure_blob_build_request_with_compression() {
         container_name,
         blob_time_format,
         blob_append_uuid,
+        blob_extension: compression.extension(),
         encoder: (
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::default().into(),
                 TextSerializerConfig::default().build().into(),
-            ),
+            )
+            .into(),
         ),
         compression,
     };
@@ -155,12 +160,14 @@ fn azure_blob_build_request_with_time_format() {
         container_name,
         blob_time_format,
         blob_append_uuid,
+        blob_extension: compression.extension(),
         encoder: (
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::default().into(),
                 TextSerializerConfig::default().build().into(),
-            ),
+            )
+            .into(),
         ),
         compression,
     };
@@ -206,12 +213,14 @@ fn azure_blob_build_request_with_uuid() {
         container_name,
         blob_time_format,
         blob_append_uuid,
+        blob_extension: compression.extension(),
         encoder: (
             Default::default(),
             Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::default().into(),
                 TextSerializerConfig::default().build().into(),
-            ),
+            )
+            .into(),
         ),
         compression,
     };
@@ -1,7 +1,9 @@
 //! Configuration for the `Clickhouse` sink.
 
 use super::{
+    encoder::ClickhouseEncoder,
     request_builder::ClickhouseRequestBuilder,
+    schema::{Schema, SchemaError},
     service::{ClickhouseRetryLogic, ClickhouseServiceRequestBuilder},
     sink::{ClickhouseSink, PartitionKey},
 };
@@ -14,7 +16,7 @@ use crate::{
 };
 use http::{Request, StatusCode, Uri};
 use hyper::Body;
-use std::fmt;
+use std::{fmt, sync::Arc};
 use vector_lib::codecs::{encoding::Framer, JsonSerializerConfig, NewlineDelimitedEncoderConfig};
 
 /// Data format.
@@ -37,6 +39,39 @@ pub enum Format {
 
     /// JSONAsString.
     JsonAsString,
+
+    /// RowBinary.
+    ///
+    /// Rows are encoded from the schema of the table, which is fetched when the sink starts, so
+    /// the table can't be templated.
+    RowBinary,
+
+    /// Native.
+    ///
+    /// Batches are encoded column by column from the schema of the table, which is fetched when
+    /// the sink starts, so the table can't be templated.
+    Native,
+}
+
+impl Format {
+    /// Whether the format is encoded from the schema of the table.
+    pub(super) const fn is_binary(self) -> bool {
+        matches!(self, Format::RowBinary | Format::Native)
+    }
+
+    /// The name of the format in `INSERT` queries.
+    ///
+    /// Rows are sent as `RowBinaryWithDefaults`, which lets the columns of missing fields take
+    /// their default.
+    pub(super) const fn insert_name(self) -> &'static str {
+        match self {
+            Format::JsonEachRow => "JSONEachRow",
+            Format::JsonAsObject => "JSONAsObject",
+            Format::JsonAsString => "JSONAsString",
+            Format::RowBinary => "RowBinaryWithDefaults",
+            Format::Native => "Native",
+        }
+    }
 }
 
 impl fmt::Display for Format {
@@ -45,6 +80,8 @@ impl fmt::Display for Format {
             Format::JsonEachRow => write!(f, "JSONEachRow"),
             Format::JsonAsObject => write!(f, "JSONAsObject"),
             Format::JsonAsString => write!(f, "JSONAsString"),
+            Format::RowBinary => write!(f, "RowBinary"),
+            Format::Native => write!(f, "Native"),
         }
     }
 }
@@ -68,6 +105,9 @@ pub struct ClickhouseConfig {
     pub database: Option<Template>,
 
     /// The format to parse input data.
+    ///
+    /// With the binary formats, fields are mapped to the columns of the same name, and events with
+    /// fields that can't be converted to the type of their column are rejected.
     #[serde(default)]
     pub format: Format,
 
@@ -128,6 +168,47 @@ impl SinkConfig for ClickhouseConfig {
 
         let client = HttpClient::new(tls_settings, &cx.proxy)?;
 
+        let database = self.database.clone().unwrap_or_else(|| {
+            "default"
+                .try_into()
+                .expect("'default' should be a valid template")
+        });
+
+        let (encoder, columns) = if self.format.is_binary() {
+            if database.is_dynamic() || self.table.is_dynamic() {
+                return Err(SchemaError::DynamicTable {
+                    format: self.format,
+                }
+                .into());
+            }
+            let schema = Arc::new(
+                Schema::fetch(
+                    &client,
+                    &endpoint,
+                    auth.as_ref(),
+                    database.get_ref(),
+                    self.table.get_ref(),
+                    self.format,
+                )
+                .await?,
+            );
+            let columns = schema.column_list();
+            let encoder = match self.format {
+                Format::Native => ClickhouseEncoder::Native(self.encoding.clone(), schema),
+                _ => ClickhouseEncoder::RowBinary(self.encoding.clone(), schema),
+            };
+            (encoder, Some(columns))
+        } else {
+            let encoder = ClickhouseEncoder::Json((
+                self.encoding.clone(),
+                Encoder::<Framer>::new(
+                    NewlineDelimitedEncoderConfig.build().into(),
+                    JsonSerializerConfig::default().build().into(),
+                ),
+            ));
+            (encoder, None)
+        };
+
         let clickhouse_service_request_builder = ClickhouseServiceRequestBuilder {
             auth: auth.clone(),
             endpoint: endpoint.clone(),
@@ -135,6 +216,7 @@ impl SinkConfig for ClickhouseConfig {
             date_time_best_effort: self.date_time_best_effort,
             insert_random_shard: self.insert_random_shard,
             compression: self.compression,
+            columns,
         };
 
         let service: HttpService<ClickhouseServiceRequestBuilder, PartitionKey> =
@@ -148,21 +230,9 @@ impl SinkConfig for ClickhouseConfig {
 
         let batch_settings = self.batch.into_batcher_settings()?;
 
-        let database = self.database.clone().unwrap_or_else(|| {
-            "default"
-                .try_into()
-                .expect("'default' should be a valid template")
-        });
-
         let request_builder = ClickhouseRequestBuilder {
             compression: self.compression,
-            encoding: (
-                self.encoding.clone(),
-                Encoder::<Framer>::new(
-                    NewlineDelimitedEncoderConfig.build().into(),
-                    JsonSerializerConfig::default().build().into(),
-                ),
-            ),
+            encoding: encoder,
         };
 
         let sink = ClickhouseSink::new(
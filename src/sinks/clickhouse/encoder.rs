@@ -0,0 +1,1029 @@
+//! Encoding for the `Clickhouse` sink.
+//!
+//! Events are encoded as newline-delimited JSON for the JSON formats. For the binary formats, the
+//! fields are encoded into the columns of the table schema, coercing their values into the type of
+//! their column where they can be, such as strings into numbers or timestamps.
+//!
+//! Missing fields, and nulls in columns that aren't `Nullable`, are left for ClickHouse to fill in
+//! with the default of their column. Rows are sent as `RowBinaryWithDefaults`, which flags each
+//! value that is left out. `Native` blocks can only leave out whole columns, so rows are grouped
+//! into a block for each set of columns they have values for.
+
+use std::{
+    borrow::Cow,
+    io,
+    net::{IpAddr, Ipv4Addr},
+    sync::Arc,
+};
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use chrono_tz::Tz;
+use snafu::Snafu;
+use uuid::Uuid;
+use vector_lib::{codecs::encoding::Framer, event::ObjectMap};
+
+use super::schema::{Column, ColumnType, EnumValues, Schema};
+use crate::{internal_events::ClickhouseUnmappableFieldError, sinks::prelude::*};
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Can't encode {} as `{}`.", value, ty))]
+pub(super) struct EncodingError {
+    value: String,
+    ty: String,
+}
+
+fn mismatch(ty: &ColumnType, value: &Value) -> EncodingError {
+    let value = match value {
+        Value::Object(_) => "an object".to_owned(),
+        Value::Array(_) => "an array".to_owned(),
+        Value::Bytes(bytes) if bytes.len() > 64 => "a long string".to_owned(),
+        value => value.to_string(),
+    };
+    EncodingError {
+        value,
+        ty: ty.to_string(),
+    }
+}
+
+/// Encodes the payloads of requests, in either a JSON or a binary format.
+pub(super) enum ClickhouseEncoder {
+    Json((Transformer, Encoder<Framer>)),
+    RowBinary(Transformer, Arc<Schema>),
+    Native(Transformer, Arc<Schema>),
+}
+
+/// The events of a request.
+///
+/// For the binary formats, the events are encoded as the request is split from its batch, so that
+/// the events that can't be encoded are rejected before their finalizers are taken, rather than
+/// failing the request of the other events.
+pub(super) enum ClickhouseEvents {
+    Json(Vec<Event>),
+    Binary {
+        events: Vec<Event>,
+        payload: Vec<u8>,
+    },
+}
+
+impl ClickhouseEvents {
+    pub(super) fn events(&self) -> &[Event] {
+        match self {
+            Self::Json(events) | Self::Binary { events, .. } => events,
+        }
+    }
+
+    pub(super) fn events_mut(&mut self) -> &mut Vec<Event> {
+        match self {
+            Self::Json(events) | Self::Binary { events, .. } => events,
+        }
+    }
+}
+
+impl ClickhouseEncoder {
+    /// Prepares the events of a batch to be encoded, encoding them right away for the binary
+    /// formats.
+    pub(super) fn prepare(&self, mut events: Vec<Event>) -> ClickhouseEvents {
+        let (transformer, schema) = match self {
+            Self::Json(_) => return ClickhouseEvents::Json(events),
+            Self::RowBinary(transformer, schema) | Self::Native(transformer, schema) => {
+                (transformer, schema)
+            }
+        };
+
+        for event in &mut events {
+            transformer.transform(event);
+        }
+        let (events, payload) = if let Self::Native(..) = self {
+            encode_blocks(schema, events)
+        } else {
+            encode_rows(schema, events)
+        };
+        ClickhouseEvents::Binary { events, payload }
+    }
+}
+
+impl encoding::Encoder<ClickhouseEvents> for ClickhouseEncoder {
+    fn encode_input(
+        &self,
+        input: ClickhouseEvents,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let (events, payload) = match (self, input) {
+            (Self::Json(encoder), ClickhouseEvents::Json(events)) => {
+                return encoding::Encoder::encode_input(encoder, events, writer);
+            }
+            (_, ClickhouseEvents::Binary { events, payload }) => (events, payload),
+            (_, ClickhouseEvents::Json(_)) => {
+                unreachable!("events are prepared by the encoder of the sink")
+            }
+        };
+
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        for event in &events {
+            byte_size.add_event(event, event.estimated_json_encoded_size_of());
+        }
+
+        write_all(writer, events.len(), &payload)?;
+        Ok((payload.len(), byte_size))
+    }
+}
+
+/// Rejects an event with a field that can't be encoded into its column.
+fn reject(event: Event, column: &Column, error: EncodingError) {
+    emit!(ClickhouseUnmappableFieldError {
+        column: &column.name,
+        error,
+    });
+    event.metadata().update_status(EventStatus::Rejected);
+}
+
+/// Whether the value of a column is left for ClickHouse to fill in with the default of the column,
+/// which is the case for missing fields, and for nulls in columns that aren't `Nullable`.
+fn is_default(ty: &ColumnType, value: Option<&Value>) -> bool {
+    match value {
+        None => true,
+        Some(Value::Null) => !ty.is_nullable(),
+        Some(_) => false,
+    }
+}
+
+/// Encodes events as rows of the `RowBinaryWithDefaults` format, rejecting those that can't be.
+fn encode_rows(schema: &Schema, events: Vec<Event>) -> (Vec<Event>, Vec<u8>) {
+    let mut buf = Vec::new();
+    let mut encoded = Vec::with_capacity(events.len());
+    for event in events {
+        let start = buf.len();
+        match encode_row(schema, event.as_log(), &mut buf) {
+            Ok(()) => encoded.push(event),
+            Err((column, error)) => {
+                buf.truncate(start);
+                reject(event, column, error);
+            }
+        }
+    }
+    (encoded, buf)
+}
+
+/// Encodes an event as a row of the `RowBinaryWithDefaults` format, where each value is preceded
+/// by whether it is left out for the default of its column.
+fn encode_row<'a>(
+    schema: &'a Schema,
+    log: &LogEvent,
+    buf: &mut Vec<u8>,
+) -> Result<(), (&'a Column, EncodingError)> {
+    for column in &schema.columns {
+        let value = column.get(log);
+        if is_default(&column.ty, value) {
+            buf.push(1);
+        } else {
+            buf.push(0);
+            write_value(&column.ty, value, buf).map_err(|error| (column, error))?;
+        }
+    }
+    Ok(())
+}
+
+/// Encodes events as blocks of the `Native` format, rejecting those that can't be.
+///
+/// Rows are grouped into a block for each set of columns they have values for, and the other
+/// columns are left out of the block, for ClickHouse to fill in with their default.
+fn encode_blocks(schema: &Schema, events: Vec<Event>) -> (Vec<Event>, Vec<u8>) {
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut encoded = Vec::with_capacity(events.len());
+    for event in events {
+        let values = schema
+            .columns
+            .iter()
+            .map(|column| column.get(event.as_log()))
+            .collect::<Vec<_>>();
+        let mut included = schema
+            .columns
+            .iter()
+            .zip(&values)
+            .map(|(column, value)| !is_default(&column.ty, *value))
+            .collect::<Vec<_>>();
+        // A block without columns has no rows either, so rows without any values keep the first
+        // column, with the default of its type.
+        if !included.contains(&true) {
+            included[0] = true;
+        }
+
+        let block = match blocks.iter().position(|block| block.included == included) {
+            Some(index) => &mut blocks[index],
+            None => {
+                blocks.push(Block::new(schema, included));
+                blocks.last_mut().expect("a block was just added")
+            }
+        };
+        match block.push(schema, &values) {
+            Ok(()) => encoded.push(event),
+            Err((column, error)) => reject(event, column, error),
+        }
+    }
+
+    let mut buf = Vec::new();
+    for block in blocks {
+        block.write(schema, &mut buf);
+    }
+    (encoded, buf)
+}
+
+/// A block of the `Native` format, with the columns its rows have values for.
+struct Block {
+    included: Vec<bool>,
+    columns: Vec<Option<ColumnBuilder>>,
+    rows: usize,
+    /// The lengths of the buffers of the columns before the row being pushed, to roll back to if
+    /// it can't be encoded.
+    lengths: Vec<usize>,
+}
+
+impl Block {
+    fn new(schema: &Schema, included: Vec<bool>) -> Self {
+        let columns = schema
+            .columns
+            .iter()
+            .zip(&included)
+            .map(|(column, included)| included.then(|| ColumnBuilder::new(&column.ty)))
+            .collect();
+        Self {
+            included,
+            columns,
+            rows: 0,
+            lengths: Vec::new(),
+        }
+    }
+
+    fn push<'a>(
+        &mut self,
+        schema: &'a Schema,
+        values: &[Option<&Value>],
+    ) -> Result<(), (&'a Column, EncodingError)> {
+        self.lengths.clear();
+        for builder in self.columns.iter().flatten() {
+            builder.lengths(&mut self.lengths);
+        }
+
+        let result = schema
+            .columns
+            .iter()
+            .zip(&mut self.columns)
+            .zip(values)
+            .try_for_each(|((column, builder), value)| match builder {
+                Some(builder) => builder.push(*value).map_err(|error| (column, error)),
+                None => Ok(()),
+            });
+        match result {
+            Ok(()) => self.rows += 1,
+            Err(_) => {
+                let mut lengths = self.lengths.iter().copied();
+                for builder in self.columns.iter_mut().flatten() {
+                    builder.truncate(&mut lengths);
+                }
+            }
+        }
+        result
+    }
+
+    fn write(self, schema: &Schema, buf: &mut Vec<u8>) {
+        if self.rows == 0 {
+            return;
+        }
+        let columns = schema
+            .columns
+            .iter()
+            .zip(self.columns)
+            .filter_map(|(column, builder)| Some((column, builder?)))
+            .collect::<Vec<_>>();
+
+        write_varint(columns.len() as u64, buf);
+        write_varint(self.rows as u64, buf);
+        for (column, builder) in columns {
+            write_string(column.name.as_bytes(), buf);
+            // `LowCardinality` columns are sent as their inner type, which the query allows with
+            // `low_cardinality_allow_in_native_format=0`.
+            write_string(
+                column.ty.without_low_cardinality().to_string().as_bytes(),
+                buf,
+            );
+            builder.write(buf);
+        }
+    }
+}
+
+/// The values of a column of a `Native` block, built row by row, as each of the types the column
+/// is made of is written separately.
+enum ColumnBuilder {
+    /// A type that isn't made of other types, with its values written one after the other.
+    Scalar(ColumnType, Vec<u8>),
+    /// The null map, followed by the values, with nulls written as the default of the inner type.
+    Nullable(Vec<u8>, Box<ColumnBuilder>),
+    /// The offset at which each array ends in the items, followed by the items.
+    Array {
+        ty: ColumnType,
+        offsets: Vec<u64>,
+        items: Box<ColumnBuilder>,
+    },
+    /// The offset at which each map ends in the entries, followed by the keys, then the values.
+    Map {
+        ty: ColumnType,
+        offsets: Vec<u64>,
+        keys: Box<ColumnBuilder>,
+        values: Box<ColumnBuilder>,
+    },
+}
+
+impl ColumnBuilder {
+    fn new(ty: &ColumnType) -> Self {
+        match ty {
+            ColumnType::Nullable(inner) => Self::Nullable(Vec::new(), Box::new(Self::new(inner))),
+            ColumnType::LowCardinality(inner) => Self::new(inner),
+            ColumnType::Array(inner) => Self::Array {
+                ty: ty.clone(),
+                offsets: Vec::new(),
+                items: Box::new(Self::new(inner)),
+            },
+            ColumnType::Map(key_ty, value_ty) => Self::Map {
+                ty: ty.clone(),
+                offsets: Vec::new(),
+                keys: Box::new(Self::new(key_ty)),
+                values: Box::new(Self::new(value_ty)),
+            },
+            ty => Self::Scalar(ty.clone(), Vec::new()),
+        }
+    }
+
+    fn push(&mut self, value: Option<&Value>) -> Result<(), EncodingError> {
+        let value = non_null(value);
+        match self {
+            Self::Scalar(ty, buf) => write_scalar(ty, value, buf),
+            Self::Nullable(nulls, inner) => {
+                nulls.push(u8::from(value.is_none()));
+                inner.push(value)
+            }
+            Self::Array { ty, offsets, items } => {
+                let array = as_array(ty, value)?;
+                array.iter().try_for_each(|item| items.push(Some(item)))?;
+                offsets.push(offsets.last().copied().unwrap_or_default() + array.len() as u64);
+                Ok(())
+            }
+            Self::Map {
+                ty,
+                offsets,
+                keys,
+                values,
+            } => {
+                let len = match as_map(ty, value)? {
+                    Some(map) => {
+                        for (key, value) in map {
+                            keys.push(Some(&Value::from(key.as_str())))?;
+                            values.push(Some(value))?;
+                        }
+                        map.len() as u64
+                    }
+                    None => 0,
+                };
+                offsets.push(offsets.last().copied().unwrap_or_default() + len);
+                Ok(())
+            }
+        }
+    }
+
+    /// Collects the lengths of the buffers of the column.
+    fn lengths(&self, lengths: &mut Vec<usize>) {
+        match self {
+            Self::Scalar(_, buf) | Self::Nullable(buf, _) => lengths.push(buf.len()),
+            Self::Array { offsets, .. } | Self::Map { offsets, .. } => lengths.push(offsets.len()),
+        }
+        match self {
+            Self::Scalar(..) => {}
+            Self::Nullable(_, inner) | Self::Array { items: inner, .. } => inner.lengths(lengths),
+            Self::Map { keys, values, .. } => {
+                keys.lengths(lengths);
+                values.lengths(lengths);
+            }
+        }
+    }
+
+    /// Truncates the buffers of the column to the lengths collected by [`Self::lengths`].
+    fn truncate(&mut self, lengths: &mut impl Iterator<Item = usize>) {
+        let length = lengths.next().expect("a length for every buffer");
+        match self {
+            Self::Scalar(_, buf) => buf.truncate(length),
+            Self::Nullable(nulls, inner) => {
+                nulls.truncate(length);
+                inner.truncate(lengths);
+            }
+            Self::Array { offsets, items, .. } => {
+                offsets.truncate(length);
+                items.truncate(lengths);
+            }
+            Self::Map {
+                offsets,
+                keys,
+                values,
+                ..
+            } => {
+                offsets.truncate(length);
+                keys.truncate(lengths);
+                values.truncate(lengths);
+            }
+        }
+    }
+
+    fn write(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Scalar(_, values) => buf.extend_from_slice(values),
+            Self::Nullable(nulls, inner) => {
+                buf.extend_from_slice(nulls);
+                inner.write(buf);
+            }
+            Self::Array { offsets, items, .. } => {
+                offsets
+                    .iter()
+                    .for_each(|offset| buf.extend(offset.to_le_bytes()));
+                items.write(buf);
+            }
+            Self::Map {
+                offsets,
+                keys,
+                values,
+                ..
+            } => {
+                offsets
+                    .iter()
+                    .for_each(|offset| buf.extend(offset.to_le_bytes()));
+                keys.write(buf);
+                values.write(buf);
+            }
+        }
+    }
+}
+
+fn non_null(value: Option<&Value>) -> Option<&Value> {
+    value.filter(|value| !value.is_null())
+}
+
+/// Writes a value in the `RowBinary` format.
+fn write_value(
+    ty: &ColumnType,
+    value: Option<&Value>,
+    buf: &mut Vec<u8>,
+) -> Result<(), EncodingError> {
+    let value = non_null(value);
+    match ty {
+        ColumnType::Nullable(inner) => match value {
+            None => {
+                buf.push(1);
+                Ok(())
+            }
+            Some(value) => {
+                buf.push(0);
+                write_value(inner, Some(value), buf)
+            }
+        },
+        ColumnType::LowCardinality(inner) => write_value(inner, value, buf),
+        ColumnType::Array(inner) => {
+            let items = as_array(ty, value)?;
+            write_varint(items.len() as u64, buf);
+            items
+                .iter()
+                .try_for_each(|item| write_value(inner, Some(item), buf))
+        }
+        ColumnType::Map(key_ty, value_ty) => {
+            let Some(map) = as_map(ty, value)? else {
+                write_varint(0, buf);
+                return Ok(());
+            };
+            write_varint(map.len() as u64, buf);
+            for (key, value) in map {
+                write_value(key_ty, Some(&Value::from(key.as_str())), buf)?;
+                write_value(value_ty, Some(value), buf)?;
+            }
+            Ok(())
+        }
+        ty => write_scalar(ty, value, buf),
+    }
+}
+
+fn as_array<'a>(ty: &ColumnType, value: Option<&'a Value>) -> Result<&'a [Value], EncodingError> {
+    match value {
+        None => Ok(&[]),
+        Some(Value::Array(items)) => Ok(items),
+        Some(value) => Err(mismatch(ty, value)),
+    }
+}
+
+fn as_map<'a>(
+    ty: &ColumnType,
+    value: Option<&'a Value>,
+) -> Result<Option<&'a ObjectMap>, EncodingError> {
+    match value {
+        None => Ok(None),
+        Some(Value::Object(map)) => Ok(Some(map)),
+        Some(value) => Err(mismatch(ty, value)),
+    }
+}
+
+/// Writes a value of a type that isn't made of other types, which is the same in both formats.
+fn write_scalar(
+    ty: &ColumnType,
+    value: Option<&Value>,
+    buf: &mut Vec<u8>,
+) -> Result<(), EncodingError> {
+    let Some(value) = non_null(value) else {
+        write_default(ty, buf);
+        return Ok(());
+    };
+    let mismatch = || mismatch(ty, value);
+
+    match ty {
+        ColumnType::UInt8 => buf.push(to_int::<u8>(ty, value)?),
+        ColumnType::UInt16 => buf.extend(to_int::<u16>(ty, value)?.to_le_bytes()),
+        ColumnType::UInt32 => buf.extend(to_int::<u32>(ty, value)?.to_le_bytes()),
+        ColumnType::UInt64 => buf.extend(to_int::<u64>(ty, value)?.to_le_bytes()),
+        ColumnType::UInt128 => buf.extend(to_int::<u128>(ty, value)?.to_le_bytes()),
+        ColumnType::Int8 => buf.extend(to_int::<i8>(ty, value)?.to_le_bytes()),
+        ColumnType::Int16 => buf.extend(to_int::<i16>(ty, value)?.to_le_bytes()),
+        ColumnType::Int32 => buf.extend(to_int::<i32>(ty, value)?.to_le_bytes()),
+        ColumnType::Int64 => buf.extend(to_int::<i64>(ty, value)?.to_le_bytes()),
+        ColumnType::Int128 => buf.extend(to_int::<i128>(ty, value)?.to_le_bytes()),
+        ColumnType::Float32 => {
+            buf.extend((to_float(value).ok_or_else(mismatch)? as f32).to_le_bytes())
+        }
+        ColumnType::Float64 => buf.extend(to_float(value).ok_or_else(mismatch)?.to_le_bytes()),
+        ColumnType::Bool => buf.push(u8::from(to_bool(value).ok_or_else(mismatch)?)),
+        ColumnType::String => write_string(&to_bytes(value), buf),
+        ColumnType::FixedString(length) => {
+            let bytes = to_bytes(value);
+            if bytes.len() > *length {
+                return Err(mismatch());
+            }
+            buf.extend_from_slice(&bytes);
+            write_zeros(length - bytes.len(), buf);
+        }
+        ColumnType::Date => {
+            let days = to_days(value).and_then(|days| u16::try_from(days).ok());
+            buf.extend(days.ok_or_else(mismatch)?.to_le_bytes())
+        }
+        ColumnType::Date32 => {
+            let days = to_days(value).and_then(|days| i32::try_from(days).ok());
+            buf.extend(days.ok_or_else(mismatch)?.to_le_bytes())
+        }
+        ColumnType::DateTime(time_zone) => {
+            let tz = time_zone.as_ref().map(|time_zone| &time_zone.tz);
+            let seconds = to_timestamp(value, tz)
+                .and_then(|timestamp| u32::try_from(timestamp.timestamp()).ok());
+            buf.extend(seconds.ok_or_else(mismatch)?.to_le_bytes())
+        }
+        ColumnType::DateTime64(precision, time_zone) => {
+            let tz = time_zone.as_ref().map(|time_zone| &time_zone.tz);
+            let ticks = to_timestamp(value, tz).and_then(|timestamp| {
+                let subsec_ticks = timestamp.timestamp_subsec_nanos() / 10_u32.pow(9 - precision);
+                timestamp
+                    .timestamp()
+                    .checked_mul(10_i64.pow(*precision))?
+                    .checked_add(i64::from(subsec_ticks))
+            });
+            buf.extend(ticks.ok_or_else(mismatch)?.to_le_bytes())
+        }
+        ColumnType::Uuid => {
+            let uuid = as_str(value).and_then(|uuid| Uuid::parse_str(uuid).ok());
+            let (high, low) = uuid.ok_or_else(mismatch)?.as_u64_pair();
+            buf.extend(high.to_le_bytes());
+            buf.extend(low.to_le_bytes());
+        }
+        ColumnType::Ipv4 => {
+            let ip = as_str(value).and_then(|ip| ip.parse::<Ipv4Addr>().ok());
+            buf.extend(u32::from(ip.ok_or_else(mismatch)?).to_le_bytes())
+        }
+        ColumnType::Ipv6 => {
+            let ip = match as_str(value).and_then(|ip| ip.parse::<IpAddr>().ok()) {
+                Some(IpAddr::V4(ip)) => ip.to_ipv6_mapped(),
+                Some(IpAddr::V6(ip)) => ip,
+                None => return Err(mismatch()),
+            };
+            buf.extend(ip.octets())
+        }
+        ColumnType::Enum8(values) => {
+            let number = to_enum(values, value).and_then(|number| i8::try_from(number).ok());
+            buf.extend(number.ok_or_else(mismatch)?.to_le_bytes())
+        }
+        ColumnType::Enum16(values) => {
+            buf.extend(to_enum(values, value).ok_or_else(mismatch)?.to_le_bytes())
+        }
+        ColumnType::Nullable(_)
+        | ColumnType::LowCardinality(_)
+        | ColumnType::Array(_)
+        | ColumnType::Map(..) => return write_value(ty, Some(value), buf),
+    }
+    Ok(())
+}
+
+/// Writes the default value of a type, for missing fields and nulls.
+fn write_default(ty: &ColumnType, buf: &mut Vec<u8>) {
+    match ty {
+        ColumnType::UInt8 | ColumnType::Int8 | ColumnType::Bool => write_zeros(1, buf),
+        ColumnType::UInt16 | ColumnType::Int16 | ColumnType::Date => write_zeros(2, buf),
+        ColumnType::UInt32
+        | ColumnType::Int32
+        | ColumnType::Float32
+        | ColumnType::Date32
+        | ColumnType::DateTime(_)
+        | ColumnType::Ipv4 => write_zeros(4, buf),
+        ColumnType::UInt64
+        | ColumnType::Int64
+        | ColumnType::Float64
+        | ColumnType::DateTime64(..) => write_zeros(8, buf),
+        ColumnType::UInt128 | ColumnType::Int128 | ColumnType::Uuid | ColumnType::Ipv6 => {
+            write_zeros(16, buf)
+        }
+        ColumnType::FixedString(length) => write_zeros(*length, buf),
+        // The default of an enum is its first value.
+        ColumnType::Enum8(values) => {
+            let number = i8::try_from(values.values[0].1).unwrap_or_default();
+            buf.extend(number.to_le_bytes())
+        }
+        ColumnType::Enum16(values) => buf.extend(values.values[0].1.to_le_bytes()),
+        ColumnType::Nullable(_) => buf.push(1),
+        ColumnType::LowCardinality(inner) => write_default(inner, buf),
+        // Empty strings, arrays, and maps are all written as a length of zero.
+        ColumnType::String | ColumnType::Array(_) | ColumnType::Map(..) => write_varint(0, buf),
+    }
+}
+
+fn write_zeros(count: usize, buf: &mut Vec<u8>) {
+    buf.resize(buf.len() + count, 0);
+}
+
+/// Writes an unsigned LEB128 number, as used for lengths.
+fn write_varint(mut number: u64, buf: &mut Vec<u8>) {
+    while number >= 0x80 {
+        buf.push((number as u8) | 0x80);
+        number >>= 7;
+    }
+    buf.push(number as u8);
+}
+
+fn write_string(bytes: &[u8], buf: &mut Vec<u8>) {
+    write_varint(bytes.len() as u64, buf);
+    buf.extend_from_slice(bytes);
+}
+
+fn as_str(value: &Value) -> Option<&str> {
+    match value {
+        Value::Bytes(bytes) => std::str::from_utf8(bytes).ok().map(str::trim),
+        _ => None,
+    }
+}
+
+fn to_int<T>(ty: &ColumnType, value: &Value) -> Result<T, EncodingError>
+where
+    T: TryFrom<i64> + TryFrom<u64> + std::str::FromStr,
+{
+    let number = match value {
+        Value::Integer(number) => T::try_from(*number).ok(),
+        Value::Boolean(boolean) => T::try_from(u64::from(*boolean)).ok(),
+        Value::Float(number) if number.fract() == 0.0 => T::try_from(number.into_inner() as i64)
+            .ok()
+            .filter(|_| number.abs() < i64::MAX as f64),
+        value => as_str(value).and_then(|number| number.parse().ok()),
+    };
+    number.ok_or_else(|| mismatch(ty, value))
+}
+
+fn to_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(number) => Some(*number as f64),
+        Value::Float(number) => Some(number.into_inner()),
+        value => as_str(value).and_then(|number| number.parse().ok()),
+    }
+}
+
+fn to_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Boolean(boolean) => Some(*boolean),
+        Value::Integer(0) => Some(false),
+        Value::Integer(1) => Some(true),
+        value => match as_str(value)? {
+            "true" | "1" => Some(true),
+            "false" | "0" => Some(false),
+            _ => None,
+        },
+    }
+}
+
+/// Gets the bytes of a value for `String` columns, where objects and arrays are written as JSON.
+fn to_bytes(value: &Value) -> Cow<'_, [u8]> {
+    match value {
+        Value::Bytes(bytes) => Cow::Borrowed(bytes),
+        Value::Timestamp(timestamp) => Cow::Owned(
+            timestamp
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                .into_bytes(),
+        ),
+        Value::Object(_) | Value::Array(_) => {
+            Cow::Owned(serde_json::to_vec(value).expect("values are valid JSON"))
+        }
+        value => Cow::Owned(value.to_string_lossy().into_owned().into_bytes()),
+    }
+}
+
+/// Gets a timestamp from a timestamp, a number of seconds since the epoch, or a string in either
+/// the RFC 3339 format or the `YYYY-MM-DD hh:mm:ss` format of ClickHouse.
+///
+/// Strings without an offset are in the time zone of the column, or in UTC for columns without
+/// one.
+fn to_timestamp(value: &Value, tz: Option<&Tz>) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Timestamp(timestamp) => Some(*timestamp),
+        Value::Integer(seconds) => Utc.timestamp_opt(*seconds, 0).single(),
+        Value::Float(seconds) => {
+            let floor = seconds.floor();
+            let nanos = ((seconds.into_inner() - floor) * 1e9) as u32;
+            Utc.timestamp_opt(floor as i64, nanos).single()
+        }
+        value => {
+            let timestamp = as_str(value)?;
+            if let Ok(timestamp) = DateTime::parse_from_rfc3339(timestamp) {
+                return Some(timestamp.with_timezone(&Utc));
+            }
+            let naive = NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S%.f").ok()?;
+            match tz {
+                // Date-times skipped by a change of offset don't exist, and those repeated by one
+                // are taken at their first occurrence.
+                Some(tz) => tz
+                    .from_local_datetime(&naive)
+                    .earliest()
+                    .map(|timestamp| timestamp.with_timezone(&Utc)),
+                None => Some(Utc.from_utc_datetime(&naive)),
+            }
+        }
+    }
+}
+
+/// Gets the number of days since the epoch from a number of days, a `YYYY-MM-DD` string, or
+/// anything that [`to_timestamp`] accepts.
+fn to_days(value: &Value) -> Option<i64> {
+    let date = match value {
+        Value::Integer(days) => return Some(*days),
+        value => as_str(value)
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .or_else(|| to_timestamp(value, None).map(|timestamp| timestamp.date_naive()))?,
+    };
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).expect("the epoch is a valid date");
+    Some(date.signed_duration_since(epoch).num_days())
+}
+
+/// Gets the number of an enum value, from either its name or its number.
+fn to_enum(values: &EnumValues, value: &Value) -> Option<i16> {
+    match value {
+        Value::Integer(number) => values
+            .values
+            .iter()
+            .map(|(_, value)| *value)
+            .find(|value| i64::from(*value) == *number),
+        value => {
+            let name = as_str(value)?;
+            values
+                .values
+                .iter()
+                .find(|(value, _)| value == name)
+                .map(|(_, value)| *value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(columns: &[(&str, &str)]) -> Schema {
+        Schema {
+            columns: columns
+                .iter()
+                .map(|(name, ty)| Column::new(name.to_string(), ColumnType::parse(ty).unwrap()))
+                .collect(),
+        }
+    }
+
+    fn row(schema: &Schema, log: &LogEvent) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode_row(schema, log, &mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn encode_row_binary() {
+        let schema = schema(&[
+            ("id", "UInt32"),
+            ("level", "LowCardinality(String)"),
+            ("status", "Enum8('ok' = 1, 'error' = 2)"),
+            ("latency", "Nullable(Float32)"),
+            ("tags", "Array(String)"),
+            ("attributes", "Map(String, Int16)"),
+        ]);
+
+        let mut log = LogEvent::default();
+        log.insert("id", "42");
+        log.insert("level", "info");
+        log.insert("status", "error");
+        log.insert("latency", Value::Null);
+        log.insert("tags", vec!["a", "bc"]);
+        log.insert("attributes.retries", 3);
+
+        assert_eq!(
+            row(&schema, &log),
+            [
+                &[0, 42, 0, 0, 0][..],
+                &[0, 4, b'i', b'n', b'f', b'o'],
+                &[0, 2],
+                &[0, 1],
+                &[0, 2, 1, b'a', 2, b'b', b'c'],
+                &[0, 1, 7, b'r', b'e', b't', b'r', b'i', b'e', b's', 3, 0],
+            ]
+            .concat()
+        );
+
+        // Missing fields, and nulls in columns that aren't `Nullable`, are left for the default of
+        // their column.
+        let mut log = LogEvent::default();
+        log.insert("id", Value::Null);
+        assert_eq!(row(&schema, &log), [1, 1, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn encode_row_binary_timestamps() {
+        let schema = schema(&[
+            ("date", "Date"),
+            ("time", "DateTime('UTC')"),
+            ("precise_time", "DateTime64(3)"),
+        ]);
+        let timestamp = Utc.timestamp_opt(86_400 * 2 + 1, 5_000_000).unwrap();
+
+        let mut log = LogEvent::default();
+        log.insert("date", "1970-01-03");
+        log.insert("time", timestamp);
+        log.insert("precise_time", "1970-01-03T00:00:01.005Z");
+
+        assert_eq!(
+            row(&schema, &log),
+            [
+                &[0][..],
+                &2_u16.to_le_bytes(),
+                &[0],
+                &172_801_u32.to_le_bytes(),
+                &[0],
+                &172_801_005_i64.to_le_bytes(),
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn naive_date_times_are_in_the_column_time_zone() {
+        let value = Value::from("1970-01-03 01:00:01");
+
+        let mut buf = Vec::new();
+        let ty = ColumnType::parse("DateTime('Europe/Paris')").unwrap();
+        write_value(&ty, Some(&value), &mut buf).unwrap();
+        assert_eq!(buf, 172_801_u32.to_le_bytes());
+
+        let mut buf = Vec::new();
+        let ty = ColumnType::parse("DateTime64(3, 'Europe/Paris')").unwrap();
+        write_value(&ty, Some(&value), &mut buf).unwrap();
+        assert_eq!(buf, 172_801_000_i64.to_le_bytes());
+
+        // Offsets win over the time zone of the column, and columns without one are in UTC.
+        let mut buf = Vec::new();
+        let ty = ColumnType::parse("DateTime('Europe/Paris')").unwrap();
+        write_value(&ty, Some(&Value::from("1970-01-03T00:00:01Z")), &mut buf).unwrap();
+        let ty = ColumnType::parse("DateTime").unwrap();
+        write_value(&ty, Some(&Value::from("1970-01-03 00:00:01")), &mut buf).unwrap();
+        assert_eq!(
+            buf,
+            [172_801_u32.to_le_bytes(), 172_801_u32.to_le_bytes()].concat()
+        );
+    }
+
+    #[test]
+    fn encode_native() {
+        let schema = schema(&[
+            ("name", "LowCardinality(Nullable(String))"),
+            ("values", "Array(UInt8)"),
+            ("count", "UInt8"),
+        ]);
+
+        let mut first = LogEvent::default();
+        first.insert("name", "a");
+        first.insert("values", vec![1, 2]);
+        let mut second = LogEvent::default();
+        second.insert("name", Value::Null);
+        second.insert("values", vec![3]);
+        let mut third = LogEvent::default();
+        third.insert("count", 4);
+
+        let (events, payload) =
+            encode_blocks(&schema, vec![first.into(), second.into(), third.into()]);
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            payload,
+            [
+                // The rows with a name and values, leaving out the count.
+                &[2, 2][..],
+                &[4, b'n', b'a', b'm', b'e'],
+                &[16],
+                b"Nullable(String)",
+                &[0, 1, 1, b'a', 0],
+                &[6, b'v', b'a', b'l', b'u', b'e', b's'],
+                &[12],
+                b"Array(UInt8)",
+                &2_u64.to_le_bytes(),
+                &3_u64.to_le_bytes(),
+                &[1, 2, 3],
+                // The row with only a count.
+                &[1, 1],
+                &[5, b'c', b'o', b'u', b'n', b't'],
+                &[5],
+                b"UInt8",
+                &[4],
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn encode_native_rejects_unmappable_events() {
+        let schema = schema(&[("values", "Array(UInt8)"), ("name", "String")]);
+
+        let mut first = LogEvent::default();
+        first.insert("values", vec![1]);
+        first.insert("name", "a");
+        // Rejected after some of its values were pushed already.
+        let mut second = LogEvent::default();
+        second.insert("values", vec![Value::from(2), Value::from(-1)]);
+        second.insert("name", "b");
+        let mut third = LogEvent::default();
+        third.insert("values", vec![3]);
+        third.insert("name", "c");
+
+        let (events, payload) =
+            encode_blocks(&schema, vec![first.into(), second.into(), third.into()]);
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            payload,
+            [
+                &[2, 2][..],
+                &[6, b'v', b'a', b'l', b'u', b'e', b's'],
+                &[12],
+                b"Array(UInt8)",
+                &1_u64.to_le_bytes(),
+                &2_u64.to_le_bytes(),
+                &[1, 3],
+                &[4, b'n', b'a', b'm', b'e'],
+                &[6],
+                b"String",
+                &[1, b'a', 1, b'c'],
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn unmappable_fields() {
+        for (ty, value) in [
+            ("UInt8", Value::from(256)),
+            ("Int32", Value::from("not a number")),
+            ("Array(String)", Value::from("not an array")),
+            ("Enum8('a' = 1)", Value::from("b")),
+            ("FixedString(2)", Value::from("abc")),
+            ("UUID", Value::from("not a uuid")),
+            ("DateTime", Value::from("yesterday")),
+        ] {
+            let mut buf = Vec::new();
+            let ty = ColumnType::parse(ty).unwrap();
+            assert!(write_value(&ty, Some(&value), &mut buf).is_err(), "{ty}");
+        }
+    }
+
+    #[test]
+    fn prepare_rejects_unmappable_events() {
+        let encoder = ClickhouseEncoder::RowBinary(
+            Transformer::default(),
+            Arc::new(schema(&[("count", "UInt8")])),
+        );
+
+        let mut valid = LogEvent::default();
+        valid.insert("count", 12);
+        let mut invalid = LogEvent::default();
+        invalid.insert("count", -1);
+
+        let ClickhouseEvents::Binary { events, payload } =
+            encoder.prepare(vec![valid.into(), invalid.into()])
+        else {
+            panic!("binary formats are encoded as they are prepared");
+        };
+        assert_eq!(events.len(), 1);
+        assert_eq!(payload, [0, 12]);
+    }
+}
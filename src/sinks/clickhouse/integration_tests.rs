@@ -19,7 +19,7 @@ use vector_lib::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event,
 use vector_lib::lookup::PathPrefix;
 use warp::Filter;
 
-use super::*;
+use super::{config::Format, *};
 use crate::{
     codecs::{TimestampFormat, Transformer},
     config::{log_schema, SinkConfig, SinkContext},
@@ -391,6 +391,167 @@ async fn templated_table() {
     }
 }
 
+#[tokio::test]
+async fn insert_events_row_binary() {
+    trace_init();
+
+    insert_events_binary(Format::RowBinary).await;
+}
+
+#[tokio::test]
+async fn insert_events_native() {
+    trace_init();
+
+    insert_events_binary(Format::Native).await;
+}
+
+async fn insert_events_binary(format: Format) {
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(2);
+
+    let config = ClickhouseConfig {
+        endpoint: host.parse().unwrap(),
+        table: table.clone().try_into().unwrap(),
+        format,
+        batch,
+        request: TowerRequestConfig {
+            retry_attempts: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(host);
+    client
+        .create_table(
+            &table,
+            "host LowCardinality(String), timestamp DateTime64(3, 'UTC'), message String, \
+             count Nullable(UInt32), tags Array(String), labels Map(String, String), \
+             level Enum8('info' = 1, 'error' = 2), length UInt64 MATERIALIZED length(message), \
+             source String DEFAULT 'vector'",
+        )
+        .await;
+
+    let (sink, _hc) = config.build(SinkContext::default()).await.unwrap();
+
+    let (mut first, mut first_receiver) = make_event();
+    first.as_mut_log().insert("count", 3);
+    first.as_mut_log().insert("tags", vec!["a", "b"]);
+    first.as_mut_log().insert("labels.env", "prod");
+    first.as_mut_log().insert("level", "error");
+    first.as_mut_log().insert("source", "app");
+    let (second, mut second_receiver) = make_event();
+
+    run_and_assert_sink_compliance(sink, stream::iter(vec![first, second]), &SINK_TAGS).await;
+
+    let output = client.select_all(&table).await;
+    assert_eq!(2, output.rows);
+
+    let rows = output
+        .data
+        .iter()
+        .map(|row| {
+            (
+                &row["host"],
+                &row["message"],
+                &row["count"],
+                &row["tags"],
+                &row["labels"],
+                &row["level"],
+                &row["source"],
+            )
+        })
+        .collect::<Vec<_>>();
+    assert!(rows.contains(&(
+        &"example.com".into(),
+        &"raw log line".into(),
+        &3.into(),
+        &serde_json::json!(["a", "b"]),
+        &serde_json::json!({"env": "prod"}),
+        &"error".into(),
+        &"app".into(),
+    )));
+    // Missing fields are inserted as the default of their column.
+    assert!(rows.contains(&(
+        &"example.com".into(),
+        &"raw log line".into(),
+        &Value::Null,
+        &serde_json::json!([]),
+        &serde_json::json!({}),
+        &"info".into(),
+        &"vector".into(),
+    )));
+
+    assert_eq!(first_receiver.try_recv(), Ok(BatchStatus::Delivered));
+    assert_eq!(second_receiver.try_recv(), Ok(BatchStatus::Delivered));
+}
+
+#[tokio::test]
+async fn binary_format_rejects_unmappable_events() {
+    trace_init();
+
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(1);
+
+    let config = ClickhouseConfig {
+        endpoint: host.parse().unwrap(),
+        table: table.clone().try_into().unwrap(),
+        format: Format::RowBinary,
+        batch,
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(host);
+    client
+        .create_table(
+            &table,
+            "host String, timestamp DateTime64(3), message String, count UInt8",
+        )
+        .await;
+
+    let (sink, _hc) = config.build(SinkContext::default()).await.unwrap();
+
+    let (mut unmappable, mut unmappable_receiver) = make_event();
+    unmappable.as_mut_log().insert("count", 300);
+    let (mut valid, mut valid_receiver) = make_event();
+    valid.as_mut_log().insert("count", 30);
+
+    timeout(
+        Duration::from_secs(5),
+        sink.run_events(vec![unmappable, valid]),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    let output = client.select_all(&table).await;
+    assert_eq!(1, output.rows);
+    assert_eq!(output.data[0]["count"], 30);
+
+    assert_eq!(unmappable_receiver.try_recv(), Ok(BatchStatus::Rejected));
+    assert_eq!(valid_receiver.try_recv(), Ok(BatchStatus::Delivered));
+}
+
+#[tokio::test]
+async fn binary_format_requires_static_table() {
+    trace_init();
+
+    let config = ClickhouseConfig {
+        endpoint: clickhouse_address().parse().unwrap(),
+        table: "{{ .table }}".try_into().unwrap(),
+        format: Format::Native,
+        ..Default::default()
+    };
+
+    assert!(config.build(SinkContext::default()).await.is_err());
+}
+
 fn make_event() -> (Event, BatchStatusReceiver) {
     let (batch, receiver) = BatchNotifier::new_with_receiver();
     let mut event = LogEvent::from("raw log line").with_batch_notifier(&batch);
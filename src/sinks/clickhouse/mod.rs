@@ -5,14 +5,16 @@
 //!
 //! Events are sent to Clickhouse using the HTTP interface with a query of the following structure:
 //! `INSERT INTO my_db.my_table FORMAT JSONEachRow`. The event payload is encoded as new-line
-//! delimited JSON.
+//! delimited JSON, or in the `RowBinary` or `Native` formats from the schema of the table.
 //!
 //! This sink only supports logs for now but could support metrics and traces as well in the future.
 
 mod config;
+mod encoder;
 #[cfg(all(test, feature = "clickhouse-integration-tests"))]
 mod integration_tests;
 mod request_builder;
+mod schema;
 mod service;
 mod sink;
 pub use self::config::ClickhouseConfig;
@@ -1,19 +1,21 @@
 //! `RequestBuilder` implementation for the `Clickhouse` sink.
 
-use super::sink::PartitionKey;
+use super::{
+    encoder::{ClickhouseEncoder, ClickhouseEvents},
+    sink::PartitionKey,
+};
 use crate::sinks::{prelude::*, util::http::HttpRequest};
 use bytes::Bytes;
-use vector_lib::codecs::encoding::Framer;
 
 pub(super) struct ClickhouseRequestBuilder {
     pub(super) compression: Compression,
-    pub(super) encoding: (Transformer, Encoder<Framer>),
+    pub(super) encoding: ClickhouseEncoder,
 }
 
 impl RequestBuilder<(PartitionKey, Vec<Event>)> for ClickhouseRequestBuilder {
     type Metadata = (PartitionKey, EventFinalizers);
-    type Events = Vec<Event>;
-    type Encoder = (Transformer, Encoder<Framer>);
+    type Events = ClickhouseEvents;
+    type Encoder = ClickhouseEncoder;
     type Payload = Bytes;
     type Request = HttpRequest<PartitionKey>;
     type Error = std::io::Error;
@@ -30,10 +32,11 @@ impl RequestBuilder<(PartitionKey, Vec<Event>)> for ClickhouseRequestBuilder {
         &self,
         input: (PartitionKey, Vec<Event>),
     ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
-        let (key, mut events) = input;
+        let (key, events) = input;
 
-        let finalizers = events.take_finalizers();
-        let builder = RequestMetadataBuilder::from_events(&events);
+        let mut events = self.encoding.prepare(events);
+        let finalizers = events.events_mut().take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(events.events());
         ((key, finalizers), builder, events)
     }
 
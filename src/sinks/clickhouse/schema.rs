@@ -0,0 +1,561 @@
+//! Table schemas for the binary formats of the `Clickhouse` sink.
+//!
+//! The `RowBinary` and `Native` formats carry no field names, so the columns of the table, along
+//! with their types, are fetched from `system.columns` when the sink is built.
+
+use std::fmt;
+
+use chrono_tz::Tz;
+use http::{Request, StatusCode, Uri};
+use hyper::Body;
+use serde::Deserialize;
+use snafu::Snafu;
+use vector_lib::lookup::{
+    event_path,
+    lookup_v2::{parse_value_path, OwnedTargetPath},
+};
+
+use super::config::Format;
+use crate::{
+    event::{LogEvent, Value},
+    http::{Auth, HttpClient},
+};
+
+#[derive(Debug, Snafu)]
+pub(super) enum SchemaError {
+    #[snafu(display(
+        "`database` and `table` can't be templated with the `{}` format, as the table schema is fetched at startup.",
+        format
+    ))]
+    DynamicTable { format: Format },
+    #[snafu(display(
+        "Failed to fetch the schema of table `{}`.`{}`, with status {}: {}",
+        database,
+        table,
+        status,
+        body
+    ))]
+    UnexpectedStatus {
+        database: String,
+        table: String,
+        status: StatusCode,
+        body: String,
+    },
+    #[snafu(display("Invalid column description: {}", source))]
+    InvalidColumn { source: serde_json::Error },
+    #[snafu(display(
+        "Table `{}`.`{}` doesn't exist, or has no columns that can be inserted into.",
+        database,
+        table
+    ))]
+    NoColumns { database: String, table: String },
+    #[snafu(display(
+        "Column `{}` has the type `{}`, which the `{}` format can't encode. Give the column a default, or use a JSON format.",
+        column,
+        ty,
+        format
+    ))]
+    UnsupportedType {
+        column: String,
+        ty: String,
+        format: Format,
+    },
+}
+
+/// The columns that events are inserted into, in the order the table declares them.
+#[derive(Clone, Debug)]
+pub(super) struct Schema {
+    pub(super) columns: Vec<Column>,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct Column {
+    pub(super) name: String,
+    pub(super) ty: ColumnType,
+    /// The path of the field for columns with dots in their name, such as the columns of `Nested`
+    /// structures, which are looked up as nested fields when the event has no such top-level field.
+    nested_path: Option<OwnedTargetPath>,
+}
+
+impl Column {
+    pub(super) fn new(name: String, ty: ColumnType) -> Self {
+        let nested_path = if name.contains('.') {
+            parse_value_path(&name).ok().map(OwnedTargetPath::event)
+        } else {
+            None
+        };
+        Self {
+            name,
+            ty,
+            nested_path,
+        }
+    }
+
+    /// Gets the value of the field that maps to the column.
+    pub(super) fn get<'a>(&self, log: &'a LogEvent) -> Option<&'a Value> {
+        log.get(event_path!(self.name.as_str())).or_else(|| {
+            self.nested_path
+                .as_ref()
+                .and_then(|nested_path| log.get(nested_path))
+        })
+    }
+}
+
+impl Schema {
+    /// Fetches the schema of the table from `system.columns`.
+    ///
+    /// `MATERIALIZED` and `ALIAS` columns can't be inserted into, and are left out. So are the
+    /// columns with a default whose type can't be encoded, which ClickHouse fills in instead.
+    pub(super) async fn fetch(
+        client: &HttpClient,
+        endpoint: &Uri,
+        auth: Option<&Auth>,
+        database: &str,
+        table: &str,
+        format: Format,
+    ) -> crate::Result<Self> {
+        let mut request = Request::get(get_columns_uri(endpoint, database, table))
+            .body(Body::empty())
+            .unwrap();
+        if let Some(auth) = auth {
+            auth.apply(&mut request);
+        }
+
+        let response = client.send(request).await?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        if status != StatusCode::OK {
+            return Err(SchemaError::UnexpectedStatus {
+                database: database.to_owned(),
+                table: table.to_owned(),
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            }
+            .into());
+        }
+
+        let rows = body
+            .split(|byte| *byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(serde_json::from_slice::<ColumnRow>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|source| SchemaError::InvalidColumn { source })?;
+        Ok(Self::from_rows(rows, database, table, format)?)
+    }
+
+    fn from_rows(
+        rows: Vec<ColumnRow>,
+        database: &str,
+        table: &str,
+        format: Format,
+    ) -> Result<Self, SchemaError> {
+        let mut columns = Vec::new();
+        for row in rows {
+            match row.default_kind.as_str() {
+                "MATERIALIZED" | "ALIAS" => continue,
+                default_kind => match ColumnType::parse(&row.ty) {
+                    Some(ty) => columns.push(Column::new(row.name, ty)),
+                    None if !default_kind.is_empty() => continue,
+                    None => {
+                        return Err(SchemaError::UnsupportedType {
+                            column: row.name,
+                            ty: row.ty,
+                            format,
+                        })
+                    }
+                },
+            }
+        }
+
+        if columns.is_empty() {
+            return Err(SchemaError::NoColumns {
+                database: database.to_owned(),
+                table: table.to_owned(),
+            });
+        }
+        Ok(Self { columns })
+    }
+
+    /// The list of columns to insert into, as used in the `INSERT` query.
+    pub(super) fn column_list(&self) -> String {
+        let names = self
+            .columns
+            .iter()
+            .map(|column| format!("\"{}\"", column.name.replace('\"', "\\\"")))
+            .collect::<Vec<_>>();
+        format!("({})", names.join(", "))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ColumnRow {
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+    default_kind: String,
+}
+
+fn get_columns_uri(endpoint: &Uri, database: &str, table: &str) -> String {
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair(
+            "query",
+            "SELECT name, type, default_kind FROM system.columns \
+             WHERE database = {database:String} AND table = {table:String} \
+             ORDER BY position FORMAT JSONEachRow",
+        )
+        .append_pair("param_database", database)
+        .append_pair("param_table", table)
+        .finish();
+
+    let mut uri = endpoint.to_string();
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    uri.push('?');
+    uri.push_str(&query);
+    uri
+}
+
+/// The types of columns that events can be encoded into.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum ColumnType {
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    UInt128,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Int128,
+    Float32,
+    Float64,
+    Bool,
+    String,
+    FixedString(usize),
+    Date,
+    Date32,
+    DateTime(Option<ColumnTimeZone>),
+    /// The precision, and the time zone.
+    DateTime64(u32, Option<ColumnTimeZone>),
+    Uuid,
+    Ipv4,
+    Ipv6,
+    Enum8(EnumValues),
+    Enum16(EnumValues),
+    Nullable(Box<ColumnType>),
+    LowCardinality(Box<ColumnType>),
+    Array(Box<ColumnType>),
+    Map(Box<ColumnType>, Box<ColumnType>),
+}
+
+/// The time zone argument of a `DateTime` or `DateTime64` column.
+///
+/// It doesn't change how values are encoded, but date-times without an offset are in this time
+/// zone.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct ColumnTimeZone {
+    pub(super) tz: Tz,
+    declaration: String,
+}
+
+/// The values of an `Enum8` or `Enum16` column, by name.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct EnumValues {
+    pub(super) values: Vec<(String, i16)>,
+    declaration: String,
+}
+
+impl ColumnType {
+    /// Parses a type as described in `system.columns`, or returns `None` if the type isn't
+    /// supported.
+    pub(super) fn parse(ty: &str) -> Option<Self> {
+        let ty = ty.trim();
+        let (name, args) = match ty.find('(') {
+            Some(start) if ty.ends_with(')') => (&ty[..start], Some(&ty[start + 1..ty.len() - 1])),
+            Some(_) => return None,
+            None => (ty, None),
+        };
+
+        Some(match (name, args) {
+            ("UInt8", None) => Self::UInt8,
+            ("UInt16", None) => Self::UInt16,
+            ("UInt32", None) => Self::UInt32,
+            ("UInt64", None) => Self::UInt64,
+            ("UInt128", None) => Self::UInt128,
+            ("Int8", None) => Self::Int8,
+            ("Int16", None) => Self::Int16,
+            ("Int32", None) => Self::Int32,
+            ("Int64", None) => Self::Int64,
+            ("Int128", None) => Self::Int128,
+            ("Float32", None) => Self::Float32,
+            ("Float64", None) => Self::Float64,
+            ("Bool", None) => Self::Bool,
+            ("String", None) => Self::String,
+            ("FixedString", Some(length)) => Self::FixedString(length.trim().parse().ok()?),
+            ("Date", None) => Self::Date,
+            ("Date32", None) => Self::Date32,
+            ("DateTime", None) => Self::DateTime(None),
+            ("DateTime", Some(time_zone)) => {
+                Self::DateTime(Some(ColumnTimeZone::parse(time_zone)?))
+            }
+            ("DateTime64", Some(args)) => {
+                let mut args = split_args(args).into_iter();
+                let precision = args.next()?.parse().ok().filter(|p| *p <= 9)?;
+                let time_zone = match args.next() {
+                    Some(time_zone) => Some(ColumnTimeZone::parse(time_zone)?),
+                    None => None,
+                };
+                Self::DateTime64(precision, time_zone)
+            }
+            ("UUID", None) => Self::Uuid,
+            ("IPv4", None) => Self::Ipv4,
+            ("IPv6", None) => Self::Ipv6,
+            ("Enum8", Some(args)) => Self::Enum8(EnumValues::parse(args)?),
+            ("Enum16", Some(args)) => Self::Enum16(EnumValues::parse(args)?),
+            ("Nullable", Some(inner)) => Self::Nullable(Box::new(Self::parse(inner)?)),
+            ("LowCardinality", Some(inner)) => Self::LowCardinality(Box::new(Self::parse(inner)?)),
+            ("Array", Some(inner)) => Self::Array(Box::new(Self::parse(inner)?)),
+            ("Map", Some(args)) => match split_args(args).as_slice() {
+                [key, value] => {
+                    Self::Map(Box::new(Self::parse(key)?), Box::new(Self::parse(value)?))
+                }
+                _ => return None,
+            },
+            _ => return None,
+        })
+    }
+
+    /// Whether the column can hold nulls.
+    pub(super) fn is_nullable(&self) -> bool {
+        match self {
+            Self::Nullable(_) => true,
+            Self::LowCardinality(inner) => inner.is_nullable(),
+            _ => false,
+        }
+    }
+
+    /// The type as sent in `Native` blocks, where `LowCardinality` columns are sent as their
+    /// inner type.
+    pub(super) fn without_low_cardinality(&self) -> Self {
+        match self {
+            Self::LowCardinality(inner) => inner.without_low_cardinality(),
+            Self::Nullable(inner) => Self::Nullable(Box::new(inner.without_low_cardinality())),
+            Self::Array(inner) => Self::Array(Box::new(inner.without_low_cardinality())),
+            Self::Map(key, value) => Self::Map(
+                Box::new(key.without_low_cardinality()),
+                Box::new(value.without_low_cardinality()),
+            ),
+            ty => ty.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UInt8 => f.write_str("UInt8"),
+            Self::UInt16 => f.write_str("UInt16"),
+            Self::UInt32 => f.write_str("UInt32"),
+            Self::UInt64 => f.write_str("UInt64"),
+            Self::UInt128 => f.write_str("UInt128"),
+            Self::Int8 => f.write_str("Int8"),
+            Self::Int16 => f.write_str("Int16"),
+            Self::Int32 => f.write_str("Int32"),
+            Self::Int64 => f.write_str("Int64"),
+            Self::Int128 => f.write_str("Int128"),
+            Self::Float32 => f.write_str("Float32"),
+            Self::Float64 => f.write_str("Float64"),
+            Self::Bool => f.write_str("Bool"),
+            Self::String => f.write_str("String"),
+            Self::FixedString(length) => write!(f, "FixedString({length})"),
+            Self::Date => f.write_str("Date"),
+            Self::Date32 => f.write_str("Date32"),
+            Self::DateTime(None) => f.write_str("DateTime"),
+            Self::DateTime(Some(time_zone)) => write!(f, "DateTime({})", time_zone.declaration),
+            Self::DateTime64(precision, None) => write!(f, "DateTime64({precision})"),
+            Self::DateTime64(precision, Some(time_zone)) => {
+                write!(f, "DateTime64({precision}, {})", time_zone.declaration)
+            }
+            Self::Uuid => f.write_str("UUID"),
+            Self::Ipv4 => f.write_str("IPv4"),
+            Self::Ipv6 => f.write_str("IPv6"),
+            Self::Enum8(values) => write!(f, "Enum8({})", values.declaration),
+            Self::Enum16(values) => write!(f, "Enum16({})", values.declaration),
+            Self::Nullable(inner) => write!(f, "Nullable({inner})"),
+            Self::LowCardinality(inner) => write!(f, "LowCardinality({inner})"),
+            Self::Array(inner) => write!(f, "Array({inner})"),
+            Self::Map(key, value) => write!(f, "Map({key}, {value})"),
+        }
+    }
+}
+
+impl ColumnTimeZone {
+    /// Parses a time zone, declared as a quoted name.
+    fn parse(declaration: &str) -> Option<Self> {
+        let declaration = declaration.trim();
+        let name = declaration.strip_prefix('\'')?.strip_suffix('\'')?;
+        Some(Self {
+            tz: name.parse().ok()?,
+            declaration: declaration.to_owned(),
+        })
+    }
+}
+
+impl EnumValues {
+    /// Parses the values of an enum, declared as `'name' = value, ...`.
+    fn parse(declaration: &str) -> Option<Self> {
+        let values = split_args(declaration)
+            .into_iter()
+            .map(|arg| {
+                let (name, value) = arg.rsplit_once('=')?;
+                let name = name.trim().strip_prefix('\'')?.strip_suffix('\'')?;
+                let name = name.replace("\\'", "'").replace("\\\\", "\\");
+                Some((name, value.trim().parse().ok()?))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        if values.is_empty() {
+            return None;
+        }
+        Some(Self {
+            values,
+            declaration: declaration.to_owned(),
+        })
+    }
+}
+
+/// Splits the arguments of a type on the commas that aren't nested in parentheses or quotes.
+fn split_args(args: &str) -> Vec<&str> {
+    let mut split = Vec::new();
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (index, c) in args.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '\'' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth -= 1,
+            ',' if !quoted && depth == 0 => {
+                split.push(args[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(args[start..].trim());
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(name: &str, ty: &str, default_kind: &str) -> ColumnRow {
+        ColumnRow {
+            name: name.to_owned(),
+            ty: ty.to_owned(),
+            default_kind: default_kind.to_owned(),
+        }
+    }
+
+    #[test]
+    fn parse_types() {
+        for ty in [
+            "UInt8",
+            "Int128",
+            "FixedString(16)",
+            "DateTime",
+            "DateTime('Europe/Paris')",
+            "DateTime64(3)",
+            "DateTime64(6, 'UTC')",
+            "Enum8('a' = 1, 'b, c' = 2)",
+            "Nullable(String)",
+            "LowCardinality(Nullable(String))",
+            "Array(Array(UInt32))",
+            "Map(LowCardinality(String), Array(Nullable(Float64)))",
+        ] {
+            assert_eq!(ColumnType::parse(ty).unwrap().to_string(), ty);
+        }
+
+        assert_eq!(
+            ColumnType::parse("Enum8('a' = 1, 'b, c' = 2)"),
+            Some(ColumnType::Enum8(EnumValues {
+                values: vec![("a".to_owned(), 1), ("b, c".to_owned(), 2)],
+                declaration: "'a' = 1, 'b, c' = 2".to_owned(),
+            }))
+        );
+        assert_eq!(
+            ColumnType::parse("Map(LowCardinality(String), UInt8)")
+                .unwrap()
+                .without_low_cardinality(),
+            ColumnType::Map(Box::new(ColumnType::String), Box::new(ColumnType::UInt8))
+        );
+    }
+
+    #[test]
+    fn parse_unsupported_types() {
+        for ty in [
+            "Decimal(10, 2)",
+            "Tuple(String, UInt8)",
+            "Array(Decimal(10, 2))",
+            "DateTime64(12)",
+            "Map(String)",
+            "Object('json')",
+            "DateTime('Mars/Olympus_Mons')",
+        ] {
+            assert_eq!(ColumnType::parse(ty), None, "{ty}");
+        }
+    }
+
+    #[test]
+    fn schema_from_rows() {
+        let schema = Schema::from_rows(
+            vec![
+                row("message", "String", ""),
+                row("length", "UInt64", "MATERIALIZED"),
+                row("amount", "Decimal(10, 2)", "DEFAULT"),
+                row("labels.name", "Array(String)", ""),
+            ],
+            "default",
+            "logs",
+            Format::RowBinary,
+        )
+        .unwrap();
+
+        assert_eq!(
+            schema
+                .columns
+                .iter()
+                .map(|column| column.name.as_str())
+                .collect::<Vec<_>>(),
+            ["message", "labels.name"]
+        );
+        assert_eq!(schema.column_list(), r#"("message", "labels.name")"#);
+
+        let mut log = LogEvent::from("hello");
+        log.insert("labels.name", vec!["a"]);
+        assert_eq!(
+            schema.columns[1].get(&log),
+            Some(&Value::from(vec![Value::from("a")]))
+        );
+    }
+
+    #[test]
+    fn schema_with_unsupported_type() {
+        let error = Schema::from_rows(
+            vec![row("amount", "Decimal(10, 2)", "")],
+            "default",
+            "logs",
+            Format::Native,
+        )
+        .unwrap_err();
+        assert!(matches!(error, SchemaError::UnsupportedType { column, .. } if column == "amount"));
+
+        let error = Schema::from_rows(vec![], "default", "logs", Format::Native).unwrap_err();
+        assert!(matches!(error, SchemaError::NoColumns { .. }));
+    }
+}
@@ -67,6 +67,8 @@ pub(super) struct ClickhouseServiceRequestBuilder {
     pub(super) date_time_best_effort: bool,
     pub(super) insert_random_shard: bool,
     pub(super) compression: Compression,
+    /// The list of columns to insert into, for the binary formats.
+    pub(super) columns: Option<String>,
 }
 
 impl HttpServiceRequestBuilder<PartitionKey> for ClickhouseServiceRequestBuilder {
@@ -80,6 +82,7 @@ impl HttpServiceRequestBuilder<PartitionKey> for ClickhouseServiceRequestBuilder
             &self.endpoint,
             &metadata.database,
             &metadata.table,
+            self.columns.as_deref(),
             metadata.format,
             self.skip_unknown_fields,
             self.date_time_best_effort,
@@ -90,8 +93,14 @@ impl HttpServiceRequestBuilder<PartitionKey> for ClickhouseServiceRequestBuilder
 
         let payload = request.take_payload();
 
+        let content_type = if metadata.format.is_binary() {
+            "application/octet-stream"
+        } else {
+            "application/x-ndjson"
+        };
+
         let mut builder = Request::post(&uri)
-            .header(CONTENT_TYPE, "application/x-ndjson")
+            .header(CONTENT_TYPE, content_type)
             .header(CONTENT_LENGTH, payload.len());
         if let Some(ce) = self.compression.content_encoding() {
             builder = builder.header(CONTENT_ENCODING, ce);
@@ -107,10 +116,12 @@ impl HttpServiceRequestBuilder<PartitionKey> for ClickhouseServiceRequestBuilder
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn set_uri_query(
     uri: &Uri,
     database: &str,
     table: &str,
+    columns: Option<&str>,
     format: Format,
     skip_unknown: bool,
     date_time_best_effort: bool,
@@ -120,10 +131,13 @@ fn set_uri_query(
         .append_pair(
             "query",
             format!(
-                "INSERT INTO \"{}\".\"{}\" FORMAT {}",
+                "INSERT INTO \"{}\".\"{}\" {}FORMAT {}",
                 database,
                 table.replace('\"', "\\\""),
-                format
+                columns
+                    .map(|columns| format!("{columns} "))
+                    .unwrap_or_default(),
+                format.insert_name()
             )
             .as_str(),
         )
@@ -144,6 +158,9 @@ fn set_uri_query(
     if insert_random_shard {
         uri.push_str("insert_distributed_one_random_shard=1&")
     }
+    if format == Format::Native {
+        uri.push_str("low_cardinality_allow_in_native_format=0&")
+    }
     uri.push_str(query.as_str());
 
     uri.parse::<Uri>()
@@ -161,6 +178,7 @@ mod tests {
             &"http://localhost:80".parse().unwrap(),
             "my_database",
             "my_table",
+            None,
             Format::JsonEachRow,
             false,
             true,
@@ -176,6 +194,7 @@ mod tests {
             &"http://localhost:80".parse().unwrap(),
             "my_database",
             "my_\"table\"",
+            None,
             Format::JsonEachRow,
             false,
             false,
@@ -190,6 +209,7 @@ mod tests {
             &"http://localhost:80".parse().unwrap(),
             "my_database",
             "my_\"table\"",
+            None,
             Format::JsonAsObject,
             true,
             true,
@@ -201,6 +221,22 @@ mod tests {
                                      input_format_skip_unknown_fields=1&\
                                      date_time_input_format=best_effort&\
                                      query=INSERT+INTO+%22my_database%22.%22my_%5C%22table%5C%22%22+FORMAT+JSONAsObject");
+
+        let uri = set_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "my_database",
+            "my_table",
+            Some(r#"("message", "timestamp")"#),
+            Format::Native,
+            false,
+            false,
+            false,
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?\
+                                     input_format_import_nested_json=1&\
+                                     low_cardinality_allow_in_native_format=0&\
+                                     query=INSERT+INTO+%22my_database%22.%22my_table%22+%28%22message%22%2C+%22timestamp%22%29+FORMAT+Native");
     }
 
     #[test]
@@ -209,6 +245,7 @@ mod tests {
             &"localhost:80".parse().unwrap(),
             "my_database",
             "my_table",
+            None,
             Format::JsonEachRow,
             false,
             false,
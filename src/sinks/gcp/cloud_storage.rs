@@ -9,15 +9,16 @@ use snafu::ResultExt;
 use snafu::Snafu;
 use tower::ServiceBuilder;
 use uuid::Uuid;
-use vector_lib::codecs::encoding::Framer;
+use vector_lib::codecs::encoding::BatchSerializerConfig;
 use vector_lib::configurable::configurable_component;
 use vector_lib::event::{EventFinalizers, Finalizable};
 use vector_lib::{request_metadata::RequestMetadata, TimeZone};
 
+use crate::sinks::util::encoding::object_compression;
 use crate::sinks::util::metadata::RequestMetadataBuilder;
 use crate::sinks::util::service::TowerRequestConfigDefaults;
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    codecs::{EncoderKind, EncodingConfigWithFraming, SinkType, Transformer},
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     event::Event,
     gcp::{GcpAuthConfig, GcpAuthenticator, Scope},
@@ -140,13 +141,18 @@ pub struct GcsSinkConfig {
 
     /// The filename extension to use in the object key.
     ///
-    /// If not specified, the extension is determined by the compression scheme used.
+    /// If not specified, the extension is determined by the compression scheme used, or by the
+    /// batch encoding used.
     #[configurable(metadata(docs::advanced))]
     filename_extension: Option<String>,
 
     #[serde(flatten)]
     encoding: EncodingConfigWithFraming,
 
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    batch_encoding: Option<BatchSerializerConfig>,
+
     #[configurable(derived)]
     #[serde(default)]
     compression: Compression,
@@ -194,6 +200,7 @@ fn default_config(encoding: EncodingConfigWithFraming) -> GcsSinkConfig {
         filename_append_uuid: true,
         filename_extension: Default::default(),
         encoding,
+        batch_encoding: None,
         compression: Compression::gzip_default(),
         batch: Default::default(),
         request: Default::default(),
@@ -237,7 +244,11 @@ impl SinkConfig for GcsSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type() & DataType::Log)
+        let input_type = match &self.batch_encoding {
+            Some(batch_encoding) => batch_encoding.input_type(),
+            None => self.encoding.config().1.input_type(),
+        };
+        Input::new(input_type & DataType::Log)
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -293,7 +304,7 @@ struct RequestSettings {
     extension: String,
     time_format: String,
     append_uuid: bool,
-    encoder: (Transformer, Encoder<Framer>),
+    encoder: (Transformer, EncoderKind),
     compression: Compression,
     tz_offset: Option<FixedOffset>,
 }
@@ -301,7 +312,7 @@ struct RequestSettings {
 impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
     type Metadata = (String, EventFinalizers);
     type Events = Vec<Event>;
-    type Encoder = (Transformer, Encoder<Framer>);
+    type Encoder = (Transformer, EncoderKind);
     type Payload = Bytes;
     type Request = GcsRequest;
     type Error = io::Error;
@@ -371,14 +382,15 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
 impl RequestSettings {
     fn new(config: &GcsSinkConfig, cx: SinkContext) -> crate::Result<Self> {
         let transformer = config.encoding.transformer();
-        let (framer, serializer) = config.encoding.build(SinkType::MessageBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let encoder = config
+            .encoding
+            .build_encoder_kind(config.batch_encoding.as_ref(), SinkType::MessageBased)?;
         let acl = config
             .acl
             .map(|acl| HeaderValue::from_str(&to_string(acl)).unwrap());
+        let (compression, extension) = object_compression(&encoder, config.compression);
         let content_type = HeaderValue::from_str(encoder.content_type()).unwrap();
-        let content_encoding = config
-            .compression
+        let content_encoding = compression
             .content_encoding()
             .map(|ce| HeaderValue::from_str(&to_string(ce)).unwrap());
         let storage_class = config.storage_class.unwrap_or_default();
@@ -396,7 +408,7 @@ impl RequestSettings {
         let extension = config
             .filename_extension
             .clone()
-            .unwrap_or_else(|| config.compression.extension().into());
+            .unwrap_or_else(|| extension.into());
         let time_format = config.filename_time_format.clone();
         let append_uuid = config.filename_append_uuid;
         let offset = config
@@ -413,7 +425,7 @@ impl RequestSettings {
             extension,
             time_format,
             append_uuid,
-            compression: config.compression,
+            compression,
             encoder: (transformer, encoder),
             tz_offset: offset,
         })
@@ -431,7 +443,7 @@ fn make_header((name, value): (&String, &String)) -> crate::Result<(HeaderName,
 #[cfg(test)]
 mod tests {
     use futures_util::{future::ready, stream};
-    use vector_lib::codecs::encoding::FramingConfig;
+    use vector_lib::codecs::encoding::{FramingConfig, ParquetSerializerConfig};
     use vector_lib::codecs::{
         JsonSerializerConfig, NewlineDelimitedEncoderConfig, TextSerializerConfig,
     };
@@ -551,4 +563,38 @@ mod tests {
         let req = build_request(None, true, Compression::gzip_default());
         assert_ne!(req.key, "key/date.log.gz".to_string());
     }
+
+    #[test]
+    fn gcs_build_request_with_batch_encoding() {
+        let sink_config = GcsSinkConfig {
+            key_prefix: Some("key/".into()),
+            filename_time_format: "date".into(),
+            filename_append_uuid: false,
+            batch_encoding: Some(ParquetSerializerConfig::default().into()),
+            ..default_config((None::<FramingConfig>, TextSerializerConfig::default()).into())
+        };
+        let request_settings = request_settings(&sink_config, SinkContext::default());
+
+        // Parquet compresses its output itself, so the default gzip compression isn't applied.
+        assert_eq!(request_settings.compression, Compression::None);
+        assert_eq!(request_settings.content_encoding, None);
+        assert_eq!(
+            request_settings.content_type,
+            HeaderValue::from_static("application/vnd.apache.parquet")
+        );
+
+        let log = LogEvent::from("message").into();
+        let key = sink_config
+            .key_partitioner()
+            .unwrap()
+            .partition(&log)
+            .unwrap();
+        let (metadata, metadata_request_builder, _events) =
+            request_settings.split_input((key, vec![log]));
+        let payload =
+            EncodeResult::uncompressed(Bytes::new(), GroupedCountByteSize::new_untagged());
+        let request_metadata = metadata_request_builder.build(&payload);
+        let req = request_settings.build_request(metadata, request_metadata, payload);
+        assert_eq!(req.key, "key/date.parquet".to_string());
+    }
 }
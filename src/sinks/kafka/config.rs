@@ -54,14 +54,27 @@ pub struct KafkaSinkConfig {
     /// If the field does not exist in the log or in the tags, a blank value is used. If
     /// unspecified, the key is not sent.
     ///
-    /// Kafka uses a hash of the key to choose the partition or uses round-robin if the record has
-    /// no key.
+    /// Unless `partition` is set, the `partitioner` uses a hash of the key to choose the partition.
     #[configurable(metadata(docs::advanced))]
     #[configurable(metadata(docs::examples = "user_id"))]
     #[configurable(metadata(docs::examples = ".my_topic"))]
     #[configurable(metadata(docs::examples = "%my_topic"))]
     pub key_field: Option<ConfigTargetPath>,
 
+    /// The partition to write events to.
+    ///
+    /// This can be either a partition number, or a template that renders to one, such as
+    /// `{{ partition }}` to use the value of a field. If unset, or if the template does not render
+    /// to a valid partition number, the `partitioner` chooses the partition.
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = 0, docs::examples = "{{ partition }}"))]
+    pub partition: Option<KafkaPartitionTemplate>,
+
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partitioner: Option<KafkaPartitioner>,
+
     #[configurable(derived)]
     pub encoding: EncodingConfig,
 
@@ -117,6 +130,29 @@ pub struct KafkaSinkConfig {
     #[configurable(metadata(docs::examples = "headers"))]
     pub headers_key: Option<ConfigTargetPath>,
 
+    /// Headers to add to the Kafka records.
+    ///
+    /// The values are rendered from templates, and are written after the headers from
+    /// `headers_key`. Headers whose template fails to render are not written.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::additional_props_description = "A Kafka record header."))]
+    #[configurable(metadata(docs::examples = "example_headers()"))]
+    pub headers: HashMap<String, Template>,
+
+    /// Enables the idempotent producer.
+    ///
+    /// Records are written exactly once and in order per partition, even when the producer retries
+    /// sending them. Always enabled when `transaction` is set.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    pub idempotence: bool,
+
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<KafkaTransactionConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -126,6 +162,95 @@ pub struct KafkaSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// A partition number, either fixed or rendered from a template.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(untagged)]
+pub enum KafkaPartitionTemplate {
+    /// A fixed partition number.
+    Fixed(i32),
+
+    /// A template that renders to a partition number.
+    Template(Template),
+}
+
+/// The partitioner choosing the partition of records, when `partition` is not set.
+///
+/// If unset, the `consistent_random` partitioner of librdkafka is used, which hashes keys with
+/// CRC32. Records with the same key then land on different partitions than those written by the
+/// Java client.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum KafkaPartitioner {
+    /// Hashes keys with murmur2, like the Java client, and writes each record without a key to a
+    /// random partition.
+    Murmur2,
+
+    /// Writes each record to a random partition, regardless of its key.
+    Random,
+
+    /// Hashes keys with murmur2, like the Java client, and writes records without a key to the
+    /// same partition until `sticky.partitioning.linger.ms` elapses, like the default partitioner of
+    /// the Java client.
+    Sticky,
+}
+
+impl KafkaPartitioner {
+    /// The librdkafka options implementing this partitioner.
+    const fn librdkafka_options(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Murmur2 => &[
+                ("partitioner", "murmur2_random"),
+                ("sticky.partitioning.linger.ms", "0"),
+            ],
+            Self::Random => &[
+                ("partitioner", "random"),
+                ("sticky.partitioning.linger.ms", "0"),
+            ],
+            Self::Sticky => &[("partitioner", "murmur2_random")],
+        }
+    }
+}
+
+/// Kafka transaction configuration.
+///
+/// When set, the events of each batch are written in a single Kafka transaction, and are only
+/// acknowledged once the transaction is committed. The offsets of the messages these events were
+/// consumed from, by `kafka` sources with `exactly_once` enabled, are committed in the same
+/// transaction, so that a Kafka-to-Kafka pipeline does not duplicate records when it is restarted.
+///
+/// The `batch` settings determine how many events are written in each transaction.
+///
+/// The sink stops if the producer fails fatally, for example when it is fenced off by another
+/// producer with the same transactional ID.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaTransactionConfig {
+    /// The transactional ID of the producer.
+    ///
+    /// It must be unique to this sink and stable across restarts, so that transactions left open by
+    /// a previous run are aborted, and so that producers from previous runs are fenced off.
+    #[configurable(metadata(docs::examples = "vector-kafka-sink-1"))]
+    pub transactional_id: String,
+
+    /// Maximum time, in milliseconds, a transaction can take to complete.
+    ///
+    /// The broker aborts transactions that stay open for longer. This is also the timeout of the
+    /// operations initializing, committing, and aborting transactions.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[serde(default = "default_transaction_timeout_ms")]
+    #[configurable(metadata(docs::examples = 60000))]
+    #[configurable(metadata(docs::human_name = "Transaction Timeout"))]
+    pub timeout_ms: Duration,
+}
+
+const fn default_transaction_timeout_ms() -> Duration {
+    Duration::from_millis(60000) // default in librdkafka
+}
+
 const fn default_socket_timeout_ms() -> Duration {
     Duration::from_millis(60000) // default in librdkafka
 }
@@ -134,6 +259,13 @@ const fn default_message_timeout_ms() -> Duration {
     Duration::from_millis(300000) // default in librdkafka
 }
 
+fn example_headers() -> HashMap<String, String> {
+    HashMap::<_, _>::from_iter([
+        ("tenant".to_string(), "{{ tenant }}".to_string()),
+        ("source".to_string(), "vector".to_string()),
+    ])
+}
+
 fn example_librdkafka_options() -> HashMap<String, String> {
     HashMap::<_, _>::from_iter([
         ("client.id".to_string(), "${ENV_VAR}".to_string()),
@@ -231,6 +363,46 @@ impl KafkaSinkConfig {
                 );
                 client_config.set(key, &value.to_string());
             }
+
+            let mut delivery_options = Vec::new();
+            if self.idempotence || self.transaction.is_some() {
+                delivery_options.push(("enable.idempotence", "true".to_string()));
+            }
+            if let Some(transaction) = &self.transaction {
+                delivery_options.push(("transactional.id", transaction.transactional_id.clone()));
+                delivery_options.push((
+                    "transaction.timeout.ms",
+                    transaction.timeout_ms.as_millis().to_string(),
+                ));
+            }
+            for (key, value) in delivery_options {
+                if let Some(val) = self.librdkafka_options.get(key) {
+                    return Err(format!("Delivery settings `idempotence` and `transaction` set `librdkafka_options.{}={}`.\
+                                        The config already sets this as `librdkafka_options.{}={}`.\
+                                        Please delete one.", key, value, key, val).into());
+                }
+                debug!(
+                    librdkafka_option = key,
+                    value = %value,
+                    "Applying delivery option as librdkafka option."
+                );
+                client_config.set(key, &value);
+            }
+
+            if let Some(partitioner) = self.partitioner {
+                for (key, value) in partitioner.librdkafka_options() {
+                    if let Some(val) = self.librdkafka_options.get(*key) {
+                        return Err(format!("Partitioner setting `partitioner` sets `librdkafka_options.{}={}`.\
+                                            The config already sets this as `librdkafka_options.{}={}`.\
+                                            Please delete one.", key, value, key, val).into());
+                    }
+                    debug!(
+                        librdkafka_option = key,
+                        value, "Applying partitioner option as librdkafka option."
+                    );
+                    client_config.set(*key, *value);
+                }
+            }
         }
 
         for (key, value) in self.librdkafka_options.iter() {
@@ -249,6 +421,8 @@ impl GenerateConfig for KafkaSinkConfig {
             topic: Template::try_from("topic-1234".to_owned()).unwrap(),
             healthcheck_topic: None,
             key_field: Some(ConfigTargetPath::try_from("user_id".to_owned()).unwrap()),
+            partition: None,
+            partitioner: None,
             encoding: JsonSerializerConfig::default().into(),
             batch: Default::default(),
             compression: KafkaCompression::None,
@@ -257,6 +431,9 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            headers: Default::default(),
+            idempotence: false,
+            transaction: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -266,8 +443,8 @@ impl GenerateConfig for KafkaSinkConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "kafka")]
 impl SinkConfig for KafkaSinkConfig {
-    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let sink = KafkaSink::new(self.clone())?;
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let sink = KafkaSink::new(self.clone(), cx.offsets_links)?;
         let hc = healthcheck(self.clone()).boxed();
         Ok((VectorSink::from_event_streamsink(sink), hc))
     }
@@ -282,6 +459,10 @@ impl SinkConfig for KafkaSinkConfig {
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &self.acknowledgements
     }
+
+    fn commits_source_offsets(&self) -> bool {
+        self.transaction.is_some()
+    }
 }
 
 #[cfg(test)]
@@ -292,4 +473,86 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    fn transactional_config(librdkafka_options: &str) -> KafkaSinkConfig {
+        toml::from_str(&format!(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic"
+            encoding.codec = "json"
+            transaction.transactional_id = "vector-1"
+            librdkafka_options = {{ {} }}
+            "#,
+            librdkafka_options
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn transaction_sets_producer_options() {
+        let client_config = transactional_config("")
+            .to_rdkafka(KafkaRole::Producer)
+            .unwrap();
+        assert_eq!(client_config.get("enable.idempotence"), Some("true"));
+        assert_eq!(client_config.get("transactional.id"), Some("vector-1"));
+        assert_eq!(client_config.get("transaction.timeout.ms"), Some("60000"));
+
+        let client_config = transactional_config("")
+            .to_rdkafka(KafkaRole::Consumer)
+            .unwrap();
+        assert_eq!(client_config.get("transactional.id"), None);
+    }
+
+    #[test]
+    fn transaction_errors_on_double_set() {
+        let config = transactional_config(r#""transactional.id" = "other""#);
+        assert!(config.to_rdkafka(KafkaRole::Producer).is_err());
+    }
+
+    fn partitioner_config(partitioner: &str, librdkafka_options: &str) -> KafkaSinkConfig {
+        toml::from_str(&format!(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic"
+            encoding.codec = "json"
+            partitioner = "{}"
+            librdkafka_options = {{ {} }}
+            "#,
+            partitioner, librdkafka_options
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn partitioner_sets_producer_options() {
+        let client_config = partitioner_config("murmur2", "")
+            .to_rdkafka(KafkaRole::Producer)
+            .unwrap();
+        assert_eq!(client_config.get("partitioner"), Some("murmur2_random"));
+        assert_eq!(
+            client_config.get("sticky.partitioning.linger.ms"),
+            Some("0")
+        );
+
+        let client_config =
+            partitioner_config("sticky", r#""sticky.partitioning.linger.ms" = "50""#)
+                .to_rdkafka(KafkaRole::Producer)
+                .unwrap();
+        assert_eq!(client_config.get("partitioner"), Some("murmur2_random"));
+        assert_eq!(
+            client_config.get("sticky.partitioning.linger.ms"),
+            Some("50")
+        );
+
+        let client_config = partitioner_config("random", "")
+            .to_rdkafka(KafkaRole::Consumer)
+            .unwrap();
+        assert_eq!(client_config.get("partitioner"), None);
+    }
+
+    #[test]
+    fn partitioner_errors_on_double_set() {
+        let config = partitioner_config("random", r#""partitioner" = "fnv1a""#);
+        assert!(config.to_rdkafka(KafkaRole::Producer).is_err());
+    }
 }
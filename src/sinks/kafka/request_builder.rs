@@ -1,19 +1,28 @@
+use std::{collections::HashMap, sync::Arc};
+
 use bytes::Bytes;
 use rdkafka::message::{Header, OwnedHeaders};
-use vector_lib::lookup::OwnedTargetPath;
+use vector_lib::lookup::{path, OwnedTargetPath};
 
 use crate::{
-    internal_events::KafkaHeaderExtractionError,
+    internal_events::{KafkaHeaderExtractionError, KafkaInvalidPartitionError},
     sinks::{
-        kafka::service::{KafkaRequest, KafkaRequestMetadata},
+        kafka::{
+            config::KafkaPartitionTemplate,
+            service::{ConsumedOffset, KafkaRequest, KafkaRequestMetadata},
+        },
         prelude::*,
     },
 };
 
 pub struct KafkaRequestBuilder {
     pub key_field: Option<OwnedTargetPath>,
+    pub partition: Option<KafkaPartitionTemplate>,
     pub headers_key: Option<OwnedTargetPath>,
+    pub headers: HashMap<String, Template>,
     pub encoder: (Transformer, Encoder<()>),
+    /// Whether to extract the consumed offsets of events, to commit them in transactions.
+    pub consumed_offsets: bool,
 }
 
 impl RequestBuilder<(String, Event)> for KafkaRequestBuilder {
@@ -42,9 +51,21 @@ impl RequestBuilder<(String, Event)> for KafkaRequestBuilder {
         let metadata = KafkaRequestMetadata {
             finalizers: event.take_finalizers(),
             key: get_key(&event, self.key_field.as_ref()),
+            partition: self
+                .partition
+                .as_ref()
+                .and_then(|partition| partition.render(&event)),
             timestamp_millis: get_timestamp_millis(&event),
-            headers: get_headers(&event, self.headers_key.as_ref()),
+            headers: render_headers(
+                get_headers(&event, self.headers_key.as_ref()),
+                &self.headers,
+                &event,
+            ),
             topic,
+            consumed_offset: self
+                .consumed_offsets
+                .then(|| get_consumed_offset(&event))
+                .flatten(),
         };
 
         (metadata, builder, event)
@@ -75,6 +96,35 @@ fn get_key(event: &Event, key_field: Option<&OwnedTargetPath>) -> Option<Bytes>
     })
 }
 
+impl KafkaPartitionTemplate {
+    /// Renders the partition of the event, if it is a valid partition number.
+    fn render(&self, event: &Event) -> Option<i32> {
+        match self {
+            Self::Fixed(partition) => Some(*partition),
+            Self::Template(template) => {
+                let value = template
+                    .render_string(event)
+                    .map_err(|error| {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some("partition"),
+                            drop_event: false,
+                        })
+                    })
+                    .ok()?;
+                value
+                    .parse()
+                    .ok()
+                    .filter(|partition| *partition >= 0)
+                    .or_else(|| {
+                        emit!(KafkaInvalidPartitionError { value: &value });
+                        None
+                    })
+            }
+        }
+    }
+}
+
 fn get_timestamp_millis(event: &Event) -> Option<i64> {
     match &event {
         Event::Log(log) => log.get_timestamp().and_then(|v| v.as_timestamp()).copied(),
@@ -84,6 +134,24 @@ fn get_timestamp_millis(event: &Event) -> Option<i64> {
     .map(|ts| ts.timestamp_millis())
 }
 
+fn get_consumed_offset(event: &Event) -> Option<ConsumedOffset> {
+    let log = event.maybe_as_log()?;
+    let metadata = log.metadata().value();
+    Some(ConsumedOffset {
+        source: Arc::clone(log.metadata().source_id()?),
+        topic: metadata
+            .get(path!("kafka", "topic"))?
+            .as_str()?
+            .into_owned(),
+        partition: metadata
+            .get(path!("kafka", "partition"))?
+            .as_integer()?
+            .try_into()
+            .ok()?,
+        offset: metadata.get(path!("kafka", "offset"))?.as_integer()?,
+    })
+}
+
 fn get_headers(event: &Event, headers_key: Option<&OwnedTargetPath>) -> Option<OwnedHeaders> {
     headers_key.and_then(|headers_key| {
         if let Event::Log(log) = event {
@@ -117,13 +185,45 @@ fn get_headers(event: &Event, headers_key: Option<&OwnedTargetPath>) -> Option<O
     })
 }
 
+/// Adds the headers rendered from templates to those extracted from the event.
+fn render_headers(
+    headers: Option<OwnedHeaders>,
+    templates: &HashMap<String, Template>,
+    event: &Event,
+) -> Option<OwnedHeaders> {
+    templates.iter().fold(headers, |headers, (key, template)| {
+        match template.render_string(event) {
+            Ok(value) => Some(
+                headers
+                    .unwrap_or_else(|| OwnedHeaders::new_with_capacity(templates.len()))
+                    .insert(Header {
+                        key,
+                        value: Some(value.as_bytes()),
+                    }),
+            ),
+            Err(error) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("headers"),
+                    drop_event: false,
+                });
+                headers
+            }
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Bytes;
     use rdkafka::message::Headers;
+    use vector_lib::lookup::metadata_path;
 
     use super::*;
-    use crate::event::{LogEvent, ObjectMap};
+    use crate::{
+        config::ComponentKey,
+        event::{LogEvent, ObjectMap},
+    };
 
     #[test]
     fn kafka_get_headers() {
@@ -141,4 +241,76 @@ mod tests {
         assert_eq!(headers.get(1).key, "b-key");
         assert_eq!(headers.get(1).value.unwrap(), "b-value".as_bytes());
     }
+
+    #[test]
+    fn kafka_render_headers() {
+        let headers_key = OwnedTargetPath::try_from("headers".to_string()).unwrap();
+        let mut header_values = ObjectMap::new();
+        header_values.insert("a-key".into(), Value::Bytes(Bytes::from("a-value")));
+
+        let mut event = Event::Log(LogEvent::from("hello"));
+        event.as_mut_log().insert(&headers_key, header_values);
+        event.as_mut_log().insert("tenant", "acme");
+
+        let templates = HashMap::from([
+            (
+                "tenant".to_string(),
+                Template::try_from("{{ tenant }}").unwrap(),
+            ),
+            (
+                "missing".to_string(),
+                Template::try_from("{{ missing }}").unwrap(),
+            ),
+        ]);
+
+        let headers =
+            render_headers(get_headers(&event, Some(&headers_key)), &templates, &event).unwrap();
+        assert_eq!(headers.count(), 2);
+        assert_eq!(headers.get(0).key, "a-key");
+        assert_eq!(headers.get(1).key, "tenant");
+        assert_eq!(headers.get(1).value.unwrap(), "acme".as_bytes());
+
+        assert!(render_headers(None, &HashMap::new(), &event).is_none());
+    }
+
+    #[test]
+    fn kafka_render_partition() {
+        let mut event = Event::Log(LogEvent::from("hello"));
+        event.as_mut_log().insert("partition", 3);
+        event.as_mut_log().insert("invalid", "three");
+        event.as_mut_log().insert("negative", -1);
+
+        let render = |template: &str| {
+            KafkaPartitionTemplate::Template(Template::try_from(template).unwrap()).render(&event)
+        };
+        assert_eq!(KafkaPartitionTemplate::Fixed(1).render(&event), Some(1));
+        assert_eq!(render("{{ partition }}"), Some(3));
+        assert_eq!(render("{{ invalid }}"), None);
+        assert_eq!(render("{{ negative }}"), None);
+        assert_eq!(render("{{ missing }}"), None);
+    }
+
+    #[test]
+    fn kafka_get_consumed_offset() {
+        let mut event = Event::Log(LogEvent::from("hello"));
+        assert_eq!(get_consumed_offset(&event), None);
+
+        let log = event.as_mut_log();
+        log.insert(metadata_path!("kafka", "topic"), "topic");
+        log.insert(metadata_path!("kafka", "partition"), 3);
+        log.insert(metadata_path!("kafka", "offset"), 42);
+        assert_eq!(get_consumed_offset(&event), None);
+
+        let source = Arc::new(ComponentKey::from("in"));
+        event.set_source_id(Arc::clone(&source));
+        assert_eq!(
+            get_consumed_offset(&event),
+            Some(ConsumedOffset {
+                source,
+                topic: "topic".into(),
+                partition: 3,
+                offset: 42,
+            })
+        );
+    }
 }
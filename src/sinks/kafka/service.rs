@@ -1,21 +1,29 @@
 use std::{
+    collections::HashMap,
     sync::{
         atomic::{AtomicUsize, Ordering},
-        Arc,
+        Arc, Mutex,
     },
     task::{Context, Poll},
     time::Duration,
 };
 
 use bytes::Bytes;
+use futures::future::join_all;
 use rdkafka::{
-    error::KafkaError,
+    error::{KafkaError, KafkaResult},
     message::OwnedHeaders,
-    producer::{FutureProducer, FutureRecord},
+    producer::{FutureProducer, FutureRecord, Producer},
     types::RDKafkaErrorCode,
+    Offset, TopicPartitionList,
 };
+use snafu::{ResultExt, Snafu};
 
-use crate::{kafka::KafkaStatisticsContext, sinks::prelude::*};
+use crate::{
+    config::{ComponentKey, OffsetsLink},
+    kafka::{GroupMetadataLookup, KafkaStatisticsContext},
+    sinks::prelude::*,
+};
 
 pub struct KafkaRequest {
     pub body: Bytes,
@@ -26,9 +34,21 @@ pub struct KafkaRequest {
 pub struct KafkaRequestMetadata {
     pub finalizers: EventFinalizers,
     pub key: Option<Bytes>,
+    pub partition: Option<i32>,
     pub timestamp_millis: Option<i64>,
     pub headers: Option<OwnedHeaders>,
     pub topic: String,
+    pub consumed_offset: Option<ConsumedOffset>,
+}
+
+/// The position of the message an event was consumed from, by a `kafka` source with exactly-once
+/// delivery enabled.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsumedOffset {
+    pub source: Arc<ComponentKey>,
+    pub topic: String,
+    pub partition: i32,
+    pub offset: i64,
 }
 
 pub struct KafkaResponse {
@@ -119,59 +139,488 @@ impl Service<KafkaRequest> for KafkaService {
     fn call(&mut self, request: KafkaRequest) -> Self::Future {
         let this = self.clone();
 
+        Box::pin(async move { this.send(request).await })
+    }
+}
+
+impl KafkaService {
+    /// Enqueues the record of a request on the producer, and waits for its delivery.
+    async fn send(&self, request: KafkaRequest) -> Result<KafkaResponse, KafkaError> {
+        let raw_byte_size =
+            request.body.len() + request.metadata.key.as_ref().map_or(0, |x| x.len());
+        let event_byte_size = request
+            .request_metadata
+            .into_events_estimated_json_encoded_byte_size();
+
+        let mut record = FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
+        if let Some(key) = &request.metadata.key {
+            record = record.key(&key[..]);
+        }
+        if let Some(partition) = request.metadata.partition {
+            record = record.partition(partition);
+        }
+        if let Some(timestamp) = request.metadata.timestamp_millis {
+            record = record.timestamp(timestamp);
+        }
+        if let Some(headers) = request.metadata.headers {
+            record = record.headers(headers);
+        }
+
+        // Manually poll [FutureProducer::send_result] instead of [FutureProducer::send] to track
+        // records that fail to be enqueued on the producer.
+        let mut blocked_state: Option<BlockedRecordState> = None;
+        loop {
+            match self.kafka_producer.send_result(record) {
+                // Record was successfully enqueued on the producer.
+                Ok(fut) => {
+                    // Drop the blocked state (if any), as the producer is no longer blocked.
+                    drop(blocked_state.take());
+                    return fut
+                        .await
+                        .expect("producer unexpectedly dropped")
+                        .map(|_| KafkaResponse {
+                            event_byte_size,
+                            raw_byte_size,
+                        })
+                        .map_err(|(err, _)| err);
+                }
+                // Producer queue is full.
+                Err((
+                    KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull),
+                    original_record,
+                )) => {
+                    if blocked_state.is_none() {
+                        blocked_state =
+                            Some(BlockedRecordState::new(Arc::clone(&self.records_blocked)));
+                    }
+                    record = original_record;
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+                // A different error occurred.
+                Err((err, _)) => return Err(err),
+            };
+        }
+    }
+}
+
+/// A batch of requests, written in a single Kafka transaction.
+pub struct KafkaTransactionRequest {
+    requests: Vec<KafkaRequest>,
+    finalizers: EventFinalizers,
+    request_metadata: RequestMetadata,
+}
+
+impl KafkaTransactionRequest {
+    pub fn new(mut requests: Vec<KafkaRequest>) -> Self {
+        let finalizers =
+            requests
+                .iter_mut()
+                .fold(EventFinalizers::default(), |mut finalizers, request| {
+                    finalizers.merge(request.take_finalizers());
+                    finalizers
+                });
+        let request_metadata = RequestMetadata::from_batch(
+            requests
+                .iter()
+                .map(|request| request.request_metadata.clone()),
+        );
+        Self {
+            requests,
+            finalizers,
+            request_metadata,
+        }
+    }
+
+    /// Returns the first and last offsets of the consumed messages, by partition, for the sources
+    /// whose offsets are committed by this sink.
+    fn consumed_ranges(
+        &self,
+        offsets_links: &HashMap<ComponentKey, OffsetsLink>,
+    ) -> HashMap<ConsumedPartition, (i64, i64)> {
+        let mut ranges = HashMap::<_, (i64, i64)>::new();
+        for consumed in self
+            .requests
+            .iter()
+            .filter_map(|request| request.metadata.consumed_offset.as_ref())
+            .filter(|consumed| offsets_links.contains_key(consumed.source.as_ref()))
+        {
+            let partition = ConsumedPartition {
+                source: Arc::clone(&consumed.source),
+                topic: consumed.topic.clone(),
+                partition: consumed.partition,
+            };
+            let (first, last) = ranges
+                .entry(partition)
+                .or_insert((consumed.offset, consumed.offset));
+            *first = (*first).min(consumed.offset);
+            *last = (*last).max(consumed.offset);
+        }
+        ranges
+    }
+}
+
+impl Finalizable for KafkaTransactionRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for KafkaTransactionRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.request_metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.request_metadata
+    }
+}
+
+/// A partition consumed by a source whose offsets are committed by this sink.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct ConsumedPartition {
+    source: Arc<ComponentKey>,
+    topic: String,
+    partition: i32,
+}
+
+/// Returns the offsets to commit for the consumed messages, by source. These are the offsets of
+/// the next messages to consume from each partition.
+fn offsets_to_commit(
+    ranges: &HashMap<ConsumedPartition, (i64, i64)>,
+) -> KafkaResult<HashMap<Arc<ComponentKey>, TopicPartitionList>> {
+    let mut offsets = HashMap::<_, TopicPartitionList>::new();
+    for (consumed, (_, last)) in ranges {
+        offsets
+            .entry(Arc::clone(&consumed.source))
+            .or_default()
+            .add_partition_offset(
+                &consumed.topic,
+                consumed.partition,
+                Offset::Offset(last + 1),
+            )?;
+    }
+    Ok(offsets)
+}
+
+#[derive(Debug, Snafu)]
+pub enum KafkaTransactionError {
+    #[snafu(display("Kafka transaction failed: {}", source))]
+    Transaction { source: KafkaError },
+
+    #[snafu(display(
+        "The consumer of source {:?} is not running, so its offsets can't be committed.",
+        source_id
+    ))]
+    ConsumerNotFound { source_id: Arc<ComponentKey> },
+
+    #[snafu(display(
+        "A previous transaction with messages of partition {} of topic {:?}, consumed by source {:?}, was aborted, and these messages haven't been consumed again yet.",
+        partition,
+        topic,
+        source_id
+    ))]
+    PartitionNotRewound {
+        source_id: Arc<ComponentKey>,
+        topic: String,
+        partition: i32,
+    },
+
+    #[snafu(display(
+        "Kafka producer failed fatally, no more transactions can be written: {}",
+        reason
+    ))]
+    Fatal { reason: String },
+}
+
+/// The state of the transactions of a producer, which only has one open at a time.
+#[derive(Default)]
+struct TransactionState {
+    /// The offset of the first consumed message of each partition written in a failed transaction.
+    ///
+    /// The source rewinds these partitions, and until the transactions start from these messages
+    /// again, no offsets past them can be committed, as that would skip the messages in between.
+    aborted: HashMap<ConsumedPartition, i64>,
+}
+
+impl TransactionState {
+    /// Returns the first partition of the given consumed messages which hasn't been rewound to the
+    /// messages of an aborted transaction yet.
+    fn not_rewound<'a>(
+        &self,
+        ranges: &'a HashMap<ConsumedPartition, (i64, i64)>,
+    ) -> Option<&'a ConsumedPartition> {
+        ranges.iter().find_map(|(partition, (first, _))| {
+            self.aborted
+                .get(partition)
+                .is_some_and(|aborted| first > aborted)
+                .then_some(partition)
+        })
+    }
+
+    fn committed(&mut self, ranges: &HashMap<ConsumedPartition, (i64, i64)>) {
+        for partition in ranges.keys() {
+            self.aborted.remove(partition);
+        }
+    }
+
+    fn failed(&mut self, ranges: &HashMap<ConsumedPartition, (i64, i64)>) {
+        for (partition, (first, _)) in ranges {
+            let aborted = self.aborted.entry(partition.clone()).or_insert(*first);
+            *aborted = (*aborted).min(*first);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct KafkaTransactionService {
+    service: KafkaService,
+
+    /// Serializes transactions, as a producer only has one open at a time.
+    transaction: Arc<tokio::sync::Mutex<TransactionState>>,
+
+    /// The timeout of the blocking transaction operations.
+    timeout: Duration,
+
+    /// The links to the sources whose offsets are committed in the transactions, by source.
+    offsets_links: Arc<HashMap<ComponentKey, OffsetsLink>>,
+
+    /// The fatal error the producer ran into, such as being fenced off by another producer with the
+    /// same transactional ID, after which it can't write any more transactions.
+    fatal: Arc<Mutex<Option<String>>>,
+}
+
+impl KafkaTransactionService {
+    pub(crate) fn new(
+        service: KafkaService,
+        timeout: Duration,
+        offsets_links: HashMap<ComponentKey, OffsetsLink>,
+    ) -> Self {
+        Self {
+            service,
+            transaction: Arc::default(),
+            timeout,
+            offsets_links: Arc::new(offsets_links),
+            fatal: Arc::default(),
+        }
+    }
+
+    /// Initializes transactions on the producer, aborting any left open by a previous producer with
+    /// the same transactional ID.
+    pub(crate) async fn init(&self) -> KafkaResult<()> {
+        let timeout = self.timeout;
+        self.blocking(move |producer| producer.init_transactions(timeout))
+            .await
+    }
+
+    /// Runs a transaction operation, which may block for up to the timeout, on the blocking pool.
+    async fn blocking<T, F>(&self, operation: F) -> KafkaResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&FutureProducer<KafkaStatisticsContext>) -> KafkaResult<T> + Send + 'static,
+    {
+        let producer = self.service.kafka_producer.clone();
+        tokio::task::spawn_blocking(move || operation(&producer))
+            .await
+            .expect("Kafka transaction operation panicked")
+    }
+
+    fn fatal_error(&self) -> Option<KafkaTransactionError> {
+        let reason = self.fatal.lock().expect("poisoned lock").clone()?;
+        Some(KafkaTransactionError::Fatal { reason })
+    }
+
+    /// Checks whether the producer ran into a fatal error, in which case it must not be used
+    /// anymore, not even to abort the current transaction.
+    fn check_fatal_error(&self) -> Option<KafkaTransactionError> {
+        if let Some((code, reason)) = self.service.kafka_producer.client().fatal_error() {
+            let reason = format!("{} ({:?})", reason, code);
+            error!(message = "Kafka producer failed fatally.", %reason);
+            *self.fatal.lock().expect("poisoned lock") = Some(reason);
+        }
+        self.fatal_error()
+    }
+
+    async fn write_transaction(
+        &self,
+        request: KafkaTransactionRequest,
+        ranges: &HashMap<ConsumedPartition, (i64, i64)>,
+    ) -> Result<usize, KafkaTransactionError> {
+        if let Err(error) = self.service.kafka_producer.begin_transaction() {
+            return Err(self
+                .check_fatal_error()
+                .unwrap_or(KafkaTransactionError::Transaction { source: error }));
+        }
+
+        match self.produce_and_commit(request, ranges).await {
+            Ok(raw_byte_size) => Ok(raw_byte_size),
+            Err(error) => {
+                if let Some(fatal) = self.check_fatal_error() {
+                    return Err(fatal);
+                }
+                let timeout = self.timeout;
+                if let Err(error) = self
+                    .blocking(move |producer| producer.abort_transaction(timeout))
+                    .await
+                {
+                    warn!(message = "Failed to abort Kafka transaction.", %error);
+                    if let Some(fatal) = self.check_fatal_error() {
+                        return Err(fatal);
+                    }
+                }
+                Err(error)
+            }
+        }
+    }
+
+    async fn produce_and_commit(
+        &self,
+        request: KafkaTransactionRequest,
+        ranges: &HashMap<ConsumedPartition, (i64, i64)>,
+    ) -> Result<usize, KafkaTransactionError> {
+        let offsets = offsets_to_commit(ranges).context(TransactionSnafu)?;
+
+        let mut raw_byte_size = 0;
+        for response in join_all(
+            request
+                .requests
+                .into_iter()
+                .map(|request| self.service.send(request)),
+        )
+        .await
+        {
+            raw_byte_size += response.context(TransactionSnafu)?.raw_byte_size;
+        }
+
+        let timeout = self.timeout;
+        for (source_id, offsets) in offsets {
+            let group_metadata = self
+                .offsets_links
+                .get(source_id.as_ref())
+                .and_then(|link| link.get::<GroupMetadataLookup>())
+                .and_then(|lookup| lookup.group_metadata())
+                .ok_or(KafkaTransactionError::ConsumerNotFound { source_id })?;
+            self.blocking(move |producer| {
+                producer.send_offsets_to_transaction(&offsets, &group_metadata, timeout)
+            })
+            .await
+            .context(TransactionSnafu)?;
+        }
+
+        self.blocking(move |producer| producer.commit_transaction(timeout))
+            .await
+            .context(TransactionSnafu)?;
+
+        Ok(raw_byte_size)
+    }
+}
+
+impl Service<KafkaTransactionRequest> for KafkaTransactionService {
+    type Response = KafkaResponse;
+    type Error = KafkaTransactionError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Stop the sink once the producer can't write transactions anymore.
+        if let Some(fatal) = self.fatal_error() {
+            return Poll::Ready(Err(fatal));
+        }
+        self.service
+            .poll_ready(cx)
+            .map_err(|source| KafkaTransactionError::Transaction { source })
+    }
+
+    fn call(&mut self, request: KafkaTransactionRequest) -> Self::Future {
+        let this = self.clone();
+
         Box::pin(async move {
-            let raw_byte_size =
-                request.body.len() + request.metadata.key.as_ref().map_or(0, |x| x.len());
+            let mut state = this.transaction.lock().await;
+            if let Some(fatal) = this.fatal_error() {
+                return Err(fatal);
+            }
+
             let event_byte_size = request
                 .request_metadata
+                .clone()
                 .into_events_estimated_json_encoded_byte_size();
+            let ranges = request.consumed_ranges(&this.offsets_links);
 
-            let mut record =
-                FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
-            if let Some(key) = &request.metadata.key {
-                record = record.key(&key[..]);
-            }
-            if let Some(timestamp) = request.metadata.timestamp_millis {
-                record = record.timestamp(timestamp);
-            }
-            if let Some(headers) = request.metadata.headers {
-                record = record.headers(headers);
-            }
-
-            // Manually poll [FutureProducer::send_result] instead of [FutureProducer::send] to track
-            // records that fail to be enqueued on the producer.
-            let mut blocked_state: Option<BlockedRecordState> = None;
-            loop {
-                match this.kafka_producer.send_result(record) {
-                    // Record was successfully enqueued on the producer.
-                    Ok(fut) => {
-                        // Drop the blocked state (if any), as the producer is no longer blocked.
-                        drop(blocked_state.take());
-                        return fut
-                            .await
-                            .expect("producer unexpectedly dropped")
-                            .map(|_| KafkaResponse {
-                                event_byte_size,
-                                raw_byte_size,
-                            })
-                            .map_err(|(err, _)| err);
-                    }
-                    // Producer queue is full.
-                    Err((
-                        KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull),
-                        original_record,
-                    )) => {
-                        if blocked_state.is_none() {
-                            blocked_state =
-                                Some(BlockedRecordState::new(Arc::clone(&this.records_blocked)));
-                        }
-                        record = original_record;
-                        tokio::time::sleep(Duration::from_millis(100)).await;
-                    }
-                    // A different error occurred.
-                    Err((err, _)) => return Err(err),
-                };
+            let result = match state.not_rewound(&ranges) {
+                Some(partition) => Err(KafkaTransactionError::PartitionNotRewound {
+                    source_id: Arc::clone(&partition.source),
+                    topic: partition.topic.clone(),
+                    partition: partition.partition,
+                }),
+                None => this.write_transaction(request, &ranges).await,
+            };
+            match result {
+                Ok(raw_byte_size) => {
+                    state.committed(&ranges);
+                    Ok(KafkaResponse {
+                        event_byte_size,
+                        raw_byte_size,
+                    })
+                }
+                Err(error) => {
+                    state.failed(&ranges);
+                    Err(error)
+                }
             }
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partition(partition: i32) -> ConsumedPartition {
+        ConsumedPartition {
+            source: Arc::new(ComponentKey::from("in")),
+            topic: "topic".into(),
+            partition,
+        }
+    }
+
+    #[test]
+    fn transaction_state_blocks_aborted_partitions_until_rewound() {
+        let mut state = TransactionState::default();
+        let aborted = HashMap::from([(partition(0), (10, 19)), (partition(1), (5, 9))]);
+        assert_eq!(state.not_rewound(&aborted), None);
+        state.failed(&aborted);
+
+        // Transactions past the aborted messages are refused, and don't move the rewind points.
+        let skipping = HashMap::from([(partition(0), (20, 29)), (partition(2), (0, 9))]);
+        assert_eq!(state.not_rewound(&skipping), Some(&partition(0)));
+        state.failed(&skipping);
+        assert_eq!(state.aborted.get(&partition(0)), Some(&10));
+
+        // Transactions starting from the aborted messages again go through.
+        let rewound = HashMap::from([(partition(0), (10, 29))]);
+        assert_eq!(state.not_rewound(&rewound), None);
+        state.committed(&rewound);
+        assert_eq!(state.aborted.get(&partition(0)), None);
+
+        let other = HashMap::from([(partition(1), (12, 15))]);
+        assert_eq!(state.not_rewound(&other), Some(&partition(1)));
+    }
+
+    #[test]
+    fn offsets_to_commit_follow_last_consumed_messages() {
+        let ranges = HashMap::from([(partition(0), (10, 19)), (partition(1), (5, 9))]);
+        let offsets = offsets_to_commit(&ranges).unwrap();
+        let offsets = &offsets[&Arc::new(ComponentKey::from("in"))];
+
+        assert_eq!(
+            offsets
+                .find_partition("topic", 0)
+                .map(|partition| partition.offset()),
+            Some(Offset::Offset(20))
+        );
+        assert_eq!(
+            offsets
+                .find_partition("topic", 1)
+                .map(|partition| partition.offset()),
+            Some(Offset::Offset(10))
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use rdkafka::{
     consumer::{BaseConsumer, Consumer},
     error::KafkaError,
@@ -7,12 +9,17 @@ use rdkafka::{
 use snafu::{ResultExt, Snafu};
 use tokio::time::Duration;
 use tracing::Span;
+use vector_lib::stream::batcher::limiter::ItemBatchSize;
 use vrl::path::OwnedTargetPath;
 
-use super::config::{KafkaRole, KafkaSinkConfig};
+use super::config::{KafkaPartitionTemplate, KafkaRole, KafkaSinkConfig};
 use crate::{
+    config::{ComponentKey, OffsetsLink},
     kafka::KafkaStatisticsContext,
-    sinks::kafka::{request_builder::KafkaRequestBuilder, service::KafkaService},
+    sinks::kafka::{
+        request_builder::KafkaRequestBuilder,
+        service::{KafkaRequest, KafkaService, KafkaTransactionRequest, KafkaTransactionService},
+    },
     sinks::prelude::*,
 };
 
@@ -27,9 +34,15 @@ pub struct KafkaSink {
     transformer: Transformer,
     encoder: Encoder<()>,
     service: KafkaService,
+    /// The transaction timeout and batch settings, when writing events in transactions.
+    transaction: Option<(Duration, BatcherSettings)>,
+    /// The links to the sources whose offsets are committed in the transactions, by source.
+    offsets_links: HashMap<ComponentKey, OffsetsLink>,
     topic: Template,
     key_field: Option<OwnedTargetPath>,
+    partition: Option<KafkaPartitionTemplate>,
     headers_key: Option<OwnedTargetPath>,
+    headers: HashMap<String, Template>,
 }
 
 pub(crate) fn create_producer(
@@ -45,31 +58,48 @@ pub(crate) fn create_producer(
 }
 
 impl KafkaSink {
-    pub(crate) fn new(config: KafkaSinkConfig) -> crate::Result<Self> {
+    pub(crate) fn new(
+        config: KafkaSinkConfig,
+        offsets_links: HashMap<ComponentKey, OffsetsLink>,
+    ) -> crate::Result<Self> {
         let producer_config = config.to_rdkafka(KafkaRole::Producer)?;
         let producer = create_producer(producer_config)?;
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
+        let transaction = match &config.transaction {
+            Some(transaction) => Some((
+                transaction.timeout_ms,
+                config.batch.into_batcher_settings()?,
+            )),
+            None => None,
+        };
 
         Ok(KafkaSink {
             headers_key: config.headers_key.map(|key| key.0),
+            headers: config.headers,
             transformer,
             encoder,
             service: KafkaService::new(producer),
+            transaction,
+            offsets_links,
             topic: config.topic,
             key_field: config.key_field.map(|key| key.0),
+            partition: config.partition,
         })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let request_builder = KafkaRequestBuilder {
             key_field: self.key_field,
+            partition: self.partition,
             headers_key: self.headers_key,
+            headers: self.headers,
             encoder: (self.transformer, self.encoder),
+            consumed_offsets: self.transaction.is_some(),
         };
 
-        input
+        let requests = input
             .filter_map(|event| {
                 // Compute the topic.
                 future::ready(
@@ -95,11 +125,42 @@ impl KafkaSink {
                     }
                     Ok(req) => Some(req),
                 }
-            })
-            .into_driver(self.service)
-            .protocol("kafka")
-            .run()
-            .await
+            });
+
+        match self.transaction {
+            Some((timeout, batch_settings)) => {
+                let service =
+                    KafkaTransactionService::new(self.service, timeout, self.offsets_links);
+                if let Err(error) = service.init().await {
+                    error!(message = "Failed to initialize Kafka transactions.", %error);
+                    return Err(());
+                }
+
+                requests
+                    .batched(batch_settings.as_item_size_config(KafkaRequestSizer))
+                    .map(KafkaTransactionRequest::new)
+                    .into_driver(service)
+                    .protocol("kafka")
+                    .run()
+                    .await
+            }
+            None => {
+                requests
+                    .into_driver(self.service)
+                    .protocol("kafka")
+                    .run()
+                    .await
+            }
+        }
+    }
+}
+
+/// Sizes requests by their payload, to batch them in transactions.
+struct KafkaRequestSizer;
+
+impl ItemBatchSize<KafkaRequest> for KafkaRequestSizer {
+    fn size(&self, item: &KafkaRequest) -> usize {
+        item.body.len()
     }
 }
 
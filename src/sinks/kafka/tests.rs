@@ -20,7 +20,7 @@ mod integration_test {
     };
 
     use super::super::{
-        config::{KafkaRole, KafkaSinkConfig},
+        config::{KafkaRole, KafkaSinkConfig, KafkaTransactionConfig},
         sink::KafkaSink,
         *,
     };
@@ -57,6 +57,8 @@ mod integration_test {
             topic: Template::try_from(topic.clone()).unwrap(),
             healthcheck_topic: None,
             key_field: None,
+            partition: None,
+            partitioner: None,
             encoding: TextSerializerConfig::default().into(),
             batch: BatchConfig::default(),
             compression: KafkaCompression::None,
@@ -65,6 +67,9 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            headers: Default::default(),
+            idempotence: false,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config).await.unwrap();
@@ -81,6 +86,8 @@ mod integration_test {
             topic: Template::try_from(topic.clone()).unwrap(),
             healthcheck_topic: Some(String::from("topic-1234")),
             key_field: None,
+            partition: None,
+            partitioner: None,
             encoding: TextSerializerConfig::default().into(),
             batch: BatchConfig::default(),
             compression: KafkaCompression::None,
@@ -89,6 +96,9 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            headers: Default::default(),
+            idempotence: false,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config).await.unwrap();
@@ -179,6 +189,8 @@ mod integration_test {
             healthcheck_topic: None,
             encoding: TextSerializerConfig::default().into(),
             key_field: None,
+            partition: None,
+            partitioner: None,
             auth: KafkaAuthConfig {
                 sasl: None,
                 tls: None,
@@ -188,12 +200,15 @@ mod integration_test {
             batch,
             librdkafka_options,
             headers_key: None,
+            headers: Default::default(),
+            idempotence: false,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         config.clone().to_rdkafka(KafkaRole::Consumer)?;
         config.clone().to_rdkafka(KafkaRole::Producer)?;
         self::sink::healthcheck(config.clone()).await?;
-        KafkaSink::new(config)
+        KafkaSink::new(config, Default::default())
     }
 
     #[tokio::test]
@@ -300,6 +315,80 @@ mod integration_test {
         .await;
     }
 
+    #[tokio::test]
+    async fn kafka_happy_path_transactional() {
+        crate::test_util::trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let config = KafkaSinkConfig {
+            bootstrap_servers: kafka_address(9091),
+            topic: Template::try_from(topic.clone()).unwrap(),
+            healthcheck_topic: None,
+            key_field: None,
+            partition: None,
+            partitioner: None,
+            encoding: TextSerializerConfig::default().into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: Duration::from_millis(60000),
+            message_timeout_ms: Duration::from_millis(300000),
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            headers: Default::default(),
+            idempotence: false,
+            transaction: Some(KafkaTransactionConfig {
+                transactional_id: format!("vector-{}", random_string(10)),
+                timeout_ms: Duration::from_millis(60000),
+            }),
+            acknowledgements: Default::default(),
+        };
+
+        let num_events = 100;
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input, events) = random_lines_with_stream(100, num_events, Some(batch));
+
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let sink = KafkaSink::new(config, Default::default()).unwrap();
+            let sink = VectorSink::from_event_streamsink(sink);
+            sink.run(events).await
+        })
+        .await
+        .expect("Running sink failed");
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        // Only records of committed transactions are visible to this consumer.
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", kafka_address(9091).as_str());
+        client_config.set("group.id", &random_string(10));
+        client_config.set("isolation.level", "read_committed");
+
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(&topic, 0)
+            .set_offset(Offset::Beginning)
+            .unwrap();
+
+        let consumer: BaseConsumer = client_config.create().unwrap();
+        consumer.assign(&tpl).unwrap();
+
+        let mut failures = 0;
+        let mut out = Vec::new();
+        while failures < 100 && out.len() < input.len() {
+            match consumer.poll(Duration::from_secs(3)) {
+                Some(Ok(msg)) => {
+                    let s: &str = msg.payload_view().unwrap().unwrap();
+                    out.push(s.to_owned());
+                }
+                _ => {
+                    failures += 1;
+                    thread::sleep(Duration::from_millis(50));
+                }
+            }
+        }
+
+        assert_eq!(out, input);
+    }
+
     async fn kafka_happy_path(
         server: String,
         sasl: Option<KafkaSaslConfig>,
@@ -323,12 +412,18 @@ mod integration_test {
 
         let topic = format!("test-{}", random_string(10));
         let headers_key = ConfigTargetPath::try_from("headers_key".to_string()).unwrap();
+        let header_1_key = "header-1-key";
+        let header_1_value = "header-1-value";
+        let header_2_key = "header-2-key";
+        let header_2_value = "header-2-value";
         let kafka_auth = KafkaAuthConfig { sasl, tls };
         let config = KafkaSinkConfig {
             bootstrap_servers: server.clone(),
             topic: Template::try_from(format!("{}-%Y%m%d", topic)).unwrap(),
             healthcheck_topic: None,
             key_field: None,
+            partition: None,
+            partitioner: None,
             encoding: TextSerializerConfig::default().into(),
             batch: BatchConfig::default(),
             compression,
@@ -337,6 +432,12 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
+            headers: HashMap::from([(
+                header_2_key.to_string(),
+                Template::try_from(header_2_value).unwrap(),
+            )]),
+            idempotence: false,
+            transaction: None,
             acknowledgements: Default::default(),
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
@@ -346,8 +447,6 @@ mod integration_test {
         let (batch, mut receiver) = BatchNotifier::new_with_receiver();
         let (input, events) = random_lines_with_stream(100, num_events, Some(batch));
 
-        let header_1_key = "header-1-key";
-        let header_1_value = "header-1-value";
         let input_events = events.map(move |mut events| {
             let headers_key = headers_key.clone();
             let mut header_values = ObjectMap::new();
@@ -363,7 +462,7 @@ mod integration_test {
 
         if test_telemetry_tags {
             assert_data_volume_sink_compliance(&DATA_VOLUME_SINK_TAGS, async move {
-                let sink = KafkaSink::new(config).unwrap();
+                let sink = KafkaSink::new(config, Default::default()).unwrap();
                 let sink = VectorSink::from_event_streamsink(sink);
                 sink.run(input_events).await
             })
@@ -371,7 +470,7 @@ mod integration_test {
             .expect("Running sink failed");
         } else {
             assert_sink_compliance(&SINK_TAGS, async move {
-                let sink = KafkaSink::new(config).unwrap();
+                let sink = KafkaSink::new(config, Default::default()).unwrap();
                 let sink = VectorSink::from_event_streamsink(sink);
                 sink.run(input_events).await
             })
@@ -424,6 +523,9 @@ mod integration_test {
                     let header = msg.headers().unwrap().get(0);
                     assert_eq!(header.key, header_1_key);
                     assert_eq!(header.value.unwrap(), header_1_value.as_bytes());
+                    let header = msg.headers().unwrap().get(1);
+                    assert_eq!(header.key, header_2_key);
+                    assert_eq!(header.value.unwrap(), header_2_value.as_bytes());
                 }
                 None if out.len() >= input.len() => break,
                 _ => {
@@ -1,53 +1,27 @@
-use std::time::Duration;
+use std::collections::HashMap;
 
-use rand::Rng;
-use rumqttc::{MqttOptions, QoS, TlsConfiguration, Transport};
-use snafu::{ResultExt, Snafu};
 use vector_lib::codecs::JsonSerializerConfig;
 
+use snafu::ResultExt;
+
 use crate::template::Template;
 use crate::{
     codecs::EncodingConfig,
+    common::mqtt::{ConfigurationError, ConfigurationSnafu, MqttCommonConfig, MqttError, MqttQoS},
     config::{AcknowledgementsConfig, Input, SinkConfig, SinkContext},
     sinks::{
-        mqtt::sink::{ConfigurationSnafu, MqttConnector, MqttError, MqttSink, TlsSnafu},
+        mqtt::sink::{ConnectOptions, MqttConnector, MqttSink},
         prelude::*,
         Healthcheck, VectorSink,
     },
-    tls::{MaybeTlsSettings, TlsEnableableConfig},
 };
 
 /// Configuration for the `mqtt` sink
 #[configurable_component(sink("mqtt"))]
 #[derive(Clone, Debug)]
 pub struct MqttSinkConfig {
-    /// MQTT server address (The broker’s domain name or IP address).
-    #[configurable(metadata(docs::examples = "mqtt.example.com", docs::examples = "127.0.0.1"))]
-    pub host: String,
-
-    /// TCP port of the MQTT server to connect to.
-    #[serde(default = "default_port")]
-    pub port: u16,
-
-    /// MQTT username.
-    pub user: Option<String>,
-
-    /// MQTT password.
-    pub password: Option<String>,
-
-    /// MQTT client ID.
-    pub client_id: Option<String>,
-
-    /// Connection keep-alive interval.
-    #[serde(default = "default_keep_alive")]
-    pub keep_alive: u16,
-
-    /// If set to true, the MQTT session is cleaned on login.
-    #[serde(default = "default_clean_session")]
-    pub clean_session: bool,
-
-    #[configurable(derived)]
-    pub tls: Option<TlsEnableableConfig>,
+    #[serde(flatten)]
+    pub common: MqttCommonConfig,
 
     /// MQTT publish topic (templates allowed)
     pub topic: Template,
@@ -66,67 +40,88 @@ pub struct MqttSinkConfig {
     #[configurable(derived)]
     #[serde(default = "default_qos")]
     pub quality_of_service: MqttQoS,
-}
 
-/// Supported Quality of Service types for MQTT.
-#[configurable_component]
-#[derive(Clone, Copy, Debug, Derivative)]
-#[derivative(Default)]
-#[serde(rename_all = "lowercase")]
-#[allow(clippy::enum_variant_names)]
-pub enum MqttQoS {
-    /// AtLeastOnce.
-    #[derivative(Default)]
-    AtLeastOnce,
-
-    /// AtMostOnce.
-    AtMostOnce,
-
-    /// ExactlyOnce.
-    ExactlyOnce,
-}
+    #[configurable(derived)]
+    #[serde(default)]
+    pub protocol_version: MqttProtocolVersion,
 
-impl From<MqttQoS> for QoS {
-    fn from(value: MqttQoS) -> Self {
-        match value {
-            MqttQoS::AtLeastOnce => QoS::AtLeastOnce,
-            MqttQoS::AtMostOnce => QoS::AtMostOnce,
-            MqttQoS::ExactlyOnce => QoS::ExactlyOnce,
-        }
-    }
+    /// Whether the broker should retain the messages, as the last message of their topic.
+    #[serde(default)]
+    pub retain: bool,
+
+    #[configurable(derived)]
+    pub properties: Option<MqttPublishPropertiesConfig>,
 }
 
-const fn default_port() -> u16 {
-    1883
+const fn default_qos() -> MqttQoS {
+    MqttQoS::AtLeastOnce
 }
 
-const fn default_keep_alive() -> u16 {
-    60
+/// Version of the MQTT protocol to use.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MqttProtocolVersion {
+    /// MQTT 3.1.1.
+    #[default]
+    #[serde(rename = "v3.1.1")]
+    V311,
+
+    /// MQTT 5.0.
+    ///
+    /// This is required to publish messages with `properties`.
+    #[serde(rename = "v5")]
+    V5,
 }
 
-const fn default_clean_session() -> bool {
-    false
+/// MQTT 5 properties to publish the messages with.
+///
+/// Templated properties that fail to render are left unset, rather than dropping the event.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct MqttPublishPropertiesConfig {
+    /// User properties to add to the messages.
+    ///
+    /// Both the keys and the values are forwarded as is, but the values can be templated.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "A user property to add to the messages."
+    ))]
+    #[configurable(metadata(docs::examples = "user_properties_examples()"))]
+    pub user_properties: HashMap<String, Template>,
+
+    /// The content type of the messages.
+    #[configurable(metadata(docs::examples = "application/json"))]
+    pub content_type: Option<Template>,
+
+    /// The topic that receivers of the messages should send their responses to.
+    #[configurable(metadata(docs::examples = "responses/{{ service }}"))]
+    pub response_topic: Option<Template>,
+
+    /// The time, in seconds, after which the broker discards the messages if they are not yet
+    /// delivered.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub message_expiry_interval_secs: Option<u32>,
 }
 
-const fn default_qos() -> MqttQoS {
-    MqttQoS::AtLeastOnce
+fn user_properties_examples() -> HashMap<String, String> {
+    let mut example = HashMap::new();
+    example.insert("service".to_string(), "{{ service }}".to_string());
+    example.insert("source".to_string(), "vector".to_string());
+
+    example
 }
 
 impl Default for MqttSinkConfig {
     fn default() -> Self {
         Self {
-            host: "localhost".into(),
-            port: default_port(),
-            user: None,
-            password: None,
-            client_id: None,
-            keep_alive: default_keep_alive(),
-            clean_session: default_clean_session(),
-            tls: None,
+            common: MqttCommonConfig::default(),
             topic: Template::try_from("vector").expect("Cannot parse as a template"),
             encoding: JsonSerializerConfig::default().into(),
             acknowledgements: AcknowledgementsConfig::default(),
             quality_of_service: MqttQoS::default(),
+            protocol_version: MqttProtocolVersion::default(),
+            retain: false,
+            properties: None,
         }
     }
 }
@@ -155,53 +150,20 @@ impl SinkConfig for MqttSinkConfig {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Snafu)]
-pub enum ConfigurationError {
-    #[snafu(display("Client ID is not allowed to be empty."))]
-    EmptyClientId,
-    #[snafu(display("Username and password must be either both provided or both missing."))]
-    InvalidCredentials,
-}
-
 impl MqttSinkConfig {
     fn build_connector(&self) -> Result<MqttConnector, MqttError> {
-        let client_id = self.client_id.clone().unwrap_or_else(|| {
-            let hash = rand::thread_rng()
-                .sample_iter(&rand_distr::Alphanumeric)
-                .take(6)
-                .map(char::from)
-                .collect::<String>();
-            format!("vectorSink{hash}")
-        });
-
-        if client_id.is_empty() {
-            return Err(ConfigurationError::EmptyClientId).context(ConfigurationSnafu);
-        }
-        let tls = MaybeTlsSettings::from_config(&self.tls, false).context(TlsSnafu)?;
-        let mut options = MqttOptions::new(&client_id, &self.host, self.port);
-        options.set_keep_alive(Duration::from_secs(self.keep_alive.into()));
-        options.set_clean_session(self.clean_session);
-        match (&self.user, &self.password) {
-            (Some(user), Some(password)) => {
-                options.set_credentials(user, password);
+        let options = match self.protocol_version {
+            MqttProtocolVersion::V311 => {
+                if self.properties.is_some() {
+                    return Err(ConfigurationError::PropertiesRequireV5)
+                        .context(ConfigurationSnafu);
+                }
+                ConnectOptions::V311(self.common.build_options("vectorSink")?)
             }
-            (None, None) => {}
-            _ => {
-                return Err(MqttError::Configuration {
-                    source: ConfigurationError::InvalidCredentials,
-                });
+            MqttProtocolVersion::V5 => {
+                ConnectOptions::V5(self.common.build_options_v5("vectorSink")?)
             }
-        }
-        if let Some(tls) = tls.tls() {
-            let ca = tls.authorities_pem().flatten().collect();
-            let client_auth = None;
-            let alpn = Some(vec!["mqtt".into()]);
-            options.set_transport(Transport::Tls(TlsConfiguration::Simple {
-                ca,
-                client_auth,
-                alpn,
-            }));
-        }
+        };
         MqttConnector::new(options, self.topic.to_string())
     }
 }
@@ -214,4 +176,29 @@ mod test {
     fn generate_config() {
         crate::test_util::test_generate_config::<MqttSinkConfig>();
     }
+
+    #[test]
+    fn properties_require_v5() {
+        let config: MqttSinkConfig = toml::from_str(
+            r#"
+            host = "localhost"
+            topic = "vector"
+            encoding.codec = "json"
+            properties.user_properties.service = "{{ service }}"
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            config.build_connector(),
+            Err(MqttError::Configuration {
+                source: ConfigurationError::PropertiesRequireV5
+            })
+        ));
+
+        let config = MqttSinkConfig {
+            protocol_version: MqttProtocolVersion::V5,
+            ..config
+        };
+        assert!(config.build_connector().is_ok());
+    }
 }
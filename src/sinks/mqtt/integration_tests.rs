@@ -1,11 +1,13 @@
+use crate::common::mqtt::{MqttCommonConfig, MqttQoS};
 use crate::config::{SinkConfig, SinkContext};
-use crate::sinks::mqtt::config::MqttQoS;
 use crate::sinks::mqtt::MqttSinkConfig;
 use crate::template::Template;
 use crate::test_util::components::{run_and_assert_sink_compliance, SINK_TAGS};
 use crate::test_util::{random_lines_with_stream, trace_init};
 use rumqttc::{AsyncClient, Event, Incoming, MqttOptions, QoS};
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
+
+use super::config::{MqttProtocolVersion, MqttPublishPropertiesConfig};
 
 fn mqtt_broker_address() -> String {
     let result = std::env::var("MQTT_BROKER_ADDRESS").unwrap_or_else(|_| "emqx".into());
@@ -26,8 +28,11 @@ async fn mqtt_happy() {
 
     let topic = "test";
     let cnf = MqttSinkConfig {
-        host: mqtt_broker_address(),
-        port: mqtt_broker_port(),
+        common: MqttCommonConfig {
+            host: mqtt_broker_address(),
+            port: mqtt_broker_port(),
+            ..Default::default()
+        },
         topic: Template::try_from(topic).expect("Cannot parse the topic template"),
         quality_of_service: MqttQoS::AtLeastOnce,
         ..Default::default()
@@ -102,3 +107,81 @@ async fn mqtt_happy() {
 
     assert_eq!(messages, input);
 }
+
+#[tokio::test]
+async fn mqtt_v5_properties() {
+    use rumqttc::v5::{
+        mqttbytes::{v5::Packet, QoS},
+        AsyncClient, Event, MqttOptions,
+    };
+
+    trace_init();
+
+    let topic = "test-v5";
+    let cnf = MqttSinkConfig {
+        common: MqttCommonConfig {
+            host: mqtt_broker_address(),
+            port: mqtt_broker_port(),
+            ..Default::default()
+        },
+        topic: Template::try_from(topic).expect("Cannot parse the topic template"),
+        quality_of_service: MqttQoS::AtLeastOnce,
+        protocol_version: MqttProtocolVersion::V5,
+        properties: Some(MqttPublishPropertiesConfig {
+            user_properties: HashMap::from([(
+                "source".to_owned(),
+                Template::try_from("vector").unwrap(),
+            )]),
+            content_type: Some(Template::try_from("application/json").unwrap()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let cx = SinkContext::default();
+    let (sink, healthcheck) = cnf.build(cx).await.expect("Cannot build the sink");
+    healthcheck.await.expect("Health check failed");
+
+    let mut mqtt_options = MqttOptions::new(
+        "integration-test-consumer-v5",
+        mqtt_broker_address(),
+        mqtt_broker_port(),
+    );
+    mqtt_options.set_keep_alive(Duration::from_secs(5));
+
+    let (client, mut eventloop) = AsyncClient::new(mqtt_options, 10);
+    client
+        .subscribe(topic, QoS::AtLeastOnce)
+        .await
+        .expect("Cannot subscribe to the topic");
+
+    // Wait for the subscription to be acknowledged before publishing anything.
+    loop {
+        let event = eventloop.poll().await.expect("Cannot poll the event loop");
+        if let Event::Incoming(Packet::SubAck(_)) = event {
+            break;
+        }
+    }
+
+    let (input, events) = random_lines_with_stream(100, 1, None);
+    run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+
+    let publish = loop {
+        let event = tokio::time::timeout(Duration::from_secs(5), eventloop.poll())
+            .await
+            .expect("Timed out waiting for the message")
+            .expect("Cannot poll the event loop");
+        if let Event::Incoming(Packet::Publish(publish)) = event {
+            break publish;
+        }
+    };
+
+    let message = serde_json::from_slice::<serde_json::Value>(&publish.payload).unwrap();
+    assert_eq!(message["message"].as_str().unwrap(), input[0]);
+    let properties = publish.properties.expect("The message has no properties");
+    assert_eq!(
+        properties.user_properties,
+        vec![("source".to_owned(), "vector".to_owned())]
+    );
+    assert_eq!(properties.content_type.as_deref(), Some("application/json"));
+}
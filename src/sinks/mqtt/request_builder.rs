@@ -1,6 +1,7 @@
 use std::io;
 
 use bytes::{Bytes, BytesMut};
+use rumqttc::v5::mqttbytes::v5::PublishProperties;
 use tokio_util::codec::Encoder as _;
 
 use crate::sinks::prelude::*;
@@ -9,6 +10,7 @@ use super::{service::MqttRequest, sink::MqttEvent};
 
 pub(super) struct MqttMetadata {
     topic: String,
+    properties: Option<PublishProperties>,
     finalizers: EventFinalizers,
 }
 
@@ -69,6 +71,7 @@ impl RequestBuilder<MqttEvent> for MqttRequestBuilder {
 
         let metadata = MqttMetadata {
             topic: input.topic,
+            properties: input.properties,
             finalizers: input.event.take_finalizers(),
         };
 
@@ -85,6 +88,7 @@ impl RequestBuilder<MqttEvent> for MqttRequestBuilder {
         MqttRequest {
             body,
             topic: mqtt_metadata.topic,
+            properties: mqtt_metadata.properties,
             finalizers: mqtt_metadata.finalizers,
             metadata,
         }
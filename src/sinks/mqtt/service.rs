@@ -3,10 +3,10 @@ use std::task::{Context, Poll};
 use crate::sinks::prelude::*;
 use bytes::Bytes;
 use futures::future::BoxFuture;
-use rumqttc::{AsyncClient, ClientError};
+use rumqttc::{v5::mqttbytes::v5::PublishProperties, AsyncClient, ClientError};
 use snafu::Snafu;
 
-use super::config::MqttQoS;
+use crate::common::mqtt::MqttQoS;
 
 pub(super) struct MqttResponse {
     byte_size: usize,
@@ -30,6 +30,7 @@ impl DriverResponse for MqttResponse {
 pub(super) struct MqttRequest {
     pub(super) body: Bytes,
     pub(super) topic: String,
+    pub(super) properties: Option<PublishProperties>,
     pub(super) finalizers: EventFinalizers,
     pub(super) metadata: RequestMetadata,
 }
@@ -50,15 +51,25 @@ impl MetaDescriptive for MqttRequest {
     }
 }
 
+/// A client for the configured protocol version.
+#[derive(Clone)]
+pub(super) enum MqttClient {
+    V311(AsyncClient),
+    V5(rumqttc::v5::AsyncClient),
+}
+
 pub(super) struct MqttService {
-    pub(super) client: AsyncClient,
+    pub(super) client: MqttClient,
     pub(super) quality_of_service: MqttQoS,
+    pub(super) retain: bool,
 }
 
 #[derive(Debug, Snafu)]
 pub(super) enum MqttError {
     #[snafu(display("error"))]
     Error { error: ClientError },
+    #[snafu(display("error"))]
+    ErrorV5 { error: rumqttc::v5::ClientError },
 }
 
 impl Service<MqttRequest> for MqttService {
@@ -72,20 +83,38 @@ impl Service<MqttRequest> for MqttService {
 
     fn call(&mut self, req: MqttRequest) -> Self::Future {
         let quality_of_service = self.quality_of_service;
+        let retain = self.retain;
         let client = self.client.clone();
 
         Box::pin(async move {
             let byte_size = req.body.len();
 
-            let res = client
-                .publish(&req.topic, quality_of_service.into(), false, req.body)
-                .await;
+            let res = match (client, req.properties) {
+                (MqttClient::V311(client), _) => client
+                    .publish(&req.topic, quality_of_service.into(), retain, req.body)
+                    .await
+                    .map_err(|error| MqttError::Error { error }),
+                (MqttClient::V5(client), Some(properties)) => client
+                    .publish_with_properties(
+                        &req.topic,
+                        quality_of_service.into(),
+                        retain,
+                        req.body,
+                        properties,
+                    )
+                    .await
+                    .map_err(|error| MqttError::ErrorV5 { error }),
+                (MqttClient::V5(client), None) => client
+                    .publish(&req.topic, quality_of_service.into(), retain, req.body)
+                    .await
+                    .map_err(|error| MqttError::ErrorV5 { error }),
+            };
             match res {
                 Ok(()) => Ok(MqttResponse {
                     byte_size,
                     json_size: req.metadata.into_events_estimated_json_encoded_byte_size(),
                 }),
-                Err(error) => Err(MqttError::Error { error }),
+                Err(error) => Err(error),
             }
         })
     }
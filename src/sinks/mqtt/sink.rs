@@ -1,44 +1,69 @@
 use async_trait::async_trait;
 use futures::{stream::BoxStream, StreamExt};
-use rumqttc::{AsyncClient, EventLoop, MqttOptions};
-use snafu::{ResultExt, Snafu};
-use vector_lib::tls::TlsError;
+use rumqttc::{v5::mqttbytes::v5::PublishProperties, AsyncClient, MqttOptions};
+use snafu::ResultExt;
 
+use crate::common::mqtt::{MqttError, MqttQoS, TopicTemplateSnafu};
 use crate::internal_events::MqttConnectionError;
 use crate::sinks::prelude::*;
 
 use super::{
-    config::{ConfigurationError, MqttQoS},
+    config::MqttPublishPropertiesConfig,
     request_builder::{MqttEncoder, MqttRequestBuilder},
-    service::MqttService,
+    service::{MqttClient, MqttService},
     MqttSinkConfig,
 };
 
-#[derive(Debug, Snafu)]
-#[snafu(visibility(pub))]
-pub enum MqttError {
-    #[snafu(display("invalid topic template: {}", source))]
-    TopicTemplate { source: TemplateParseError },
-    #[snafu(display("TLS error: {}", source))]
-    Tls { source: TlsError },
-    #[snafu(display("MQTT configuration error: {}", source))]
-    Configuration { source: ConfigurationError },
+/// The options to connect to the broker with, for the configured protocol version.
+#[derive(Clone)]
+pub enum ConnectOptions {
+    V311(MqttOptions),
+    V5(rumqttc::v5::MqttOptions),
 }
 
 #[derive(Clone)]
 pub struct MqttConnector {
-    options: MqttOptions,
+    options: ConnectOptions,
     topic: Template,
 }
 
 impl MqttConnector {
-    pub fn new(options: MqttOptions, topic: String) -> Result<Self, MqttError> {
+    pub fn new(options: ConnectOptions, topic: String) -> Result<Self, MqttError> {
         let topic = Template::try_from(topic).context(TopicTemplateSnafu)?;
         Ok(Self { options, topic })
     }
 
-    fn connect(&self) -> (AsyncClient, EventLoop) {
-        AsyncClient::new(self.options.clone(), 1024)
+    /// Creates a client, spawning the event loop that keeps its connection moving forward.
+    fn connect(&self) -> MqttClient {
+        // If an error is returned by the event loop there is currently no way to tie this back
+        // to the event that was posted which means we can't accurately provide delivery
+        // guarantees.
+        // We need this issue resolved first:
+        // https://github.com/bytebeamio/rumqtt/issues/349
+        match &self.options {
+            ConnectOptions::V311(options) => {
+                let (client, mut connection) = AsyncClient::new(options.clone(), 1024);
+                tokio::spawn(async move {
+                    loop {
+                        if let Err(error) = connection.poll().await {
+                            emit!(MqttConnectionError { error });
+                        }
+                    }
+                });
+                MqttClient::V311(client)
+            }
+            ConnectOptions::V5(options) => {
+                let (client, mut connection) = rumqttc::v5::AsyncClient::new(options.clone(), 1024);
+                tokio::spawn(async move {
+                    loop {
+                        if let Err(error) = connection.poll().await {
+                            emit!(MqttConnectionError { error });
+                        }
+                    }
+                });
+                MqttClient::V5(client)
+            }
+        }
     }
 
     pub async fn healthcheck(&self) -> crate::Result<()> {
@@ -52,10 +77,13 @@ pub struct MqttSink {
     encoder: Encoder<()>,
     connector: MqttConnector,
     quality_of_service: MqttQoS,
+    retain: bool,
+    properties: Option<MqttPublishPropertiesConfig>,
 }
 
 pub(super) struct MqttEvent {
     pub(super) topic: String,
+    pub(super) properties: Option<PublishProperties>,
     pub(super) event: Event,
 }
 
@@ -70,6 +98,8 @@ impl MqttSink {
             encoder,
             connector,
             quality_of_service: config.quality_of_service,
+            retain: config.retain,
+            properties: config.properties.clone(),
         })
     }
 
@@ -87,34 +117,25 @@ impl MqttSink {
             })
             .ok()?;
 
-        Some(MqttEvent { topic, event })
+        let properties = self
+            .properties
+            .as_ref()
+            .map(|properties| render_properties(properties, &event));
+
+        Some(MqttEvent {
+            topic,
+            properties,
+            event,
+        })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
-        let (client, mut connection) = self.connector.connect();
-
-        // This is necessary to keep the mqtt event loop moving forward.
-        tokio::spawn(async move {
-            loop {
-                // If an error is returned here there is currently no way to tie this back
-                // to the event that was posted which means we can't accurately provide
-                // delivery guarantees.
-                // We need this issue resolved first:
-                // https://github.com/bytebeamio/rumqtt/issues/349
-                match connection.poll().await {
-                    Ok(_) => {}
-                    Err(connection_error) => {
-                        emit!(MqttConnectionError {
-                            error: connection_error
-                        });
-                    }
-                }
-            }
-        });
+        let client = self.connector.connect();
 
         let service = ServiceBuilder::new().service(MqttService {
             client,
             quality_of_service: self.quality_of_service,
+            retain: self.retain,
         });
 
         let request_builder = MqttRequestBuilder {
@@ -149,3 +170,40 @@ impl StreamSink<Event> for MqttSink {
         self.run_inner(input).await
     }
 }
+
+/// Renders the publish properties for the event, leaving the templated ones that fail to render
+/// unset.
+fn render_properties(config: &MqttPublishPropertiesConfig, event: &Event) -> PublishProperties {
+    let render = |template: &Template, field: &'static str| {
+        template
+            .render_string(event)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: false,
+                })
+            })
+            .ok()
+    };
+
+    PublishProperties {
+        message_expiry_interval: config.message_expiry_interval_secs,
+        content_type: config
+            .content_type
+            .as_ref()
+            .and_then(|template| render(template, "properties.content_type")),
+        response_topic: config
+            .response_topic
+            .as_ref()
+            .and_then(|template| render(template, "properties.response_topic")),
+        user_properties: config
+            .user_properties
+            .iter()
+            .filter_map(|(key, template)| {
+                render(template, "properties.user_properties").map(|value| (key.clone(), value))
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
@@ -76,6 +76,16 @@ pub struct NatsSinkConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub(super) request: TowerRequestConfig<NatsTowerRequestConfigDefaults>,
+
+    /// Whether to publish messages to [JetStream][jetstream].
+    ///
+    /// When enabled, the sink waits for the server to acknowledge each message, so that events are
+    /// only marked as delivered once the message is stored by the stream capturing its subject.
+    /// Publishing to a subject that no stream captures then fails.
+    ///
+    /// [jetstream]: https://docs.nats.io/nats-concepts/jetstream
+    #[serde(default)]
+    pub(super) jetstream: bool,
 }
 
 fn default_name() -> String {
@@ -93,6 +103,7 @@ impl GenerateConfig for NatsSinkConfig {
             tls: None,
             url: "nats://127.0.0.1:4222".into(),
             request: Default::default(),
+            jetstream: false,
         })
         .unwrap()
     }
@@ -27,4 +27,8 @@ enum NatsError {
     Connect { source: async_nats::ConnectError },
     #[snafu(display("NATS Server Error: {}", source))]
     ServerError { source: async_nats::Error },
+    #[snafu(display("NATS JetStream Publish Error: {}", source))]
+    JetStreamPublish {
+        source: async_nats::jetstream::context::PublishError,
+    },
 }
@@ -4,14 +4,16 @@ use std::{
 };
 
 use futures_util::TryFutureExt;
+use snafu::ResultExt;
 
 use crate::sinks::prelude::*;
 
-use super::{request_builder::NatsRequest, NatsError};
+use super::{request_builder::NatsRequest, JetStreamPublishSnafu, NatsError};
 
 #[derive(Clone)]
 pub(super) struct NatsService {
     pub(super) connection: Arc<async_nats::Client>,
+    pub(super) jetstream: Option<async_nats::jetstream::Context>,
 }
 
 pub(super) struct NatsResponse {
@@ -46,6 +48,22 @@ impl Service<NatsRequest> for NatsService {
     fn call(&mut self, req: NatsRequest) -> Self::Future {
         let connection = Arc::clone(&self.connection);
 
+        if let Some(jetstream) = self.jetstream.clone() {
+            return Box::pin(async move {
+                // The first await sends the message, and the second waits for the stream to
+                // acknowledge that it was stored.
+                jetstream
+                    .publish(req.subject, req.bytes)
+                    .await
+                    .context(JetStreamPublishSnafu)?
+                    .await
+                    .context(JetStreamPublishSnafu)?;
+                Ok(NatsResponse {
+                    metadata: req.metadata,
+                })
+            });
+        }
+
         Box::pin(async move {
             match connection
                 .publish(req.subject, req.bytes)
@@ -21,6 +21,7 @@ pub(super) struct NatsSink {
     transformer: Transformer,
     encoder: Encoder<()>,
     connection: Arc<async_nats::Client>,
+    jetstream: Option<async_nats::jetstream::Context>,
     subject: Template,
 }
 
@@ -43,6 +44,9 @@ impl NatsSink {
 
     pub(super) async fn new(config: NatsSinkConfig) -> Result<Self, NatsError> {
         let connection = Arc::new(config.connect().await?);
+        let jetstream = config
+            .jetstream
+            .then(|| async_nats::jetstream::new(connection.as_ref().clone()));
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build().context(EncodingSnafu)?;
         let encoder = Encoder::<()>::new(serializer);
@@ -52,6 +56,7 @@ impl NatsSink {
         Ok(NatsSink {
             request,
             connection,
+            jetstream,
             transformer,
             encoder,
             subject,
@@ -72,6 +77,7 @@ impl NatsSink {
             .settings(request, NatsRetryLogic)
             .service(NatsService {
                 connection: Arc::clone(&self.connection),
+                jetstream: self.jetstream.clone(),
             });
 
         input
@@ -1,3 +1,5 @@
+use std::num::NonZeroUsize;
+
 use redis::{aio::ConnectionManager, RedisResult};
 use snafu::prelude::*;
 
@@ -30,6 +32,12 @@ pub enum DataTypeConfig {
     ///
     /// Redis channels function in a pub/sub fashion, allowing many-to-many broadcasting and receiving.
     Channel,
+
+    /// The Redis `stream` type.
+    ///
+    /// Each message is appended to the stream as a new entry, which consumers can read at their own
+    /// pace and acknowledge.
+    Stream,
 }
 
 /// List-specific options.
@@ -61,6 +69,36 @@ pub enum Method {
     LPush,
 }
 
+/// Stream-specific options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The field of the stream entries to store the encoded message in.
+    #[serde(default = "default_stream_field")]
+    #[derivative(Default(value = "default_stream_field()"))]
+    #[configurable(metadata(docs::examples = "payload"))]
+    pub(super) field: String,
+
+    /// The maximum number of entries to keep in the stream.
+    ///
+    /// Older entries are trimmed as new ones are added. By default, the stream is not trimmed.
+    #[configurable(metadata(docs::examples = 100000))]
+    pub(super) maxlen: Option<NonZeroUsize>,
+
+    /// Whether to trim the stream to roughly `maxlen` entries, rather than exactly.
+    ///
+    /// Approximate trimming is much more efficient, and the stream is never trimmed below `maxlen`.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    pub(super) approximate: bool,
+}
+
+fn default_stream_field() -> String {
+    "message".to_owned()
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 pub struct RedisDefaultBatchSettings;
 
@@ -86,6 +124,10 @@ pub struct RedisSinkConfig {
     #[serde(alias = "list")]
     pub(super) list_option: Option<ListOption>,
 
+    #[configurable(derived)]
+    #[serde(alias = "stream")]
+    pub(super) stream_option: Option<StreamOption>,
+
     /// The URL of the Redis endpoint to connect to.
     ///
     /// The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
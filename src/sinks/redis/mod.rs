@@ -19,7 +19,7 @@ use snafu::Snafu;
 
 use crate::sinks::prelude::*;
 
-use self::config::Method;
+use self::config::{Method, StreamOption};
 
 use super::util::EncodedLength;
 
@@ -31,7 +31,7 @@ pub(super) enum RedisSinkError {
     SendError { source: RedisError },
 }
 
-#[derive(Clone, Copy, Debug, Derivative)]
+#[derive(Clone, Debug, Derivative)]
 #[derivative(Default)]
 pub enum DataType {
     /// The Redis `list` type.
@@ -44,6 +44,11 @@ pub enum DataType {
     ///
     /// Redis channels function in a pub/sub fashion, allowing many-to-many broadcasting and receiving.
     Channel,
+
+    /// The Redis `stream` type.
+    ///
+    /// Messages are appended to the stream as entries, which is trimmed as configured.
+    Stream(StreamOption),
 }
 
 /// Wrapper for an `Event` that also stored the rendered key.
@@ -1,6 +1,6 @@
 use std::task::{Context, Poll};
 
-use redis::aio::ConnectionManager;
+use redis::{aio::ConnectionManager, streams::StreamMaxlen};
 
 use crate::sinks::prelude::*;
 
@@ -54,13 +54,44 @@ impl Service<RedisRequest> for RedisService {
                         pipe.publish(kv.key, kv.value.as_ref());
                     }
                 }
+                super::DataType::Stream(ref options) => {
+                    if count > 1 {
+                        pipe.atomic();
+                    }
+                    let items = [(options.field.as_str(), kv.value.as_ref())];
+                    match options.maxlen {
+                        Some(maxlen) if options.approximate => pipe.xadd_maxlen(
+                            kv.key,
+                            StreamMaxlen::Approx(maxlen.get()),
+                            "*",
+                            &items,
+                        ),
+                        Some(maxlen) => pipe.xadd_maxlen(
+                            kv.key,
+                            StreamMaxlen::Equals(maxlen.get()),
+                            "*",
+                            &items,
+                        ),
+                        None => pipe.xadd(kv.key, "*", &items),
+                    };
+                }
             }
         }
 
         let byte_size = kvs.metadata.events_byte_size();
+        let is_stream = matches!(self.data_type, super::DataType::Stream(_));
 
         Box::pin(async move {
-            match pipe.query_async(&mut conn).await {
+            let result = if is_stream {
+                // Adding an entry replies with its ID rather than with a count, and only ever
+                // fails with an error.
+                pipe.query_async::<_, Vec<String>>(&mut conn)
+                    .await
+                    .map(|ids| vec![true; ids.len()])
+            } else {
+                pipe.query_async(&mut conn).await
+            };
+            match result {
                 Ok(event_status) => Ok(RedisResponse {
                     event_status,
                     events_byte_size: kvs.metadata.into_events_estimated_json_encoded_byte_size(),
@@ -27,6 +27,9 @@ impl RedisSink {
         let data_type = match config.data_type {
             DataTypeConfig::Channel => super::DataType::Channel,
             DataTypeConfig::List => super::DataType::List(method.unwrap_or_default()),
+            DataTypeConfig::Stream => {
+                super::DataType::Stream(config.stream_option.clone().unwrap_or_default())
+            }
         };
 
         let batcher_settings = config.batch.validate()?.into_batcher_settings()?;
@@ -71,7 +74,7 @@ impl RedisSink {
 
         let service = RedisService {
             conn: self.conn.clone(),
-            data_type: self.data_type,
+            data_type: self.data_type.clone(),
         };
 
         let service = ServiceBuilder::new()
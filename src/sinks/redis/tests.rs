@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, num::NonZeroUsize};
 
 use vector_lib::codecs::{JsonSerializerConfig, TextSerializerConfig};
 use vector_lib::event::LogEvent;
@@ -15,6 +15,25 @@ fn generate_config() {
     crate::test_util::test_generate_config::<RedisSinkConfig>();
 }
 
+#[test]
+fn parse_stream_config() {
+    let config: RedisSinkConfig = toml::from_str(
+        r#"
+        endpoint = "redis://127.0.0.1:6379/0"
+        key = "vector"
+        data_type = "stream"
+        stream.maxlen = 1000
+        encoding.codec = "json"
+        "#,
+    )
+    .unwrap();
+
+    let stream = config.stream_option.unwrap();
+    assert_eq!(stream.field, "message");
+    assert_eq!(stream.maxlen.map(NonZeroUsize::get), Some(1000));
+    assert!(stream.approximate);
+}
+
 #[test]
 fn redis_event_json() {
     let msg = "hello_world".to_owned();
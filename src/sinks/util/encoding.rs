@@ -3,11 +3,18 @@ use std::io;
 use bytes::BytesMut;
 use itertools::{Itertools, Position};
 use tokio_util::codec::Encoder as _;
+#[cfg(feature = "codecs-parquet")]
+use vector_lib::codecs::encoding::BatchSerializer;
 use vector_lib::codecs::encoding::Framer;
 use vector_lib::request_metadata::GroupedCountByteSize;
 use vector_lib::{config::telemetry, EstimatedJsonEncodedSizeOf};
 
-use crate::{codecs::Transformer, event::Event, internal_events::EncoderWriteError};
+use super::Compression;
+use crate::{
+    codecs::{EncoderKind, Transformer},
+    event::Event,
+    internal_events::EncoderWriteError,
+};
 
 pub trait Encoder<T> {
     /// Encodes the input into the provided writer.
@@ -28,47 +35,93 @@ impl Encoder<Vec<Event>> for (Transformer, crate::codecs::Encoder<Framer>) {
         events: Vec<Event>,
         writer: &mut dyn io::Write,
     ) -> io::Result<(usize, GroupedCountByteSize)> {
-        let mut encoder = self.1.clone();
-        let mut bytes_written = 0;
-        let mut n_events_pending = events.len();
-        let batch_prefix = encoder.batch_prefix();
-        write_all(writer, n_events_pending, batch_prefix)?;
-        bytes_written += batch_prefix.len();
+        encode_framed(&self.0, &self.1, events, writer)
+    }
+}
 
-        let mut byte_size = telemetry().create_request_count_byte_size();
+impl Encoder<Vec<Event>> for (Transformer, EncoderKind) {
+    fn encode_input(
+        &self,
+        events: Vec<Event>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        match &self.1 {
+            EncoderKind::Framed(encoder) => encode_framed(&self.0, encoder, events, writer),
+            #[cfg(feature = "codecs-parquet")]
+            EncoderKind::Batch(serializer) => encode_batch(&self.0, serializer, events, writer),
+        }
+    }
+}
 
-        for (position, mut event) in events.into_iter().with_position() {
-            self.0.transform(&mut event);
-
-            // Ensure the json size is calculated after any fields have been removed
-            // by the transformer.
-            byte_size.add_event(&event, event.estimated_json_encoded_size_of());
-
-            let mut bytes = BytesMut::new();
-            match position {
-                Position::Last | Position::Only => {
-                    encoder
-                        .serialize(event, &mut bytes)
-                        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
-                }
-                _ => {
-                    encoder
-                        .encode(event, &mut bytes)
-                        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
-                }
+fn encode_framed(
+    transformer: &Transformer,
+    encoder: &crate::codecs::Encoder<Framer>,
+    events: Vec<Event>,
+    writer: &mut dyn io::Write,
+) -> io::Result<(usize, GroupedCountByteSize)> {
+    let mut encoder = encoder.clone();
+    let mut bytes_written = 0;
+    let mut n_events_pending = events.len();
+    let batch_prefix = encoder.batch_prefix();
+    write_all(writer, n_events_pending, batch_prefix)?;
+    bytes_written += batch_prefix.len();
+
+    let mut byte_size = telemetry().create_request_count_byte_size();
+
+    for (position, mut event) in events.into_iter().with_position() {
+        transformer.transform(&mut event);
+
+        // Ensure the json size is calculated after any fields have been removed
+        // by the transformer.
+        byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+
+        let mut bytes = BytesMut::new();
+        match position {
+            Position::Last | Position::Only => {
+                encoder
+                    .serialize(event, &mut bytes)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+            }
+            _ => {
+                encoder
+                    .encode(event, &mut bytes)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
             }
-            write_all(writer, n_events_pending, &bytes)?;
-            bytes_written += bytes.len();
-            n_events_pending -= 1;
         }
+        write_all(writer, n_events_pending, &bytes)?;
+        bytes_written += bytes.len();
+        n_events_pending -= 1;
+    }
+
+    let batch_suffix = encoder.batch_suffix();
+    assert!(n_events_pending == 0);
+    write_all(writer, 0, batch_suffix)?;
+    bytes_written += batch_suffix.len();
 
-        let batch_suffix = encoder.batch_suffix();
-        assert!(n_events_pending == 0);
-        write_all(writer, 0, batch_suffix)?;
-        bytes_written += batch_suffix.len();
+    Ok((bytes_written, byte_size))
+}
 
-        Ok((bytes_written, byte_size))
+#[cfg(feature = "codecs-parquet")]
+fn encode_batch(
+    transformer: &Transformer,
+    serializer: &BatchSerializer,
+    mut events: Vec<Event>,
+    writer: &mut dyn io::Write,
+) -> io::Result<(usize, GroupedCountByteSize)> {
+    let mut byte_size = telemetry().create_request_count_byte_size();
+    for event in events.iter_mut() {
+        transformer.transform(event);
+        byte_size.add_event(event, event.estimated_json_encoded_size_of());
     }
+
+    let n_events = events.len();
+    let mut bytes = BytesMut::new();
+    serializer
+        .encode(events, &mut bytes)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    write_all(writer, n_events, &bytes)?;
+
+    Ok((bytes.len(), byte_size))
 }
 
 impl Encoder<Event> for (Transformer, crate::codecs::Encoder<()>) {
@@ -100,6 +153,21 @@ impl Encoder<Event> for (Transformer, crate::codecs::Encoder<()>) {
 /// * `writer`           - The object implementing io::Write to write data to.
 /// * `n_events_pending` - The number of events that are dropped if this write fails.
 /// * `buf`              - The buffer to write.
+/// Returns the compression to apply to the objects written by a sink with the given encoder, and
+/// the extension to name them with by default, given the configured compression.
+///
+/// Batch codecs compress their output themselves, so the configured compression is not applied
+/// on top of them, and objects are named with the extension of the codec instead.
+pub const fn object_compression(
+    encoder: &EncoderKind,
+    compression: Compression,
+) -> (Compression, &'static str) {
+    match encoder.file_extension() {
+        Some(extension) => (Compression::None, extension),
+        None => (compression, compression.extension()),
+    }
+}
+
 pub fn write_all(
     writer: &mut dyn io::Write,
     n_events_pending: usize,
@@ -335,6 +403,45 @@ mod tests {
         assert_eq!(CountByteSize(3, input_json_size), json_size.size().unwrap());
     }
 
+    #[cfg(feature = "codecs-parquet")]
+    #[test]
+    fn test_encode_batch_parquet() {
+        use vector_lib::codecs::encoding::ParquetSerializerConfig;
+
+        let encoding = (
+            Transformer::new(None, Some(vec!["secret".into()]), None).unwrap(),
+            EncoderKind::Batch(ParquetSerializerConfig::default().build().unwrap().into()),
+        );
+
+        let mut writer = Vec::new();
+        let input = vec![
+            Event::Log(LogEvent::from(BTreeMap::from([
+                (KeyString::from("key"), Value::from("value1")),
+                (KeyString::from("secret"), Value::from("hunter2")),
+            ]))),
+            Event::Log(LogEvent::from(BTreeMap::from([(
+                KeyString::from("key"),
+                Value::from("value2"),
+            )]))),
+        ];
+
+        let input_json_size = input
+            .iter()
+            .cloned()
+            .map(|mut event| {
+                event.as_mut_log().remove("secret");
+                event.estimated_json_encoded_size_of()
+            })
+            .sum::<JsonSize>();
+
+        let (written, json_size) = encoding.encode_input(input, &mut writer).unwrap();
+        assert_eq!(written, writer.len());
+        assert!(writer.starts_with(b"PAR1"));
+        assert!(writer.ends_with(b"PAR1"));
+        assert!(!writer.windows(7).any(|window| window == b"hunter2"));
+        assert_eq!(CountByteSize(2, input_json_size), json_size.size().unwrap());
+    }
+
     #[test]
     fn test_encode_event_json() {
         let encoding = (
@@ -1,28 +1,9 @@
-use std::{
-    fmt::Debug,
-    io,
-    net::SocketAddr,
-    num::NonZeroU64,
-    task::{Context, Poll},
-    time::{Duration, Instant},
-};
+use std::{fmt::Debug, num::NonZeroU64, time::Instant};
 
 use async_trait::async_trait;
 use bytes::BytesMut;
 use futures::{pin_mut, sink::SinkExt, stream::BoxStream, Sink, Stream, StreamExt};
-use snafu::{ResultExt, Snafu};
-use tokio::{net::TcpStream, time};
-use tokio_tungstenite::{
-    client_async_with_config,
-    tungstenite::{
-        client::{uri_mode, IntoClientRequest},
-        error::{Error as WsError, ProtocolError, UrlError},
-        handshake::client::Request as WsRequest,
-        protocol::{Message, WebSocketConfig},
-        stream::Mode as UriMode,
-    },
-    WebSocketStream as WsStream,
-};
+use tokio_tungstenite::tungstenite::{error::Error as WsError, protocol::Message};
 use tokio_util::codec::Encoder as _;
 use vector_lib::{
     emit,
@@ -34,160 +15,13 @@ use vector_lib::{
 
 use crate::{
     codecs::{Encoder, Transformer},
-    dns,
+    common::websocket::{check_received_pong_time, is_closed, PingInterval, WebSocketConnector},
     event::{Event, EventStatus, Finalizable},
-    http::Auth,
-    internal_events::{
-        ConnectionOpen, OpenGauge, WsConnectionError, WsConnectionEstablished,
-        WsConnectionFailedError, WsConnectionShutdown,
-    },
-    sinks::util::{retries::ExponentialBackoff, StreamSink},
+    internal_events::{ConnectionOpen, OpenGauge, WsConnectionError, WsConnectionShutdown},
+    sinks::util::StreamSink,
     sinks::websocket::config::WebSocketSinkConfig,
-    tls::{MaybeTlsSettings, MaybeTlsStream, TlsError},
 };
 
-#[derive(Debug, Snafu)]
-#[snafu(visibility(pub))]
-pub enum WebSocketError {
-    #[snafu(display("Creating WebSocket client failed: {}", source))]
-    CreateFailed { source: WsError },
-    #[snafu(display("Connect error: {}", source))]
-    ConnectError { source: TlsError },
-    #[snafu(display("Unable to resolve DNS: {}", source))]
-    DnsError { source: dns::DnsError },
-    #[snafu(display("No addresses returned."))]
-    NoAddresses,
-}
-
-#[derive(Clone)]
-pub struct WebSocketConnector {
-    uri: String,
-    host: String,
-    port: u16,
-    tls: MaybeTlsSettings,
-    auth: Option<Auth>,
-}
-
-impl WebSocketConnector {
-    pub fn new(
-        uri: String,
-        tls: MaybeTlsSettings,
-        auth: Option<Auth>,
-    ) -> Result<Self, WebSocketError> {
-        let request = (&uri).into_client_request().context(CreateFailedSnafu)?;
-        let (host, port) = Self::extract_host_and_port(&request).context(CreateFailedSnafu)?;
-
-        Ok(Self {
-            uri,
-            host,
-            port,
-            tls,
-            auth,
-        })
-    }
-
-    fn extract_host_and_port(request: &WsRequest) -> Result<(String, u16), WsError> {
-        let host = request
-            .uri()
-            .host()
-            .ok_or(WsError::Url(UrlError::NoHostName))?
-            .to_string();
-        let mode = uri_mode(request.uri())?;
-        let port = request.uri().port_u16().unwrap_or(match mode {
-            UriMode::Tls => 443,
-            UriMode::Plain => 80,
-        });
-
-        Ok((host, port))
-    }
-
-    const fn fresh_backoff() -> ExponentialBackoff {
-        ExponentialBackoff::from_millis(2)
-            .factor(250)
-            .max_delay(Duration::from_secs(60))
-    }
-
-    async fn tls_connect(&self) -> Result<MaybeTlsStream<TcpStream>, WebSocketError> {
-        let ip = dns::Resolver
-            .lookup_ip(self.host.clone())
-            .await
-            .context(DnsSnafu)?
-            .next()
-            .ok_or(WebSocketError::NoAddresses)?;
-
-        let addr = SocketAddr::new(ip, self.port);
-        self.tls
-            .connect(&self.host, &addr)
-            .await
-            .context(ConnectSnafu)
-    }
-
-    async fn connect(&self) -> Result<WsStream<MaybeTlsStream<TcpStream>>, WebSocketError> {
-        let mut request = (&self.uri)
-            .into_client_request()
-            .context(CreateFailedSnafu)?;
-
-        if let Some(auth) = &self.auth {
-            auth.apply(&mut request);
-        }
-
-        let maybe_tls = self.tls_connect().await?;
-
-        let ws_config = WebSocketConfig::default();
-
-        let (ws_stream, _response) = client_async_with_config(request, maybe_tls, Some(ws_config))
-            .await
-            .context(CreateFailedSnafu)?;
-
-        Ok(ws_stream)
-    }
-
-    async fn connect_backoff(&self) -> WsStream<MaybeTlsStream<TcpStream>> {
-        let mut backoff = Self::fresh_backoff();
-        loop {
-            match self.connect().await {
-                Ok(ws_stream) => {
-                    emit!(WsConnectionEstablished {});
-                    return ws_stream;
-                }
-                Err(error) => {
-                    emit!(WsConnectionFailedError {
-                        error: Box::new(error)
-                    });
-                    time::sleep(backoff.next().unwrap()).await;
-                }
-            }
-        }
-    }
-
-    pub async fn healthcheck(&self) -> crate::Result<()> {
-        self.connect().await.map(|_| ()).map_err(Into::into)
-    }
-}
-
-struct PingInterval {
-    interval: Option<time::Interval>,
-}
-
-impl PingInterval {
-    fn new(period: Option<u64>) -> Self {
-        Self {
-            interval: period.map(|period| time::interval(Duration::from_secs(period))),
-        }
-    }
-
-    fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<time::Instant> {
-        match self.interval.as_mut() {
-            Some(interval) => interval.poll_tick(cx),
-            None => Poll::Pending,
-        }
-    }
-
-    async fn tick(&mut self) -> time::Instant {
-        std::future::poll_fn(|cx| self.poll_tick(cx)).await
-    }
-}
-
 pub struct WebSocketSink {
     transformer: Transformer,
     encoder: Encoder<()>,
@@ -221,26 +55,14 @@ impl WebSocketSink {
         ws_stream.split()
     }
 
-    fn check_received_pong_time(&self, last_pong: Instant) -> Result<(), WsError> {
-        if let Some(ping_timeout) = self.ping_timeout {
-            if last_pong.elapsed() > Duration::from_secs(ping_timeout.into()) {
-                return Err(WsError::Io(io::Error::new(
-                    io::ErrorKind::TimedOut,
-                    "Pong not received in time",
-                )));
-            }
-        }
-
-        Ok(())
-    }
-
     const fn should_encode_as_binary(&self) -> bool {
         use vector_lib::codecs::encoding::Serializer::{
-            Avro, Csv, Gelf, Json, Logfmt, Native, NativeJson, Protobuf, RawMessage, Text,
+            Avro, Csv, Gelf, Json, Logfmt, Native, NativeJson, Protobuf, RawMessage,
+            SchemaRegistry, Text,
         };
 
         match self.encoder.serializer() {
-            RawMessage(_) | Avro(_) | Native(_) | Protobuf(_) => true,
+            RawMessage(_) | Avro(_) | Native(_) | Protobuf(_) | SchemaRegistry(_) => true,
             Csv(_) | Logfmt(_) | Gelf(_) | Json(_) | Text(_) | NativeJson(_) => false,
         }
     }
@@ -275,7 +97,7 @@ impl WebSocketSink {
         loop {
             let result = tokio::select! {
                 _ = ping_interval.tick() => {
-                    match self.check_received_pong_time(last_pong) {
+                    match check_received_pong_time(self.ping_timeout, last_pong) {
                         Ok(()) => ws_sink.send(Message::Ping(PING.to_vec())).await.map(|_| ()),
                         Err(e) => Err(e)
                     }
@@ -376,22 +198,13 @@ impl StreamSink<Event> for WebSocketSink {
     }
 }
 
-const fn is_closed(error: &WsError) -> bool {
-    matches!(
-        error,
-        WsError::ConnectionClosed
-            | WsError::AlreadyClosed
-            | WsError::Protocol(ProtocolError::ResetWithoutClosingHandshake)
-    )
-}
-
 #[cfg(test)]
 mod tests {
-    use std::net::SocketAddr;
+    use std::{net::SocketAddr, time::Duration};
 
     use futures::{future, FutureExt, StreamExt};
     use serde_json::Value as JsonValue;
-    use tokio::time::timeout;
+    use tokio::time::{self, timeout};
     use tokio_tungstenite::{
         accept_async, accept_hdr_async,
         tungstenite::error::{Error as WsError, ProtocolError},
@@ -402,11 +215,12 @@ mod tests {
     use super::*;
     use crate::{
         config::{SinkConfig, SinkContext},
+        http::Auth,
         test_util::{
             components::{run_and_assert_sink_compliance, SINK_TAGS},
             next_addr, random_lines_with_stream, trace_init, CountReceiver,
         },
-        tls::{self, TlsConfig, TlsEnableableConfig},
+        tls::{self, MaybeTlsSettings, TlsConfig, TlsEnableableConfig},
     };
 
     #[tokio::test(flavor = "multi_thread")]
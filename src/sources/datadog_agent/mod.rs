@@ -315,6 +315,10 @@ impl SourceConfig for DatadogAgentConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn can_buffer(&self) -> bool {
+        true
+    }
 }
 
 #[derive(Clone, Copy, Debug, Snafu)]
@@ -66,6 +66,10 @@ impl SourceConfig for HttpConfig {
     fn can_acknowledge(&self) -> bool {
         self.0.can_acknowledge()
     }
+
+    fn can_buffer(&self) -> bool {
+        self.0.can_buffer()
+    }
 }
 
 /// Configuration for the `http_server` source.
@@ -398,6 +402,10 @@ impl SourceConfig for SimpleHttpConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn can_buffer(&self) -> bool {
+        true
+    }
 }
 
 #[derive(Clone)]
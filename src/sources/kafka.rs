@@ -4,7 +4,7 @@ use std::{
     pin::Pin,
     sync::{
         mpsc::{sync_channel, SyncSender},
-        Arc, OnceLock, Weak,
+        Arc, Mutex, OnceLock, Weak,
     },
     time::Duration,
 };
@@ -22,7 +22,7 @@ use rdkafka::{
     error::KafkaError,
     message::{BorrowedMessage, Headers as _, Message},
     types::RDKafkaErrorCode,
-    ClientConfig, ClientContext, Statistics, TopicPartitionList,
+    ClientConfig, ClientContext, Offset, Statistics, TopicPartitionList,
 };
 use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
@@ -41,7 +41,9 @@ use vector_lib::codecs::{
     decoding::{DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
-use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath};
+use vector_lib::lookup::{
+    lookup_v2::OptionalValuePath, metadata_path, owned_value_path, path, OwnedValuePath,
+};
 
 use vector_lib::configurable::configurable_component;
 use vector_lib::finalizer::OrderedFinalizer;
@@ -54,13 +56,14 @@ use vrl::value::{kind::Collection, Kind, ObjectMap};
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{
-        log_schema, LogSchema, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
-        SourceOutput,
+        log_schema, LogSchema, OffsetsLink, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext, SourceOutput,
     },
     event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{
-        KafkaBytesReceived, KafkaEventsReceived, KafkaOffsetUpdateError, KafkaReadError,
-        StreamClosedError,
+        KafkaBytesReceived, KafkaEventsReceived, KafkaOffsetUpdateError, KafkaPartitionAssigned,
+        KafkaPartitionRevoked, KafkaPartitionRewound, KafkaPartitionSeek, KafkaReadError,
+        KafkaRewindError, KafkaSeekError, StreamClosedError,
     },
     kafka,
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
@@ -79,6 +82,8 @@ enum BuildError {
     CreateError { source: rdkafka::error::KafkaError },
     #[snafu(display("Could not subscribe to Kafka topics: {}", source))]
     SubscribeError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Acknowledgements must be enabled to use exactly_once"))]
+    ExactlyOnceWithoutAcknowledgements,
 }
 
 /// Metrics (beta) configuration.
@@ -89,6 +94,72 @@ struct Metrics {
     pub topic_lag_metric: bool,
 }
 
+/// Position to start consuming partitions from when the source starts.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "position", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The position to start consuming from."))]
+enum SeekConfig {
+    /// Start from the earliest message retained in each partition.
+    Earliest,
+
+    /// Start from the end of each partition, only consuming messages produced from then on.
+    Latest,
+
+    /// Start from the earliest message of each partition with a timestamp at or after the given time.
+    ///
+    /// Partitions without any such message are consumed from their end.
+    Timestamp {
+        /// The time to start consuming from.
+        #[configurable(metadata(docs::examples = "2024-06-01T00:00:00Z"))]
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Start from explicit offsets.
+    ///
+    /// Partitions that are not listed are consumed from their committed offsets.
+    Offsets {
+        /// The offsets to start consuming from.
+        offsets: Vec<PartitionOffset>,
+    },
+}
+
+impl SeekConfig {
+    /// The offset to move the given partition to, if any.
+    ///
+    /// For `timestamp`, this is the timestamp in milliseconds, which needs to be resolved to an
+    /// offset with `offsets_for_times`.
+    fn offset(&self, topic: &str, partition: i32) -> Option<Offset> {
+        match self {
+            Self::Earliest => Some(Offset::Beginning),
+            Self::Latest => Some(Offset::End),
+            Self::Timestamp { timestamp } => Some(Offset::Offset(timestamp.timestamp_millis())),
+            Self::Offsets { offsets } => offsets
+                .iter()
+                .find(|offset| offset.topic == topic && offset.partition == partition)
+                .map(|offset| Offset::Offset(offset.offset)),
+        }
+    }
+}
+
+/// The offset to start consuming a partition from.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+struct PartitionOffset {
+    /// The topic of the partition.
+    #[configurable(metadata(docs::examples = "topic-1"))]
+    topic: String,
+
+    /// The partition number.
+    #[configurable(metadata(docs::examples = 0))]
+    partition: i32,
+
+    /// The offset of the first message to consume.
+    #[configurable(metadata(docs::examples = 42))]
+    offset: i64,
+}
+
 /// Configuration for the `kafka` source.
 #[serde_as]
 #[configurable_component(source("kafka", "Collect logs from Apache Kafka."))]
@@ -126,6 +197,16 @@ pub struct KafkaSourceConfig {
     #[configurable(metadata(docs::examples = "example_auto_offset_reset_values()"))]
     auto_offset_reset: String,
 
+    /// Moves each partition to the given position the first time it is assigned to the source.
+    ///
+    /// Unlike `auto_offset_reset`, this overrides the offsets committed for the consumer group, which
+    /// allows replaying data from a point in time or from known offsets, for example when onboarding a
+    /// topic or after an incident. Partitions assigned again after a consumer group rebalance resume
+    /// from their committed offsets, until the source is restarted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::advanced))]
+    seek: Option<SeekConfig>,
+
     /// The Kafka session timeout.
     #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
     #[configurable(metadata(docs::examples = 5000, docs::examples = 10000))]
@@ -245,6 +326,23 @@ pub struct KafkaSourceConfig {
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
 
+    /// Hands off committing consumer offsets to transactional `kafka` sinks.
+    ///
+    /// When enabled, this source does not commit offsets itself. Instead, `kafka` sinks with a
+    /// `transaction` configured commit the offsets of the messages they write in the same transaction
+    /// as the produced records, so that a Kafka-to-Kafka pipeline does not duplicate records when it
+    /// is restarted. To that end, the consumer group, topic, partition, and offset of each message are
+    /// added to the event metadata, under `%kafka`, regardless of the log namespace.
+    ///
+    /// The source must send its events directly to a single `kafka` sink with a `transaction`
+    /// configured, and to no other component. When a transaction is aborted, the partitions of its
+    /// messages are rewound, so that they're consumed again.
+    ///
+    /// Offsets are only committed once events are written, so acknowledgements must be enabled.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    exactly_once: bool,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -333,6 +431,10 @@ impl SourceConfig for KafkaSourceConfig {
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
                 .build()?;
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+        snafu::ensure!(
+            acknowledgements || !self.exactly_once,
+            ExactlyOnceWithoutAcknowledgementsSnafu
+        );
 
         if let Some(d) = self.drain_timeout_ms {
             snafu::ensure!(
@@ -355,6 +457,7 @@ impl SourceConfig for KafkaSourceConfig {
             cx.shutdown,
             false,
             log_namespace,
+            cx.offsets_link,
         )))
     }
 
@@ -409,6 +512,35 @@ impl SourceConfig for KafkaSourceConfig {
                 None,
             );
 
+        let schema_definition = if self.exactly_once {
+            let schema_definition = schema_definition.with_metadata_field(
+                &owned_value_path!(Self::NAME, "group_id"),
+                Kind::bytes(),
+                None,
+            );
+            match log_namespace {
+                LogNamespace::Legacy => schema_definition
+                    .with_metadata_field(
+                        &owned_value_path!(Self::NAME, "topic"),
+                        Kind::bytes(),
+                        None,
+                    )
+                    .with_metadata_field(
+                        &owned_value_path!(Self::NAME, "partition"),
+                        Kind::integer(),
+                        None,
+                    )
+                    .with_metadata_field(
+                        &owned_value_path!(Self::NAME, "offset"),
+                        Kind::integer(),
+                        None,
+                    ),
+                LogNamespace::Vector => schema_definition,
+            }
+        } else {
+            schema_definition
+        };
+
         vec![SourceOutput::new_logs(
             self.decoding.output_type(),
             schema_definition,
@@ -418,6 +550,10 @@ impl SourceConfig for KafkaSourceConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn hands_off_offsets(&self) -> bool {
+        self.exactly_once
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -430,6 +566,7 @@ async fn kafka_source(
     shutdown: ShutdownSignal,
     eof: bool,
     log_namespace: LogNamespace,
+    offsets_link: Option<OffsetsLink>,
 ) -> Result<(), ()> {
     let span = info_span!("kafka_source");
     let consumer = Arc::new(consumer);
@@ -440,6 +577,17 @@ async fn kafka_source(
         .set(Arc::downgrade(&consumer))
         .expect("Error setting up consumer context.");
 
+    // The transactional sink committing the offsets of the consumed messages needs the group
+    // metadata of the consumer to do so.
+    if let Some(link) = offsets_link.filter(|_| config.exactly_once) {
+        let consumer = Arc::downgrade(&consumer);
+        link.provide(kafka::GroupMetadataLookup::new(move || {
+            consumer
+                .upgrade()
+                .and_then(|consumer| consumer.group_metadata())
+        }));
+    }
+
     // EOF signal allowing the coordination task to tell the kafka client task when all partitions have reached EOF
     let (eof_tx, eof_rx) = eof.then(oneshot::channel::<()>).unzip();
 
@@ -585,6 +733,7 @@ impl ConsumerStateInner<Consuming> {
         exit_eof: bool,
     ) -> (oneshot::Sender<()>, tokio::task::AbortHandle) {
         let keys = self.config.keys();
+        let store_offsets = !self.config.exactly_once;
         let decoder = self.decoder.clone();
         let log_namespace = self.log_namespace;
         let mut out = self.out.clone();
@@ -594,6 +743,7 @@ impl ConsumerStateInner<Consuming> {
         let handle = join_set.spawn(async move {
             let mut messages = p.stream();
             let (finalizer, mut ack_stream) = OrderedFinalizer::<FinalizerEntry>::new(None);
+            let mut rewind = (!store_offsets).then(PartitionRewind::default);
 
             // finalizer is the entry point for new pending acknowledgements;
             // when it is dropped, no new messages will be consumed, and the
@@ -616,11 +766,14 @@ impl ConsumerStateInner<Consuming> {
 
                     ack = ack_stream.next() => match ack {
                         Some((status, entry)) => {
-                            if status == BatchStatus::Delivered {
+                            if status == BatchStatus::Delivered && store_offsets {
                                 if let Err(error) =  consumer.store_offset(&entry.topic, entry.partition, entry.offset) {
                                     emit!(KafkaOffsetUpdateError { error });
                                 }
                             }
+                            if let Some(rewind) = rewind.as_mut() {
+                                rewind.acknowledged(status, &entry, &consumer);
+                            }
                         }
                         None if finalizer.is_none() => {
                             debug!("Acknowledgement stream complete for partition {}:{}.", &tp.0, tp.1);
@@ -1034,6 +1187,8 @@ struct Keys {
     partition: Option<OwnedValuePath>,
     offset: Option<OwnedValuePath>,
     headers: Option<OwnedValuePath>,
+    /// The consumer group to record in the event metadata, for exactly-once delivery.
+    group_id: Option<String>,
 }
 
 impl Keys {
@@ -1045,6 +1200,7 @@ impl Keys {
             partition: config.partition_key.path.clone(),
             offset: config.offset_key.path.clone(),
             headers: config.headers_key.path.clone(),
+            group_id: config.exactly_once.then(|| config.group_id.clone()),
         }
     }
 }
@@ -1161,6 +1317,82 @@ impl ReceivedMessage {
                 path!("headers"),
                 self.headers.clone(),
             );
+
+            if let Some(group_id) = &keys.group_id {
+                log.insert(
+                    metadata_path!(KafkaSourceConfig::NAME, "group_id"),
+                    group_id.clone(),
+                );
+                // The Vector namespace already has the rest of the position in the metadata.
+                if log_namespace == LogNamespace::Legacy {
+                    log.insert(
+                        metadata_path!(KafkaSourceConfig::NAME, "topic"),
+                        self.topic.clone(),
+                    );
+                    log.insert(
+                        metadata_path!(KafkaSourceConfig::NAME, "partition"),
+                        self.partition,
+                    );
+                    log.insert(
+                        metadata_path!(KafkaSourceConfig::NAME, "offset"),
+                        self.offset,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Rewinds a partition consumed with exactly-once delivery when the transaction writing some of its
+/// messages is aborted, back to the first of these messages, which is also the next message to
+/// consume according to the committed offsets.
+///
+/// Otherwise, the offsets of the following messages, which would be committed in later
+/// transactions, would cause the messages of the aborted transaction to be skipped.
+#[derive(Default)]
+struct PartitionRewind {
+    /// The offset the partition was last rewound to, while the messages consumed before rewinding
+    /// are still being acknowledged.
+    rewound_to: Option<i64>,
+}
+
+impl PartitionRewind {
+    fn acknowledged(
+        &mut self,
+        status: BatchStatus,
+        entry: &FinalizerEntry,
+        consumer: &StreamConsumer<KafkaSourceContext>,
+    ) {
+        // Acknowledgements come in the order the messages were consumed, so those of the messages
+        // consumed before rewinding, which are all past the offset rewound to, come first.
+        if let Some(offset) = self.rewound_to {
+            if entry.offset > offset {
+                return;
+            }
+            self.rewound_to = None;
+        }
+
+        if status != BatchStatus::Delivered {
+            match consumer.seek(
+                &entry.topic,
+                entry.partition,
+                Offset::Offset(entry.offset),
+                Duration::ZERO,
+            ) {
+                Ok(()) => {
+                    emit!(KafkaPartitionRewound {
+                        topic: &entry.topic,
+                        partition: entry.partition,
+                        offset: entry.offset,
+                    });
+                    self.rewound_to = Some(entry.offset);
+                }
+                Err(error) => emit!(KafkaRewindError {
+                    topic: &entry.topic,
+                    partition: entry.partition,
+                    error,
+                }),
+            }
         }
     }
 }
@@ -1207,7 +1439,7 @@ fn create_consumer(
             &config.fetch_wait_max_ms.as_millis().to_string(),
         )
         .set("enable.partition.eof", "false")
-        .set("enable.auto.commit", "true")
+        .set("enable.auto.commit", (!config.exactly_once).to_string())
         .set(
             "auto.commit.interval.ms",
             &config.commit_interval_ms.as_millis().to_string(),
@@ -1229,6 +1461,9 @@ fn create_consumer(
         .create_with_context::<_, StreamConsumer<_>>(KafkaSourceContext::new(
             config.metrics.topic_lag_metric,
             acknowledgements,
+            !config.exactly_once,
+            config.seek.clone(),
+            config.socket_timeout_ms,
             callbacks,
             Span::current(),
         ))
@@ -1258,6 +1493,8 @@ enum KafkaCallback {
 
 struct KafkaSourceContext {
     acknowledgements: bool,
+    /// Whether offsets are committed by this consumer, rather than by transactional producers.
+    commit_offsets: bool,
     stats: kafka::KafkaStatisticsContext,
 
     /// A callback channel used to coordinate between the main consumer task and the acknowledgement task
@@ -1265,12 +1502,24 @@ struct KafkaSourceContext {
 
     /// A weak reference to the consumer, so that we can commit offsets during a rebalance operation
     consumer: OnceLock<Weak<StreamConsumer<KafkaSourceContext>>>,
+
+    /// The position to move partitions to the first time they are assigned, if any.
+    seek: Option<SeekConfig>,
+
+    /// The partitions that were already moved to the `seek` position.
+    seeked: Mutex<HashSet<TopicPartition>>,
+
+    /// Timeout for the requests made to resolve and apply the `seek` position.
+    seek_timeout: Duration,
 }
 
 impl KafkaSourceContext {
     fn new(
         expose_lag_metrics: bool,
         acknowledgements: bool,
+        commit_offsets: bool,
+        seek: Option<SeekConfig>,
+        seek_timeout: Duration,
         callbacks: UnboundedSender<KafkaCallback>,
         span: Span,
     ) -> Self {
@@ -1280,8 +1529,12 @@ impl KafkaSourceContext {
                 span,
             },
             acknowledgements,
+            commit_offsets,
             consumer: OnceLock::default(),
             callbacks,
+            seek,
+            seeked: Mutex::default(),
+            seek_timeout,
         }
     }
 
@@ -1311,7 +1564,13 @@ impl KafkaSourceContext {
         let _ = self.callbacks.send(KafkaCallback::PartitionsAssigned(
             tpl.elements()
                 .iter()
-                .map(|tp| (tp.topic().into(), tp.partition()))
+                .map(|tp| {
+                    emit!(KafkaPartitionAssigned {
+                        topic: tp.topic(),
+                        partition: tp.partition(),
+                    });
+                    (tp.topic().into(), tp.partition())
+                })
                 .collect(),
             send,
         ));
@@ -1331,7 +1590,13 @@ impl KafkaSourceContext {
         let _ = self.callbacks.send(KafkaCallback::PartitionsRevoked(
             tpl.elements()
                 .iter()
-                .map(|tp| (tp.topic().into(), tp.partition()))
+                .map(|tp| {
+                    emit!(KafkaPartitionRevoked {
+                        topic: tp.topic(),
+                        partition: tp.partition(),
+                    });
+                    (tp.topic().into(), tp.partition())
+                })
                 .collect(),
             send,
         ));
@@ -1341,7 +1606,85 @@ impl KafkaSourceContext {
         }
     }
 
+    /// Move the newly assigned partitions to the `seek` position, unless they were already moved
+    /// there since the source started. This runs after the assignment took effect, since only
+    /// assigned partitions can be sought.
+    fn seek_partitions(&self, tpl: &TopicPartitionList) {
+        let Some(seek) = &self.seek else {
+            return;
+        };
+        let mut seeked = self
+            .seeked
+            .lock()
+            .expect("Seeked partitions lock was poisoned.");
+
+        let mut positions = TopicPartitionList::new();
+        for element in tpl.elements() {
+            let tp: TopicPartition = (element.topic().into(), element.partition());
+            if seeked.contains(&tp) {
+                continue;
+            }
+            if let Some(offset) = seek.offset(&tp.0, tp.1) {
+                if let Err(error) = positions.add_partition_offset(&tp.0, tp.1, offset) {
+                    emit!(KafkaSeekError {
+                        topic: &tp.0,
+                        partition: tp.1,
+                        error,
+                    });
+                    continue;
+                }
+            }
+            // Partitions without an explicit offset are not sought, now or later.
+            seeked.insert(tp);
+        }
+        if positions.count() == 0 {
+            return;
+        }
+
+        let Some(consumer) = self
+            .consumer
+            .get()
+            .expect("Consumer reference was not initialized.")
+            .upgrade()
+        else {
+            return;
+        };
+
+        let positions = match seek {
+            SeekConfig::Timestamp { .. } => {
+                match consumer.offsets_for_times(positions.clone(), self.seek_timeout) {
+                    Ok(positions) => positions,
+                    Err(error) => return emit_seek_errors(&positions, &error),
+                }
+            }
+            _ => positions,
+        };
+
+        match consumer.seek_partitions(positions.clone(), self.seek_timeout) {
+            Ok(result) => {
+                for element in result.elements() {
+                    match element.error() {
+                        Ok(()) => emit!(KafkaPartitionSeek {
+                            topic: element.topic(),
+                            partition: element.partition(),
+                            offset: element.offset(),
+                        }),
+                        Err(error) => emit!(KafkaSeekError {
+                            topic: element.topic(),
+                            partition: element.partition(),
+                            error,
+                        }),
+                    }
+                }
+            }
+            Err(error) => emit_seek_errors(&positions, &error),
+        }
+    }
+
     fn commit_consumer_state(&self) {
+        if !self.commit_offsets {
+            return;
+        }
         if let Some(consumer) = self
             .consumer
             .get()
@@ -1358,6 +1701,16 @@ impl KafkaSourceContext {
     }
 }
 
+fn emit_seek_errors(tpl: &TopicPartitionList, error: &KafkaError) {
+    for element in tpl.elements() {
+        emit!(KafkaSeekError {
+            topic: element.topic(),
+            partition: element.partition(),
+            error: error.clone(),
+        });
+    }
+}
+
 impl ClientContext for KafkaSourceContext {
     fn stats(&self, statistics: Statistics) {
         self.stats.stats(statistics)
@@ -1383,6 +1736,16 @@ impl ConsumerContext for KafkaSourceContext {
             }
         }
     }
+
+    fn post_rebalance(&self, rebalance: &Rebalance) {
+        if let Rebalance::Assign(tpl) = rebalance {
+            // TODO  workaround for https://github.com/fede1024/rust-rdkafka/issues/681
+            if tpl.capacity() == 0 {
+                return;
+            }
+            self.seek_partitions(tpl);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1391,6 +1754,7 @@ mod test {
     use vector_lib::schema::Definition;
 
     use super::*;
+    use crate::event::LogEvent;
 
     pub fn kafka_host() -> String {
         std::env::var("KAFKA_HOST").unwrap_or_else(|_| "localhost".into())
@@ -1519,6 +1883,68 @@ mod test {
         )
     }
 
+    #[test]
+    fn parse_seek() {
+        let config: KafkaSourceConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topics = ["topic"]
+            group_id = "group"
+            seek.position = "timestamp"
+            seek.timestamp = "2024-06-01T00:00:00Z"
+            "#,
+        )
+        .unwrap();
+        let timestamp = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        assert_eq!(config.seek, Some(SeekConfig::Timestamp { timestamp }));
+
+        let config: KafkaSourceConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topics = ["topic"]
+            group_id = "group"
+            seek.position = "offsets"
+            seek.offsets = [{ topic = "topic", partition = 1, offset = 42 }]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.seek,
+            Some(SeekConfig::Offsets {
+                offsets: vec![PartitionOffset {
+                    topic: "topic".into(),
+                    partition: 1,
+                    offset: 42,
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn seek_offsets() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            SeekConfig::Earliest.offset("topic", 0),
+            Some(Offset::Beginning)
+        );
+        assert_eq!(SeekConfig::Latest.offset("topic", 0), Some(Offset::End));
+        assert_eq!(
+            SeekConfig::Timestamp { timestamp }.offset("topic", 0),
+            Some(Offset::Offset(1_717_200_000_000))
+        );
+
+        let seek = SeekConfig::Offsets {
+            offsets: vec![PartitionOffset {
+                topic: "topic".into(),
+                partition: 1,
+                offset: 42,
+            }],
+        };
+        assert_eq!(seek.offset("topic", 1), Some(Offset::Offset(42)));
+        assert_eq!(seek.offset("topic", 0), None);
+        assert_eq!(seek.offset("other", 1), None);
+    }
+
     #[tokio::test]
     async fn consumer_create_ok() {
         let config = make_config("topic", "group", LogNamespace::Legacy, None);
@@ -1533,6 +1959,45 @@ mod test {
         };
         assert!(create_consumer(&config, true).is_err());
     }
+
+    #[test]
+    fn exactly_once_records_position_in_metadata() {
+        let config = KafkaSourceConfig {
+            exactly_once: true,
+            ..make_config("topic", "group", LogNamespace::Legacy, None)
+        };
+        let message = ReceivedMessage {
+            timestamp: None,
+            key: Value::Null,
+            headers: ObjectMap::new(),
+            topic: "topic".into(),
+            partition: 2,
+            offset: 42,
+        };
+
+        for log_namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let mut event = Event::Log(LogEvent::default());
+            message.apply(&config.keys(), &mut event, log_namespace);
+
+            let metadata = event.as_log().metadata().value();
+            assert_eq!(
+                metadata.get(path!("kafka", "group_id")),
+                Some(&Value::from("group"))
+            );
+            assert_eq!(
+                metadata.get(path!("kafka", "topic")),
+                Some(&Value::from("topic"))
+            );
+            assert_eq!(
+                metadata.get(path!("kafka", "partition")),
+                Some(&Value::from(2))
+            );
+            assert_eq!(
+                metadata.get(path!("kafka", "offset")),
+                Some(&Value::from(42))
+            );
+        }
+    }
 }
 
 #[cfg(feature = "kafka-integration-tests")]
@@ -1829,6 +2294,7 @@ mod integration_test {
             shutdown,
             eof,
             log_namespace,
+            None,
         ));
         (trigger_shutdown, shutdown_done)
     }
@@ -2030,6 +2496,55 @@ mod integration_test {
         assert_eq!(total, expect_count);
     }
 
+    #[tokio::test]
+    async fn seeks_past_committed_offsets() {
+        let (topic, group_id, _) = send_to_test_topic(1, 10).await;
+
+        let mut opts = HashMap::new();
+        opts.insert("enable.partition.eof".into(), "true".into());
+
+        let consume = |seek: Option<SeekConfig>| {
+            let config = KafkaSourceConfig {
+                seek,
+                ..make_config(&topic, &group_id, LogNamespace::Legacy, Some(opts.clone()))
+            };
+            async move {
+                let (tx, rx) = SourceSender::new_test_errors(|_| false);
+                let (trigger_shutdown, shutdown_done) =
+                    spawn_kafka(tx, config, true, true, LogNamespace::Legacy);
+                let events = rx.collect::<Vec<Event>>().await;
+                drop(trigger_shutdown);
+                shutdown_done.await;
+                let mut offsets = events
+                    .iter()
+                    .map(|event| event.as_log()["offset"].as_integer().unwrap())
+                    .collect::<Vec<_>>();
+                offsets.sort_unstable();
+                offsets
+            }
+        };
+
+        // Consume the whole topic, committing the offsets of the consumer group.
+        assert_eq!(consume(None).await, (0..10).collect::<Vec<_>>());
+        assert_eq!(fetch_tpl_offset(&group_id, &topic, 0), Offset::Offset(10));
+
+        // Replay the topic regardless of the committed offsets.
+        assert_eq!(
+            consume(Some(SeekConfig::Earliest)).await,
+            (0..10).collect::<Vec<_>>()
+        );
+
+        let offsets = vec![PartitionOffset {
+            topic: topic.clone(),
+            partition: 0,
+            offset: 5,
+        }];
+        assert_eq!(
+            consume(Some(SeekConfig::Offsets { offsets })).await,
+            (5..10).collect::<Vec<_>>()
+        );
+    }
+
     async fn consume_with_rebalance(rebalance_strategy: String) {
         // 1. Send N events (if running against a pre-populated kafka topic, use send_count=0 and expect_count=expected number of messages; otherwise just set send_count)
         let send_count: usize = std::env::var("KAFKA_SEND_COUNT")
@@ -0,0 +1,260 @@
+use snafu::ResultExt;
+use vector_lib::codecs::decoding::{DeserializerConfig, FramingConfig};
+use vector_lib::config::{LegacyKey, LogNamespace, SourceAcknowledgementsConfig};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path};
+use vrl::value::Kind;
+
+use crate::{
+    codecs::DecodingConfig,
+    common::mqtt::{ConfigurationError, ConfigurationSnafu, MqttCommonConfig, MqttError, MqttQoS},
+    config::{SourceConfig, SourceContext, SourceOutput},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+};
+
+use super::source::MqttSource;
+
+/// Configuration for the `mqtt` source.
+#[configurable_component(source("mqtt", "Collect events from topics on an MQTT broker."))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSourceConfig {
+    #[serde(flatten)]
+    pub common: MqttCommonConfig,
+
+    /// The MQTT topic filters to subscribe to.
+    ///
+    /// Filters may contain the single-level wildcard `+` and the multi-level wildcard `#`.
+    #[configurable(metadata(docs::examples = "sensors/+/temperature"))]
+    #[configurable(metadata(docs::examples = "devices/#"))]
+    #[derivative(Default(value = "vec![\"vector\".to_owned()]"))]
+    pub topics: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub quality_of_service: MqttQoS,
+
+    /// Overrides the name of the log field used to add the topic each event was published to.
+    ///
+    /// By default, `"topic"` is used.
+    #[serde(default = "default_topic_key")]
+    #[derivative(Default(value = "default_topic_key()"))]
+    pub topic_key: OptionalValuePath,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: SourceAcknowledgementsConfig,
+}
+
+fn default_topic_key() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!("topic"))
+}
+
+impl_generate_config_from_default!(MqttSourceConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SourceConfig for MqttSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+
+        self.validate_topics()?;
+        self.validate_session()?;
+        let mut options = self.common.build_options("vectorSource")?;
+        // Publishes are only acknowledged to the broker once their events are delivered, so that
+        // undelivered messages are sent again when a persistent session is resumed.
+        options.set_manual_acks(acknowledgements);
+
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        let source = MqttSource::new(self.clone(), options, decoder, log_namespace);
+        Ok(Box::pin(source.run(cx.out, cx.shutdown, acknowledgements)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                self.topic_key.path.clone().map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("topic"),
+                Kind::bytes(),
+                None,
+            );
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl MqttSourceConfig {
+    fn validate_topics(&self) -> Result<(), MqttError> {
+        if self.topics.is_empty() {
+            return Err(ConfigurationError::EmptyTopics).context(ConfigurationSnafu);
+        }
+        match self
+            .topics
+            .iter()
+            .find(|filter| !rumqttc::valid_filter(filter))
+        {
+            Some(filter) => Err(ConfigurationError::InvalidTopicFilter {
+                filter: filter.clone(),
+            })
+            .context(ConfigurationSnafu),
+            None => Ok(()),
+        }
+    }
+
+    /// Persistent sessions are kept by the broker for a client ID, so a random one would leave a
+    /// session behind on every start, and never resume any of them.
+    fn validate_session(&self) -> Result<(), MqttError> {
+        if self.common.clean_session || self.common.client_id.is_some() {
+            Ok(())
+        } else {
+            Err(ConfigurationError::PersistentSessionWithoutClientId).context(ConfigurationSnafu)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::lookup::OwnedTargetPath;
+    use vector_lib::schema::Definition;
+    use vrl::value::kind::Collection;
+
+    use super::*;
+    use crate::common::mqtt::MqttCommonConfig;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSourceConfig>();
+    }
+
+    #[test]
+    fn validates_topic_filters() {
+        let config = |topics: &[&str]| MqttSourceConfig {
+            topics: topics.iter().map(|topic| topic.to_string()).collect(),
+            ..Default::default()
+        };
+
+        assert!(config(&["sensors/+/temperature", "devices/#"])
+            .validate_topics()
+            .is_ok());
+        assert!(matches!(
+            config(&[]).validate_topics(),
+            Err(MqttError::Configuration {
+                source: ConfigurationError::EmptyTopics
+            })
+        ));
+        assert!(matches!(
+            config(&["devices/#/status"]).validate_topics(),
+            Err(MqttError::Configuration {
+                source: ConfigurationError::InvalidTopicFilter { .. }
+            })
+        ));
+    }
+
+    #[test]
+    fn validates_persistent_session_client_id() {
+        let config = |client_id: Option<&str>, clean_session| MqttSourceConfig {
+            common: MqttCommonConfig {
+                client_id: client_id.map(Into::into),
+                clean_session,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert!(config(Some("vector"), false).validate_session().is_ok());
+        assert!(config(None, true).validate_session().is_ok());
+        assert!(matches!(
+            config(None, false).validate_session(),
+            Err(MqttError::Configuration {
+                source: ConfigurationError::PersistentSessionWithoutClientId
+            })
+        ));
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let config = MqttSourceConfig {
+            log_namespace: Some(true),
+            ..Default::default()
+        };
+
+        let definition = config
+            .outputs(LogNamespace::Vector)
+            .remove(0)
+            .schema_definition(true);
+
+        let expected_definition =
+            Definition::new_with_default_metadata(Kind::bytes(), [LogNamespace::Vector])
+                .with_meaning(OwnedTargetPath::event_root(), "message")
+                .with_metadata_field(
+                    &owned_value_path!("vector", "source_type"),
+                    Kind::bytes(),
+                    None,
+                )
+                .with_metadata_field(
+                    &owned_value_path!("vector", "ingest_timestamp"),
+                    Kind::timestamp(),
+                    None,
+                )
+                .with_metadata_field(&owned_value_path!("mqtt", "topic"), Kind::bytes(), None);
+
+        assert_eq!(definition, Some(expected_definition));
+    }
+
+    #[test]
+    fn output_schema_definition_legacy_namespace() {
+        let config = MqttSourceConfig::default();
+
+        let definition = config
+            .outputs(LogNamespace::Legacy)
+            .remove(0)
+            .schema_definition(true);
+
+        let expected_definition = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [LogNamespace::Legacy],
+        )
+        .with_event_field(
+            &owned_value_path!("message"),
+            Kind::bytes(),
+            Some("message"),
+        )
+        .with_event_field(&owned_value_path!("timestamp"), Kind::timestamp(), None)
+        .with_event_field(&owned_value_path!("source_type"), Kind::bytes(), None)
+        .with_event_field(&owned_value_path!("topic"), Kind::bytes(), None);
+
+        assert_eq!(definition, Some(expected_definition));
+    }
+}
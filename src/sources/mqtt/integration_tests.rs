@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use futures::Stream;
+use rumqttc::{AsyncClient, MqttOptions, QoS};
+use vector_lib::config::log_schema;
+
+use super::MqttSourceConfig;
+use crate::common::mqtt::{MqttCommonConfig, MqttQoS};
+use crate::config::{SourceConfig, SourceContext};
+use crate::event::{Event, EventStatus};
+use crate::test_util::components::{assert_source_compliance, SOURCE_TAGS};
+use crate::test_util::{collect_n, random_string, trace_init};
+use crate::SourceSender;
+
+fn mqtt_broker_address() -> String {
+    std::env::var("MQTT_BROKER_ADDRESS").unwrap_or_else(|_| "emqx".into())
+}
+
+fn mqtt_broker_port() -> u16 {
+    std::env::var("MQTT_BROKER_PORT")
+        .unwrap_or_else(|_| "1883".into())
+        .parse::<u16>()
+        .expect("Cannot parse as u16")
+}
+
+fn make_config(topics: Vec<String>) -> MqttSourceConfig {
+    MqttSourceConfig {
+        common: MqttCommonConfig {
+            host: mqtt_broker_address(),
+            port: mqtt_broker_port(),
+            clean_session: true,
+            ..Default::default()
+        },
+        topics,
+        quality_of_service: MqttQoS::AtLeastOnce,
+        ..Default::default()
+    }
+}
+
+/// Builds the source and runs it in the background, waiting briefly so that it is subscribed
+/// before anything is published.
+async fn spawn_source(
+    config: &MqttSourceConfig,
+) -> (
+    tokio::task::JoinHandle<Result<(), ()>>,
+    impl Stream<Item = Event> + Unpin,
+) {
+    let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+    (spawn_source_with_sender(config, tx).await, rx)
+}
+
+async fn spawn_source_with_sender(
+    config: &MqttSourceConfig,
+    tx: SourceSender,
+) -> tokio::task::JoinHandle<Result<(), ()>> {
+    let source = config
+        .build(SourceContext::new_test(tx, None))
+        .await
+        .expect("source should not fail to build");
+    let handle = tokio::spawn(source);
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    handle
+}
+
+async fn publish(messages: &[(&str, &str)]) {
+    let mut options = MqttOptions::new(
+        format!("integration-test-producer-{}", random_string(6)),
+        mqtt_broker_address(),
+        mqtt_broker_port(),
+    );
+    options.set_keep_alive(Duration::from_secs(5));
+
+    let (client, mut eventloop) = AsyncClient::new(options, 16);
+    tokio::spawn(async move { while eventloop.poll().await.is_ok() {} });
+
+    for (topic, message) in messages {
+        client
+            .publish(*topic, QoS::AtLeastOnce, false, message.as_bytes().to_vec())
+            .await
+            .expect("Cannot publish the message");
+    }
+    // Give the event loop time to flush the publishes before the client is dropped.
+    tokio::time::sleep(Duration::from_millis(500)).await;
+}
+
+#[tokio::test]
+async fn mqtt_source_wildcard_topics() {
+    trace_init();
+
+    let prefix = format!("test-{}", random_string(10));
+    let config = make_config(vec![
+        format!("{prefix}/+/temperature"),
+        format!("{prefix}/alerts/#"),
+    ]);
+
+    let events = assert_source_compliance(&SOURCE_TAGS, async {
+        let (_handle, rx) = spawn_source(&config).await;
+        publish(&[
+            (&format!("{prefix}/kitchen/temperature"), "21"),
+            (&format!("{prefix}/kitchen/humidity"), "40"),
+            (&format!("{prefix}/alerts/kitchen/smoke"), "detected"),
+        ])
+        .await;
+        collect_n(rx, 2).await
+    })
+    .await;
+
+    let mut received = events
+        .iter()
+        .map(|event| {
+            let log = event.as_log();
+            (
+                log["topic"].to_string_lossy().into_owned(),
+                log[log_schema().message_key().unwrap().to_string()]
+                    .to_string_lossy()
+                    .into_owned(),
+            )
+        })
+        .collect::<Vec<_>>();
+    received.sort();
+
+    assert_eq!(
+        received,
+        vec![
+            (format!("{prefix}/alerts/kitchen/smoke"), "detected".into()),
+            (format!("{prefix}/kitchen/temperature"), "21".into()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn mqtt_source_persistent_session() {
+    trace_init();
+
+    let topic = format!("test-{}", random_string(10));
+    let mut config = make_config(vec![topic.clone()]);
+    config.common.client_id = Some(format!("vector-test-{}", random_string(6)));
+    config.common.clean_session = false;
+
+    // Subscribe once, so that the broker keeps the session when the source goes away.
+    let (handle, _rx) = spawn_source(&config).await;
+    handle.abort();
+    _ = handle.await;
+
+    // Messages published while the source is disconnected are queued in its session.
+    publish(&[(&topic, "while away")]).await;
+
+    let (_handle, rx) = spawn_source(&config).await;
+    let events = collect_n(rx, 1).await;
+    assert_eq!(
+        events[0].as_log()[log_schema().message_key().unwrap().to_string()],
+        "while away".into()
+    );
+    assert_eq!(events[0].as_log()["topic"], topic.into());
+}
+
+#[tokio::test]
+async fn mqtt_source_redelivers_errored_messages() {
+    trace_init();
+
+    let topic = format!("test-{}", random_string(10));
+    let mut config = make_config(vec![topic.clone()]);
+    config.common.client_id = Some(format!("vector-test-{}", random_string(6)));
+    config.common.clean_session = false;
+
+    // The first delivery errors, so the source reconnects and the broker redelivers the message.
+    let (tx, rx) = SourceSender::new_test_errors(|n| n == 0);
+    let _handle = spawn_source_with_sender(&config, tx).await;
+    publish(&[(&topic, "redelivered")]).await;
+
+    let events = collect_n(rx, 2).await;
+    for event in events {
+        assert_eq!(
+            event.as_log()[log_schema().message_key().unwrap().to_string()],
+            "redelivered".into()
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use futures::StreamExt;
+use rumqttc::{
+    AsyncClient, Event as MqttEvent, MqttOptions, Packet, Publish, SubscribeFilter,
+    SubscribeReasonCode,
+};
+use tokio_util::codec::FramedRead;
+use vector_lib::codecs::StreamDecodingError as _;
+use vector_lib::config::{LegacyKey, LogNamespace};
+use vector_lib::finalizer::OrderedFinalizer;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+};
+use vector_lib::lookup::owned_value_path;
+use vector_lib::EstimatedJsonEncodedSizeOf;
+
+use crate::{
+    codecs::Decoder,
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{
+        MqttAckError, MqttReceiveError, MqttRedeliveryReconnect, MqttSubscribeError,
+        MqttUndeliveredAck, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+use super::config::MqttSourceConfig;
+
+/// How long to wait before polling the event loop again after a connection error, or before
+/// reconnecting to have undelivered messages sent again.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// The capacity of the request channel of the client.
+const REQUEST_CAPACITY: usize = 1024;
+
+pub struct MqttSource {
+    config: MqttSourceConfig,
+    options: MqttOptions,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+}
+
+impl MqttSource {
+    pub const fn new(
+        config: MqttSourceConfig,
+        options: MqttOptions,
+        decoder: Decoder,
+        log_namespace: LogNamespace,
+    ) -> Self {
+        Self {
+            config,
+            options,
+            decoder,
+            log_namespace,
+        }
+    }
+
+    pub async fn run(
+        self,
+        mut out: SourceSender,
+        shutdown: ShutdownSignal,
+        acknowledgements: bool,
+    ) -> Result<(), ()> {
+        let (mut client, mut eventloop) = AsyncClient::new(self.options.clone(), REQUEST_CAPACITY);
+        // Publishes are finalized along with the connection they were received on.
+        let (finalizer, mut ack_stream) = OrderedFinalizer::<(usize, Publish)>::maybe_new(
+            acknowledgements,
+            Some(shutdown.clone()),
+        );
+        let mut connection = 0;
+        let events_received = register!(EventsReceived);
+        let bytes_received = register!(BytesReceived::from(Protocol::from("mqtt")));
+
+        // Subscriptions are kept by the broker as part of a persistent session, and subscribing
+        // again on every reconnect would have it send its retained messages once more. They are
+        // only made on the first connection, and when the broker has no session to resume.
+        let mut subscribed = false;
+
+        let mut shutdown = shutdown.fuse();
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                entry = ack_stream.next() => match entry {
+                    // Packet IDs are only meaningful on the connection they were received on, and
+                    // the broker sends unacknowledged messages again on a new connection anyway.
+                    Some((status, (received_on, publish))) if received_on == connection => {
+                        match status {
+                            // MQTT has no way to reject a message, so messages rejected by a sink
+                            // are acked to keep the broker from delivering them again.
+                            BatchStatus::Delivered | BatchStatus::Rejected => {
+                                ack_publish(&client, &publish).await;
+                            }
+                            // Without a persistent session, the broker can't deliver the message
+                            // again, and leaving it unacknowledged would only have it count against
+                            // the messages in flight for the rest of the connection.
+                            BatchStatus::Errored if self.config.common.clean_session => {
+                                emit!(MqttUndeliveredAck { topic: &publish.topic });
+                                ack_publish(&client, &publish).await;
+                            }
+                            // Unacknowledged messages are only delivered again when the session is
+                            // resumed, so reconnect, after a delay to not do so in a tight loop
+                            // while the sink keeps failing.
+                            BatchStatus::Errored => {
+                                emit!(MqttRedeliveryReconnect { topic: &publish.topic });
+                                tokio::time::sleep(RECONNECT_DELAY).await;
+                                (client, eventloop) =
+                                    AsyncClient::new(self.options.clone(), REQUEST_CAPACITY);
+                                connection += 1;
+                            }
+                        }
+                    }
+                    _ => {}
+                },
+                event = eventloop.poll() => match event {
+                    Ok(MqttEvent::Incoming(Packet::ConnAck(ack))) => {
+                        if !subscribed || !ack.session_present {
+                            self.subscribe(&client).await;
+                            subscribed = true;
+                        }
+                    }
+                    Ok(MqttEvent::Incoming(Packet::SubAck(ack))) => {
+                        for (code, topic) in ack.return_codes.iter().zip(&self.config.topics) {
+                            if matches!(code, SubscribeReasonCode::Failure) {
+                                emit!(MqttSubscribeError { topic });
+                            }
+                        }
+                    }
+                    Ok(MqttEvent::Incoming(Packet::Publish(publish))) => {
+                        bytes_received.emit(ByteSize(publish.payload.len()));
+                        let events = self.decode(&publish).await;
+                        let count = events.len();
+                        events_received.emit(CountByteSize(
+                            count,
+                            events.estimated_json_encoded_size_of(),
+                        ));
+                        send(&mut out, finalizer.as_ref(), (connection, publish), events)
+                            .await
+                            .map_err(|_| emit!(StreamClosedError { count }))?;
+                    }
+                    Ok(_) => {}
+                    Err(error) => {
+                        emit!(MqttReceiveError { error });
+                        connection += 1;
+                        // The next poll reconnects, so don't retry in a tight loop while the
+                        // broker is unreachable.
+                        tokio::time::sleep(RECONNECT_DELAY).await;
+                    }
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn subscribe(&self, client: &AsyncClient) {
+        let filters = self.config.topics.iter().map(|topic| {
+            SubscribeFilter::new(topic.clone(), self.config.quality_of_service.into())
+        });
+        // This only fails if the event loop has been dropped, which it can't have been yet.
+        if let Err(error) = client.subscribe_many(filters).await {
+            error!(message = "Failed to subscribe.", %error);
+        }
+    }
+
+    async fn decode(&self, publish: &Publish) -> Vec<Event> {
+        let now = Utc::now();
+        let mut events = Vec::new();
+        let mut stream = FramedRead::new(publish.payload.as_ref(), self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((decoded, _byte_size)) => {
+                    events.extend(decoded.into_iter().map(|mut event| {
+                        if let Event::Log(ref mut log) = event {
+                            self.log_namespace.insert_standard_vector_source_metadata(
+                                log,
+                                MqttSourceConfig::NAME,
+                                now,
+                            );
+                            self.log_namespace.insert_source_metadata(
+                                MqttSourceConfig::NAME,
+                                log,
+                                self.config
+                                    .topic_key
+                                    .path
+                                    .as_ref()
+                                    .map(LegacyKey::InsertIfEmpty),
+                                &owned_value_path!("topic"),
+                                publish.topic.as_str(),
+                            );
+                        }
+                        event
+                    }));
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs`, no further handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+        events
+    }
+}
+
+async fn send(
+    out: &mut SourceSender,
+    finalizer: Option<&OrderedFinalizer<(usize, Publish)>>,
+    publish: (usize, Publish),
+    mut events: Vec<Event>,
+) -> Result<(), ()> {
+    match finalizer {
+        Some(finalizer) => {
+            let receiver = BatchNotifier::apply_to(&mut events);
+            out.send_batch(events).await.map_err(|_| ())?;
+            finalizer.add(publish, receiver);
+        }
+        // Without acknowledgements, publishes are acked by the event loop as they arrive.
+        None => out.send_batch(events).await.map_err(|_| ())?,
+    }
+    Ok(())
+}
+
+async fn ack_publish(client: &AsyncClient, publish: &Publish) {
+    if let Err(error) = client.ack(publish).await {
+        emit!(MqttAckError { error });
+    }
+}
@@ -1,15 +1,25 @@
+use std::time::Duration;
+
+use async_nats::jetstream::{
+    self,
+    consumer::{pull, push, AckPolicy},
+    AckKind,
+};
 use chrono::Utc;
-use futures::{pin_mut, StreamExt};
+use futures::{pin_mut, stream::BoxStream, StreamExt, TryStreamExt};
+use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
 use vector_lib::codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
 use vector_lib::configurable::configurable_component;
+use vector_lib::finalizer::UnorderedFinalizer;
 use vector_lib::internal_event::{
     ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+    Registered,
 };
 use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path};
 use vector_lib::{
-    config::{LegacyKey, LogNamespace},
+    config::{LegacyKey, LogNamespace, SourceAcknowledgementsConfig},
     EstimatedJsonEncodedSizeOf,
 };
 use vrl::value::Kind;
@@ -17,10 +27,10 @@ use vrl::value::Kind;
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
-    event::Event,
-    internal_events::StreamClosedError,
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{NatsAckError, NatsReceiveError, StreamClosedError},
     nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
     tls::TlsEnableableConfig,
     SourceSender,
@@ -34,8 +44,22 @@ enum BuildError {
     Connect { source: async_nats::ConnectError },
     #[snafu(display("NATS Subscribe Error: {}", source))]
     Subscribe { source: async_nats::SubscribeError },
+    #[snafu(display("NATS JetStream Stream Error: {}", source))]
+    JetStreamStream {
+        source: jetstream::context::GetStreamError,
+    },
+    #[snafu(display("NATS JetStream Consumer Error: {}", source))]
+    JetStreamConsumer {
+        source: jetstream::stream::ConsumerError,
+    },
+    #[snafu(display("NATS JetStream Messages Error: {}", source))]
+    JetStreamMessages {
+        source: jetstream::consumer::StreamError,
+    },
 }
 
+type JetStreamMessages = BoxStream<'static, Result<jetstream::Message, async_nats::Error>>;
+
 /// Configuration for the `nats` source.
 #[configurable_component(source(
     "nats",
@@ -62,6 +86,8 @@ pub struct NatsSourceConfig {
 
     /// The NATS [subject][nats_subject] to pull messages from.
     ///
+    /// With `jetstream` set, this is the filter subject of the consumer if it is created by Vector.
+    ///
     /// [nats_subject]: https://docs.nats.io/nats-concepts/subjects
     #[configurable(metadata(docs::examples = "foo"))]
     #[configurable(metadata(docs::examples = "time.us.east"))]
@@ -71,6 +97,9 @@ pub struct NatsSourceConfig {
     subject: String,
 
     /// The NATS queue group to join.
+    ///
+    /// With `jetstream` set, this is the deliver group of a push consumer if it is created by
+    /// Vector.
     queue: Option<String>,
 
     /// The namespace to use for logs. This overrides the global setting.
@@ -109,6 +138,69 @@ pub struct NatsSourceConfig {
     #[serde(default = "default_subscription_capacity")]
     #[derivative(Default(value = "default_subscription_capacity()"))]
     subscriber_capacity: usize,
+
+    #[configurable(derived)]
+    jetstream: Option<NatsJetStreamConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+}
+
+/// Configuration for consuming messages from a [JetStream][jetstream] stream, rather than from core
+/// NATS subjects.
+///
+/// Messages are acknowledged to the server explicitly, once their events are delivered if
+/// acknowledgements are enabled, so that messages which aren't delivered are redelivered.
+///
+/// [jetstream]: https://docs.nats.io/nats-concepts/jetstream
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct NatsJetStreamConfig {
+    /// The name of the [stream][nats_stream] to consume messages from.
+    ///
+    /// [nats_stream]: https://docs.nats.io/nats-concepts/jetstream/streams
+    #[configurable(metadata(docs::examples = "events"))]
+    stream: String,
+
+    /// The name of the durable [consumer][nats_consumer] to consume messages with.
+    ///
+    /// If the consumer doesn't exist yet, it is created with explicit acknowledgements and with
+    /// `subject` as its filter subject. An existing consumer is used as it is, and must be of the
+    /// configured `consumer_type`.
+    ///
+    /// [nats_consumer]: https://docs.nats.io/nats-concepts/jetstream/consumers
+    #[configurable(metadata(docs::examples = "vector"))]
+    consumer: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    consumer_type: NatsJetStreamConsumerType,
+
+    /// The amount of time the server waits for a message to be acknowledged before redelivering it.
+    ///
+    /// With end-to-end acknowledgements enabled, messages are only acknowledged once their events
+    /// are delivered, so this must be longer than it takes the sinks to deliver them. Like the
+    /// other consumer settings, this only applies if the consumer is created by Vector.
+    #[serde(default = "default_ack_wait_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Acknowledgement Wait"))]
+    ack_wait_secs: Duration,
+}
+
+/// The type of JetStream consumer to consume messages with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum NatsJetStreamConsumerType {
+    /// A pull consumer, which messages are fetched from in batches.
+    #[default]
+    Pull,
+
+    /// A push consumer, which delivers messages to a subject that is subscribed to.
+    Push,
 }
 
 fn default_subject_key_field() -> OptionalValuePath {
@@ -119,6 +211,10 @@ const fn default_subscription_capacity() -> usize {
     4096
 }
 
+const fn default_ack_wait_secs() -> Duration {
+    Duration::from_secs(30)
+}
+
 impl GenerateConfig for NatsSourceConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(
@@ -136,11 +232,26 @@ impl GenerateConfig for NatsSourceConfig {
 impl SourceConfig for NatsSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
-        let (connection, subscription) = create_subscription(self).await?;
         let decoder =
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
                 .build()?;
 
+        if let Some(jetstream) = &self.jetstream {
+            let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+            let (connection, messages) = create_jetstream_messages(self, jetstream).await?;
+            return Ok(Box::pin(nats_jetstream_source(
+                self.clone(),
+                connection,
+                messages,
+                decoder,
+                log_namespace,
+                cx.shutdown,
+                cx.out,
+                acknowledgements,
+            )));
+        }
+
+        let (connection, subscription) = create_subscription(self).await?;
         Ok(Box::pin(nats_source(
             self.clone(),
             connection,
@@ -178,7 +289,8 @@ impl SourceConfig for NatsSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        // Core NATS has no acknowledgements, only JetStream does.
+        self.jetstream.is_some()
     }
 }
 
@@ -214,55 +326,138 @@ async fn nats_source(
     let bytes_received = register!(BytesReceived::from(Protocol::TCP));
     while let Some(msg) = stream.next().await {
         bytes_received.emit(ByteSize(msg.payload.len()));
-        let mut stream = FramedRead::new(msg.payload.as_ref(), decoder.clone());
-        while let Some(next) = stream.next().await {
-            match next {
-                Ok((events, _byte_size)) => {
+        let events = decode_message(&config, &msg, &decoder, log_namespace, &events_received).await;
+        let count = events.len();
+        out.send_batch(events).await.map_err(|_| {
+            emit!(StreamClosedError { count });
+        })?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn nats_jetstream_source(
+    config: NatsSourceConfig,
+    // Take ownership of the connection so it doesn't get dropped.
+    _connection: async_nats::Client,
+    mut messages: JetStreamMessages,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+    acknowledgements: bool,
+) -> Result<(), ()> {
+    let events_received = register!(EventsReceived);
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+    let (finalizer, mut ack_stream) = UnorderedFinalizer::<jetstream::Message>::maybe_new(
+        acknowledgements,
+        Some(shutdown.clone()),
+    );
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            entry = ack_stream.next() => {
+                if let Some((status, message)) = entry {
+                    handle_ack(status, message).await;
+                }
+            },
+            message = messages.next() => match message {
+                None => break,
+                Some(Err(error)) => emit!(NatsReceiveError { error }),
+                Some(Ok(message)) => {
+                    bytes_received.emit(ByteSize(message.payload.len()));
+                    let mut events =
+                        decode_message(&config, &message, &decoder, log_namespace, &events_received)
+                            .await;
                     let count = events.len();
-                    let byte_size = events.estimated_json_encoded_size_of();
-                    events_received.emit(CountByteSize(count, byte_size));
-
-                    let now = Utc::now();
-
-                    let events = events.into_iter().map(|mut event| {
-                        if let Event::Log(ref mut log) = event {
-                            log_namespace.insert_standard_vector_source_metadata(
-                                log,
-                                NatsSourceConfig::NAME,
-                                now,
-                            );
-
-                            let legacy_subject_key_field = config
-                                .subject_key_field
-                                .path
-                                .as_ref()
-                                .map(LegacyKey::InsertIfEmpty);
-                            log_namespace.insert_source_metadata(
-                                NatsSourceConfig::NAME,
-                                log,
-                                legacy_subject_key_field,
-                                &owned_value_path!("subject"),
-                                msg.subject.as_str(),
-                            )
+                    match &finalizer {
+                        Some(finalizer) => {
+                            let receiver = BatchNotifier::apply_to(&mut events);
+                            out.send_batch(events)
+                                .await
+                                .map_err(|_| emit!(StreamClosedError { count }))?;
+                            finalizer.add(message, receiver);
                         }
-                        event
-                    });
-
-                    out.send_batch(events).await.map_err(|_| {
-                        emit!(StreamClosedError { count });
-                    })?;
+                        None => {
+                            out.send_batch(events)
+                                .await
+                                .map_err(|_| emit!(StreamClosedError { count }))?;
+                            handle_ack(BatchStatus::Delivered, message).await;
+                        }
+                    }
                 }
-                Err(error) => {
-                    // Error is logged by `crate::codecs`, no further
-                    // handling is needed here.
-                    if !error.can_continue() {
-                        break;
+            },
+        }
+    }
+    Ok(())
+}
+
+async fn handle_ack(status: BatchStatus, message: jetstream::Message) {
+    let result = match status {
+        BatchStatus::Delivered => message.ack().await,
+        // Messages rejected by a sink would be rejected again, so they are terminated to keep
+        // the server from redelivering them.
+        BatchStatus::Rejected => message.ack_with(AckKind::Term).await,
+        BatchStatus::Errored => message.ack_with(AckKind::Nak(None)).await,
+    };
+    if let Err(error) = result {
+        emit!(NatsAckError { error });
+    }
+}
+
+async fn decode_message(
+    config: &NatsSourceConfig,
+    msg: &async_nats::Message,
+    decoder: &Decoder,
+    log_namespace: LogNamespace,
+    events_received: &Registered<EventsReceived>,
+) -> Vec<Event> {
+    let mut decoded_events = Vec::new();
+    let mut stream = FramedRead::new(msg.payload.as_ref(), decoder.clone());
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((events, _byte_size)) => {
+                let count = events.len();
+                let byte_size = events.estimated_json_encoded_size_of();
+                events_received.emit(CountByteSize(count, byte_size));
+
+                let now = Utc::now();
+
+                decoded_events.extend(events.into_iter().map(|mut event| {
+                    if let Event::Log(ref mut log) = event {
+                        log_namespace.insert_standard_vector_source_metadata(
+                            log,
+                            NatsSourceConfig::NAME,
+                            now,
+                        );
+
+                        let legacy_subject_key_field = config
+                            .subject_key_field
+                            .path
+                            .as_ref()
+                            .map(LegacyKey::InsertIfEmpty);
+                        log_namespace.insert_source_metadata(
+                            NatsSourceConfig::NAME,
+                            log,
+                            legacy_subject_key_field,
+                            &owned_value_path!("subject"),
+                            msg.subject.as_str(),
+                        )
                     }
+                    event
+                }));
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
                 }
             }
         }
     }
-    Ok(())
+    decoded_events
 }
 
 async fn create_subscription(
@@ -283,6 +478,67 @@ async fn create_subscription(
     Ok((nc, subscription))
 }
 
+async fn create_jetstream_messages(
+    config: &NatsSourceConfig,
+    jetstream_config: &NatsJetStreamConfig,
+) -> Result<(async_nats::Client, JetStreamMessages), BuildError> {
+    let nc = config.connect().await?;
+    let stream = jetstream::new(nc.clone())
+        .get_stream(&jetstream_config.stream)
+        .await
+        .context(JetStreamStreamSnafu)?;
+    let name = &jetstream_config.consumer;
+
+    let messages = match jetstream_config.consumer_type {
+        NatsJetStreamConsumerType::Pull => {
+            let consumer = stream
+                .get_or_create_consumer(
+                    name,
+                    pull::Config {
+                        durable_name: Some(name.clone()),
+                        filter_subject: config.subject.clone(),
+                        ack_policy: AckPolicy::Explicit,
+                        ack_wait: jetstream_config.ack_wait_secs,
+                        ..Default::default()
+                    },
+                )
+                .await
+                .context(JetStreamConsumerSnafu)?;
+            consumer
+                .messages()
+                .await
+                .context(JetStreamMessagesSnafu)?
+                .map_err(async_nats::Error::from)
+                .boxed()
+        }
+        NatsJetStreamConsumerType::Push => {
+            let consumer = stream
+                .get_or_create_consumer(
+                    name,
+                    push::Config {
+                        durable_name: Some(name.clone()),
+                        deliver_subject: nc.new_inbox(),
+                        deliver_group: config.queue.clone(),
+                        filter_subject: config.subject.clone(),
+                        ack_policy: AckPolicy::Explicit,
+                        ack_wait: jetstream_config.ack_wait_secs,
+                        ..Default::default()
+                    },
+                )
+                .await
+                .context(JetStreamConsumerSnafu)?;
+            consumer
+                .messages()
+                .await
+                .context(JetStreamMessagesSnafu)?
+                .map_err(async_nats::Error::from)
+                .boxed()
+        }
+    };
+
+    Ok((nc, messages))
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::print_stdout)] //tests
@@ -298,6 +554,27 @@ mod tests {
         crate::test_util::test_generate_config::<NatsSourceConfig>();
     }
 
+    #[test]
+    fn parse_jetstream_config() {
+        let config: NatsSourceConfig = toml::from_str(
+            r#"
+            connection_name = "vector"
+            subject = "events.>"
+            url = "nats://127.0.0.1:4222"
+            jetstream.stream = "events"
+            jetstream.consumer = "vector"
+            "#,
+        )
+        .unwrap();
+
+        let jetstream = config.jetstream.as_ref().unwrap();
+        assert_eq!(jetstream.stream, "events");
+        assert_eq!(jetstream.consumer_type, NatsJetStreamConsumerType::Pull);
+        assert_eq!(jetstream.ack_wait_secs, Duration::from_secs(30));
+        assert!(config.can_acknowledge());
+        assert!(!NatsSourceConfig::default().can_acknowledge());
+    }
+
     #[test]
     fn output_schema_definition_vector_namespace() {
         let config = NatsSourceConfig {
@@ -366,6 +643,7 @@ mod integration_tests {
     use vector_lib::config::log_schema;
 
     use super::*;
+    use crate::event::EventStatus;
     use crate::nats::{NatsAuthCredentialsFile, NatsAuthNKey, NatsAuthToken, NatsAuthUserPassword};
     use crate::test_util::{
         collect_n,
@@ -846,4 +1124,77 @@ mod integration_tests {
             r
         );
     }
+
+    async fn jetstream_publish_and_check(consumer_type: NatsJetStreamConsumerType) {
+        let subject = format!("test-{}", random_string(10));
+        let url =
+            std::env::var("NATS_ADDRESS").unwrap_or_else(|_| String::from("nats://localhost:4222"));
+
+        let conf = NatsSourceConfig {
+            connection_name: "".to_owned(),
+            subject: subject.clone(),
+            url,
+            subject_key_field: default_subject_key_field(),
+            jetstream: Some(NatsJetStreamConfig {
+                stream: subject.clone(),
+                consumer: "vector".to_owned(),
+                consumer_type,
+                ack_wait_secs: default_ack_wait_secs(),
+            }),
+            acknowledgements: true.into(),
+            ..Default::default()
+        };
+
+        let context = jetstream::new(conf.connect().await.unwrap());
+        context
+            .create_stream(jetstream::stream::Config {
+                name: subject.clone(),
+                subjects: vec![subject.clone()],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        // Published before the source starts, so it is only received if it was stored.
+        context
+            .publish(subject.clone(), Bytes::from_static(b"my message"))
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+
+        let events = assert_source_compliance(&SOURCE_TAGS, async {
+            let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let source = conf.build(SourceContext::new_test(tx, None)).await.unwrap();
+            tokio::spawn(source);
+            collect_n(rx, 1).await
+        })
+        .await;
+
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key().unwrap().to_string()],
+            "my message".into()
+        );
+
+        // The message was acknowledged once its event was delivered.
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let info = context
+            .get_stream(&subject)
+            .await
+            .unwrap()
+            .consumer_info("vector")
+            .await
+            .unwrap();
+        assert_eq!(info.num_ack_pending, 0);
+        assert_eq!(info.num_pending, 0);
+    }
+
+    #[tokio::test]
+    async fn nats_jetstream_pull_consumer() {
+        jetstream_publish_and_check(NatsJetStreamConsumerType::Pull).await;
+    }
+
+    #[tokio::test]
+    async fn nats_jetstream_push_consumer() {
+        jetstream_publish_and_check(NatsJetStreamConsumerType::Push).await;
+    }
 }
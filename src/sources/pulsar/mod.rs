@@ -2,6 +2,8 @@
 //! Accepts log events streamed from [`Apache Pulsar`][pulsar].
 //!
 //! [pulsar]: https://pulsar.apache.org/
+use std::time::Duration;
+
 use chrono::TimeZone;
 use futures_util::StreamExt;
 use pulsar::{
@@ -10,11 +12,13 @@ use pulsar::{
     message::proto::MessageIdData,
     Authentication, Consumer, Pulsar, SubType, TokioExecutor,
 };
-use tokio_util::codec::FramedRead;
+use serde_with::serde_as;
+use snafu::Snafu;
+use tokio_util::{codec::FramedRead, time::DelayQueue};
 
 use vector_lib::{
     codecs::{
-        decoding::{DeserializerConfig, FramingConfig},
+        decoding::{format::JsonDeserializerConfig, DeserializerConfig, FramingConfig},
         StreamDecodingError,
     },
     config::{LegacyKey, LogNamespace, SourceAcknowledgementsConfig, SourceOutput},
@@ -43,7 +47,21 @@ use crate::{
     SourceSender,
 };
 
+mod schema;
+
+use schema::{SchemaConfig, SchemaDecoder};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display(
+        "A dead_letter_queue_policy requires a `shared` or `key_shared` subscription_type, not `{}`",
+        subscription_type
+    ))]
+    DeadLetterSubscriptionType { subscription_type: SubscriptionType },
+}
+
 /// Configuration for the `pulsar` source.
+#[serde_as]
 #[configurable_component(source("pulsar", "Collect logs from Apache Pulsar."))]
 #[derive(Clone, Debug, Derivative)]
 #[derivative(Default)]
@@ -66,6 +84,10 @@ pub struct PulsarSourceConfig {
     #[configurable(metadata(docs::examples = "subscription_name"))]
     subscription_name: Option<String>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    subscription_type: SubscriptionType,
+
     /// The consumer's priority level.
     ///
     /// The broker follows descending priorities. For example, 0=max-priority, 1, 2,...
@@ -82,6 +104,22 @@ pub struct PulsarSourceConfig {
     #[configurable(derived)]
     dead_letter_queue_policy: Option<DeadLetterQueuePolicy>,
 
+    /// The delay before a message is redelivered after it was rejected or failed to be delivered.
+    ///
+    /// When end-to-end acknowledgements are enabled, a message whose events are rejected by a sink
+    /// is negatively acknowledged once this delay has passed, making the broker redeliver it. By
+    /// default, messages are negatively acknowledged as soon as the events fail.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = 1000, docs::examples = 60000))]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::human_name = "Negative Acknowledgement Redelivery Delay"))]
+    negative_ack_redelivery_delay_ms: Duration,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schema: Option<SchemaConfig>,
+
     #[configurable(derived)]
     #[serde(default = "default_framing_message_based")]
     #[derivative(Default(value = "default_framing_message_based()"))]
@@ -161,7 +199,55 @@ pub struct OAuth2Config {
     scope: Option<String>,
 }
 
+/// The subscription type of the consumer.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum SubscriptionType {
+    /// Only one consumer may attach to the subscription.
+    Exclusive,
+
+    /// Messages are distributed across the consumers of the subscription, in a round-robin fashion.
+    #[default]
+    Shared,
+
+    /// Messages are distributed across the consumers of the subscription, with messages of the same
+    /// key always delivered to the same consumer.
+    KeyShared,
+
+    /// Messages are delivered to a single active consumer, with the other consumers taking over if
+    /// it disconnects.
+    Failover,
+}
+
+impl std::fmt::Display for SubscriptionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Exclusive => "exclusive",
+            Self::Shared => "shared",
+            Self::KeyShared => "key_shared",
+            Self::Failover => "failover",
+        })
+    }
+}
+
+impl From<SubscriptionType> for SubType {
+    fn from(subscription_type: SubscriptionType) -> Self {
+        match subscription_type {
+            SubscriptionType::Exclusive => SubType::Exclusive,
+            SubscriptionType::Shared => SubType::Shared,
+            SubscriptionType::KeyShared => SubType::KeyShared,
+            SubscriptionType::Failover => SubType::Failover,
+        }
+    }
+}
+
 /// Dead Letter Queue policy configuration.
+///
+/// Messages that are negatively acknowledged, such as those whose events are rejected by a sink
+/// when end-to-end acknowledgements are enabled, are sent to the dead letter topic once they have
+/// been redelivered `max_redeliver_count` times. Requires a `shared` or `key_shared`
+/// `subscription_type`.
 #[configurable_component]
 #[derive(Clone, Debug)]
 struct DeadLetterQueuePolicy {
@@ -190,11 +276,18 @@ impl SourceConfig for PulsarSourceConfig {
         let decoder =
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
                 .build()?;
+        let schema_decoder = self
+            .schema
+            .as_ref()
+            .map(|schema| schema.build(&cx.proxy))
+            .transpose()?;
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
 
         Ok(Box::pin(pulsar_source(
             consumer,
             decoder,
+            schema_decoder,
+            self.negative_ack_redelivery_delay_ms,
             cx.shutdown,
             cx.out,
             acknowledgements,
@@ -205,9 +298,13 @@ impl SourceConfig for PulsarSourceConfig {
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
         let log_namespace = global_log_namespace.merge(self.log_namespace);
 
-        let schema_definition = self
-            .decoding
-            .schema_definition(log_namespace)
+        let mut schema_definition = self.decoding.schema_definition(log_namespace);
+        if self.schema.is_some() {
+            // Messages written with a schema are decoded as structured events instead.
+            schema_definition = schema_definition
+                .merge(JsonDeserializerConfig::default().schema_definition(log_namespace));
+        }
+        let schema_definition = schema_definition
             .with_standard_vector_source_metadata()
             .with_source_metadata(
                 Self::NAME,
@@ -245,6 +342,18 @@ impl PulsarSourceConfig {
     async fn create_consumer(
         &self,
     ) -> crate::Result<pulsar::consumer::Consumer<String, TokioExecutor>> {
+        if self.dead_letter_queue_policy.is_some()
+            && !matches!(
+                self.subscription_type,
+                SubscriptionType::Shared | SubscriptionType::KeyShared
+            )
+        {
+            return Err(BuildError::DeadLetterSubscriptionType {
+                subscription_type: self.subscription_type,
+            }
+            .into());
+        }
+
         let mut builder = Pulsar::builder(&self.endpoint, TokioExecutor);
 
         if let Some(auth) = &self.auth {
@@ -269,7 +378,7 @@ impl PulsarSourceConfig {
         let mut consumer_builder = pulsar
             .consumer()
             .with_topics(&self.topics)
-            .with_subscription_type(SubType::Shared)
+            .with_subscription_type(self.subscription_type.into())
             .with_options(pulsar::consumer::ConsumerOptions {
                 priority_level: self.priority_level,
                 ..Default::default()
@@ -299,9 +408,12 @@ impl PulsarSourceConfig {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn pulsar_source(
     mut consumer: Consumer<String, TokioExecutor>,
     decoder: Decoder,
+    mut schema_decoder: Option<SchemaDecoder>,
+    negative_ack_redelivery_delay: Duration,
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
     acknowledgements: bool,
@@ -314,19 +426,29 @@ async fn pulsar_source(
     let events_received = register!(EventsReceived);
     let pulsar_error_events = register!(PulsarErrorEvent);
 
+    // Messages whose events failed, to be negatively acknowledged once the redelivery delay passed.
+    let mut delayed_nacks = DelayQueue::new();
+
     loop {
         tokio::select! {
             _ = &mut shutdown => break,
             entry = ack_stream.next() => {
                 if let Some((status, entry)) = entry {
-                    handle_ack(&mut consumer, status, entry, &pulsar_error_events).await;
+                    if status != BatchStatus::Delivered && !negative_ack_redelivery_delay.is_zero() {
+                        delayed_nacks.insert(entry, negative_ack_redelivery_delay);
+                    } else {
+                        handle_ack(&mut consumer, status, entry, &pulsar_error_events).await;
+                    }
                 }
             },
+            Some(expired) = delayed_nacks.next() => {
+                handle_ack(&mut consumer, BatchStatus::Errored, expired.into_inner(), &pulsar_error_events).await;
+            },
             Some(maybe_message) = consumer.next() => {
                 match maybe_message {
                     Ok(msg) => {
                         bytes_received.emit(ByteSize(msg.payload.data.len()));
-                        parse_message(msg, &decoder, &finalizer, &mut out, &mut consumer, log_namespace, &events_received, &pulsar_error_events).await;
+                        parse_message(msg, &decoder, schema_decoder.as_mut(), &finalizer, &mut out, &mut consumer, log_namespace, &events_received, &pulsar_error_events).await;
                     }
                     Err(error) => {
                         pulsar_error_events.emit(PulsarErrorEventData{
@@ -346,6 +468,7 @@ async fn pulsar_source(
 async fn parse_message(
     msg: Message<String>,
     decoder: &Decoder,
+    schema_decoder: Option<&mut SchemaDecoder>,
     finalizer: &Option<OrderedFinalizer<FinalizerEntry>>,
     out: &mut SourceSender,
     consumer: &mut Consumer<String, TokioExecutor>,
@@ -353,74 +476,76 @@ async fn parse_message(
     events_received: &Registered<EventsReceived>,
     pulsar_error_events: &Registered<PulsarErrorEvent>,
 ) {
-    let publish_time = i64::try_from(msg.payload.metadata.publish_time)
-        .ok()
-        .and_then(|millis| chrono::Utc.timestamp_millis_opt(millis).latest());
-    let topic = msg.topic.clone();
-    let producer_name = msg.payload.metadata.producer_name.clone();
-
-    let mut stream = FramedRead::new(msg.payload.data.as_ref(), decoder.clone());
-    let stream = async_stream::stream! {
-        while let Some(next) = stream.next().await {
-            match next {
-                Ok((events, _byte_size)) => {
-                    events_received.emit(CountByteSize(
-                        events.len(),
-                        events.estimated_json_encoded_size_of(),
-                    ));
-
-                    let now = chrono::Utc::now();
-
-                    let events = events.into_iter().map(|mut event| {
-                        if let Event::Log(ref mut log) = event {
-                            log_namespace.insert_standard_vector_source_metadata(
-                                log,
-                                PulsarSourceConfig::NAME,
-                                now,
-                            );
-
-                            log_namespace.insert_source_metadata(
-                                PulsarSourceConfig::NAME,
-                                log,
-                                Some(LegacyKey::InsertIfEmpty(path!("publish_time"))),
-                                path!("publish_time"),
-                                publish_time,
-                            );
-
-                            log_namespace.insert_source_metadata(
-                                PulsarSourceConfig::NAME,
-                                log,
-                                Some(LegacyKey::InsertIfEmpty(path!("topic"))),
-                                path!("topic"),
-                                topic.clone(),
-                            );
-
-                            log_namespace.insert_source_metadata(
-                                PulsarSourceConfig::NAME,
-                                log,
-                                Some(LegacyKey::InsertIfEmpty(path!("producer_name"))),
-                                path!("producer_name"),
-                                producer_name.clone(),
-                            );
-                        }
-                        event
-                    });
+    let metadata = MessageMetadata {
+        publish_time: i64::try_from(msg.payload.metadata.publish_time)
+            .ok()
+            .and_then(|millis| chrono::Utc.timestamp_millis_opt(millis).latest()),
+        topic: msg.topic.clone(),
+        producer_name: msg.payload.metadata.producer_name.clone(),
+    };
+
+    let schema_events = match (schema_decoder, &msg.payload.metadata.schema_version) {
+        (Some(schema_decoder), Some(version)) => match schema_decoder
+            .decode(&msg.topic, version, &msg.payload.data, log_namespace)
+            .await
+        {
+            Ok(events) => events,
+            Err(error) => {
+                pulsar_error_events.emit(PulsarErrorEventData {
+                    msg: error.to_string(),
+                    error_type: PulsarErrorEventType::Schema,
+                });
+                None
+            }
+        },
+        _ => None,
+    };
+
+    let stream = match schema_events {
+        Some(events) => {
+            events_received.emit(CountByteSize(
+                events.len(),
+                events.estimated_json_encoded_size_of(),
+            ));
 
-                    for event in events {
-                        yield event;
-                    }
-                }
-                Err(error) => {
-                    // Error is logged by `crate::codecs`, no further
-                    // handling is needed here.
-                    if !error.can_continue() {
-                        break;
+            let now = chrono::Utc::now();
+            futures_util::stream::iter(events.into_iter().map(move |mut event| {
+                metadata.insert(&mut event, log_namespace, now);
+                event
+            }))
+            .boxed()
+        }
+        None => {
+            let mut stream = FramedRead::new(msg.payload.data.as_ref(), decoder.clone());
+            async_stream::stream! {
+                while let Some(next) = stream.next().await {
+                    match next {
+                        Ok((events, _byte_size)) => {
+                            events_received.emit(CountByteSize(
+                                events.len(),
+                                events.estimated_json_encoded_size_of(),
+                            ));
+
+                            let now = chrono::Utc::now();
+
+                            for mut event in events {
+                                metadata.insert(&mut event, log_namespace, now);
+                                yield event;
+                            }
+                        }
+                        Err(error) => {
+                            // Error is logged by `crate::codecs`, no further
+                            // handling is needed here.
+                            if !error.can_continue() {
+                                break;
+                            }
+                        }
                     }
                 }
             }
+            .boxed()
         }
-    }
-    .boxed();
+    };
 
     finalize_event_stream(
         consumer,
@@ -434,6 +559,54 @@ async fn parse_message(
     .await;
 }
 
+/// The metadata of a message, inserted into each of its events.
+struct MessageMetadata {
+    publish_time: Option<chrono::DateTime<chrono::Utc>>,
+    topic: String,
+    producer_name: String,
+}
+
+impl MessageMetadata {
+    fn insert(
+        &self,
+        event: &mut Event,
+        log_namespace: LogNamespace,
+        now: chrono::DateTime<chrono::Utc>,
+    ) {
+        if let Event::Log(ref mut log) = event {
+            log_namespace.insert_standard_vector_source_metadata(
+                log,
+                PulsarSourceConfig::NAME,
+                now,
+            );
+
+            log_namespace.insert_source_metadata(
+                PulsarSourceConfig::NAME,
+                log,
+                Some(LegacyKey::InsertIfEmpty(path!("publish_time"))),
+                path!("publish_time"),
+                self.publish_time,
+            );
+
+            log_namespace.insert_source_metadata(
+                PulsarSourceConfig::NAME,
+                log,
+                Some(LegacyKey::InsertIfEmpty(path!("topic"))),
+                path!("topic"),
+                self.topic.clone(),
+            );
+
+            log_namespace.insert_source_metadata(
+                PulsarSourceConfig::NAME,
+                log,
+                Some(LegacyKey::InsertIfEmpty(path!("producer_name"))),
+                path!("producer_name"),
+                self.producer_name.clone(),
+            );
+        }
+    }
+}
+
 /// Send the event stream created by the framed read to the `out` stream.
 async fn finalize_event_stream(
     consumer: &mut Consumer<String, TokioExecutor>,
@@ -508,12 +681,62 @@ async fn handle_ack(
 
 #[cfg(test)]
 mod tests {
-    use crate::sources::pulsar::PulsarSourceConfig;
+    use std::time::Duration;
+
+    use crate::sources::pulsar::{PulsarSourceConfig, SubscriptionType};
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<PulsarSourceConfig>();
     }
+
+    #[test]
+    fn parse_subscription_options() {
+        let config: PulsarSourceConfig = toml::from_str(
+            r#"
+            endpoint = "pulsar://127.0.0.1:6650"
+            topics = ["my-topic"]
+            subscription_type = "key_shared"
+            negative_ack_redelivery_delay_ms = 5000
+            dead_letter_queue_policy.max_redeliver_count = 3
+            dead_letter_queue_policy.dead_letter_topic = "my-topic-dlq"
+            schema.admin_endpoint = "http://127.0.0.1:8080"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.subscription_type, SubscriptionType::KeyShared);
+        assert_eq!(
+            config.negative_ack_redelivery_delay_ms,
+            Duration::from_secs(5)
+        );
+        assert!(config.schema.is_some());
+
+        let config: PulsarSourceConfig = toml::from_str(
+            r#"
+            endpoint = "pulsar://127.0.0.1:6650"
+            topics = ["my-topic"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.subscription_type, SubscriptionType::Shared);
+        assert!(config.negative_ack_redelivery_delay_ms.is_zero());
+    }
+
+    #[tokio::test]
+    async fn dead_letter_queue_requires_shared_subscription() {
+        let config: PulsarSourceConfig = toml::from_str(
+            r#"
+            endpoint = "pulsar://127.0.0.1:6650"
+            topics = ["my-topic"]
+            subscription_type = "failover"
+            dead_letter_queue_policy.max_redeliver_count = 3
+            dead_letter_queue_policy.dead_letter_topic = "my-topic-dlq"
+            "#,
+        )
+        .unwrap();
+        let error = config.create_consumer().await.err().unwrap();
+        assert!(error.to_string().contains("`failover`"));
+    }
 }
 
 #[cfg(feature = "pulsar-integration-tests")]
@@ -523,6 +746,9 @@ mod integration_tests {
     use crate::config::log_schema;
     use crate::test_util::components::{assert_source_compliance, SOURCE_TAGS};
     use crate::test_util::{collect_n, random_string, trace_init};
+    use std::time::Instant;
+    use tokio::time::timeout;
+    use vector_lib::event::EventStatus;
 
     fn pulsar_address() -> String {
         std::env::var("PULSAR_ADDRESS").unwrap_or_else(|_| "pulsar://127.0.0.1:6650".into())
@@ -548,24 +774,134 @@ mod integration_tests {
         pulsar_send_receive(false, LogNamespace::Vector).await;
     }
 
-    async fn pulsar_send_receive(acknowledgements: bool, log_namespace: LogNamespace) {
-        trace_init();
-
-        let topic = format!("test-{}", random_string(10));
-        let cnf = PulsarSourceConfig {
+    fn make_config(topic: &str, acknowledgements: bool) -> PulsarSourceConfig {
+        PulsarSourceConfig {
             endpoint: pulsar_address(),
-            topics: vec![topic.clone()],
+            topics: vec![topic.to_owned()],
             consumer_name: None,
             subscription_name: None,
+            subscription_type: SubscriptionType::Shared,
             priority_level: None,
             batch_size: None,
             auth: None,
             dead_letter_queue_policy: None,
+            negative_ack_redelivery_delay_ms: Duration::ZERO,
+            schema: None,
             framing: FramingConfig::Bytes,
             decoding: DeserializerConfig::Bytes,
             acknowledgements: acknowledgements.into(),
             log_namespace: None,
-        };
+        }
+    }
+
+    /// Runs the source for the given config, with the given sender.
+    async fn spawn_source(cnf: &PulsarSourceConfig, out: SourceSender) {
+        let consumer = cnf.create_consumer().await.unwrap();
+        let decoder = DecodingConfig::new(
+            cnf.framing.clone(),
+            cnf.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build()
+        .unwrap();
+        tokio::spawn(pulsar_source(
+            consumer,
+            decoder,
+            None,
+            cnf.negative_ack_redelivery_delay_ms,
+            ShutdownSignal::noop(),
+            out,
+            true,
+            LogNamespace::Legacy,
+        ));
+    }
+
+    async fn send_message(topic: &str, msg: &str) {
+        let pulsar = Pulsar::<TokioExecutor>::builder(pulsar_address(), TokioExecutor)
+            .build()
+            .await
+            .unwrap();
+        let mut producer = pulsar.producer().with_topic(topic).build().await.unwrap();
+        producer
+            .send_non_blocking(msg)
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn redelivers_errored_messages_after_delay() {
+        trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let mut cnf = make_config(&topic, true);
+        cnf.negative_ack_redelivery_delay_ms = Duration::from_secs(2);
+
+        let (tx, mut rx) = SourceSender::new_test_errors(|n| n == 0);
+        spawn_source(&cnf, tx).await;
+        send_message(&topic, "redelivered message").await;
+
+        let first = timeout(Duration::from_secs(30), rx.next())
+            .await
+            .unwrap()
+            .unwrap();
+        let received_at = Instant::now();
+        let second = timeout(Duration::from_secs(30), rx.next())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(received_at.elapsed() >= Duration::from_secs(2));
+        for event in [first, second] {
+            assert_eq!(
+                event.as_log()[log_schema().message_key().unwrap().to_string()],
+                "redelivered message".into()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn routes_rejected_messages_to_dead_letter_topic() {
+        trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let dead_letter_topic = format!("{}-dlq", topic);
+        let mut cnf = make_config(&topic, true);
+        cnf.dead_letter_queue_policy = Some(DeadLetterQueuePolicy {
+            max_redeliver_count: 1,
+            dead_letter_topic: dead_letter_topic.clone(),
+        });
+
+        let pulsar = Pulsar::<TokioExecutor>::builder(pulsar_address(), TokioExecutor)
+            .build()
+            .await
+            .unwrap();
+        let mut dead_letters: Consumer<String, TokioExecutor> = pulsar
+            .consumer()
+            .with_topic(&dead_letter_topic)
+            .with_subscription("dead-letters")
+            .build()
+            .await
+            .unwrap();
+
+        let (tx, _rx) = SourceSender::new_test_finalize(EventStatus::Rejected);
+        spawn_source(&cnf, tx).await;
+        send_message(&topic, "dead letter").await;
+
+        let msg = timeout(Duration::from_secs(30), dead_letters.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(msg.payload.data, b"dead letter");
+    }
+
+    async fn pulsar_send_receive(acknowledgements: bool, log_namespace: LogNamespace) {
+        trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let cnf = make_config(&topic, acknowledgements);
 
         let pulsar = Pulsar::<TokioExecutor>::builder(&cnf.endpoint, TokioExecutor)
             .build()
@@ -590,6 +926,8 @@ mod integration_tests {
             tokio::spawn(pulsar_source(
                 consumer,
                 decoder,
+                None,
+                Duration::ZERO,
                 ShutdownSignal::noop(),
                 tx,
                 acknowledgements,
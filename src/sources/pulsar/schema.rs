@@ -0,0 +1,400 @@
+//! Decoding of messages written with a schema, fetched from the Pulsar admin API.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use http::{Request, StatusCode};
+use hyper::Body;
+use serde::Deserialize;
+use smallvec::SmallVec;
+use snafu::{ResultExt, Snafu};
+use vector_lib::codecs::decoding::format::{AvroDeserializer, Deserializer, JsonDeserializer};
+use vector_lib::configurable::configurable_component;
+use vector_lib::{config::LogNamespace, event::Event};
+
+use crate::{
+    config::ProxyConfig,
+    http::{Auth, HttpClient, HttpError},
+    tls::{TlsConfig, TlsSettings},
+};
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a failure to fetch a schema is remembered before it is fetched again, so that messages
+/// written with it don't each wait for the admin API in the meantime.
+const FAILURE_TTL: Duration = Duration::from_secs(30);
+
+/// Decoding of messages written with a schema.
+///
+/// Messages written with an `AVRO` or `JSON` schema are decoded into structured events, using the
+/// schema they were written with, as fetched from the Pulsar admin API. Other messages, such as
+/// those without a schema or with a `STRING` schema, are decoded with `decoding`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaConfig {
+    /// The endpoint of the Pulsar admin API.
+    #[configurable(metadata(docs::examples = "http://127.0.0.1:8080"))]
+    admin_endpoint: String,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+}
+
+impl SchemaConfig {
+    pub(super) fn build(&self, proxy: &ProxyConfig) -> crate::Result<SchemaDecoder> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        Ok(SchemaDecoder {
+            client: HttpClient::new(tls, proxy)?,
+            endpoint: self.admin_endpoint.trim_end_matches('/').to_owned(),
+            auth: self.auth.clone(),
+            schemas: HashMap::new(),
+        })
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum SchemaError {
+    #[snafu(display("Invalid schema version {:?}, expected an 8-byte version.", version))]
+    InvalidVersion { version: Vec<u8> },
+    #[snafu(display("Failed to build schema request: {}", source))]
+    BuildRequest { source: http::Error },
+    #[snafu(display("Failed to fetch schema: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("Failed to read schema response: {}", source))]
+    ReadResponse { source: hyper::Error },
+    #[snafu(display("Timed out fetching schema."))]
+    Timeout,
+    #[snafu(display("Admin API responded with {}: {}", status, body))]
+    UnexpectedStatus { status: StatusCode, body: String },
+    #[snafu(display("Invalid schema response: {}", source))]
+    InvalidResponse { source: serde_json::Error },
+    #[snafu(display("Invalid Avro schema: {}", source))]
+    InvalidAvroSchema { source: apache_avro::Error },
+    #[snafu(display("Schema unavailable, fetching it recently failed: {}", error))]
+    RecentFailure { error: String },
+    #[snafu(display("Failed to decode message: {}", source))]
+    Decode { source: vector_lib::Error },
+}
+
+/// A schema, as returned by the admin API.
+#[derive(Deserialize)]
+struct SchemaResponse {
+    #[serde(rename = "type")]
+    schema_type: String,
+    data: String,
+}
+
+/// How to decode the messages written with a schema.
+enum TopicSchema {
+    Avro(AvroDeserializer),
+    Json,
+    /// Schemas whose messages are decoded with `decoding`.
+    Other,
+}
+
+impl TopicSchema {
+    fn new(schema: SchemaResponse) -> Result<Self, SchemaError> {
+        Ok(match schema.schema_type.as_str() {
+            "AVRO" => Self::Avro(AvroDeserializer::new(
+                apache_avro::Schema::parse_str(&schema.data).context(InvalidAvroSchemaSnafu)?,
+                false,
+            )),
+            "JSON" => Self::Json,
+            _ => Self::Other,
+        })
+    }
+}
+
+/// Decodes messages written with a schema, caching the schemas fetched from the admin API.
+pub(super) struct SchemaDecoder {
+    client: HttpClient,
+    endpoint: String,
+    auth: Option<Auth>,
+    /// The schemas fetched so far, by topic and schema version.
+    schemas: HashMap<(String, i64), CachedSchema>,
+}
+
+impl SchemaDecoder {
+    /// Decodes the payload of a message written with the given schema version of its topic.
+    ///
+    /// Returns `None` if messages of this schema are to be decoded with `decoding` instead.
+    pub(super) async fn decode(
+        &mut self,
+        topic: &str,
+        version: &[u8],
+        payload: &[u8],
+        log_namespace: LogNamespace,
+    ) -> Result<Option<SmallVec<[Event; 1]>>, SchemaError> {
+        let version = <[u8; 8]>::try_from(version)
+            .map(i64::from_be_bytes)
+            .map_err(|_| SchemaError::InvalidVersion {
+                version: version.to_vec(),
+            })?;
+        let key = (schema_path(topic), version);
+        let expired = match self.schemas.get(&key) {
+            Some(CachedSchema::Fetched(_)) => false,
+            Some(CachedSchema::Failed { error, at }) if at.elapsed() < FAILURE_TTL => {
+                return RecentFailureSnafu { error }.fail();
+            }
+            _ => true,
+        };
+        if expired {
+            let schema = match self.fetch(&key.0, version).await {
+                Ok(schema) => CachedSchema::Fetched(schema),
+                Err(error) => {
+                    self.schemas.insert(
+                        key,
+                        CachedSchema::Failed {
+                            error: error.to_string(),
+                            at: Instant::now(),
+                        },
+                    );
+                    return Err(error);
+                }
+            };
+            self.schemas.insert(key.clone(), schema);
+        }
+
+        let payload = Bytes::copy_from_slice(payload);
+        match &self.schemas[&key] {
+            CachedSchema::Fetched(TopicSchema::Avro(deserializer)) => {
+                deserializer.parse(payload, log_namespace)
+            }
+            CachedSchema::Fetched(TopicSchema::Json) => {
+                JsonDeserializer::new(false).parse(payload, log_namespace)
+            }
+            CachedSchema::Fetched(TopicSchema::Other) => return Ok(None),
+            CachedSchema::Failed { error, .. } => return RecentFailureSnafu { error }.fail(),
+        }
+        .map(Some)
+        .context(DecodeSnafu)
+    }
+
+    async fn fetch(&self, path: &str, version: i64) -> Result<TopicSchema, SchemaError> {
+        let mut request = Request::get(format!(
+            "{}/admin/v2/schemas/{}/schema/{}",
+            self.endpoint, path, version
+        ))
+        .body(Body::empty())
+        .context(BuildRequestSnafu)?;
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let (status, body) = tokio::time::timeout(FETCH_TIMEOUT, async {
+            let response = self.client.send(request).await.context(RequestSnafu)?;
+            let status = response.status();
+            let body = hyper::body::to_bytes(response.into_body())
+                .await
+                .context(ReadResponseSnafu)?;
+            Ok::<_, SchemaError>((status, body))
+        })
+        .await
+        .map_err(|_| SchemaError::Timeout)??;
+
+        if !status.is_success() {
+            return UnexpectedStatusSnafu {
+                status,
+                body: String::from_utf8_lossy(&body),
+            }
+            .fail();
+        }
+        let schema = serde_json::from_slice(&body).context(InvalidResponseSnafu)?;
+        TopicSchema::new(schema)
+    }
+}
+
+/// The path of a topic in the admin API, such as `public/default/my-topic` for
+/// `persistent://public/default/my-topic-partition-0`. The partitions of a topic share its schemas.
+fn schema_path(topic: &str) -> String {
+    let name = topic.split_once("://").map_or(topic, |(_, name)| name);
+    let name = match name.rsplit_once("-partition-") {
+        Some((base, index)) if !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()) => {
+            base
+        }
+        _ => name,
+    };
+    if name.contains('/') {
+        name.to_owned()
+    } else {
+        format!("public/default/{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use futures::FutureExt;
+    use http::Response;
+
+    use super::*;
+    use crate::{sinks::util::test::build_test_server_generic, test_util::next_addr};
+
+    const AVRO_SCHEMA: &str = r#"{ "type": "record", "name": "log", "fields": [{ "name": "message", "type": "string" }] }"#;
+
+    /// Serves the given status and body as every schema, returning the decoder and the number of
+    /// requests served.
+    fn decoder_with_admin_api(
+        status: StatusCode,
+        body: &'static str,
+    ) -> (SchemaDecoder, Arc<AtomicUsize>) {
+        let addr = next_addr();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let served = Arc::clone(&requests);
+        let (_rx, trigger, server) = build_test_server_generic(addr, move || {
+            served.fetch_add(1, Ordering::SeqCst);
+            Response::builder()
+                .status(status)
+                .body(Body::from(body))
+                .unwrap()
+        });
+        tokio::spawn(server.map(move |_| drop(trigger)));
+
+        let decoder = SchemaConfig {
+            admin_endpoint: format!("http://{}/", addr),
+            auth: None,
+            tls: None,
+        }
+        .build(&ProxyConfig::default())
+        .unwrap();
+        (decoder, requests)
+    }
+
+    #[test]
+    fn topic_schema_path() {
+        assert_eq!(
+            schema_path("persistent://public/default/my-topic"),
+            "public/default/my-topic"
+        );
+        assert_eq!(
+            schema_path("persistent://tenant/ns/my-topic-partition-3"),
+            "tenant/ns/my-topic"
+        );
+        assert_eq!(
+            schema_path("non-persistent://tenant/ns/my-partition-topic"),
+            "tenant/ns/my-partition-topic"
+        );
+        assert_eq!(schema_path("my-topic"), "public/default/my-topic");
+    }
+
+    #[test]
+    fn topic_schema_types() {
+        let schema = |schema_type: &str, data: &str| {
+            TopicSchema::new(SchemaResponse {
+                schema_type: schema_type.into(),
+                data: data.into(),
+            })
+        };
+
+        assert!(matches!(
+            schema("AVRO", AVRO_SCHEMA),
+            Ok(TopicSchema::Avro(_))
+        ));
+        assert!(matches!(schema("JSON", AVRO_SCHEMA), Ok(TopicSchema::Json)));
+        assert!(matches!(schema("STRING", ""), Ok(TopicSchema::Other)));
+        assert!(matches!(
+            schema("AVRO", "{"),
+            Err(SchemaError::InvalidAvroSchema { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn decodes_messages_with_their_schema() {
+        let (mut decoder, requests) = decoder_with_admin_api(
+            StatusCode::OK,
+            r#"{ "type": "JSON", "data": "{}", "properties": {} }"#,
+        );
+        let schema = apache_avro::Schema::parse_str(AVRO_SCHEMA).unwrap();
+        decoder.schemas.insert(
+            ("public/default/avro".into(), 1),
+            CachedSchema::Fetched(TopicSchema::Avro(AvroDeserializer::new(
+                schema.clone(),
+                false,
+            ))),
+        );
+        let version = 1i64.to_be_bytes();
+
+        let record = apache_avro::types::Value::Record(vec![(
+            "message".into(),
+            apache_avro::types::Value::String("from avro".into()),
+        )]);
+        let payload = apache_avro::to_avro_datum(&schema, record).unwrap();
+        let events = decoder
+            .decode("avro", &version, &payload, LogNamespace::Legacy)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["message"], "from avro".into());
+        assert_eq!(requests.load(Ordering::SeqCst), 0);
+
+        for _ in 0..2 {
+            let events = decoder
+                .decode(
+                    "persistent://public/default/json-partition-1",
+                    &version,
+                    br#"{ "message": "from json" }"#,
+                    LogNamespace::Legacy,
+                )
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(events[0].as_log()["message"], "from json".into());
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        assert!(matches!(
+            decoder
+                .decode("json", &[1], b"{}", LogNamespace::Legacy)
+                .await,
+            Err(SchemaError::InvalidVersion { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn caches_fetch_failures() {
+        let (mut decoder, requests) =
+            decoder_with_admin_api(StatusCode::INTERNAL_SERVER_ERROR, "unavailable");
+        let version = 1i64.to_be_bytes();
+
+        assert!(matches!(
+            decoder
+                .decode("my-topic", &version, b"{}", LogNamespace::Legacy)
+                .await,
+            Err(SchemaError::UnexpectedStatus { .. })
+        ));
+        let error = decoder
+            .decode("my-topic", &version, b"{}", LogNamespace::Legacy)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, SchemaError::RecentFailure { .. }));
+        assert!(error.to_string().contains("unavailable"));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // Once the failure expires, the schema is fetched again.
+        if let Some(CachedSchema::Failed { at, .. }) = decoder
+            .schemas
+            .get_mut(&("public/default/my-topic".to_owned(), 1))
+        {
+            *at -= FAILURE_TTL;
+        }
+        assert!(matches!(
+            decoder
+                .decode("my-topic", &version, b"{}", LogNamespace::Legacy)
+                .await,
+            Err(SchemaError::UnexpectedStatus { .. })
+        ));
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+}
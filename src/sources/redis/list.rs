@@ -1,14 +1,25 @@
-use redis::{aio::ConnectionManager, AsyncCommands, ErrorKind, RedisError, RedisResult};
+use redis::{aio::ConnectionManager, AsyncCommands, Direction, ErrorKind, RedisError, RedisResult};
 use snafu::{ResultExt, Snafu};
-use std::time::Duration;
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+use vector_lib::finalizer::UnorderedFinalizer;
 
-use super::{InputHandler, Method};
-use crate::{internal_events::RedisReceiveEventError, sources::Source};
+use super::{run_with_acks, InputHandler, Method};
+use crate::{
+    event::{BatchNotifier, BatchStatus},
+    internal_events::{RedisAckError, RedisReceiveEventError},
+    shutdown::ShutdownSignal,
+    sources::Source,
+};
+
+/// The longest an errored message waits before it is pushed back onto the list.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
 
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("Failed to create connection: {}", source))]
     Connection { source: RedisError },
+    #[snafu(display("Failed to requeue messages from the processing list: {}", source))]
+    Requeue { source: RedisError },
 }
 
 impl InputHandler {
@@ -61,7 +72,200 @@ impl InputHandler {
     }
 }
 
-async fn backoff_exponential(exp: u32) {
+impl InputHandler {
+    /// Reads messages by moving them into a processing list, from which they are only removed once
+    /// their events are delivered.
+    pub(super) async fn watch_reliable(
+        mut self,
+        method: Method,
+        processing_key: String,
+        acknowledgements: bool,
+    ) -> crate::Result<Source> {
+        let mut conn = self
+            .client
+            .get_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+        // Reads block their connection, so acknowledgements are sent on one of their own.
+        let ack_conn = self
+            .client
+            .get_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+
+        let requeued = requeue(&mut conn, &processing_key, &self.key, method)
+            .await
+            .context(RequeueSnafu {})?;
+        if requeued > 0 {
+            info!(
+                message = "Requeued messages left in the processing list.",
+                count = requeued,
+                processing_key = %processing_key,
+            );
+        }
+
+        Ok(Box::pin(async move {
+            let acker = &Acker {
+                conn: ack_conn,
+                key: self.key.clone(),
+                processing_key: processing_key.clone(),
+                method,
+                shutdown: self.cx.shutdown.clone(),
+                attempts: Mutex::default(),
+            };
+
+            let reads = |finalizer: Option<UnorderedFinalizer<String>>| async move {
+                let mut shutdown = self.cx.shutdown.clone();
+                let mut retry: u32 = 0;
+                loop {
+                    // A message moved just as the source shuts down stays in the processing list,
+                    // to be requeued on the next start.
+                    let res = tokio::select! {
+                        res = blmove(&mut conn, &self.key, &processing_key, method) => res,
+                        _ = &mut shutdown => break
+                    };
+
+                    match res {
+                        Err(error) => {
+                            let kind = error.kind();
+
+                            emit!(RedisReceiveEventError::from(error));
+
+                            if kind == ErrorKind::IoError {
+                                retry += 1;
+                                backoff_exponential(retry).await
+                            }
+                        }
+                        Ok(line) => {
+                            retry = 0;
+                            let mut events = self.decode_line(&line).await;
+                            match &finalizer {
+                                Some(finalizer) => {
+                                    let receiver = BatchNotifier::apply_to(&mut events);
+                                    if let Err(()) = self.send(events).await {
+                                        break;
+                                    }
+                                    finalizer.add(line, receiver);
+                                }
+                                None => {
+                                    if let Err(()) = self.send(events).await {
+                                        break;
+                                    }
+                                    acker.handle_ack(BatchStatus::Delivered, line).await;
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+            let acks = move |status, line| acker.handle_ack(status, line);
+
+            run_with_acks(acknowledgements, reads, acks).await;
+            Ok(())
+        }))
+    }
+}
+
+struct Acker {
+    conn: ConnectionManager,
+    key: String,
+    processing_key: String,
+    method: Method,
+    shutdown: ShutdownSignal,
+    /// The number of times in a row the events of each errored message failed to be delivered.
+    /// Identical messages share their count.
+    attempts: Mutex<HashMap<String, u32>>,
+}
+
+impl Acker {
+    async fn handle_ack(&self, status: BatchStatus, line: String) {
+        let mut conn = self.conn.clone();
+        let mut pipe = redis::pipe();
+        pipe.atomic().lrem(&self.processing_key, 1, &line).ignore();
+        // Messages rejected by a sink would be rejected again, so they are dropped as well. Errored
+        // ones are pushed back onto the end of the list that is not read from, so that the other
+        // messages are read before they are tried again, after a delay growing with every attempt
+        // so that they aren't tried in a tight loop while the sink keeps failing.
+        if status == BatchStatus::Errored {
+            let attempts = {
+                let mut attempts = self.attempts.lock().expect("attempts lock poisoned");
+                let count = attempts.entry(line.clone()).or_default();
+                *count += 1;
+                *count
+            };
+            // The message is left in the processing list in the meantime, to be requeued on the
+            // next start should the source shut down first.
+            let mut shutdown = self.shutdown.clone();
+            tokio::select! {
+                _ = tokio::time::sleep(retry_delay(attempts)) => {}
+                _ = &mut shutdown => return,
+            }
+            match self.method {
+                Method::Lpop => pipe.rpush(&self.key, &line).ignore(),
+                Method::Rpop => pipe.lpush(&self.key, &line).ignore(),
+            };
+        } else {
+            self.attempts
+                .lock()
+                .expect("attempts lock poisoned")
+                .remove(&line);
+        }
+        let result: RedisResult<()> = pipe.query_async(&mut conn).await;
+        if let Err(error) = result {
+            emit!(RedisAckError { error });
+        }
+    }
+}
+
+/// The delay before a message that errored `attempts` times in a row is pushed back onto the list.
+fn retry_delay(attempts: u32) -> Duration {
+    Duration::from_secs(1 << attempts.min(6)).min(MAX_RETRY_DELAY)
+}
+
+/// Moves the messages left in the processing list back onto the end of the list that is read
+/// from, oldest last, so that they are read again first.
+async fn requeue(
+    conn: &mut ConnectionManager,
+    processing_key: &str,
+    key: &str,
+    method: Method,
+) -> RedisResult<usize> {
+    let mut count = 0;
+    loop {
+        let line: Option<String> = conn
+            .lmove(processing_key, key, Direction::Left, read_direction(method))
+            .await?;
+        match line {
+            Some(_) => count += 1,
+            None => return Ok(count),
+        }
+    }
+}
+
+async fn blmove(
+    conn: &mut ConnectionManager,
+    key: &str,
+    processing_key: &str,
+    method: Method,
+) -> RedisResult<String> {
+    conn.blmove(
+        key,
+        processing_key,
+        read_direction(method),
+        Direction::Left,
+        0.0,
+    )
+    .await
+}
+
+const fn read_direction(method: Method) -> Direction {
+    match method {
+        Method::Lpop => Direction::Left,
+        Method::Rpop => Direction::Right,
+    }
+}
+
+pub(super) async fn backoff_exponential(exp: u32) {
     let ms = if exp <= 4 { 2_u64.pow(exp + 5) } else { 1000 };
     tokio::time::sleep(Duration::from_millis(ms)).await;
 }
@@ -1,3 +1,5 @@
+use std::{fmt::Debug, future::Future};
+
 use bytes::Bytes;
 use chrono::Utc;
 use futures::StreamExt;
@@ -8,12 +10,13 @@ use vector_lib::codecs::{
     StreamDecodingError,
 };
 use vector_lib::configurable::configurable_component;
+use vector_lib::finalizer::UnorderedFinalizer;
 use vector_lib::internal_event::{
     ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol, Registered,
 };
 use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath};
 use vector_lib::{
-    config::{LegacyKey, LogNamespace},
+    config::{LegacyKey, LogNamespace, SourceAcknowledgementsConfig},
     EstimatedJsonEncodedSizeOf,
 };
 use vrl::value::Kind;
@@ -21,18 +24,21 @@ use vrl::value::Kind;
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{log_schema, GenerateConfig, SourceConfig, SourceContext, SourceOutput},
-    event::Event,
+    event::{BatchStatus, Event},
     internal_events::{EventsReceived, StreamClosedError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
 };
 
 mod channel;
 mod list;
+mod stream;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("Failed to build redis client: {}", source))]
     Client { source: redis::RedisError },
+    #[snafu(display("Failed to get the hostname: {}", source))]
+    Hostname { source: std::io::Error },
 }
 
 /// Data type to use for reading messages from Redis.
@@ -49,15 +55,39 @@ pub enum DataTypeConfig {
     ///
     /// This is based on Redis' Pub/Sub capabilities.
     Channel,
+
+    /// The `stream` data type.
+    ///
+    /// Entries are read as part of a consumer group, which allows the stream to be shared between
+    /// several instances of the source.
+    Stream,
 }
 
 /// Options for the Redis `list` data type.
 #[configurable_component]
-#[derive(Copy, Clone, Debug, Default, Derivative, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Derivative, Eq, PartialEq)]
 #[serde(deny_unknown_fields, rename_all = "lowercase")]
 pub struct ListOption {
     #[configurable(derived)]
     method: Method,
+
+    /// Whether to read messages reliably, rather than popping them off the list.
+    ///
+    /// Each message is moved into a processing list with `BLMOVE`, and only removed from it once
+    /// its events are delivered, or sent on when acknowledgements are disabled. Messages whose
+    /// events failed to be delivered are pushed back onto the other end of the list to be read
+    /// again. Messages left in the processing list, for example by a crash, are moved back onto the
+    /// list when the source starts.
+    #[serde(default)]
+    reliable: bool,
+
+    /// The Redis key of the processing list to use in reliable mode.
+    ///
+    /// Each instance of the source reading from the same list must use a different processing list.
+    ///
+    /// By default, this is the `key` followed by `:processing:` and the hostname.
+    #[configurable(metadata(docs::examples = "vector:processing:vector-0"))]
+    processing_key: Option<String>,
 }
 
 /// Method for getting events from the `list` data type.
@@ -74,6 +104,71 @@ pub enum Method {
     Rpop,
 }
 
+/// Options for the Redis `stream` data type.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The name of the consumer group to read the stream with.
+    ///
+    /// The group is created, along with the stream, if it does not exist yet. A new group starts
+    /// at the beginning of the stream.
+    #[serde(default = "default_stream_group")]
+    #[derivative(Default(value = "default_stream_group()"))]
+    #[configurable(metadata(docs::examples = "vector"))]
+    group: String,
+
+    /// The name of the consumer within the consumer group.
+    ///
+    /// Each instance of the source reading from the same group must use a different name.
+    ///
+    /// By default, the hostname is used.
+    #[configurable(metadata(docs::examples = "vector-0"))]
+    consumer: Option<String>,
+
+    /// The field of the stream entries that holds the message to decode.
+    ///
+    /// Entries without this field are acknowledged and dropped.
+    #[serde(default = "default_stream_field")]
+    #[derivative(Default(value = "default_stream_field()"))]
+    #[configurable(metadata(docs::examples = "payload"))]
+    field: String,
+
+    /// The maximum number of entries to read from the stream at a time.
+    #[serde(default = "default_stream_count")]
+    #[derivative(Default(value = "default_stream_count()"))]
+    count: usize,
+
+    /// The time, in seconds, that an entry must have gone unacknowledged before it is claimed
+    /// from the consumer it was delivered to.
+    ///
+    /// This is how entries delivered to a consumer that went away are eventually processed. The
+    /// source checks for such entries on the same interval.
+    ///
+    /// Set to `0` to disable claiming entries from other consumers.
+    #[serde(default = "default_stream_claim_min_idle_secs")]
+    #[derivative(Default(value = "default_stream_claim_min_idle_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    claim_min_idle_secs: u64,
+}
+
+fn default_stream_group() -> String {
+    "vector".to_owned()
+}
+
+fn default_stream_field() -> String {
+    "message".to_owned()
+}
+
+const fn default_stream_count() -> usize {
+    100
+}
+
+const fn default_stream_claim_min_idle_secs() -> u64 {
+    300
+}
+
 pub struct ConnectionInfo {
     protocol: &'static str,
     endpoint: String,
@@ -98,13 +193,16 @@ impl From<&redis::ConnectionInfo> for ConnectionInfo {
 #[derive(Clone, Debug, Derivative)]
 #[serde(deny_unknown_fields)]
 pub struct RedisSourceConfig {
-    /// The Redis data type (`list` or `channel`) to use.
+    /// The Redis data type (`list`, `channel`, or `stream`) to use.
     #[serde(default)]
     data_type: DataTypeConfig,
 
     #[configurable(derived)]
     list: Option<ListOption>,
 
+    #[configurable(derived)]
+    stream: Option<StreamOption>,
+
     /// The Redis URL to connect to.
     ///
     /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be `redis` or `rediss` for connections secured using TLS.
@@ -137,6 +235,10 @@ pub struct RedisSourceConfig {
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
     log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
 }
 
 impl GenerateConfig for RedisSourceConfig {
@@ -176,6 +278,7 @@ impl SourceConfig for RedisSourceConfig {
             connection_info.protocol
         )));
         let events_received = register!(EventsReceived);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
         let handler = InputHandler {
             client,
             bytes_received: bytes_received.clone(),
@@ -189,10 +292,31 @@ impl SourceConfig for RedisSourceConfig {
 
         match self.data_type {
             DataTypeConfig::List => {
-                let method = self.list.unwrap_or_default().method;
-                handler.watch(method).await
+                let options = self.list.clone().unwrap_or_default();
+                if options.reliable {
+                    let processing_key = match options.processing_key {
+                        Some(processing_key) => processing_key,
+                        None => format!(
+                            "{}:processing:{}",
+                            self.key,
+                            crate::get_hostname().context(HostnameSnafu)?
+                        ),
+                    };
+                    handler
+                        .watch_reliable(options.method, processing_key, acknowledgements)
+                        .await
+                } else {
+                    handler.watch(options.method).await
+                }
             }
             DataTypeConfig::Channel => handler.subscribe(connection_info).await,
+            DataTypeConfig::Stream => {
+                let mut options = self.stream.clone().unwrap_or_default();
+                if options.consumer.is_none() {
+                    options.consumer = Some(crate::get_hostname().context(HostnameSnafu)?);
+                }
+                handler.read_stream(options, acknowledgements).await
+            }
         }
     }
 
@@ -205,7 +329,7 @@ impl SourceConfig for RedisSourceConfig {
             .and_then(|k| k.path)
             .map(LegacyKey::InsertIfEmpty);
 
-        let schema_definition = self
+        let mut schema_definition = self
             .decoding
             .schema_definition(log_namespace)
             .with_source_metadata(
@@ -217,6 +341,16 @@ impl SourceConfig for RedisSourceConfig {
             )
             .with_standard_vector_source_metadata();
 
+        if let DataTypeConfig::Stream = self.data_type {
+            schema_definition = schema_definition.with_source_metadata(
+                Self::NAME,
+                None,
+                &owned_value_path!("id"),
+                Kind::bytes(),
+                None,
+            );
+        }
+
         vec![SourceOutput::new_logs(
             self.decoding.output_type(),
             schema_definition,
@@ -224,7 +358,11 @@ impl SourceConfig for RedisSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        match self.data_type {
+            DataTypeConfig::List => self.list.as_ref().is_some_and(|list| list.reliable),
+            DataTypeConfig::Channel => false,
+            DataTypeConfig::Stream => true,
+        }
     }
 }
 
@@ -241,11 +379,18 @@ struct InputHandler {
 
 impl InputHandler {
     async fn handle_line(&mut self, line: String) -> Result<(), ()> {
+        let events = self.decode_line(&line).await;
+        self.send(events).await
+    }
+
+    /// Decodes a message read from Redis into events, without sending them on.
+    async fn decode_line(&self, line: &str) -> Vec<Event> {
         let now = Utc::now();
 
         self.bytes_received.emit(ByteSize(line.len()));
 
-        let mut stream = FramedRead::new(line.as_ref(), self.decoder.clone());
+        let mut decoded = Vec::new();
+        let mut stream = FramedRead::new(line.as_bytes(), self.decoder.clone());
         while let Some(next) = stream.next().await {
             match next {
                 Ok((events, _byte_size)) => {
@@ -253,7 +398,7 @@ impl InputHandler {
                     let byte_size = events.estimated_json_encoded_size_of();
                     self.events_received.emit(CountByteSize(count, byte_size));
 
-                    let events = events.into_iter().map(|mut event| {
+                    decoded.extend(events.into_iter().map(|mut event| {
                         if let Event::Log(ref mut log) = event {
                             self.log_namespace.insert_vector_metadata(
                                 log,
@@ -278,12 +423,7 @@ impl InputHandler {
                         };
 
                         event
-                    });
-
-                    if (self.cx.out.send_batch(events).await).is_err() {
-                        emit!(StreamClosedError { count });
-                        return Err(());
-                    }
+                    }));
                 }
                 Err(error) => {
                     // Error is logged by `crate::codecs::Decoder`, no further
@@ -294,10 +434,53 @@ impl InputHandler {
                 }
             }
         }
+        decoded
+    }
+
+    async fn send(&mut self, events: Vec<Event>) -> Result<(), ()> {
+        let count = events.len();
+        if (self.cx.out.send_batch(events).await).is_err() {
+            emit!(StreamClosedError { count });
+            return Err(());
+        }
         Ok(())
     }
 }
 
+/// Runs `reads` along with acknowledging the messages they read to Redis, as their events are
+/// finalized.
+///
+/// With acknowledgements disabled, `reads` gets no finalizer and acknowledges each message itself
+/// once its events are sent. Acknowledgements are handled concurrently, so that one waiting to
+/// retry a message doesn't hold up the others, and they drain once `reads` returns and drops the
+/// finalizer.
+async fn run_with_acks<T, R, A>(
+    acknowledgements: bool,
+    reads: impl FnOnce(Option<UnorderedFinalizer<T>>) -> R,
+    handle_ack: impl Fn(BatchStatus, T) -> A,
+) where
+    T: Send + Debug + 'static,
+    R: Future<Output = ()>,
+    A: Future<Output = ()>,
+{
+    let (finalizer, ack_stream) = if acknowledgements {
+        let (finalizer, ack_stream) = UnorderedFinalizer::<T>::new(None);
+        (Some(finalizer), Some(ack_stream))
+    } else {
+        (None, None)
+    };
+
+    let acks = async {
+        if let Some(ack_stream) = ack_stream {
+            ack_stream
+                .for_each_concurrent(None, |(status, message)| handle_ack(status, message))
+                .await;
+        }
+    };
+
+    tokio::join!(reads(finalizer), acks);
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -306,18 +489,69 @@ mod test {
     fn generate_config() {
         crate::test_util::test_generate_config::<RedisSourceConfig>();
     }
+
+    #[test]
+    fn parse_stream_config() {
+        let config: RedisSourceConfig = toml::from_str(
+            r#"
+            url = "redis://127.0.0.1:6379/0"
+            key = "vector"
+            data_type = "stream"
+            stream.group = "readers"
+            "#,
+        )
+        .unwrap();
+
+        let stream = config.stream.unwrap();
+        assert_eq!(stream.group, "readers");
+        assert_eq!(stream.consumer, None);
+        assert_eq!(stream.field, "message");
+        assert_eq!(stream.claim_min_idle_secs, 300);
+        assert!(config.can_acknowledge());
+    }
+
+    #[test]
+    fn reliable_list_can_acknowledge() {
+        let config: RedisSourceConfig = toml::from_str(
+            r#"
+            url = "redis://127.0.0.1:6379/0"
+            key = "vector"
+            list.method = "rpop"
+            list.reliable = true
+            "#,
+        )
+        .unwrap();
+        assert!(config.can_acknowledge());
+
+        let config: RedisSourceConfig = toml::from_str(
+            r#"
+            url = "redis://127.0.0.1:6379/0"
+            key = "vector"
+            list.method = "rpop"
+            "#,
+        )
+        .unwrap();
+        assert!(!config.can_acknowledge());
+    }
 }
 
 #[cfg(all(test, feature = "redis-integration-tests"))]
 mod integration_test {
-    use redis::AsyncCommands;
+    use futures::StreamExt;
+    use redis::{
+        streams::{StreamPendingReply, StreamReadOptions, StreamReadReply},
+        AsyncCommands,
+    };
 
     use super::*;
     use crate::{
         config::log_schema,
+        event::EventStatus,
         test_util::{
             collect_n,
-            components::{run_and_assert_source_compliance_n, SOURCE_TAGS},
+            components::{
+                assert_source_compliance, run_and_assert_source_compliance_n, SOURCE_TAGS,
+            },
             random_string,
         },
         SourceSender,
@@ -344,13 +578,16 @@ mod integration_test {
             data_type: DataTypeConfig::List,
             list: Some(ListOption {
                 method: Method::Rpop,
+                ..Default::default()
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(false),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
@@ -385,13 +622,16 @@ mod integration_test {
             data_type: DataTypeConfig::List,
             list: Some(ListOption {
                 method: Method::Rpop,
+                ..Default::default()
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: Some(OptionalValuePath::from(owned_value_path!("remapped_key"))),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(true),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 1, &SOURCE_TAGS).await;
@@ -426,13 +666,16 @@ mod integration_test {
             data_type: DataTypeConfig::List,
             list: Some(ListOption {
                 method: Method::Lpop,
+                ..Default::default()
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(false),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
@@ -460,12 +703,14 @@ mod integration_test {
         let config = RedisSourceConfig {
             data_type: DataTypeConfig::Channel,
             list: None,
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(false),
+            acknowledgements: Default::default(),
         };
 
         let (tx, rx) = SourceSender::new_test();
@@ -509,4 +754,242 @@ mod integration_test {
             );
         }
     }
+
+    #[tokio::test]
+    async fn redis_source_list_reliable() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_connection_manager().await.unwrap();
+
+        let key = format!("test-key-{}", random_string(10));
+        let processing_key = format!("{}:processing", key);
+
+        // A message left in the processing list by a previous run is requeued first.
+        let _: i32 = conn.rpush(&processing_key, "0").await.unwrap();
+        let _: i32 = conn.rpush(&key, "1").await.unwrap();
+        let _: i32 = conn.rpush(&key, "2").await.unwrap();
+
+        let config = RedisSourceConfig {
+            data_type: DataTypeConfig::List,
+            list: Some(ListOption {
+                method: Method::Lpop,
+                reliable: true,
+                processing_key: Some(processing_key.clone()),
+            }),
+            stream: None,
+            url: REDIS_SERVER.to_owned(),
+            key: key.clone(),
+            redis_key: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            log_namespace: Some(false),
+            acknowledgements: true.into(),
+        };
+
+        let events = assert_source_compliance(&SOURCE_TAGS, async {
+            let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            collect_n(rx, 3).await
+        })
+        .await;
+
+        for (event, message) in events.iter().zip(["0", "1", "2"]) {
+            assert_eq!(
+                event.as_log()[log_schema().message_key().unwrap().to_string()],
+                message.into()
+            );
+        }
+
+        // The messages were removed from the processing list once their events were delivered.
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        let len: usize = conn.llen(&key).await.unwrap();
+        assert_eq!(len, 0);
+        let len: usize = conn.llen(&processing_key).await.unwrap();
+        assert_eq!(len, 0);
+    }
+
+    #[tokio::test]
+    async fn redis_source_list_reliable_retries_errored_messages() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_connection_manager().await.unwrap();
+
+        let key = format!("test-key-{}", random_string(10));
+        let _: i32 = conn.rpush(&key, "1").await.unwrap();
+
+        let config = RedisSourceConfig {
+            data_type: DataTypeConfig::List,
+            list: Some(ListOption {
+                method: Method::Lpop,
+                reliable: true,
+                processing_key: None,
+            }),
+            stream: None,
+            url: REDIS_SERVER.to_owned(),
+            key: key.clone(),
+            redis_key: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            log_namespace: Some(false),
+            acknowledgements: true.into(),
+        };
+
+        // The first delivery errors, so the message is pushed back onto the list after a delay.
+        let (tx, rx) = SourceSender::new_test_errors(|n| n == 0);
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        let start = tokio::time::Instant::now();
+        let events = collect_n(rx, 2).await;
+        assert!(start.elapsed() >= tokio::time::Duration::from_secs(2));
+        for event in events {
+            assert_eq!(
+                event.as_log()[log_schema().message_key().unwrap().to_string()],
+                "1".into()
+            );
+        }
+    }
+
+    fn stream_config(key: &str, stream: StreamOption) -> RedisSourceConfig {
+        RedisSourceConfig {
+            data_type: DataTypeConfig::Stream,
+            list: None,
+            stream: Some(stream),
+            url: REDIS_SERVER.to_owned(),
+            key: key.to_owned(),
+            redis_key: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            log_namespace: Some(false),
+            acknowledgements: true.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn redis_source_stream_acknowledges_entries() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        for message in ["1", "2", "3"] {
+            let _: String = conn.xadd(&key, "*", &[("message", message)]).await.unwrap();
+        }
+
+        let config = stream_config(
+            &key,
+            StreamOption {
+                consumer: Some("vector-0".to_owned()),
+                ..Default::default()
+            },
+        );
+        let events = assert_source_compliance(&SOURCE_TAGS, async {
+            let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            collect_n(rx, 3).await
+        })
+        .await;
+
+        for (event, message) in events.iter().zip(["1", "2", "3"]) {
+            assert_eq!(
+                event.as_log()[log_schema().message_key().unwrap().to_string()],
+                message.into()
+            );
+        }
+
+        // The entries were acknowledged once their events were delivered.
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        let pending: StreamPendingReply = conn.xpending(&key, "vector").await.unwrap();
+        assert_eq!(pending.count(), 0);
+    }
+
+    #[tokio::test]
+    async fn redis_source_stream_claims_idle_entries() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        let _: () = conn
+            .xgroup_create_mkstream(&key, "vector", "0")
+            .await
+            .unwrap();
+        for message in ["1", "2", "3"] {
+            let _: String = conn.xadd(&key, "*", &[("message", message)]).await.unwrap();
+        }
+
+        // Another consumer reads the entries, then goes away without acknowledging them.
+        let options = StreamReadOptions::default().group("vector", "gone");
+        let _: StreamReadReply = conn.xread_options(&[&key], &[">"], &options).await.unwrap();
+
+        // The entries are claimed a page of one at a time.
+        let config = stream_config(
+            &key,
+            StreamOption {
+                consumer: Some("vector-0".to_owned()),
+                count: 1,
+                claim_min_idle_secs: 1,
+                ..Default::default()
+            },
+        );
+        let events = assert_source_compliance(&SOURCE_TAGS, async {
+            let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            collect_n(rx, 3).await
+        })
+        .await;
+
+        for (event, message) in events.iter().zip(["1", "2", "3"]) {
+            assert_eq!(
+                event.as_log()[log_schema().message_key().unwrap().to_string()],
+                message.into()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn redis_source_stream_does_not_claim_in_flight_entries() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        let _: String = conn.xadd(&key, "*", &[("message", "slow")]).await.unwrap();
+
+        let config = stream_config(
+            &key,
+            StreamOption {
+                consumer: Some("vector-0".to_owned()),
+                claim_min_idle_secs: 1,
+                ..Default::default()
+            },
+        );
+        let (tx, mut rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        // The entry stays pending while its event is held on to, and goes idle, but is not sent
+        // again.
+        let event = rx.next().await.unwrap();
+        assert_eq!(
+            event.as_log()[log_schema().message_key().unwrap().to_string()],
+            "slow".into()
+        );
+        let next = tokio::time::timeout(tokio::time::Duration::from_secs(3), rx.next()).await;
+        assert!(next.is_err());
+        drop(event);
+    }
 }
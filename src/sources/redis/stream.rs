@@ -0,0 +1,352 @@
+use std::{
+    collections::HashSet,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use redis::{
+    aio::ConnectionManager,
+    streams::{StreamId, StreamReadOptions, StreamReadReply},
+    AsyncCommands, ErrorKind, FromRedisValue, RedisError, RedisResult, Value,
+};
+use snafu::{ResultExt, Snafu};
+use vector_lib::config::LegacyKey;
+use vector_lib::finalizer::UnorderedFinalizer;
+use vector_lib::lookup::path;
+
+use super::{
+    list::backoff_exponential, run_with_acks, InputHandler, RedisSourceConfig, StreamOption,
+};
+use crate::{
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{RedisAckError, RedisReceiveEventError, RedisStreamFieldMissingError},
+    sources::Source,
+};
+
+/// How long a read waits for new entries before the source checks for entries to claim.
+const READ_BLOCK: Duration = Duration::from_secs(1);
+
+/// The ID to read with to get new entries, rather than the ones already delivered to the consumer.
+const NEW_ENTRIES: &str = ">";
+
+/// The cursor `XAUTOCLAIM` starts its walk over the pending entries at, and returns once done.
+const CLAIM_START: &str = "0-0";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed to create connection: {}", source))]
+    Connection { source: RedisError },
+    #[snafu(display("Failed to create consumer group: {}", source))]
+    CreateGroup { source: RedisError },
+}
+
+/// The ID of a stream entry to acknowledge once its events are delivered.
+type Entry = String;
+
+impl InputHandler {
+    pub(super) async fn read_stream(
+        mut self,
+        options: StreamOption,
+        acknowledgements: bool,
+    ) -> crate::Result<Source> {
+        if options.count == 0 {
+            return Err("`stream.count` must be greater than zero.".into());
+        }
+        let consumer = options.consumer.clone().unwrap_or_default();
+
+        let mut conn = self
+            .client
+            .get_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+        // Reads block their connection, so acknowledgements are sent on one of their own.
+        let ack_conn = self
+            .client
+            .get_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+
+        create_group(&mut conn, &self.key, &options.group)
+            .await
+            .context(CreateGroupSnafu {})?;
+
+        Ok(Box::pin(async move {
+            let acker = &Acker {
+                conn: ack_conn,
+                key: self.key.clone(),
+                group: options.group.clone(),
+                in_flight: Mutex::default(),
+            };
+
+            let reads = |finalizer: Option<UnorderedFinalizer<Entry>>| async move {
+                let mut shutdown = self.cx.shutdown.clone();
+                let read_options = StreamReadOptions::default()
+                    .group(&options.group, &consumer)
+                    .count(options.count)
+                    .block(READ_BLOCK.as_millis() as usize);
+                let claim_min_idle = Duration::from_secs(options.claim_min_idle_secs);
+                let mut last_claim = Instant::now();
+                // Claiming walks over the pending entries a page at a time, with reads only
+                // resuming once the walk is done.
+                let mut claim_cursor = None;
+                // Entries delivered to this consumer but never acknowledged, for example before a
+                // restart, are read first. Once they are caught up, only new entries are read.
+                let mut read_id = "0".to_owned();
+                let mut retry: u32 = 0;
+
+                loop {
+                    let claim = claim_cursor.is_some()
+                        || (!claim_min_idle.is_zero() && last_claim.elapsed() >= claim_min_idle);
+                    let res = if claim {
+                        let cursor = claim_cursor
+                            .take()
+                            .unwrap_or_else(|| CLAIM_START.to_owned());
+                        tokio::select! {
+                            res = claim_idle_entries(
+                                &mut conn,
+                                &self.key,
+                                &options.group,
+                                &consumer,
+                                claim_min_idle,
+                                &cursor,
+                                options.count,
+                            ) => res.map(|(next_cursor, entries)| {
+                                if next_cursor == CLAIM_START {
+                                    last_claim = Instant::now();
+                                } else {
+                                    claim_cursor = Some(next_cursor);
+                                }
+                                // Entries of this consumer whose events are still on their way
+                                // are claimed as well once idle, but must not be sent again.
+                                entries
+                                    .into_iter()
+                                    .filter(|entry| !acker.is_in_flight(&entry.id))
+                                    .collect::<Vec<_>>()
+                            }),
+                            _ = &mut shutdown => break,
+                        }
+                    } else {
+                        tokio::select! {
+                            res = read_group(&mut conn, &self.key, &read_id, &read_options) => res,
+                            _ = &mut shutdown => break,
+                        }
+                    };
+
+                    let entries = match res {
+                        Err(error) => {
+                            let kind = error.kind();
+                            let no_group = error.code() == Some("NOGROUP");
+
+                            emit!(RedisReceiveEventError::from(error));
+
+                            if kind == ErrorKind::IoError {
+                                retry += 1;
+                                backoff_exponential(retry).await
+                            } else if no_group {
+                                // The stream was deleted from under the source, along with the
+                                // consumer group.
+                                if let Err(error) =
+                                    create_group(&mut conn, &self.key, &options.group).await
+                                {
+                                    emit!(RedisReceiveEventError::from(error));
+                                }
+                            }
+                            continue;
+                        }
+                        Ok(entries) => entries,
+                    };
+                    retry = 0;
+
+                    if !claim && read_id != NEW_ENTRIES {
+                        match entries.last() {
+                            Some(entry) => read_id = entry.id.clone(),
+                            None => read_id = NEW_ENTRIES.to_owned(),
+                        }
+                    }
+
+                    for entry in entries {
+                        if let Err(()) = self
+                            .handle_entry(entry, &options.field, finalizer.as_ref(), acker)
+                            .await
+                        {
+                            return;
+                        }
+                    }
+                }
+            };
+            let acks = move |status, id: Entry| async move { acker.handle_ack(status, &id).await };
+
+            run_with_acks(acknowledgements, reads, acks).await;
+            Ok(())
+        }))
+    }
+
+    async fn handle_entry(
+        &mut self,
+        entry: StreamId,
+        field: &str,
+        finalizer: Option<&UnorderedFinalizer<Entry>>,
+        acker: &Acker,
+    ) -> Result<(), ()> {
+        let line = match entry.map.get(field).map(String::from_redis_value) {
+            Some(Ok(line)) => line,
+            Some(Err(error)) => {
+                emit!(RedisReceiveEventError::from(error));
+                acker.handle_ack(BatchStatus::Rejected, &entry.id).await;
+                return Ok(());
+            }
+            None => {
+                emit!(RedisStreamFieldMissingError {
+                    id: &entry.id,
+                    field
+                });
+                acker.handle_ack(BatchStatus::Rejected, &entry.id).await;
+                return Ok(());
+            }
+        };
+
+        let mut events = self.decode_line(&line).await;
+        for event in &mut events {
+            if let Event::Log(ref mut log) = event {
+                self.log_namespace.insert_source_metadata(
+                    RedisSourceConfig::NAME,
+                    log,
+                    None::<LegacyKey<&str>>,
+                    path!("id"),
+                    entry.id.as_str(),
+                );
+            }
+        }
+
+        match finalizer {
+            Some(finalizer) => {
+                let receiver = BatchNotifier::apply_to(&mut events);
+                self.send(events).await?;
+                acker.track(entry.id.clone());
+                finalizer.add(entry.id, receiver);
+            }
+            None => {
+                self.send(events).await?;
+                acker.handle_ack(BatchStatus::Delivered, &entry.id).await;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Acker {
+    conn: ConnectionManager,
+    key: String,
+    group: String,
+    /// The IDs of the entries waiting for their events to be delivered.
+    in_flight: Mutex<HashSet<Entry>>,
+}
+
+impl Acker {
+    fn track(&self, id: Entry) {
+        self.in_flight
+            .lock()
+            .expect("in-flight entries lock poisoned")
+            .insert(id);
+    }
+
+    fn is_in_flight(&self, id: &str) -> bool {
+        self.in_flight
+            .lock()
+            .expect("in-flight entries lock poisoned")
+            .contains(id)
+    }
+
+    async fn handle_ack(&self, status: BatchStatus, id: &str) {
+        self.in_flight
+            .lock()
+            .expect("in-flight entries lock poisoned")
+            .remove(id);
+        match status {
+            // Entries rejected by a sink would be rejected again, so they are acknowledged to keep
+            // them from being claimed over and over.
+            BatchStatus::Delivered | BatchStatus::Rejected => {
+                let mut conn = self.conn.clone();
+                let result: RedisResult<usize> = conn.xack(&self.key, &self.group, &[id]).await;
+                if let Err(error) = result {
+                    emit!(RedisAckError { error });
+                }
+            }
+            // Errored entries are left pending, to be claimed once they have been idle for long
+            // enough.
+            BatchStatus::Errored => {}
+        }
+    }
+}
+
+/// Creates the consumer group, along with the stream, unless it already exists.
+async fn create_group(conn: &mut ConnectionManager, key: &str, group: &str) -> RedisResult<()> {
+    let result: RedisResult<()> = conn.xgroup_create_mkstream(key, group, "0").await;
+    match result {
+        Err(error) if error.code() == Some("BUSYGROUP") => Ok(()),
+        result => result,
+    }
+}
+
+async fn read_group(
+    conn: &mut ConnectionManager,
+    key: &str,
+    id: &str,
+    options: &StreamReadOptions,
+) -> RedisResult<Vec<StreamId>> {
+    // A read that times out without any new entries returns nil.
+    let reply: Option<StreamReadReply> = conn.xread_options(&[key], &[id], options).await?;
+    Ok(reply
+        .into_iter()
+        .flat_map(|reply| reply.keys)
+        .flat_map(|key| key.ids)
+        .collect())
+}
+
+/// Claims a page of at most `count` entries that went unacknowledged by their consumer for at
+/// least `min_idle`, starting at `cursor`, using `XAUTOCLAIM`.
+///
+/// Returns the cursor to claim the next page at, which is `CLAIM_START` once all pending entries
+/// have been walked over.
+async fn claim_idle_entries(
+    conn: &mut ConnectionManager,
+    key: &str,
+    group: &str,
+    consumer: &str,
+    min_idle: Duration,
+    cursor: &str,
+    count: usize,
+) -> RedisResult<(String, Vec<StreamId>)> {
+    // The reply also lists the IDs of deleted entries from Redis 7.0, which are ignored.
+    let reply: Vec<Value> = redis::cmd("XAUTOCLAIM")
+        .arg(key)
+        .arg(group)
+        .arg(consumer)
+        .arg(min_idle.as_millis() as u64)
+        .arg(cursor)
+        .arg("COUNT")
+        .arg(count)
+        .query_async(conn)
+        .await?;
+    let mut reply = reply.into_iter();
+    let (Some(next_cursor), Some(entries)) = (reply.next(), reply.next()) else {
+        return Err(RedisError::from((
+            ErrorKind::TypeError,
+            "Unexpected reply to XAUTOCLAIM",
+        )));
+    };
+
+    // Entries deleted while pending are claimed without their fields before Redis 7.0.
+    let entries: Vec<(String, Option<Value>)> = FromRedisValue::from_redis_value(&entries)?;
+    let mut claimed = Vec::with_capacity(entries.len());
+    for (id, fields) in entries {
+        if let Some(fields) = fields {
+            claimed.push(StreamId {
+                id,
+                map: FromRedisValue::from_redis_value(&fields)?,
+            });
+        }
+    }
+
+    Ok((FromRedisValue::from_redis_value(&next_cursor)?, claimed))
+}
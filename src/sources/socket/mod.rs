@@ -950,6 +950,7 @@ mod test {
                 schema: Default::default(),
                 schema_definitions: HashMap::default(),
                 extra_context: Default::default(),
+                offsets_link: None,
             })
             .await
             .unwrap();
@@ -281,6 +281,10 @@ impl SourceConfig for SplunkConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn can_buffer(&self) -> bool {
+        true
+    }
 }
 
 /// Shared data for responding to requests.
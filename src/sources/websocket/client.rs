@@ -0,0 +1,137 @@
+use std::num::NonZeroU64;
+
+use futures::{FutureExt, SinkExt, StreamExt};
+use tokio_tungstenite::tungstenite::protocol::Message;
+use vector_lib::configurable::configurable_component;
+
+use crate::{
+    common::websocket::WebSocketConnector,
+    http::Auth,
+    internal_events::{WsConnectionError, WsConnectionShutdown},
+    shutdown::ShutdownSignal,
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+    SourceSender,
+};
+
+use super::source::WebSocketSource;
+
+/// Client mode configuration for the `websocket` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ClientConfig {
+    /// The WebSocket URI to connect to.
+    ///
+    /// This should include the protocol and host, but can also include the port, path, and any
+    /// other valid part of a URI.
+    #[configurable(metadata(docs::examples = "wss://stream.example.com/feed"))]
+    pub uri: String,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    /// The interval, in seconds, between sending [Ping][ping]s to the server.
+    ///
+    /// If this option is not configured, pings are not sent on an interval.
+    ///
+    /// If the `ping_timeout` is not set, pings are still sent but there is no expectation of pong
+    /// response times.
+    ///
+    /// [ping]: https://www.rfc-editor.org/rfc/rfc6455#section-5.5.2
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub ping_interval: Option<NonZeroU64>,
+
+    /// The number of seconds to wait for a [Pong][pong] response from the server.
+    ///
+    /// If a response is not received within this time, the connection is re-established.
+    ///
+    /// [pong]: https://www.rfc-editor.org/rfc/rfc6455#section-5.5.3
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub ping_timeout: Option<NonZeroU64>,
+
+    /// A text message to send to the server every time a connection is established.
+    ///
+    /// Streaming APIs commonly expect a subscription request like this before they start sending
+    /// any data.
+    #[configurable(metadata(docs::examples = r#"{"type": "subscribe", "channels": ["ticker"]}"#))]
+    pub initial_message: Option<String>,
+}
+
+impl ClientConfig {
+    pub fn build_connector(&self) -> Result<WebSocketConnector, crate::Error> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        Ok(WebSocketConnector::new(
+            self.uri.clone(),
+            tls,
+            self.auth.clone(),
+        )?)
+    }
+
+    /// Connects to the server and reads messages from it, reconnecting with a backoff whenever the
+    /// connection is lost, until the source shuts down.
+    ///
+    /// The backoff is only reset once data has been received on a connection, so that a server
+    /// accepting connections only to close them again isn't reconnected to in a tight loop.
+    pub async fn run(
+        self,
+        connector: WebSocketConnector,
+        source: WebSocketSource,
+        mut out: SourceSender,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let mut backoff = WebSocketConnector::fresh_backoff();
+        loop {
+            let ws_stream = tokio::select! {
+                biased;
+
+                _ = &mut shutdown => break,
+                ws_stream = connector.connect_with_backoff(&mut backoff) => ws_stream,
+            };
+            let (mut ws_sink, ws_stream) = ws_stream.split();
+
+            let sent = match &self.initial_message {
+                Some(message) => ws_sink.send(Message::text(message.clone())).await,
+                None => Ok(()),
+            };
+            let received = match sent {
+                Ok(()) => {
+                    source
+                        .handle_connection(
+                            ws_stream,
+                            ws_sink,
+                            None,
+                            self.ping_interval,
+                            self.ping_timeout,
+                            &mut out,
+                            shutdown.clone(),
+                        )
+                        .await?
+                }
+                Err(error) => {
+                    emit!(WsConnectionError { error });
+                    false
+                }
+            };
+
+            if (&mut shutdown).now_or_never().is_some() {
+                break;
+            }
+            emit!(WsConnectionShutdown);
+
+            if received {
+                backoff.reset();
+            } else {
+                tokio::select! {
+                    biased;
+
+                    _ = &mut shutdown => break,
+                    _ = tokio::time::sleep(backoff.next().unwrap()) => {},
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,285 @@
+use vector_lib::codecs::decoding::{DeserializerConfig, FramingConfig};
+use vector_lib::config::{LegacyKey, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path};
+use vrl::value::Kind;
+
+use crate::{
+    codecs::DecodingConfig,
+    config::{GenerateConfig, Resource, SourceConfig, SourceContext, SourceOutput},
+    serde::{default_decoding, default_framing_message_based},
+    tls::MaybeTlsSettings,
+};
+
+use super::{
+    client::ClientConfig,
+    server::{self, ServerConfig},
+    source::WebSocketSource,
+};
+
+/// Configuration for the `websocket` source.
+#[configurable_component(source(
+    "websocket",
+    "Collect events from WebSocket connections, either as a client or as a server."
+))]
+#[derive(Clone, Debug)]
+pub struct WebSocketSourceConfig {
+    #[serde(flatten)]
+    pub mode: Mode,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    pub decoding: DeserializerConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+}
+
+/// Connection mode for the `websocket` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[configurable(metadata(
+    docs::enum_tag_description = "Whether to connect to a server or to accept connections."
+))]
+pub enum Mode {
+    /// Connect to a WebSocket server, reconnecting whenever the connection is lost.
+    Client(ClientConfig),
+
+    /// Listen for connections from WebSocket clients.
+    Server(ServerConfig),
+}
+
+impl GenerateConfig for WebSocketSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"mode = "client"
+            uri = "ws://127.0.0.1:8080""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "websocket")]
+impl SourceConfig for WebSocketSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        match &self.mode {
+            Mode::Client(config) => {
+                let connector = config.build_connector()?;
+                let source =
+                    WebSocketSource::new(decoder, OptionalValuePath::none(), log_namespace);
+                Ok(Box::pin(config.clone().run(
+                    connector,
+                    source,
+                    cx.out,
+                    cx.shutdown,
+                )))
+            }
+            Mode::Server(config) => {
+                let listener = MaybeTlsSettings::from_config(&config.tls, true)?
+                    .bind(&config.address)
+                    .await?;
+                let source = WebSocketSource::new(decoder, config.host_key.clone(), log_namespace);
+                Ok(Box::pin(server::run(listener, source, cx.out, cx.shutdown)))
+            }
+        }
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let mut schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata();
+
+        if let Mode::Server(config) = &self.mode {
+            schema_definition = schema_definition.with_source_metadata(
+                Self::NAME,
+                config.host_key.path.clone().map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("host"),
+                Kind::bytes(),
+                Some("host"),
+            );
+        }
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        match &self.mode {
+            Mode::Client(_) => Vec::new(),
+            Mode::Server(config) => vec![Resource::tcp(config.address)],
+        }
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::{SinkExt, StreamExt};
+    use tokio::{net::TcpListener, time::Instant};
+    use tokio_tungstenite::{accept_async, connect_async, tungstenite::protocol::Message};
+    use vector_lib::config::log_schema;
+
+    use super::*;
+    use crate::{
+        event::Event,
+        sources::websocket::server::default_host_key,
+        test_util::{
+            collect_n,
+            components::{assert_source_compliance, SOCKET_PUSH_SOURCE_TAGS, SOURCE_TAGS},
+            next_addr, trace_init,
+        },
+        SourceSender,
+    };
+
+    fn make_config(mode: Mode) -> WebSocketSourceConfig {
+        WebSocketSourceConfig {
+            mode,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            log_namespace: None,
+        }
+    }
+
+    fn message(event: &Event) -> String {
+        event.as_log()[log_schema().message_key().unwrap().to_string()]
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebSocketSourceConfig>();
+    }
+
+    #[tokio::test]
+    async fn server_mode_receives_text_and_binary_messages() {
+        trace_init();
+
+        let address = next_addr();
+        let config = make_config(Mode::Server(ServerConfig {
+            address,
+            tls: None,
+            host_key: default_host_key(),
+        }));
+
+        let events = assert_source_compliance(&SOCKET_PUSH_SOURCE_TAGS, async {
+            let (tx, rx) = SourceSender::new_test();
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+
+            let (mut ws_stream, _) = connect_async(format!("ws://{address}")).await.unwrap();
+            ws_stream.send(Message::text("text message")).await.unwrap();
+            ws_stream
+                .send(Message::binary(b"binary message".to_vec()))
+                .await
+                .unwrap();
+            collect_n(rx, 2).await
+        })
+        .await;
+
+        assert_eq!(message(&events[0]), "text message");
+        assert_eq!(message(&events[1]), "binary message");
+        assert_eq!(events[0].as_log()["host"], "127.0.0.1".into());
+    }
+
+    #[tokio::test]
+    async fn client_mode_resubscribes_after_reconnecting() {
+        trace_init();
+
+        let address = next_addr();
+        let listener = TcpListener::bind(address).await.unwrap();
+        let config = make_config(Mode::Client(ClientConfig {
+            uri: format!("ws://{address}"),
+            tls: None,
+            auth: None,
+            ping_interval: None,
+            ping_timeout: None,
+            initial_message: Some("subscribe".to_owned()),
+        }));
+
+        let events = assert_source_compliance(&SOURCE_TAGS, async {
+            let (tx, rx) = SourceSender::new_test();
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+
+            for message in ["first", "second"] {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws_stream = accept_async(stream).await.unwrap();
+                let subscribe = ws_stream.next().await.unwrap().unwrap();
+                assert_eq!(subscribe, Message::text("subscribe"));
+                ws_stream.send(Message::text(message)).await.unwrap();
+                ws_stream.close(None).await.unwrap();
+            }
+            collect_n(rx, 2).await
+        })
+        .await;
+
+        assert_eq!(message(&events[0]), "first");
+        assert_eq!(message(&events[1]), "second");
+    }
+
+    #[tokio::test]
+    async fn client_mode_backs_off_from_connections_without_data() {
+        trace_init();
+
+        let address = next_addr();
+        let listener = TcpListener::bind(address).await.unwrap();
+        let config = make_config(Mode::Client(ClientConfig {
+            uri: format!("ws://{address}"),
+            tls: None,
+            auth: None,
+            ping_interval: None,
+            ping_timeout: None,
+            initial_message: Some("subscribe".to_owned()),
+        }));
+
+        let (tx, _rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        // The server closes each connection right after the subscription, so the delays before
+        // reconnecting keep growing.
+        let mut accepted = Vec::new();
+        for _ in 0..3 {
+            let (stream, _) = listener.accept().await.unwrap();
+            accepted.push(Instant::now());
+            let mut ws_stream = accept_async(stream).await.unwrap();
+            _ = ws_stream.next().await;
+            ws_stream.close(None).await.unwrap();
+        }
+
+        assert!(accepted[1] - accepted[0] >= Duration::from_millis(500));
+        assert!(accepted[2] - accepted[1] >= Duration::from_millis(1000));
+    }
+}
@@ -0,0 +1,6 @@
+mod client;
+mod config;
+mod server;
+mod source;
+
+pub use config::WebSocketSourceConfig;
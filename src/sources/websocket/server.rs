@@ -0,0 +1,113 @@
+use std::net::SocketAddr;
+
+use futures::StreamExt;
+use tokio_tungstenite::accept_async;
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::OptionalValuePath;
+
+use crate::{
+    config::log_schema,
+    internal_events::{ConnectionOpen, OpenGauge, WsAcceptError},
+    shutdown::ShutdownSignal,
+    tls::{MaybeTlsListener, TlsEnableableConfig},
+    SourceSender,
+};
+
+use super::source::WebSocketSource;
+
+/// Server mode configuration for the `websocket` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ServerConfig {
+    /// The socket address to listen for WebSocket connections on.
+    #[configurable(metadata(docs::examples = "0.0.0.0:8080"))]
+    pub address: SocketAddr,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    /// Overrides the name of the log field used to add the peer host to each event.
+    ///
+    /// The value is the IP address of the client the message was received from.
+    ///
+    /// By default, the [global `log_schema.host_key` option][global_host_key] is used.
+    ///
+    /// [global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+    #[serde(default = "default_host_key")]
+    pub host_key: OptionalValuePath,
+}
+
+pub(super) fn default_host_key() -> OptionalValuePath {
+    log_schema().host_key().cloned().into()
+}
+
+/// Accepts connections until the source shuts down, reading the messages of each one in its own
+/// task.
+pub async fn run(
+    mut listener: MaybeTlsListener,
+    source: WebSocketSource,
+    out: SourceSender,
+    mut shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let connection_gauge = OpenGauge::new();
+
+    loop {
+        let stream = tokio::select! {
+            _ = &mut shutdown => break,
+            stream = listener.accept() => stream,
+        };
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                emit!(WsAcceptError {
+                    error: Box::new(error),
+                    peer_addr: None,
+                });
+                continue;
+            }
+        };
+
+        let peer_addr = stream.peer_addr();
+        let source = source.clone();
+        let mut out = out.clone();
+        let mut shutdown = shutdown.clone();
+        let connection_gauge = connection_gauge.clone();
+        tokio::spawn(async move {
+            // The TLS handshake, if any, is done as part of the WebSocket one.
+            let accepted = tokio::select! {
+                _ = &mut shutdown => return,
+                accepted = accept_async(stream) => accepted,
+            };
+            let ws_stream = match accepted {
+                Ok(ws_stream) => ws_stream,
+                Err(error) => {
+                    emit!(WsAcceptError {
+                        error: Box::new(error),
+                        peer_addr: Some(peer_addr),
+                    });
+                    return;
+                }
+            };
+            debug!(message = "Accepted a new connection.", %peer_addr);
+
+            let _open_token = connection_gauge.open(|count| emit!(ConnectionOpen { count }));
+            let (ws_sink, ws_stream) = ws_stream.split();
+            // A failure to send events downstream is reported by the handler, and the other
+            // connections are left to run into it themselves.
+            _ = source
+                .handle_connection(
+                    ws_stream,
+                    ws_sink,
+                    Some(peer_addr),
+                    None,
+                    None,
+                    &mut out,
+                    shutdown,
+                )
+                .await;
+            debug!(message = "Connection closed.", %peer_addr);
+        });
+    }
+
+    Ok(())
+}
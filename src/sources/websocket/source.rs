@@ -0,0 +1,191 @@
+use std::{net::SocketAddr, num::NonZeroU64, time::Instant};
+
+use chrono::Utc;
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use tokio_tungstenite::tungstenite::{error::Error as WsError, protocol::Message};
+use tokio_util::codec::FramedRead;
+use vector_lib::codecs::StreamDecodingError as _;
+use vector_lib::config::{LegacyKey, LogNamespace};
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+    Registered,
+};
+use vector_lib::lookup::{lookup_v2::OptionalValuePath, path};
+use vector_lib::EstimatedJsonEncodedSizeOf;
+
+use crate::{
+    codecs::Decoder,
+    common::websocket::{check_received_pong_time, is_closed, PingInterval},
+    event::Event,
+    internal_events::{StreamClosedError, WsReceiveError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+use super::config::WebSocketSourceConfig;
+
+const PING: &[u8] = b"PING";
+
+/// Decodes the messages received on WebSocket connections into events.
+#[derive(Clone)]
+pub struct WebSocketSource {
+    decoder: Decoder,
+    host_key: OptionalValuePath,
+    log_namespace: LogNamespace,
+}
+
+impl WebSocketSource {
+    pub const fn new(
+        decoder: Decoder,
+        host_key: OptionalValuePath,
+        log_namespace: LogNamespace,
+    ) -> Self {
+        Self {
+            decoder,
+            host_key,
+            log_namespace,
+        }
+    }
+
+    /// Reads messages from the connection until either it is closed or the source shuts down,
+    /// returning whether any data was received on it.
+    ///
+    /// The only error returned is for events that can't be sent on, as the source can't go on.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn handle_connection<WS, O>(
+        &self,
+        mut ws_stream: WS,
+        mut ws_sink: O,
+        peer_addr: Option<SocketAddr>,
+        ping_interval: Option<NonZeroU64>,
+        ping_timeout: Option<NonZeroU64>,
+        out: &mut SourceSender,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<bool, ()>
+    where
+        WS: Stream<Item = Result<Message, WsError>> + Unpin,
+        O: Sink<Message, Error = WsError> + Unpin,
+    {
+        let events_received = register!(EventsReceived);
+        let bytes_received = register!(BytesReceived::from(Protocol::from("websocket")));
+
+        // tokio::time::Interval panics if the period arg is zero. Since the config members are
+        // using NonZeroU64 that is not something we need to account for.
+        let mut ping_interval = PingInterval::new(ping_interval.map(u64::from));
+        let mut last_pong = Instant::now();
+        let mut received = false;
+
+        loop {
+            let result = tokio::select! {
+                _ = &mut shutdown => {
+                    _ = ws_sink.close().await;
+                    break;
+                },
+
+                _ = ping_interval.tick() => {
+                    match check_received_pong_time(ping_timeout, last_pong) {
+                        Ok(()) => ws_sink.send(Message::Ping(PING.to_vec())).await,
+                        Err(error) => Err(error),
+                    }
+                },
+
+                message = ws_stream.next() => {
+                    // Pings are answered by tungstenite while reading from the stream, and the
+                    // stream ends once the closing handshake is done.
+                    match message {
+                        Some(Ok(Message::Text(text))) => {
+                            received = true;
+                            let events =
+                                self.decode(text.as_bytes(), peer_addr, &bytes_received).await;
+                            self.send(events, out, &events_received).await?;
+                            Ok(())
+                        },
+                        Some(Ok(Message::Binary(data))) => {
+                            received = true;
+                            let events = self.decode(&data, peer_addr, &bytes_received).await;
+                            self.send(events, out, &events_received).await?;
+                            Ok(())
+                        },
+                        Some(Ok(Message::Pong(_))) => {
+                            last_pong = Instant::now();
+                            Ok(())
+                        },
+                        Some(Ok(_)) => Ok(()),
+                        Some(Err(error)) => Err(error),
+                        None => break,
+                    }
+                },
+            };
+
+            if let Err(error) = result {
+                if !is_closed(&error) {
+                    emit!(WsReceiveError { error });
+                }
+                break;
+            }
+        }
+
+        Ok(received)
+    }
+
+    async fn decode(
+        &self,
+        payload: &[u8],
+        peer_addr: Option<SocketAddr>,
+        bytes_received: &Registered<BytesReceived>,
+    ) -> Vec<Event> {
+        bytes_received.emit(ByteSize(payload.len()));
+
+        let now = Utc::now();
+        let mut events = Vec::new();
+        let mut stream = FramedRead::new(payload, self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((decoded, _byte_size)) => {
+                    events.extend(decoded.into_iter().map(|mut event| {
+                        if let Event::Log(ref mut log) = event {
+                            self.log_namespace.insert_standard_vector_source_metadata(
+                                log,
+                                WebSocketSourceConfig::NAME,
+                                now,
+                            );
+                            if let Some(peer_addr) = peer_addr {
+                                self.log_namespace.insert_source_metadata(
+                                    WebSocketSourceConfig::NAME,
+                                    log,
+                                    self.host_key.path.as_ref().map(LegacyKey::InsertIfEmpty),
+                                    path!("host"),
+                                    peer_addr.ip().to_string(),
+                                );
+                            }
+                        }
+                        event
+                    }));
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs`, no further handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    async fn send(
+        &self,
+        events: Vec<Event>,
+        out: &mut SourceSender,
+        events_received: &Registered<EventsReceived>,
+    ) -> Result<(), ()> {
+        let count = events.len();
+        events_received.emit(CountByteSize(
+            count,
+            events.estimated_json_encoded_size_of(),
+        ));
+        out.send_batch(events)
+            .await
+            .map_err(|_| emit!(StreamClosedError { count }))
+    }
+}
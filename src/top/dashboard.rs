@@ -11,10 +11,10 @@ use num_format::{Locale, ToFormattedString};
 use number_prefix::NumberPrefix;
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Alignment, Constraint, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Sparkline, Table, TableState, Wrap},
     Frame, Terminal,
 };
 use std::io::stdout;
@@ -22,7 +22,7 @@ use tokio::sync::oneshot;
 
 use super::{
     events::capture_key_press,
-    state::{self, ConnectionStatus},
+    state::{self, ComponentRow, ConnectionStatus},
 };
 
 /// Format metrics, with thousands separation
@@ -119,6 +119,14 @@ fn format_metric_bytes(total: i64, throughput: i64, human_metrics: bool) -> Stri
     }
 }
 
+/// Format a duration in seconds as milliseconds, or `--` if unknown
+fn format_rtt(seconds: Option<f64>) -> String {
+    match seconds {
+        Some(s) => format!("{:.2} ms", s * 1000.0),
+        None => "--".into(),
+    }
+}
+
 const NUM_COLUMNS: usize = if is_allocation_tracking_enabled() {
     10
 } else {
@@ -139,6 +147,31 @@ static HEADER: [&str; NUM_COLUMNS] = [
     "Memory Used",
 ];
 
+/// Interactive state of the dashboard, driven by key presses rather than by metrics updates.
+#[derive(Debug, Default)]
+struct Selection {
+    /// Index of the selected component, in display order
+    index: usize,
+    /// Whether the detail pane for the selected component is shown
+    detail: bool,
+}
+
+impl Selection {
+    /// Applies a key press. Returns `false` if the key wasn't handled.
+    fn handle_key(&mut self, key: KeyCode, num_components: usize) -> bool {
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => self.index = self.index.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.index = (self.index + 1).min(num_components.saturating_sub(1))
+            }
+            KeyCode::Enter => self.detail = !self.detail,
+            KeyCode::Esc if self.detail => self.detail = false,
+            _ => return false,
+        }
+        true
+    }
+}
+
 struct Widgets<'a> {
     constraints: Vec<Constraint>,
     url_string: &'a str,
@@ -189,8 +222,14 @@ impl<'a> Widgets<'a> {
     }
 
     /// Renders a components table, showing sources, transforms and sinks in tabular form, with
-    /// statistics pulled from `ComponentsState`,
-    fn components_table(&self, f: &mut Frame, state: &state::State, area: Rect) {
+    /// statistics pulled from `ComponentsState`. The selected component is highlighted.
+    fn components_table(
+        &self,
+        f: &mut Frame,
+        state: &state::State,
+        selection: &Selection,
+        area: Rect,
+    ) {
         // Header columns
         let header = HEADER
             .iter()
@@ -199,7 +238,12 @@ impl<'a> Widgets<'a> {
 
         // Data columns
         let mut items = Vec::new();
-        for (_, r) in state.components.iter() {
+        let mut table_state = TableState::default();
+        for (i, r) in state.components.values().enumerate() {
+            if i == selection.index {
+                table_state.select(Some(items.len()));
+            }
+
             let mut data = vec![
                 r.key.id().to_string(),
                 (!r.has_displayable_outputs())
@@ -291,7 +335,160 @@ impl<'a> Widgets<'a> {
         let w = Table::new(items, widths)
             .header(Row::new(header).bottom_margin(1))
             .block(Block::default().borders(Borders::ALL).title("Components"))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
             .column_spacing(2);
+        f.render_stateful_widget(w, area, &mut table_state);
+    }
+
+    /// Renders the detail pane for a single component, showing per-output throughput, errors
+    /// split by type, and buffer usage and request latency over time.
+    fn component_detail(&self, f: &mut Frame, r: &ComponentRow, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Component: {}", r.key.id()));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(inner);
+        let left = Layout::default()
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(columns[0]);
+        let right = Layout::default()
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(columns[1]);
+
+        self.outputs_table(f, r, left[0]);
+        self.errors_table(f, r, left[1]);
+        self.buffer_sparkline(f, r, right[0]);
+        self.latency_sparkline(f, r, right[1]);
+    }
+
+    /// Renders sent events per output port of a component.
+    fn outputs_table(&self, f: &mut Frame, r: &ComponentRow, area: Rect) {
+        let mut outputs = r.outputs.iter().collect::<Vec<_>>();
+        outputs.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let items = outputs
+            .into_iter()
+            .map(|(id, output)| {
+                Row::new(vec![
+                    id.clone(),
+                    format_metric(
+                        output.sent_events_total,
+                        output.sent_events_throughput_sec,
+                        self.opts.human_metrics,
+                    ),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        let header = ["Output", "Events Out"]
+            .iter()
+            .map(|s| Cell::from(*s).style(Style::default().add_modifier(Modifier::BOLD)));
+        let w = Table::new(
+            items,
+            [Constraint::Percentage(50), Constraint::Percentage(50)],
+        )
+        .header(Row::new(header))
+        .block(Block::default().borders(Borders::ALL).title("Outputs"))
+        .column_spacing(2);
+        f.render_widget(w, area);
+    }
+
+    /// Renders error totals of a component, split by `error_type`.
+    fn errors_table(&self, f: &mut Frame, r: &ComponentRow, area: Rect) {
+        let items = r
+            .detail
+            .errors_by_type
+            .iter()
+            .map(|(error_type, total)| {
+                Row::new(vec![
+                    error_type.clone(),
+                    if self.opts.human_metrics {
+                        total.human_format()
+                    } else {
+                        total.thousands_format()
+                    },
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        let header = ["Error Type", "Errors"]
+            .iter()
+            .map(|s| Cell::from(*s).style(Style::default().add_modifier(Modifier::BOLD)));
+        let w = Table::new(
+            items,
+            [Constraint::Percentage(70), Constraint::Percentage(30)],
+        )
+        .header(Row::new(header))
+        .block(Block::default().borders(Borders::ALL).title("Errors"))
+        .column_spacing(2);
+        f.render_widget(w, area);
+    }
+
+    /// Renders buffer usage of a component over time.
+    fn buffer_sparkline(&self, f: &mut Frame, r: &ComponentRow, area: Rect) {
+        let detail = &r.detail;
+        if !detail.has_buffer() {
+            let block = Block::default().borders(Borders::ALL).title("Buffer");
+            f.render_widget(Paragraph::new("No buffer metrics").block(block), area);
+            return;
+        }
+
+        let mut title = format!(
+            "Buffer: {} events, {}",
+            detail.buffer_events.thousands_format(),
+            detail.buffer_byte_size.human_format_bytes()
+        );
+        if let Some(utilization) = detail.buffer_utilization() {
+            title.push_str(&format!(" ({:.1}% full)", utilization));
+        }
+
+        // Prefer bytes, as disk buffers are only bounded by size
+        let (data, max) = match detail.buffer_max_byte_size {
+            Some(max) => (&detail.buffer_byte_size_history, Some(max as u64)),
+            None => (
+                &detail.buffer_events_history,
+                detail.buffer_max_events.map(|max| max as u64),
+            ),
+        };
+        let data = data.iter().copied().collect::<Vec<_>>();
+
+        let mut w = Sparkline::default()
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .data(&data)
+            .style(Style::default().fg(Color::Yellow));
+        if let Some(max) = max {
+            w = w.max(max);
+        }
+        f.render_widget(w, area);
+    }
+
+    /// Renders mean request round-trip time of a sink over time.
+    fn latency_sparkline(&self, f: &mut Frame, r: &ComponentRow, area: Rect) {
+        let detail = &r.detail;
+        if !detail.has_requests() {
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title("Request Latency");
+            f.render_widget(Paragraph::new("No request metrics").block(block), area);
+            return;
+        }
+
+        let title = format!(
+            "Request Latency: {} ({}/s)",
+            format_rtt(detail.mean_rtt_seconds),
+            detail.requests_throughput_sec.human_format()
+        );
+        let data = detail.rtt_history.iter().copied().collect::<Vec<_>>();
+
+        let w = Sparkline::default()
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .data(&data)
+            .style(Style::default().fg(Color::Cyan));
         f.render_widget(w, area);
     }
 
@@ -305,9 +502,13 @@ impl<'a> Widgets<'a> {
         f.render_widget(w, area);
     }
 
-    /// Renders a box showing instructions on how to exit from `vector top`.
-    fn quit_box(&self, f: &mut Frame, area: Rect) {
-        let text = vec![Line::from("To quit, press ESC or 'q'")];
+    /// Renders a box showing instructions on how to navigate and exit from `vector top`.
+    fn quit_box(&self, f: &mut Frame, area: Rect, selection: &Selection) {
+        let text = vec![Line::from(if selection.detail {
+            "Use ↑/↓ to select a component, ENTER or ESC to close details, 'q' to quit"
+        } else {
+            "Use ↑/↓ to select a component, ENTER to show details. To quit, press ESC or 'q'"
+        })];
 
         let block = Block::default()
             .borders(Borders::ALL)
@@ -321,7 +522,7 @@ impl<'a> Widgets<'a> {
     }
 
     /// Draw a single frame. Creates a layout and renders widgets into it.
-    fn draw(&self, f: &mut Frame, state: state::State) {
+    fn draw(&self, f: &mut Frame, state: &state::State, selection: &Selection) {
         let size = f.size();
         let rects = Layout::default()
             .constraints(self.constraints.clone())
@@ -331,12 +532,27 @@ impl<'a> Widgets<'a> {
 
         // Require a minimum of 80 chars of line width to display the table
         if size.width >= 80 {
-            self.components_table(f, &state, rects[1]);
+            let selected = if selection.detail {
+                state.components.values().nth(selection.index)
+            } else {
+                None
+            };
+
+            match selected {
+                Some(row) => {
+                    let split = Layout::default()
+                        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .split(rects[1]);
+                    self.components_table(f, state, selection, split[0]);
+                    self.component_detail(f, row, split[1]);
+                }
+                None => self.components_table(f, state, selection, rects[1]),
+            }
         } else {
             self.components_resize_window(f, rects[1]);
         }
 
-        self.quit_box(f, rects[2]);
+        self.quit_box(f, rects[2], selection);
     }
 }
 
@@ -376,14 +592,23 @@ pub async fn init_dashboard<'a>(
     terminal.clear()?;
 
     let widgets = Widgets::new(title, url, opts);
+    let mut selection = Selection::default();
+    let mut last_state: Option<state::State> = None;
 
     loop {
         tokio::select! {
             Some(state) = state_rx.recv() => {
-                terminal.draw(|f| widgets.draw(f, state))?;
+                terminal.draw(|f| widgets.draw(f, &state, &selection))?;
+                last_state = Some(state);
             },
             k = key_press_rx.recv() => {
-                if let KeyCode::Esc | KeyCode::Char('q') = k.unwrap() {
+                let k = k.unwrap();
+                let num_components = last_state.as_ref().map_or(0, |s| s.components.len());
+                if selection.handle_key(k, num_components) {
+                    if let Some(state) = &last_state {
+                        terminal.draw(|f| widgets.draw(f, state, &selection))?;
+                    }
+                } else if let KeyCode::Esc | KeyCode::Char('q') = k {
                     _ = key_press_kill_tx.send(());
                     break
                 }
@@ -409,6 +634,33 @@ pub async fn init_dashboard<'a>(
 mod tests {
     use super::*;
 
+    #[test]
+    /// Selection should stay within the bounds of the component list
+    fn selection_bounds() {
+        let mut selection = Selection::default();
+
+        assert!(selection.handle_key(KeyCode::Up, 3));
+        assert_eq!(selection.index, 0);
+
+        for _ in 0..5 {
+            assert!(selection.handle_key(KeyCode::Down, 3));
+        }
+        assert_eq!(selection.index, 2);
+    }
+
+    #[test]
+    /// ESC should only be consumed while the detail pane is shown
+    fn selection_escape_closes_detail() {
+        let mut selection = Selection::default();
+
+        assert!(!selection.handle_key(KeyCode::Esc, 1));
+        assert!(selection.handle_key(KeyCode::Enter, 1));
+        assert!(selection.detail);
+        assert!(selection.handle_key(KeyCode::Esc, 1));
+        assert!(!selection.detail);
+        assert!(!selection.handle_key(KeyCode::Char('q'), 1));
+    }
+
     #[test]
     /// Zero should be formatted as "--" in all cases
     fn format_zero() {
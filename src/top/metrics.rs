@@ -10,7 +10,7 @@ use vector_lib::api_client::{
     Client, SubscriptionClient,
 };
 
-use super::state::{self, OutputMetrics};
+use super::state::{self, BufferUsageMetric, ComponentDetail, OutputMetrics, RequestLatencyMetric};
 use crate::{config::ComponentKey, top::state::SentEventsMetric};
 
 /// Components that have been added
@@ -40,6 +40,7 @@ async fn component_added(client: Arc<SubscriptionClient>, tx: state::EventTx) {
                     #[cfg(feature = "allocation-tracing")]
                     allocated_bytes: 0,
                     errors: 0,
+                    detail: ComponentDetail::default(),
                 }))
                 .await;
         }
@@ -309,6 +310,82 @@ async fn errors_totals(client: Arc<SubscriptionClient>, tx: state::EventTx, inte
     }
 }
 
+async fn errors_by_type(client: Arc<SubscriptionClient>, tx: state::EventTx, interval: i64) {
+    tokio::pin! {
+        let stream = client.component_errors_by_type_subscription(interval);
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_errors_by_type;
+            _ = tx
+                .send(state::EventType::ErrorsByType(
+                    c.into_iter()
+                        .map(|c| {
+                            (
+                                ComponentKey::from(c.component_id.as_str()),
+                                c.error_types
+                                    .into_iter()
+                                    .map(|e| (e.error_type, e.errors_total as i64))
+                                    .collect(),
+                            )
+                        })
+                        .collect(),
+                ))
+                .await;
+        }
+    }
+}
+
+async fn buffer_usages(client: Arc<SubscriptionClient>, tx: state::EventTx, interval: i64) {
+    tokio::pin! {
+        let stream = client.component_buffer_usages_subscription(interval);
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_buffer_usages;
+            _ = tx
+                .send(state::EventType::BufferUsages(
+                    c.into_iter()
+                        .map(|c| BufferUsageMetric {
+                            key: ComponentKey::from(c.component_id.as_str()),
+                            events: c.buffer_events as i64,
+                            byte_size: c.buffer_byte_size as i64,
+                            max_events: c.max_events.map(|v| v as i64),
+                            max_byte_size: c.max_byte_size.map(|v| v as i64),
+                        })
+                        .collect(),
+                ))
+                .await;
+        }
+    }
+}
+
+async fn request_latencies(client: Arc<SubscriptionClient>, tx: state::EventTx, interval: i64) {
+    tokio::pin! {
+        let stream = client.component_request_latencies_subscription(interval);
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_request_latencies;
+            _ = tx
+                .send(state::EventType::RequestLatencies(
+                    interval,
+                    c.into_iter()
+                        .map(|c| RequestLatencyMetric {
+                            key: ComponentKey::from(c.component_id.as_str()),
+                            requests: c.requests,
+                            mean_rtt_seconds: c.mean_rtt_seconds,
+                        })
+                        .collect(),
+                ))
+                .await;
+        }
+    }
+}
+
 /// Subscribe to each metrics channel through a separate client. This is a temporary workaround
 /// until client multiplexing is fixed. In future, we should be able to use a single client
 pub fn subscribe(
@@ -359,7 +436,10 @@ pub fn subscribe(
         )),
         #[cfg(feature = "allocation-tracing")]
         tokio::spawn(allocated_bytes(Arc::clone(&client), tx.clone(), interval)),
-        tokio::spawn(errors_totals(Arc::clone(&client), tx, interval)),
+        tokio::spawn(errors_totals(Arc::clone(&client), tx.clone(), interval)),
+        tokio::spawn(errors_by_type(Arc::clone(&client), tx.clone(), interval)),
+        tokio::spawn(buffer_usages(Arc::clone(&client), tx.clone(), interval)),
+        tokio::spawn(request_latencies(Arc::clone(&client), tx, interval)),
     ]
 }
 
@@ -404,6 +484,7 @@ pub async fn init_components(client: &Client) -> Result<state::State, ()> {
                     #[cfg(feature = "allocation-tracing")]
                     allocated_bytes: 0,
                     errors: 0,
+                    detail: ComponentDetail::default(),
                 },
             ))
         })
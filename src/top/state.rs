@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
 use chrono::{DateTime, Local};
 use ratatui::{
@@ -12,6 +12,9 @@ use crate::config::ComponentKey;
 
 type IdentifiedMetric = (ComponentKey, i64);
 
+/// Number of samples retained for sparklines in the component detail pane
+pub const HISTORY_SIZE: usize = 60;
+
 #[derive(Debug)]
 pub struct SentEventsMetric {
     pub key: ComponentKey,
@@ -19,6 +22,22 @@ pub struct SentEventsMetric {
     pub outputs: HashMap<String, i64>,
 }
 
+#[derive(Debug)]
+pub struct BufferUsageMetric {
+    pub key: ComponentKey,
+    pub events: i64,
+    pub byte_size: i64,
+    pub max_events: Option<i64>,
+    pub max_byte_size: Option<i64>,
+}
+
+#[derive(Debug)]
+pub struct RequestLatencyMetric {
+    pub key: ComponentKey,
+    pub requests: i64,
+    pub mean_rtt_seconds: f64,
+}
+
 #[derive(Debug)]
 pub enum EventType {
     InitializeState(State),
@@ -36,6 +55,11 @@ pub enum EventType {
     /// Interval in ms + identified overall metric + output-specific metrics
     SentEventsThroughputs(i64, Vec<SentEventsMetric>),
     ErrorsTotals(Vec<IdentifiedMetric>),
+    /// Identified error totals, split by `error_type`
+    ErrorsByType(Vec<(ComponentKey, Vec<(String, i64)>)>),
+    BufferUsages(Vec<BufferUsageMetric>),
+    /// Interval in ms + identified request latency metrics
+    RequestLatencies(i64, Vec<RequestLatencyMetric>),
     #[cfg(feature = "allocation-tracing")]
     AllocatedBytes(Vec<IdentifiedMetric>),
     ComponentAdded(ComponentRow),
@@ -106,6 +130,53 @@ impl From<i64> for OutputMetrics {
     }
 }
 
+/// Pushes a sample into a bounded history, discarding the oldest sample once
+/// [`HISTORY_SIZE`] is reached
+fn push_history(history: &mut VecDeque<u64>, value: u64) {
+    if history.len() == HISTORY_SIZE {
+        history.pop_front();
+    }
+    history.push_back(value);
+}
+
+/// Metrics only shown in the detail pane of the selected component
+#[derive(Debug, Clone, Default)]
+pub struct ComponentDetail {
+    pub errors_by_type: BTreeMap<String, i64>,
+    pub buffer_events: i64,
+    pub buffer_byte_size: i64,
+    pub buffer_max_events: Option<i64>,
+    pub buffer_max_byte_size: Option<i64>,
+    pub buffer_byte_size_history: VecDeque<u64>,
+    pub buffer_events_history: VecDeque<u64>,
+    pub requests_throughput_sec: i64,
+    pub mean_rtt_seconds: Option<f64>,
+    /// Mean request round-trip time, in milliseconds
+    pub rtt_history: VecDeque<u64>,
+}
+
+impl ComponentDetail {
+    /// Returns whether the component has reported any buffer metrics
+    pub fn has_buffer(&self) -> bool {
+        !self.buffer_events_history.is_empty()
+    }
+
+    /// Returns whether the component has reported any request latency metrics
+    pub fn has_requests(&self) -> bool {
+        !self.rtt_history.is_empty()
+    }
+
+    /// Returns the buffer utilisation as a percentage of its configured maximum, preferring
+    /// the byte size limit when both are known
+    pub fn buffer_utilization(&self) -> Option<f64> {
+        match (self.buffer_max_byte_size, self.buffer_max_events) {
+            (Some(max), _) if max > 0 => Some(self.buffer_byte_size as f64 / max as f64 * 100.0),
+            (_, Some(max)) if max > 0 => Some(self.buffer_events as f64 / max as f64 * 100.0),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ComponentRow {
     pub key: ComponentKey,
@@ -123,6 +194,7 @@ pub struct ComponentRow {
     #[cfg(feature = "allocation-tracing")]
     pub allocated_bytes: i64,
     pub errors: i64,
+    pub detail: ComponentDetail,
 }
 
 impl ComponentRow {
@@ -227,6 +299,48 @@ pub async fn updater(mut event_rx: EventRx) -> StateRx {
                         }
                     }
                 }
+                EventType::ErrorsByType(rows) => {
+                    for (key, error_types) in rows {
+                        if let Some(r) = state.components.get_mut(&key) {
+                            r.detail.errors_by_type.extend(error_types);
+                        }
+                    }
+                }
+                EventType::BufferUsages(rows) => {
+                    for m in rows {
+                        if let Some(r) = state.components.get_mut(&m.key) {
+                            let detail = &mut r.detail;
+                            detail.buffer_events = m.events;
+                            detail.buffer_byte_size = m.byte_size;
+                            detail.buffer_max_events = m.max_events;
+                            detail.buffer_max_byte_size = m.max_byte_size;
+                            push_history(&mut detail.buffer_events_history, m.events.max(0) as u64);
+                            push_history(
+                                &mut detail.buffer_byte_size_history,
+                                m.byte_size.max(0) as u64,
+                            );
+                        }
+                    }
+                }
+                EventType::RequestLatencies(interval, rows) => {
+                    // Components which completed no requests during the interval are omitted
+                    // by the API, so their detail is reset rather than left stale
+                    for r in state.components.values_mut() {
+                        r.detail.requests_throughput_sec = 0;
+                    }
+                    for m in rows {
+                        if let Some(r) = state.components.get_mut(&m.key) {
+                            let detail = &mut r.detail;
+                            detail.requests_throughput_sec =
+                                (m.requests as f64 * (1000.0 / interval as f64)) as i64;
+                            detail.mean_rtt_seconds = Some(m.mean_rtt_seconds);
+                            push_history(
+                                &mut detail.rtt_history,
+                                (m.mean_rtt_seconds * 1000.0).round() as u64,
+                            );
+                        }
+                    }
+                }
                 #[cfg(feature = "allocation-tracing")]
                 EventType::AllocatedBytes(rows) => {
                     for (key, v) in rows {
@@ -17,6 +17,9 @@ use tokio::{
 };
 use tracing::Instrument;
 use vector_lib::config::LogNamespace;
+use vector_lib::finalization::{
+    AddBatchNotifier, BatchNotifier, EventFinalizers, EventStatus, Finalizable,
+};
 use vector_lib::internal_event::{
     self, CountByteSize, EventsSent, InternalEventHandle as _, Registered,
 };
@@ -25,7 +28,7 @@ use vector_lib::{
     buffers::{
         topology::{
             builder::TopologyBuilder,
-            channel::{BufferReceiver, BufferSender},
+            channel::{BufferReceiver, BufferSender, LimitedReceiver},
         },
         BufferType, WhenFull,
     },
@@ -41,8 +44,9 @@ use super::{
 };
 use crate::{
     config::{
-        ComponentKey, Config, DataType, EnrichmentTableConfig, Input, Inputs, OutputId,
-        ProxyConfig, SinkContext, SourceContext, TransformContext, TransformOuter, TransformOutput,
+        ComponentKey, Config, DataType, EnrichmentTableConfig, Input, Inputs, OffsetsLink,
+        OutputId, ProxyConfig, SinkContext, SourceContext, TransformContext, TransformOuter,
+        TransformOutput,
     },
     event::{EventArray, EventContainer},
     extra_context::ExtraContext,
@@ -74,6 +78,11 @@ static TRANSFORM_CONCURRENCY_LIMIT: Lazy<usize> = Lazy::new(|| {
 
 const INTERNAL_SOURCES: [&str; 2] = ["internal_logs", "internal_metrics"];
 
+/// The maximum number of event arrays written to the buffer of a source output before flushing it.
+const SOURCE_BUFFER_FLUSH_BATCH_SIZE: usize = 128;
+
+type SharedBufferParts = (BufferSender<EventArray>, BufferReceiver<EventArray>);
+
 struct Builder<'a> {
     config: &'a super::Config,
     diff: &'a ConfigDiff,
@@ -82,10 +91,16 @@ struct Builder<'a> {
     outputs: HashMap<OutputId, UnboundedSender<fanout::ControlMessage>>,
     tasks: HashMap<ComponentKey, Task>,
     buffers: HashMap<ComponentKey, BuiltBuffer>,
+    /// The senders and receivers of the sinks reading from each shared disk buffer which haven't
+    /// been built yet, keyed by the name of the buffer.
+    shared_buffers: HashMap<String, HashMap<ComponentKey, SharedBufferParts>>,
     inputs: HashMap<ComponentKey, (BufferSender<EventArray>, Inputs<OutputId>)>,
     healthchecks: HashMap<ComponentKey, Task>,
     detach_triggers: HashMap<ComponentKey, Trigger>,
     extra_context: ExtraContext,
+    /// The sink committing the offsets of each source handing off committing them, and the link
+    /// between them, keyed by source.
+    offsets_links: HashMap<ComponentKey, (ComponentKey, OffsetsLink)>,
 }
 
 impl<'a> Builder<'a> {
@@ -99,6 +114,7 @@ impl<'a> Builder<'a> {
             config,
             diff,
             buffers,
+            shared_buffers: HashMap::new(),
             shutdown_coordinator: SourceShutdownCoordinator::default(),
             errors: vec![],
             outputs: HashMap::new(),
@@ -107,6 +123,11 @@ impl<'a> Builder<'a> {
             healthchecks: HashMap::new(),
             detach_triggers: HashMap::new(),
             extra_context,
+            offsets_links: config
+                .offset_links()
+                .into_iter()
+                .map(|(source, sink)| (source, (sink, OffsetsLink::default())))
+                .collect(),
         }
     }
 
@@ -121,6 +142,22 @@ impl<'a> Builder<'a> {
         // readonly.
         enrichment_tables.finish_load();
 
+        // Sinks which are only being rewired keep running, so all we need is their new inputs.
+        let rewired_inputs = self
+            .config
+            .sinks()
+            .filter(|(key, _)| self.diff.sinks.is_rewired(key))
+            .map(|(key, sink)| (key.clone(), sink.inputs.clone()))
+            .collect();
+
+        let fanout_keys = self
+            .config
+            .transforms()
+            .map(|(key, _)| key)
+            .chain(self.config.sinks().map(|(key, _)| key))
+            .map(|key| (key.clone(), self.config.fanout_key(key)))
+            .collect();
+
         if self.errors.is_empty() {
             Ok(TopologyPieces {
                 inputs: self.inputs,
@@ -130,6 +167,8 @@ impl<'a> Builder<'a> {
                 healthchecks: self.healthchecks,
                 shutdown_coordinator: self.shutdown_coordinator,
                 detach_triggers: self.detach_triggers,
+                rewired_inputs,
+                fanout_keys,
             })
         } else {
             Err(self.errors)
@@ -159,7 +198,11 @@ impl<'a> Builder<'a> {
         // Build enrichment tables
         'tables: for (name, table) in self.config.enrichment_tables.iter() {
             let table_name = name.to_string();
-            if ENRICHMENT_TABLES.needs_reload(&table_name) {
+            // Tables are reloaded in place, so components using them don't have to be rebuilt when
+            // either their data or their configuration changes.
+            if self.diff.enrichment_tables.contains_new(name)
+                || ENRICHMENT_TABLES.needs_reload(&table_name)
+            {
                 let indexes = if !self.diff.enrichment_tables.is_added(name) {
                     // If this is an existing enrichment table, we need to store the indexes to reapply
                     // them again post load.
@@ -236,45 +279,70 @@ impl<'a> Builder<'a> {
             let mut pumps = Vec::new();
             let mut controls = HashMap::new();
             let mut schema_definitions = HashMap::with_capacity(source_outputs.len());
+            let mut buffer_error = None;
 
             for output in source_outputs.into_iter() {
                 let mut rx = builder.add_source_output(output.clone(), key.clone());
 
                 let (mut fanout, control) = Fanout::new();
                 let source_type = source.inner.get_component_name();
-                let source = Arc::new(key.clone());
-
-                let pump = async move {
-                    debug!("Source pump starting.");
-
-                    while let Some(SourceSenderItem {
-                        events: mut array,
-                        send_reference,
-                    }) = rx.next().await
-                    {
-                        array.set_output_id(&source);
-                        array.set_source_type(source_type);
-                        fanout
-                            .send(array, Some(send_reference))
-                            .await
-                            .map_err(|e| {
-                                debug!("Source pump finished with an error.");
-                                TaskError::wrapped(e)
-                            })?;
+                let output_id = OutputId {
+                    component: key.clone(),
+                    port: output.port.clone(),
+                };
+
+                let pump = if let Some(buffer) = &source.buffer {
+                    let buffer_span = error_span!("source", buffer_type = "disk");
+                    let buffer = buffer
+                        .build(
+                            self.config.global.data_dir.clone(),
+                            output_id.to_string(),
+                            buffer_span,
+                        )
+                        .await;
+                    match buffer {
+                        Err(error) => {
+                            buffer_error = Some(error);
+                            break;
+                        }
+                        Ok(buffer) => run_buffered_source_pump(
+                            rx,
+                            buffer,
+                            fanout,
+                            Arc::new(key.clone()),
+                            source_type,
+                        )
+                        .boxed(),
                     }
+                } else {
+                    let source = Arc::new(key.clone());
+                    async move {
+                        debug!("Source pump starting.");
+
+                        while let Some(SourceSenderItem {
+                            events: mut array,
+                            send_reference,
+                        }) = rx.next().await
+                        {
+                            array.set_output_id(&source);
+                            array.set_source_type(source_type);
+                            fanout
+                                .send(array, Some(send_reference))
+                                .await
+                                .map_err(|e| {
+                                    debug!("Source pump finished with an error.");
+                                    TaskError::wrapped(e)
+                                })?;
+                        }
 
-                    debug!("Source pump finished normally.");
-                    Ok(TaskOutput::Source)
+                        debug!("Source pump finished normally.");
+                        Ok(TaskOutput::Source)
+                    }
+                    .boxed()
                 };
 
                 pumps.push(pump.instrument(span.clone()));
-                controls.insert(
-                    OutputId {
-                        component: key.clone(),
-                        port: output.port.clone(),
-                    },
-                    control,
-                );
+                controls.insert(output_id, control);
 
                 let port = output.port.clone();
                 if let Some(definition) = output.schema_definition(self.config.schema.enabled) {
@@ -282,6 +350,12 @@ impl<'a> Builder<'a> {
                 }
             }
 
+            if let Some(error) = buffer_error {
+                self.errors.push(format!("Source \"{}\": {}", key, error));
+                continue;
+            }
+
+            let buffered = source.buffer.is_some();
             let (pump_error_tx, mut pump_error_rx) = oneshot::channel();
             let pump = async move {
                 debug!("Source pump supervisor starting.");
@@ -290,9 +364,16 @@ impl<'a> Builder<'a> {
                 //
                 // If any of the pumps complete with an error, or panic/are cancelled, we return
                 // immediately.
+                //
+                // The pumps of buffered sources hold on to their buffers, so they run as part of
+                // this task instead, which releases the buffers when it's aborted.
                 let mut handles = FuturesUnordered::new();
                 for pump in pumps {
-                    handles.push(spawn_named(pump, task_name.as_ref()));
+                    if buffered {
+                        handles.push(pump.map(Ok).boxed());
+                    } else {
+                        handles.push(spawn_named(pump, task_name.as_ref()).boxed());
+                    }
                 }
 
                 let mut had_pump_error = false;
@@ -327,10 +408,12 @@ impl<'a> Builder<'a> {
                 shutdown: shutdown_signal,
                 out: pipeline,
                 proxy: ProxyConfig::merge_with_env(&self.config.global.proxy, &source.proxy),
-                acknowledgements: source.sink_acknowledgements,
+                // Buffered sources acknowledge events once they're persisted in the buffer.
+                acknowledgements: source.sink_acknowledgements || source.buffer.is_some(),
                 schema_definitions,
                 schema: self.config.schema,
                 extra_context: self.extra_context.clone(),
+                offsets_link: self.offsets_links.get(key).map(|(_, link)| link.clone()),
             };
             let source = source.inner.build(context).await;
             let server = match source {
@@ -507,6 +590,55 @@ impl<'a> Builder<'a> {
         }
     }
 
+    /// Takes the sender and receiver of the given sink out of the shared disk buffer it reads from,
+    /// building the buffer when the first of the sinks reading from it is built.
+    async fn take_shared_buffer(
+        &mut self,
+        key: &ComponentKey,
+        group: &str,
+    ) -> Result<SharedBufferParts, String> {
+        let config = self.config;
+        if !self.shared_buffers.contains_key(group) {
+            let mut readers = config
+                .sinks()
+                .filter(|(_, sink)| sink.buffer.shared_group() == Some(group))
+                .map(|(key, sink)| (key.to_string(), key, sink))
+                .collect::<Vec<_>>();
+            readers.sort_by(|a, b| a.0.cmp(&b.0));
+            let reader_ids = readers
+                .iter()
+                .map(|(id, _, _)| id.clone())
+                .collect::<Vec<_>>();
+
+            let buffer_span = error_span!("sink", buffer_type = "disk");
+            let parts = readers[0]
+                .2
+                .buffer
+                .build_shared(
+                    config.global.data_dir.clone(),
+                    group.to_string(),
+                    &reader_ids,
+                    buffer_span,
+                )
+                .await
+                .map_err(|error| error.to_string())?;
+            self.shared_buffers.insert(
+                group.to_string(),
+                readers
+                    .into_iter()
+                    .map(|(_, key, _)| key.clone())
+                    .zip(parts)
+                    .collect(),
+            );
+        }
+
+        Ok(self
+            .shared_buffers
+            .get_mut(group)
+            .and_then(|readers| readers.remove(key))
+            .expect("every sink reading from a shared buffer should have a reader"))
+    }
+
     async fn build_sinks(&mut self, enrichment_tables: &vector_lib::enrichment::TableRegistry) {
         for (key, sink) in self
             .config
@@ -544,6 +676,14 @@ impl<'a> Builder<'a> {
 
             let (tx, rx) = if let Some(buffer) = self.buffers.remove(key) {
                 buffer
+            } else if let Some(group) = sink.buffer.shared_group() {
+                match self.take_shared_buffer(key, group).await {
+                    Err(error) => {
+                        self.errors.push(format!("Sink \"{}\": {}", key, error));
+                        continue;
+                    }
+                    Ok((tx, rx)) => (tx, Arc::new(Mutex::new(Some(rx.into_stream())))),
+                }
             } else {
                 let buffer_type = match sink.buffer.stages().first().expect("cant ever be empty") {
                     BufferType::Memory { .. } => "memory",
@@ -575,6 +715,12 @@ impl<'a> Builder<'a> {
                 app_name: crate::get_app_name().to_string(),
                 app_name_slug: crate::get_slugified_app_name(),
                 extra_context: self.extra_context.clone(),
+                offsets_links: self
+                    .offsets_links
+                    .iter()
+                    .filter(|(_, (sink, _))| sink == key)
+                    .map(|(source, (_, link))| (source.clone(), link.clone()))
+                    .collect(),
             };
 
             let (sink, healthcheck) = match sink.inner.build(cx).await {
@@ -684,6 +830,8 @@ pub struct TopologyPieces {
     pub(super) healthchecks: HashMap<ComponentKey, Task>,
     pub(crate) shutdown_coordinator: SourceShutdownCoordinator,
     pub(crate) detach_triggers: HashMap<ComponentKey, Trigger>,
+    pub(super) rewired_inputs: HashMap<ComponentKey, Inputs<OutputId>>,
+    pub(super) fanout_keys: HashMap<ComponentKey, Option<ComponentKey>>,
 }
 
 impl TopologyPieces {
@@ -758,6 +906,102 @@ impl TransformNode {
     }
 }
 
+/// Runs the pump of a source output whose events are buffered on disk.
+///
+/// Events sent by the source are written to the buffer in batches of the events that are ready, and
+/// are acknowledged once their batch has been flushed to the buffer, while a separate reader sends
+/// them downstream. Once the source finishes, any events which haven't been read from the buffer yet
+/// are left in it, to be sent the next time the source starts, and the pump only waits for the
+/// events it has already sent downstream to be acknowledged.
+async fn run_buffered_source_pump(
+    mut rx: LimitedReceiver<SourceSenderItem>,
+    (mut buffer_tx, buffer_rx): (BufferSender<EventArray>, BufferReceiver<EventArray>),
+    mut fanout: Fanout,
+    source: Arc<ComponentKey>,
+    source_type: &'static str,
+) -> TaskResult {
+    debug!("Buffered source pump starting.");
+
+    let writer = async move {
+        while let Some(item) = rx.next().await {
+            // The events in the buffer get finalizers of their own, so the source's finalizers can
+            // be resolved as soon as the events are persisted.
+            let mut finalizers = EventFinalizers::default();
+            let mut batch_size = 0;
+            let mut next = Some(item);
+            let mut result = Ok(());
+
+            // Write the events the source has already sent, up to a limit, and then flush them to
+            // the buffer together.
+            while let Some(SourceSenderItem {
+                events: mut array, ..
+            }) = next.take()
+            {
+                finalizers.merge(array.take_finalizers());
+                batch_size += 1;
+                result = buffer_tx.send(array, None).await;
+                if result.is_err() {
+                    break;
+                }
+                if batch_size < SOURCE_BUFFER_FLUSH_BATCH_SIZE {
+                    next = rx.next().now_or_never().flatten();
+                }
+            }
+
+            let result = match result {
+                Ok(()) => buffer_tx.flush().await,
+                Err(error) => Err(error),
+            };
+            match result {
+                Ok(()) => finalizers.update_status(EventStatus::Delivered),
+                Err(error) => {
+                    finalizers.update_status(EventStatus::Errored);
+                    return Err(TaskError::wrapped(error));
+                }
+            }
+        }
+        Ok(())
+    };
+
+    let mut buffer_rx = buffer_rx.into_stream();
+    let mut pending = FuturesUnordered::new();
+    let reader = async {
+        loop {
+            select! {
+                Some(_) = pending.next(), if !pending.is_empty() => {}
+                array = buffer_rx.next() => {
+                    let Some(mut array) = array else {
+                        break;
+                    };
+                    array.set_output_id(&source);
+                    array.set_source_type(source_type);
+
+                    let (batch, receiver) = BatchNotifier::new_with_receiver();
+                    array.add_batch_notifier(batch);
+                    pending.push(receiver);
+
+                    fanout.send(array, None).await.map_err(TaskError::wrapped)?;
+                }
+            }
+        }
+        Ok::<_, TaskError>(())
+    };
+
+    let result = select! {
+        result = writer => result,
+        result = reader => result,
+    };
+    if let Err(error) = result {
+        debug!("Buffered source pump finished with an error.");
+        return Err(error);
+    }
+
+    while pending.next().await.is_some() {}
+
+    debug!("Buffered source pump finished normally.");
+    Ok(TaskOutput::Source)
+}
+
 fn build_transform(
     transform: Transform,
     node: TransformNode,
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use futures_util::FutureExt as _;
 
@@ -9,6 +9,8 @@ use crate::api;
 use crate::extra_context::ExtraContext;
 use crate::internal_events::{VectorRecoveryError, VectorReloadError, VectorReloaded};
 
+#[cfg(feature = "api")]
+use crate::signal::SignalTx;
 use crate::{config, signal::ShutdownError, topology::RunningTopology};
 
 #[derive(Clone, Debug)]
@@ -34,6 +36,8 @@ pub struct TopologyController {
     pub require_healthy: Option<bool>,
     #[cfg(feature = "api")]
     pub api_server: Option<api::Server>,
+    #[cfg(feature = "api")]
+    pub signal_tx: SignalTx,
     pub extra_context: ExtraContext,
 }
 
@@ -55,7 +59,11 @@ pub enum ReloadOutcome {
 }
 
 impl TopologyController {
-    pub async fn reload(&mut self, mut new_config: config::Config) -> ReloadOutcome {
+    pub async fn reload(
+        &mut self,
+        mut new_config: config::Config,
+        components_to_reload: &HashSet<config::ComponentKey>,
+    ) -> ReloadOutcome {
         new_config
             .healthchecks
             .set_require_healthy(self.require_healthy);
@@ -78,6 +86,7 @@ impl TopologyController {
                 self.topology.config(),
                 self.topology.watch(),
                 Arc::<AtomicBool>::clone(&self.topology.running),
+                self.signal_tx.clone(),
                 &Handle::current(),
             ) {
                 Ok(api_server) => {
@@ -99,7 +108,11 @@ impl TopologyController {
 
         match self
             .topology
-            .reload_config_and_respawn(new_config, self.extra_context.clone())
+            .reload_config_and_respawn_components(
+                new_config,
+                components_to_reload,
+                self.extra_context.clone(),
+            )
             .await
         {
             Ok(true) => {
@@ -9,7 +9,7 @@ use std::{
 use futures::{future, Future, FutureExt};
 use tokio::{
     sync::{mpsc, watch},
-    time::{interval, sleep_until, Duration, Instant},
+    time::{interval, sleep_until, timeout, timeout_at, Duration, Instant},
 };
 use tracing::Instrument;
 use vector_lib::buffers::topology::channel::BufferSender;
@@ -24,7 +24,10 @@ use super::{
     BuiltBuffer, TapOutput, TapResource, TaskHandle, WatchRx, WatchTx,
 };
 use crate::{
-    config::{ComponentKey, Config, ConfigDiff, HealthcheckOptions, Inputs, OutputId, Resource},
+    config::{
+        ComponentKey, Config, ConfigDiff, ConfigSnapshot, HealthcheckOptions, Inputs, OutputId,
+        Resource,
+    },
     event::EventArray,
     extra_context::ExtraContext,
     shutdown::SourceShutdownCoordinator,
@@ -34,6 +37,9 @@ use crate::{
 
 pub type ShutdownErrorReceiver = mpsc::UnboundedReceiver<ShutdownError>;
 
+/// How long a changed transform waits for its previous instance to drain before starting.
+const TRANSFORM_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[allow(dead_code)]
 pub struct RunningTopology {
     inputs: HashMap<ComponentKey, BufferSender<EventArray>>,
@@ -224,6 +230,23 @@ impl RunningTopology {
         &mut self,
         new_config: Config,
         extra_context: ExtraContext,
+    ) -> Result<bool, ()> {
+        self.reload_config_and_respawn_components(new_config, &HashSet::new(), extra_context)
+            .await
+    }
+
+    /// Same as [`Self::reload_config_and_respawn`], but additionally rebuilds the given
+    /// components even if their configuration didn't change.
+    ///
+    /// # Errors
+    ///
+    /// If all changes from the new configuration cannot be made, and the current configuration
+    /// cannot be fully restored, then `Err(())` is returned.
+    pub async fn reload_config_and_respawn_components(
+        &mut self,
+        new_config: Config,
+        components_to_reload: &HashSet<ComponentKey>,
+        extra_context: ExtraContext,
     ) -> Result<bool, ()> {
         info!("Reloading running topology with new configuration.");
 
@@ -240,7 +263,12 @@ impl RunningTopology {
         // spawning the new version of the component.
         //
         // We also shutdown any component that is simply being removed entirely.
-        let diff = ConfigDiff::new(&self.config, &new_config);
+        //
+        // Sinks whose configuration only changed in their inputs are neither shut down nor rebuilt,
+        // so their buffers and any connections they hold stay intact.
+        let new_snapshot = ConfigSnapshot::new(&new_config);
+        let diff = ConfigDiff::from_snapshots(&ConfigSnapshot::new(&self.config), &new_snapshot)
+            .with_reloaded_components(&new_snapshot, components_to_reload);
         let buffers = self.shutdown_diff(&diff, &new_config).await;
 
         // Gives windows some time to make available any port
@@ -353,12 +381,21 @@ impl RunningTopology {
             let timeout = Duration::from_secs(30);
             let mut source_shutdown_handles = Vec::new();
 
+            // The pumps of sources with disk buffers hold on to their buffers until the events
+            // they've sent downstream are acknowledged, so we wait for them to finish before the
+            // buffers can be reopened.
+            let mut buffered_pumps = Vec::new();
+
             let deadline = Instant::now() + timeout;
             for key in &diff.sources.to_remove {
                 debug!(component = %key, "Removing source.");
 
                 let previous = self.tasks.remove(key).unwrap();
-                drop(previous); // detach and forget
+                if self.config.source(key).unwrap().buffer.is_some() {
+                    buffered_pumps.push(previous);
+                } else {
+                    drop(previous); // detach and forget
+                }
 
                 self.remove_outputs(key);
                 source_shutdown_handles
@@ -368,6 +405,10 @@ impl RunningTopology {
             for key in &diff.sources.to_change {
                 debug!(component = %key, "Changing source.");
 
+                if self.config.source(key).unwrap().buffer.is_some() {
+                    buffered_pumps.push(self.tasks.remove(key).unwrap());
+                }
+
                 self.remove_outputs(key);
                 source_shutdown_handles
                     .push(self.shutdown_coordinator.shutdown_source(key, deadline));
@@ -385,6 +426,17 @@ impl RunningTopology {
                     task.await.unwrap().unwrap();
                 }
             }
+
+            // A pump that doesn't finish in time is aborted, and awaited again, so that its buffer
+            // is released before the source is rebuilt. Any events it hadn't gotten acknowledged
+            // yet stay in the buffer, and are sent again once it's reopened.
+            for mut pump in buffered_pumps {
+                if let Err(error) = timeout_at(deadline, &mut pump).await {
+                    warn!(message = "Buffered source pump did not finish in time, aborting it.", %error);
+                    pump.abort();
+                    _ = pump.await;
+                }
+            }
         }
 
         // Next, we shutdown any changed/removed transforms.  Same as before: we want allow
@@ -423,7 +475,7 @@ impl RunningTopology {
         let add_source = diff
             .sources
             .changed_and_added()
-            .map(|key| (key, new_config.source(key).unwrap().inner.resources()));
+            .map(|key| (key, new_config.source(key).unwrap().resources(key)));
         let add_sink = diff
             .sinks
             .changed_and_added()
@@ -445,12 +497,16 @@ impl RunningTopology {
             .map(|(_, key)| key.clone());
 
         // For any sink whose buffer configuration didn't change, we can reuse their buffer.
+        //
+        // Shared disk buffers are always rebuilt, as they're built once for all of the sinks
+        // reading from them.
         let reuse_buffers = diff
             .sinks
             .to_change
             .iter()
             .filter(|&key| {
-                self.config.sink(key).unwrap().buffer == new_config.sink(key).unwrap().buffer
+                let buffer = &self.config.sink(key).unwrap().buffer;
+                buffer.shared_group().is_none() && *buffer == new_config.sink(key).unwrap().buffer
             })
             .cloned()
             .collect::<HashSet<_>>();
@@ -585,6 +641,10 @@ impl RunningTopology {
                 self.inputs_tap_metadata
                     .insert(key.clone(), input.1.clone());
             }
+
+            for (key, inputs) in &new_pieces.rewired_inputs {
+                self.inputs_tap_metadata.insert(key.clone(), inputs.clone());
+            }
         }
 
         // We configure the outputs of any changed/added sources first, so they're available to any
@@ -614,6 +674,12 @@ impl RunningTopology {
             self.setup_inputs(key, diff, new_pieces).await;
         }
 
+        // Sinks that are kept running only need to be moved over to their new inputs.
+        for key in &diff.sinks.to_rewire {
+            debug!(component = %key, "Rewiring inputs for sink.");
+            self.rewire_inputs(key, diff, new_pieces);
+        }
+
         // We do a final pass here to reconnect unchanged components.
         //
         // Why would we reconnect unchanged components?  Well, as sources and transforms will
@@ -699,6 +765,63 @@ impl RunningTopology {
     ) {
         let (tx, inputs) = new_pieces.inputs.remove(key).unwrap();
 
+        // Of the sinks reading from a shared disk buffer, only the first one is connected.
+        if let Some(fanout_key) = &new_pieces.fanout_keys[key] {
+            let old_inputs = self
+                .config
+                .inputs_for_fanout_key(fanout_key)
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect::<HashSet<_>>();
+
+            let new_inputs = inputs.iter().cloned().collect::<HashSet<_>>();
+            let inputs_to_add = &new_inputs - &old_inputs;
+
+            for input in inputs {
+                let output = self.outputs.get_mut(&input).expect("unknown output");
+
+                if diff.contains(&input.component) || inputs_to_add.contains(&input) {
+                    // If the input we're connecting to is changing, that means its outputs will
+                    // have been recreated, so instead of replacing a paused sink, we have to add it
+                    // to this new output for the first time, since there's nothing to actually
+                    // replace at this point.
+                    debug!(component = %key, fanout_id = %input, "Adding component input to fanout.");
+
+                    _ = output.send(ControlMessage::Add(fanout_key.clone(), tx.clone()));
+                } else {
+                    // We know that if this component is connected to a given input, and neither
+                    // components were changed, then the output must still exist, which means we
+                    // paused this component's connection to its output, so we have to replace that
+                    // connection now:
+                    debug!(component = %key, fanout_id = %input, "Replacing component input in fanout.");
+
+                    _ = output.send(ControlMessage::Replace(fanout_key.clone(), tx.clone()));
+                }
+            }
+        }
+
+        self.inputs.insert(key.clone(), tx);
+        new_pieces
+            .detach_triggers
+            .remove(key)
+            .map(|trigger| self.detach_triggers.insert(key.clone(), trigger.into()));
+    }
+
+    fn rewire_inputs(
+        &mut self,
+        key: &ComponentKey,
+        diff: &ConfigDiff,
+        new_pieces: &mut builder::TopologyPieces,
+    ) {
+        let new_inputs = new_pieces.rewired_inputs.remove(key).unwrap();
+
+        // Sinks reading from the same shared disk buffer are rewired along with each other, so the
+        // same one of them stays connected.
+        let Some(fanout_key) = new_pieces.fanout_keys[key].clone() else {
+            return;
+        };
+
         let old_inputs = self
             .config
             .inputs_for_node(key)
@@ -706,36 +829,35 @@ impl RunningTopology {
             .flatten()
             .cloned()
             .collect::<HashSet<_>>();
+        let tx = self.inputs.get(key).cloned().unwrap();
 
-        let new_inputs = inputs.iter().cloned().collect::<HashSet<_>>();
-        let inputs_to_add = &new_inputs - &old_inputs;
+        for input in old_inputs
+            .iter()
+            .filter(|&input| !new_inputs.contains(input))
+        {
+            // Changed or removed components have already had their outputs torn down, along with
+            // any connection to this sink.
+            if diff.contains(&input.component) {
+                continue;
+            }
 
-        for input in inputs {
-            let output = self.outputs.get_mut(&input).expect("unknown output");
+            if let Some(output) = self.outputs.get_mut(input) {
+                debug!(component = %key, fanout_id = %input, "Removing component input from fanout.");
 
-            if diff.contains(&input.component) || inputs_to_add.contains(&input) {
-                // If the input we're connecting to is changing, that means its outputs will have been
-                // recreated, so instead of replacing a paused sink, we have to add it to this new
-                // output for the first time, since there's nothing to actually replace at this point.
-                debug!(component = %key, fanout_id = %input, "Adding component input to fanout.");
+                _ = output.send(ControlMessage::Remove(fanout_key.clone()));
+            }
+        }
 
-                _ = output.send(ControlMessage::Add(key.clone(), tx.clone()));
-            } else {
-                // We know that if this component is connected to a given input, and neither
-                // components were changed, then the output must still exist, which means we paused
-                // this component's connection to its output, so we have to replace that connection
-                // now:
-                debug!(component = %key, fanout_id = %input, "Replacing component input in fanout.");
+        for input in new_inputs.iter() {
+            // Inputs that are unchanged on both ends were never disconnected, so events keep
+            // flowing through them while the sink is rewired.
+            if diff.contains(&input.component) || !old_inputs.contains(input) {
+                debug!(component = %key, fanout_id = %input, "Adding component input to fanout.");
 
-                _ = output.send(ControlMessage::Replace(key.clone(), tx.clone()));
+                let output = self.outputs.get_mut(input).expect("unknown output");
+                _ = output.send(ControlMessage::Add(fanout_key.clone(), tx.clone()));
             }
         }
-
-        self.inputs.insert(key.clone(), tx);
-        new_pieces
-            .detach_triggers
-            .remove(key)
-            .map(|trigger| self.detach_triggers.insert(key.clone(), trigger.into()));
     }
 
     fn remove_outputs(&mut self, key: &ComponentKey) {
@@ -746,19 +868,21 @@ impl RunningTopology {
         self.inputs.remove(key);
         self.detach_triggers.remove(key);
 
+        // Of the sinks reading from a shared disk buffer, only the first one is connected.
+        let Some(fanout_key) = self.config.fanout_key(key) else {
+            return;
+        };
+
         let old_inputs = self.config.inputs_for_node(key).expect("node exists");
         let new_inputs = new_config
-            .inputs_for_node(key)
+            .inputs_for_fanout_key(&fanout_key)
             .unwrap_or_default()
             .iter()
             .collect::<HashSet<_>>();
 
         for input in old_inputs {
             if let Some(output) = self.outputs.get_mut(input) {
-                if diff.contains(&input.component)
-                    || diff.is_removed(key)
-                    || !new_inputs.contains(input)
-                {
+                if diff.contains(&input.component) || !new_inputs.contains(input) {
                     // 3 cases to remove the input:
                     //
                     // Case 1: If the input we're removing ourselves from is changing, that means its
@@ -766,19 +890,21 @@ impl RunningTopology {
                     // outright to ensure things are clean.
                     //
                     // Case 2: If this component itself is being removed, then pausing makes no sense
-                    // because it isn't coming back.
+                    // because it isn't coming back. The sinks reading from a shared disk buffer are
+                    // connected under a key for the buffer, though, so if any of them remain, the
+                    // connection is paused even if the sink it was made through is removed.
                     //
                     // Case 3: This component is no longer connected to the input from new config.
                     debug!(component = %key, fanout_id = %input, "Removing component input from fanout.");
 
-                    _ = output.send(ControlMessage::Remove(key.clone()));
+                    _ = output.send(ControlMessage::Remove(fanout_key.clone()));
                 } else {
                     // We know that if this component is connected to a given input, and it isn't being
                     // changed, then it will exist when we reconnect inputs, so we should pause it
                     // now to pause further sends through that component until we reconnect:
                     debug!(component = %key, fanout_id = %input, "Pausing component input in fanout.");
 
-                    _ = output.send(ControlMessage::Pause(key.clone()));
+                    _ = output.send(ControlMessage::Pause(fanout_key.clone()));
                 }
             }
         }
@@ -803,15 +929,19 @@ impl RunningTopology {
         let unchanged_sinks = self
             .config
             .sinks()
-            .filter(|(key, _)| !diff.sinks.contains(key));
+            .filter(|(key, _)| !diff.sinks.contains(key) && !diff.sinks.is_rewired(key));
         for (sink_key, sink) in unchanged_sinks {
+            let Some(fanout_key) = self.config.fanout_key(sink_key) else {
+                continue;
+            };
+
             let changed_outputs = get_changed_outputs(diff, sink.inputs.clone());
             for output_id in changed_outputs {
                 debug!(component = %sink_key, fanout_id = %output_id.component, "Reattaching component input to fanout.");
 
                 let input = self.inputs.get(sink_key).cloned().unwrap();
                 let output = self.outputs.get_mut(&output_id).unwrap();
-                _ = output.send(ControlMessage::Add(sink_key.clone(), input));
+                _ = output.send(ControlMessage::Add(fanout_key.clone(), input));
             }
         }
     }
@@ -925,10 +1055,22 @@ impl RunningTopology {
             })
         }
         .instrument(task_span);
+
+        // A changed transform only starts once its previous instance has drained the events that
+        // were still buffered for it, so that those reach downstream components before any newer
+        // ones. An idle input keeps the previous instance's buffer open, though, so we don't wait
+        // on it indefinitely.
+        let previous = self.tasks.remove(key);
+        let task = async move {
+            if let Some(previous) = previous {
+                if timeout(TRANSFORM_DRAIN_TIMEOUT, previous).await.is_err() {
+                    debug!("Previous transform did not finish draining in time.");
+                }
+            }
+            task.await
+        };
         let spawned = spawn_named(task, task_name.as_ref());
-        if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
-            drop(previous); // detach and forget
-        }
+        self.tasks.insert(key.clone(), spawned);
     }
 
     fn spawn_source(&mut self, key: &ComponentKey, new_pieces: &mut builder::TopologyPieces) {
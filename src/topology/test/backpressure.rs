@@ -1,3 +1,4 @@
+use std::num::NonZeroUsize;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
@@ -113,6 +114,50 @@ async fn buffer_drop_fan_out() {
     assert_eq!(sourced_events, expected_sourced_events);
 }
 
+/// Connects a single source to a single sink with a chained buffer, and makes sure that events
+/// overflow to the second stage before the sink backpressure is propagated to the source.
+#[tokio::test]
+async fn chained_buffer_overflow() {
+    test_util::trace_init();
+
+    let mut config = Config::builder();
+
+    let events_to_sink = 100;
+    let first_stage_max_events = 50;
+    let second_stage_max_events = 200;
+
+    let expected_sourced_events = events_to_sink
+        + first_stage_max_events
+        + second_stage_max_events
+        + *SOURCE_SENDER_BUFFER_SIZE
+        + EXTRA_SOURCE_PUMP_EVENT;
+
+    let source_counter = Arc::new(AtomicUsize::new(0));
+    config.add_source("in", backpressure_source(&source_counter));
+
+    let mut sink_outer = SinkOuter::new(vec!["in".to_string()], backpressure_sink(events_to_sink));
+    sink_outer.buffer = BufferConfig::Chained(vec![
+        BufferType::Memory {
+            max_events: NonZeroUsize::new(first_stage_max_events).unwrap(),
+            when_full: WhenFull::Overflow,
+        },
+        BufferType::Memory {
+            max_events: NonZeroUsize::new(second_stage_max_events).unwrap(),
+            when_full: WhenFull::Block,
+        },
+    ]);
+    config.add_sink_outer("out", sink_outer);
+
+    let (_topology, _) = start_topology(config.build().unwrap(), false).await;
+
+    // allow the topology to run
+    wait_until_expected(&source_counter, expected_sourced_events).await;
+
+    let sourced_events = source_counter.load(Ordering::Acquire);
+
+    assert_eq!(sourced_events, expected_sourced_events);
+}
+
 /// Connects 2 sources to a single sink, and asserts that the sum of the events produced
 /// by the sources is how many the single sink accepted.
 #[tokio::test]
@@ -154,3 +154,65 @@ async fn http_to_http_delivered() {
 async fn http_to_http_failed() {
     http_to_http(StatusCode::FORBIDDEN, StatusCode::BAD_REQUEST).await;
 }
+
+#[tokio::test]
+async fn http_to_http_buffered_source() {
+    test_util::trace_init();
+
+    let data_dir = tempfile::tempdir().unwrap();
+    let address1 = test_util::next_addr();
+    let address2 = test_util::next_addr();
+    let config = config::load_from_str(
+        &format!(
+            r#"
+data_dir = "{data_dir}"
+
+[sources.in]
+type = "http_server"
+address = "{address1}"
+buffer.type = "disk"
+buffer.max_size = 268435488
+
+[sinks.out]
+type = "http"
+inputs = ["in"]
+encoding.codec = "json"
+uri = "http://{address2}/"
+"#,
+            data_dir = data_dir.path().display(),
+            address1 = address1,
+            address2 = address2,
+        ),
+        Format::Toml,
+    )
+    .unwrap();
+    let diff = ConfigDiff::initial(&config);
+    let pieces =
+        TopologyPieces::build_or_log_errors(&config, &diff, HashMap::new(), Default::default())
+            .await
+            .unwrap();
+    let (_topology, _) = RunningTopology::start_validated(config, diff, pieces)
+        .await
+        .unwrap();
+
+    test_util::wait_for_tcp(address1).await;
+
+    let mutex = Arc::new(Mutex::new(()));
+    let pause = mutex.lock().await;
+    let mut rx_server = http_server(address2, Arc::clone(&mutex), StatusCode::FORBIDDEN).await;
+
+    // The source responds as soon as the event is persisted in its buffer, without waiting for the
+    // sink, which eventually fails to send it.
+    let (_rx_client, sender) = http_client(address1, "test");
+    let result = timeout(Duration::from_secs(4), sender)
+        .await
+        .expect("Timed out waiting to receive result from HTTP source")
+        .expect("Error receiving result from tokio task");
+    assert_eq!(result.status(), StatusCode::OK);
+
+    timeout(Duration::from_secs(4), rx_server.recv())
+        .await
+        .expect("Timed out waiting to receive event from HTTP sink")
+        .expect("Error receiving event from HTTP sink");
+    drop(pause);
+}
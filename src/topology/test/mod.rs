@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     iter,
     sync::{
         atomic::{AtomicBool, AtomicUsize, Ordering},
@@ -14,7 +14,7 @@ use crate::{
         mock::{
             basic_sink, basic_sink_failing_healthcheck, basic_sink_with_data, basic_source,
             basic_source_with_data, basic_source_with_event_counter, basic_transform,
-            error_definition_transform,
+            error_definition_transform, sinks::BasicSinkConfig,
         },
         start_topology, trace_init,
     },
@@ -294,7 +294,8 @@ async fn topology_remove_one_source() {
 
     let (mut topology, _) = start_topology(config.build().unwrap(), false).await;
 
-    let (out1, sink1) = basic_sink(10);
+    // The new sink's configuration differs, so `out1` is replaced rather than only rewired.
+    let (out1, sink1) = basic_sink_with_data(10, "v2");
 
     let mut config = Config::builder();
     config.add_source("in1", basic_source().1);
@@ -445,7 +446,8 @@ async fn topology_swap_source() {
 
     // Now, create sink #2 and replace `out2` with it, and add source #2 as `in2`, attached to `out1`:
     let (mut in2, source2) = basic_source();
-    let (out2, sink2) = basic_sink(10);
+    // The new sink's configuration differs, so `out1` is replaced rather than only rewired.
+    let (out2, sink2) = basic_sink_with_data(10, "v2");
 
     let mut config = Config::builder();
     config.add_source("in2", source2);
@@ -604,6 +606,325 @@ async fn topology_swap_sink() {
     assert_eq!(vec![event1], res2);
 }
 
+#[tokio::test]
+async fn topology_rewire_sink() {
+    trace_init();
+
+    // Add source #1 as `in1`, and sink #1 as `out1`, with sink #1 attached to `in1`:
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_sink("out1", &["in1"], sink1);
+
+    let (mut topology, _) = start_topology(config.build().unwrap(), false).await;
+
+    let h_out1 = tokio::spawn(out1.flat_map(into_message_stream).collect::<Vec<_>>());
+    in1.send_event(Event::Log(LogEvent::from("before")))
+        .await
+        .unwrap();
+
+    // Now, add source #2 as `in2`, and attach `out1` to both sources. As only the inputs of `out1`
+    // change, sink #1 keeps running throughout the reload rather than being replaced by sink #2:
+    let (mut in2, source2) = basic_source();
+    let (out2, sink2) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", basic_source().1);
+    config.add_source("in2", source2);
+    config.add_sink("out1", &["in1", "in2"], sink2);
+
+    assert!(topology
+        .reload_config_and_respawn(config.build().unwrap(), Default::default())
+        .await
+        .unwrap());
+
+    let h_out2 = tokio::spawn(out2.flat_map(into_message_stream).collect::<Vec<_>>());
+    in1.send_event(Event::Log(LogEvent::from("after")))
+        .await
+        .unwrap();
+    in2.send_event(Event::Log(LogEvent::from("that")))
+        .await
+        .unwrap();
+
+    // Drop the inputs to the two sources, which will ensure they drain all items and stop
+    // themselves, and also fully stop the topology:
+    drop(in1);
+    drop(in2);
+    topology.stop().await;
+
+    // Events from before and after the reload, from both sources, all reach sink #1.
+    let mut res1 = h_out1.await.unwrap();
+    res1.sort();
+    assert_eq!(vec!["after", "before", "that"], res1);
+    assert_eq!(Vec::<String>::new(), h_out2.await.unwrap());
+}
+
+#[tokio::test]
+async fn topology_reload_unchanged_component() {
+    trace_init();
+
+    // Add source #1 as `in1`, and sink #1 as `out1`, with sink #1 attached to `in1`:
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_sink("out1", &["in1"], sink1);
+
+    let (mut topology, _) = start_topology(config.build().unwrap(), false).await;
+
+    // Now, create an identical topology, but explicitly ask for `out1` to be reloaded, which
+    // forces it to be rebuilt as sink #2 even though its configuration didn't change:
+    let (out2, sink2) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", basic_source().1);
+    config.add_sink("out1", &["in1"], sink2);
+
+    assert!(topology
+        .reload_config_and_respawn_components(
+            config.build().unwrap(),
+            &HashSet::from([ComponentKey::from("out1")]),
+            Default::default()
+        )
+        .await
+        .unwrap());
+
+    let h_out1 = tokio::spawn(out1.flat_map(into_message_stream).collect::<Vec<_>>());
+    let h_out2 = tokio::spawn(out2.flat_map(into_message_stream).collect::<Vec<_>>());
+    in1.send_event(Event::Log(LogEvent::from("this")))
+        .await
+        .unwrap();
+
+    drop(in1);
+    topology.stop().await;
+
+    assert_eq!(Vec::<String>::new(), h_out1.await.unwrap());
+    assert_eq!(vec!["this"], h_out2.await.unwrap());
+}
+
+#[tokio::test]
+async fn topology_reload_unchanged_source() {
+    trace_init();
+
+    // Add source #1 as `in1`, and sink #1 as `out1`, with sink #1 attached to `in1`:
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_sink("out1", &["in1"], sink1);
+
+    let (mut topology, _) = start_topology(config.build().unwrap(), false).await;
+
+    // Now, create an identical topology, but explicitly ask for `in1` to be reloaded, which forces
+    // it to be rebuilt as source #2 even though its configuration didn't change, while `out1`
+    // keeps running:
+    let (mut in2, source2) = basic_source();
+
+    let mut config = Config::builder();
+    config.add_source("in1", source2);
+    config.add_sink("out1", &["in1"], basic_sink(10).1);
+
+    assert!(topology
+        .reload_config_and_respawn_components(
+            config.build().unwrap(),
+            &HashSet::from([ComponentKey::from("in1")]),
+            Default::default()
+        )
+        .await
+        .unwrap());
+
+    let h_out1 = tokio::spawn(out1.flat_map(into_message_stream).collect::<Vec<_>>());
+    in1.send_event(Event::Log(LogEvent::from("this")))
+        .await
+        .unwrap_err();
+    in2.send_event(Event::Log(LogEvent::from("that")))
+        .await
+        .unwrap();
+
+    drop(in1);
+    drop(in2);
+    topology.stop().await;
+
+    assert_eq!(vec!["that"], h_out1.await.unwrap());
+}
+
+#[tokio::test]
+async fn topology_reload_soak_loses_no_events() {
+    trace_init();
+
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+
+    // Every configuration shares the same source and sink, so that events keep flowing into the
+    // same output stream while the transform is rebuilt and the sink is rewired or reloaded:
+    let config = |increase: f64, rewired: bool| {
+        let inputs: &[&str] = if rewired { &["t1", "in2"] } else { &["t1"] };
+
+        let mut config = Config::builder();
+        config.add_source("in1", source1.clone());
+        config.add_source("in2", basic_source().1);
+        config.add_transform("t1", &["in1"], basic_transform(" transformed", increase));
+        config.add_sink("out1", inputs, sink1.clone());
+        config.build().unwrap()
+    };
+
+    let (mut topology, _) = start_topology(config(0.0, false), false).await;
+
+    let h_out1 = tokio::spawn(out1.flat_map(into_message_stream).collect::<Vec<_>>());
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let sent = Arc::new(AtomicUsize::new(0));
+    let pump_handle = {
+        let stop = Arc::clone(&stop);
+        let sent = Arc::clone(&sent);
+        tokio::spawn(async move {
+            let mut count = 0;
+            while !stop.load(Ordering::Relaxed) {
+                in1.send_event(Event::Log(LogEvent::from(count.to_string())))
+                    .await
+                    .unwrap();
+                count += 1;
+                sent.store(count, Ordering::Relaxed);
+            }
+            count
+        })
+    };
+
+    // Make sure events are flowing through the pipeline before every reload:
+    let sent = &sent;
+    let wait_for_events = || async move {
+        let target = sent.load(Ordering::Relaxed) + 50;
+        while sent.load(Ordering::Relaxed) < target {
+            yield_now().await;
+        }
+    };
+
+    let mut increase = 0.0;
+    let mut rewired = false;
+    for _ in 0..20 {
+        // Rebuild the transform, which the sink keeps reading from:
+        wait_for_events().await;
+        increase = 1.0 - increase;
+        assert!(topology
+            .reload_config_and_respawn(config(increase, rewired), Default::default())
+            .await
+            .unwrap());
+
+        // Rewire the sink, which keeps it running:
+        wait_for_events().await;
+        rewired = !rewired;
+        assert!(topology
+            .reload_config_and_respawn(config(increase, rewired), Default::default())
+            .await
+            .unwrap());
+
+        // Explicitly reload the sink, which rebuilds it on top of its existing buffer:
+        wait_for_events().await;
+        assert!(topology
+            .reload_config_and_respawn_components(
+                config(increase, rewired),
+                &HashSet::from([ComponentKey::from("out1")]),
+                Default::default()
+            )
+            .await
+            .unwrap());
+    }
+
+    wait_for_events().await;
+    stop.store(true, Ordering::Relaxed);
+    let count = pump_handle.await.unwrap();
+    topology.stop().await;
+
+    // Every event sent reaches the sink exactly once, and in the order it was sent:
+    let expected = (0..count)
+        .map(|i| format!("{i} transformed"))
+        .collect::<Vec<_>>();
+    assert_eq!(expected, h_out1.await.unwrap());
+}
+
+#[cfg(feature = "transforms-remap")]
+#[tokio::test]
+async fn topology_reload_enrichment_table_in_place() {
+    use crate::{
+        enrichment_tables::file::{Encoding, FileConfig, FileSettings},
+        transforms::remap::RemapConfig,
+    };
+
+    trace_init();
+
+    let dir = crate::test_util::temp_dir();
+    std::fs::create_dir_all(&dir).unwrap();
+    let table = |name: &str, value: &str| {
+        let path = dir.join(name);
+        std::fs::write(&path, format!("id,value\n1,{}\n", value)).unwrap();
+        FileConfig {
+            file: FileSettings {
+                path,
+                encoding: Encoding::default(),
+            },
+            schema: HashMap::new(),
+        }
+    };
+    let remap = || RemapConfig {
+        source: Some(
+            r#".message = get_enrichment_table_record!("reload_table", { "id": "1" }).value"#
+                .to_string(),
+        ),
+        ..Default::default()
+    };
+
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_enrichment_table("reload_table", table("before.csv", "before"));
+    config.add_source("in1", source1);
+    config.add_transform("enrich", &["in1"], remap());
+    config.add_sink("out1", &["enrich"], sink1);
+
+    let (mut topology, _) = start_topology(config.build().unwrap(), false).await;
+
+    // Wait for the first event to go through, so that it's enriched before the reload.
+    let mut out1 = out1.flat_map(into_message_stream).boxed();
+    in1.send_event(Event::Log(LogEvent::from("first")))
+        .await
+        .unwrap();
+    assert_eq!(Some("before".to_string()), out1.next().await);
+
+    // Now, point the table to another file. The table is reloaded in place, so the transform using
+    // it keeps running, along with the rest of the topology, and picks up the new data:
+    let mut config = Config::builder();
+    config.add_enrichment_table("reload_table", table("after.csv", "after"));
+    config.add_source("in1", basic_source().1);
+    config.add_transform("enrich", &["in1"], remap());
+    config.add_sink("out1", &["enrich"], basic_sink(10).1);
+
+    let new_config = config.build().unwrap();
+    let diff = ConfigDiff::new(topology.config(), &new_config);
+    assert!(diff
+        .enrichment_tables
+        .is_changed(&ComponentKey::from("reload_table")));
+    assert!(!diff.transforms.contains(&ComponentKey::from("enrich")));
+
+    assert!(topology
+        .reload_config_and_respawn(new_config, Default::default())
+        .await
+        .unwrap());
+
+    in1.send_event(Event::Log(LogEvent::from("second")))
+        .await
+        .unwrap();
+
+    drop(in1);
+    topology.stop().await;
+
+    assert_eq!(vec!["after"], out1.collect::<Vec<_>>().await);
+}
+
 #[tokio::test]
 async fn topology_swap_transform_is_atomic() {
     trace_init();
@@ -873,6 +1194,10 @@ async fn topology_disk_buffer_flushes_on_idle() {
     );
     sink1_outer.buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: std::num::NonZeroU64::new(268435488).unwrap(),
+        max_events: None,
+        max_age_secs: None,
+        encryption: None,
+        shared_group: None,
         when_full: WhenFull::DropNewest,
     });
     config.add_sink_outer("out1", sink1_outer);
@@ -907,6 +1232,140 @@ async fn topology_disk_buffer_flushes_on_idle() {
     assert!(rest.is_empty());
 }
 
+#[tokio::test]
+async fn topology_shared_disk_buffer() {
+    trace_init();
+
+    let tmpdir = tempfile::tempdir().expect("no tmpdir");
+    let (mut in1, source1) = basic_source();
+    let (mut out1, sink1) = basic_sink(10);
+    let (mut out2, sink2) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.set_data_dir(tmpdir.path());
+    config.add_source("in1", source1);
+    for (key, sink) in [("out1", sink1), ("out2", sink2)] {
+        let mut sink_outer = SinkOuter::new(vec![String::from("in1")], sink);
+        sink_outer.buffer = BufferConfig::Single(BufferType::DiskV2 {
+            max_size: std::num::NonZeroU64::new(268435488).unwrap(),
+            max_events: None,
+            max_age_secs: None,
+            encryption: None,
+            shared_group: Some(String::from("fanout")),
+            when_full: WhenFull::Block,
+        });
+        config.add_sink_outer(key, sink_outer);
+    }
+
+    let (topology, _) = start_topology(config.build().unwrap(), false).await;
+
+    in1.send_event(Event::Log(LogEvent::from("foo")))
+        .await
+        .unwrap();
+
+    // Both sinks read the event from the shared buffer, even though it's only written to it once.
+    for out in [&mut out1, &mut out2] {
+        let res = tokio::time::timeout(Duration::from_secs(1), out.next())
+            .await
+            .expect("timeout")
+            .map(|array| into_message(array.into_events().next().unwrap()))
+            .expect("no output");
+        assert_eq!("foo", res);
+    }
+
+    drop(in1);
+    topology.stop().await;
+
+    assert!(out1.collect::<Vec<_>>().await.is_empty());
+    assert!(out2.collect::<Vec<_>>().await.is_empty());
+}
+
+#[tokio::test]
+async fn topology_shared_disk_buffer_remove_connected_sink() {
+    trace_init();
+
+    let tmpdir = tempfile::tempdir().expect("no tmpdir");
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+    let (out2, sink2) = basic_sink(10);
+
+    let config = |sinks: Vec<(&str, BasicSinkConfig)>| {
+        let mut config = Config::builder();
+        config.set_data_dir(tmpdir.path());
+        config.add_source("in1", source1.clone());
+        for (key, sink) in sinks {
+            let mut sink_outer = SinkOuter::new(vec![String::from("in1")], sink);
+            sink_outer.buffer = BufferConfig::Single(BufferType::DiskV2 {
+                max_size: std::num::NonZeroU64::new(268435488).unwrap(),
+                max_events: None,
+                max_age_secs: None,
+                encryption: None,
+                shared_group: Some(String::from("fanout")),
+                when_full: WhenFull::Block,
+            });
+            config.add_sink_outer(key, sink_outer);
+        }
+        config.build().unwrap()
+    };
+
+    let (mut topology, _) = start_topology(
+        config(vec![("out1", sink1), ("out2", sink2.clone())]),
+        false,
+    )
+    .await;
+
+    let h_out1 = tokio::spawn(out1.collect::<Vec<_>>());
+    let h_out2 = tokio::spawn(out2.flat_map(into_message_stream).collect::<Vec<_>>());
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let sent = Arc::new(AtomicUsize::new(0));
+    let pump_handle = {
+        let stop = Arc::clone(&stop);
+        let sent = Arc::clone(&sent);
+        tokio::spawn(async move {
+            let mut count = 0;
+            while !stop.load(Ordering::Relaxed) {
+                in1.send_event(Event::Log(LogEvent::from(count.to_string())))
+                    .await
+                    .unwrap();
+                count += 1;
+                sent.store(count, Ordering::Relaxed);
+            }
+            count
+        })
+    };
+
+    let sent = &sent;
+    let wait_for_events = || async move {
+        let target = sent.load(Ordering::Relaxed) + 50;
+        while sent.load(Ordering::Relaxed) < target {
+            yield_now().await;
+        }
+    };
+
+    // Remove `out1`, which both sinks were connected through, while events are being sent, so
+    // `out2` has to take over the connection without any of them getting lost in between:
+    wait_for_events().await;
+    assert!(topology
+        .reload_config_and_respawn(config(vec![("out2", sink2)]), Default::default())
+        .await
+        .unwrap());
+    wait_for_events().await;
+
+    stop.store(true, Ordering::Relaxed);
+    let count = pump_handle.await.unwrap();
+    topology.stop().await;
+    h_out1.await.unwrap();
+
+    // The rebuilt buffer may hand `out2` events again that it had already read, if their
+    // acknowledgement wasn't persisted yet, so we only check that none of them were lost.
+    let mut seen = HashSet::new();
+    let mut received = h_out2.await.unwrap();
+    received.retain(|message| seen.insert(message.clone()));
+    let expected = (0..count).map(|i| i.to_string()).collect::<Vec<_>>();
+    assert_eq!(expected, received);
+}
+
 #[tokio::test]
 async fn topology_transform_error_definition() {
     trace_init();
@@ -187,6 +187,10 @@ async fn topology_disk_buffer_conflict() {
     let sink_key = ComponentKey::from("out");
     old_config.sinks[&sink_key].buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: NonZeroU64::new(268435488).unwrap(),
+        max_events: None,
+        max_age_secs: None,
+        encryption: None,
+        shared_group: None,
         when_full: WhenFull::Block,
     });
 
@@ -194,6 +198,10 @@ async fn topology_disk_buffer_conflict() {
     new_config.sinks[&sink_key].inner = prom_exporter_sink(address_1, 1).into();
     new_config.sinks[&sink_key].buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: NonZeroU64::new(268435488).unwrap(),
+        max_events: None,
+        max_age_secs: None,
+        encryption: None,
+        shared_group: None,
         when_full: WhenFull::Block,
     });
 
@@ -30,6 +30,11 @@ pub struct Opts {
     #[arg(short, long)]
     pub deny_warnings: bool,
 
+    /// Print the configuration, as JSON, after resolving includes and applying overlays.
+    /// Environment variables and secrets are not interpolated.
+    #[arg(long)]
+    pub print_merged: bool,
+
     /// Vector config files in TOML format to validate.
     #[arg(
         id = "config-toml",
@@ -148,6 +153,19 @@ pub fn validate_config(opts: &Opts, fmt: &mut Formatter) -> Option<Config> {
         .map_err(&mut report_error)
         .ok()?;
 
+    if opts.print_merged {
+        let source = config::load_source_from_paths(&paths)
+            .map_err(&mut report_error)
+            .ok()?;
+        let json = serde_json::to_string_pretty(&source).expect("config should serialize");
+        fmt.title("Merged configuration");
+        #[allow(clippy::print_stdout)]
+        {
+            println!("{}", json);
+        }
+        fmt.space();
+    }
+
     // Build
     let (config, warnings) = builder
         .build_with_warnings()
@@ -328,6 +328,68 @@ fn configuration_path_recomputed() {
     assert_eq!(output.stdout.as_slice(), "42\n".as_bytes());
 }
 
+fn reload_components(address: SocketAddr, mutations: bool) -> std::process::Output {
+    let config = format!(
+        r#"{}
+    [api]
+        enabled = true
+        address = "{}"
+        mutations = {}
+"#,
+        STDIO_CONFIG, address, mutations
+    );
+    let mut vector = vector_with(create_file(&config), next_addr(), true)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // Give vector time to start.
+    sleep(STARTUP_TIME);
+
+    let reload = Command::cargo_bin("vector")
+        .unwrap()
+        .args(["config", "reload", "out_console", "--url"])
+        .arg(format!("http://{}/graphql", address))
+        .output()
+        .unwrap();
+
+    // Give vector time to reload.
+    sleep(RELOAD_TIME);
+
+    // Message to assert, sent to the console source and picked up by the console sink, whether or
+    // not it was rebuilt.
+    vector
+        .stdin
+        .as_mut()
+        .unwrap()
+        .write_all("42".as_bytes())
+        .unwrap();
+
+    let output = vector.wait_with_output().unwrap();
+    assert!(output.status.success(), "Vector didn't exit successfully.");
+    assert_eq!(output.stdout.as_slice(), "42\n".as_bytes());
+
+    reload
+}
+
+#[test]
+fn reload_components_through_cli() {
+    let reload = reload_components(next_addr(), true);
+    assert!(
+        reload.status.success(),
+        "{}",
+        String::from_utf8_lossy(&reload.stderr)
+    );
+}
+
+#[test]
+fn reload_components_requires_mutations() {
+    let reload = reload_components(next_addr(), false);
+    assert!(!reload.status.success());
+    assert!(String::from_utf8_lossy(&reload.stderr).contains("api.mutations"));
+}
+
 #[test]
 fn remove_unix_socket_stream() {
     let dir = create_directory();
@@ -58,6 +58,17 @@ api: {
 				endpoint of the address set using the `bind` parameter.
 				"""
 		}
+		mutations: {
+			common:   false
+			required: false
+			type: bool: default: false
+			description: """
+				Whether GraphQL mutations, such as reloading components with
+				`vector config reload`, are allowed. The API has no authentication,
+				so anyone who can reach its address can use them; only enable
+				mutations when the API is bound to a trusted interface.
+				"""
+		}
 	}
 
 	endpoints: {
@@ -192,17 +192,195 @@ cli: {
 	options: _core_options
 
 	commands: {
+		"buffer inspect": {
+			description: """
+				Report the state of a sink's disk buffer while Vector is stopped: the data files the
+				reader and writer are on, the last record read and the next record to be written, and
+				the number of records, events, and bytes in each data file. Every record is read and
+				its checksum validated, and any data file that can't be read in full is reported. Exits
+				with a non-zero status if any errors were found.
+				"""
+
+			example: "vector buffer inspect --data-dir /var/lib/vector my_sink"
+
+			options: {
+				"data-dir": {
+					description: "The data directory of the Vector instance that owns the buffer"
+					type:        "string"
+					default:     "/var/lib/vector/"
+				}
+				"format": {
+					description: "The format to output the report in"
+					default:     "text"
+					enum: {
+						text: "Output the report as text"
+						json: "Output the report as JSON"
+					}
+				}
+			}
+
+			args: {
+				component_id: {
+					description: "The ID of the sink whose buffer to inspect"
+					type:        "string"
+				}
+			}
+		}
+
+		"buffer export": {
+			description: """
+				Export the events in a sink's disk buffer as native JSON, one event per line, while
+				Vector is stopped. Only events that haven't been acknowledged by the sink are exported,
+				unless `--include-acknowledged` is set. Records that fail validation or can't be
+				decoded are skipped and reported, and cause a non-zero exit status.
+				"""
+
+			example: "vector buffer export --data-dir /var/lib/vector --output events.json my_sink"
+
+			flags: {
+				"include-acknowledged": {
+					description: "Also export events that were already acknowledged by the sink, but are still on disk"
+				}
+			}
+
+			options: {
+				"data-dir": {
+					description: "The data directory of the Vector instance that owns the buffer"
+					type:        "string"
+					default:     "/var/lib/vector/"
+				}
+				"output": {
+					_short:      "o"
+					description: "The file to write the events to. Defaults to stdout"
+					type:        "string"
+				}
+				"encryption-key": {
+					description: "The key used to encrypt the buffer, as 64 hex characters"
+					type:        "string"
+					env_var:     "VECTOR_BUFFER_ENCRYPTION_KEY"
+				}
+				"previous-encryption-key": {
+					description: "A previous key used to encrypt the buffer, as 64 hex characters. Can be repeated"
+					type:        "string"
+				}
+			}
+
+			args: {
+				component_id: {
+					description: "The ID of the sink whose buffer to export"
+					type:        "string"
+				}
+			}
+		}
+
+		"config diff": {
+			description: """
+				Show how reloading from one configuration to another would affect a running topology:
+				which sources, transforms, sinks, and enrichment tables would be added, removed, or
+				rebuilt, whether sink buffers would be reused, and which connections would be added,
+				removed, or interrupted while the reload takes place.
+
+				With `--dry-run`, the configuration is compared against the one running in a Vector
+				instance, using its API, without reloading it.
+
+				Secrets are resolved before comparing two local configurations, so that rotated
+				secrets show up as changes. With `--dry-run`, secrets aren't sent to the running
+				instance, and components referencing them are reported as unknown instead.
+				"""
+
+			example: "vector config diff /etc/vector/vector.yaml ./vector.yaml"
+
+			flags: {
+				"json": {
+					description: "Output the report as JSON"
+				}
+				"dry-run": {
+					description: "Compare the configuration against the one running in a Vector instance, using its API"
+				}
+			}
+
+			options: {
+				"url": {
+					_short:      "u"
+					description: "The URL for the GraphQL endpoint of the running Vector instance used by `--dry-run`"
+					type:        "string"
+				}
+			}
+
+			args: {
+				paths: {
+					description: """
+						The old and the new configuration, each either a file or a directory of files.
+						With `--dry-run`, only the new configuration is given.
+						"""
+					type: "list"
+				}
+			}
+		}
+
+		"config reload": {
+			description: """
+				Ask a running Vector instance to reload its configuration from disk, rebuilding the
+				given components even if their configuration didn't change. Components whose
+				configuration is unchanged, and which aren't listed, keep running.
+
+				Requires the `api.mutations` option to be enabled on the running instance.
+				"""
+
+			example: "vector config reload my_sink"
+
+			options: {
+				"url": {
+					_short:      "u"
+					description: "The URL for the GraphQL endpoint of the running Vector instance"
+					type:        "string"
+				}
+			}
+
+			args: {
+				components: {
+					description: "IDs of the components to rebuild (comma-separated)"
+					type:        "list"
+				}
+			}
+		}
+
 		"graph": {
 			description: """
-				Generate a visual representation of topologies. The output is in the [DOT format](\(urls.dot_format)),
-				which can be rendered using [GraphViz](\(urls.graphviz)).
+				Generate a visual representation of topologies. By default the output is in the
+				[DOT format](\(urls.dot_format)), which can be rendered using [GraphViz](\(urls.graphviz)).
+				Mermaid flowcharts and JSON are also supported.
 
 				You can also visualize the output online at [webgraphviz.com](http://www.webgraphviz.com/).
+
+				When the URL of a running Vector instance's API is provided, edges are annotated with their
+				current throughput and components which reported errors while sampling are highlighted.
 				"""
 
 			example: "vector graph --config /etc/vector/vector.yaml | dot -Tsvg > graph.svg"
 
-			options: _core_options
+			options: _core_options & {
+				"format": {
+					description: "The format to render the topology in"
+					default:     "dot"
+					enum: {
+						dot:     "Output the topology in the DOT format"
+						mermaid: "Output the topology as a Mermaid flowchart"
+						json:    "Output the topology as a JSON object of nodes and edges"
+					}
+				}
+				"url": {
+					_short:      "u"
+					description: "The URL for the GraphQL endpoint of a running Vector instance to sample live metrics from"
+					type:        "string"
+				}
+				"interval": {
+					_short:      "i"
+					description: "How long to sample live metrics for (in milliseconds). Only used together with `url`"
+					type:        "integer"
+					default:     1000
+				}
+			}
 		}
 		"generate": {
 			description: "Generate a Vector configuration containing a list of components"
@@ -419,6 +597,12 @@ cli: {
 					_short:      "d"
 					description: "Fail validation on warnings"
 				}
+				"print-merged": {
+					description: """
+						Print the configuration, as JSON, after resolving `include`
+						directives and applying overlays
+						"""
+				}
 			}
 
 			options: {
@@ -1,60 +1,222 @@
 package metadata
 
-base: components: sources: configuration: proxy: {
-	description: """
-		Proxy configuration.
-
-		Configure to proxy traffic through an HTTP(S) proxy when making external requests.
-
-		Similar to common proxy configuration convention, you can set different proxies
-		to use based on the type of traffic being proxied, as well as set specific hosts that
-		should not be proxied.
-		"""
-	required: false
-	type: object: options: {
-		enabled: {
-			description: "Enables proxying support."
-			required:    false
-			type: bool: default: true
-		}
-		http: {
-			description: """
-				Proxy endpoint to use when proxying HTTP traffic.
-
-				Must be a valid URI string.
-				"""
-			required: false
-			type: string: examples: ["http://foo.bar:3128"]
-		}
-		https: {
-			description: """
-				Proxy endpoint to use when proxying HTTPS traffic.
-
-				Must be a valid URI string.
-				"""
-			required: false
-			type: string: examples: ["http://foo.bar:3128"]
+base: components: sources: configuration: {
+	buffer: {
+		description: """
+			Configures a disk buffer for the events emitted by the source.
+
+			Events are written to the buffer as soon as the source has decoded them, and acknowledged
+			to the client once they're persisted in it, rather than once they've been processed by the
+			rest of the topology. Only supported by some push-based sources.
+			"""
+		required: false
+		type: object: options: {
+			encryption: {
+				description: """
+					Encrypts the data written to disk.
+
+					When not set, data is written to disk unencrypted.
+					"""
+				relevant_when: "type = \"disk\""
+				required:      false
+				type: object: options: {
+					key: {
+						description: """
+							The key used to encrypt the buffer, as 64 hexadecimal characters (32 bytes).
+
+							This is typically provided as a secret, such as `SECRET[backend.buffer_key]`.
+							"""
+						required: true
+						type: string: examples: ["SECRET[backend.buffer_key]"]
+					}
+					previous_keys: {
+						description: """
+							Keys that were previously used to encrypt the buffer.
+
+							These keys are only used to decrypt records that were written before the key was rotated,
+							and can be removed once those records have been read out of the buffer.
+							"""
+						required: false
+						type: array: items: type: string: {}
+					}
+				}
+			}
+			max_age_secs: {
+				description: """
+					The maximum age of events in the buffer.
+
+					Events that have been in the buffer for longer than this are expired, and dropped
+					instead of being sent to the sink. Expiration happens a whole data file at a time, so
+					events may be expired up to a tenth of this duration early.
+					"""
+				relevant_when: "type = \"disk\""
+				required:      false
+				type: uint: unit: "seconds"
+			}
+			max_events: {
+				description: """
+					The maximum number of events allowed in the buffer.
+
+					For disk buffers, this is optional, and when set, the buffer is considered full once it
+					holds this many events, even if it has not yet reached `max_size`.
+					"""
+				required: false
+				type: uint: default: 500
+			}
+			max_size: {
+				description: """
+					The maximum size of the buffer on disk.
+
+					Must be at least ~256 megabytes (268435488 bytes).
+					"""
+				relevant_when: "type = \"disk\""
+				required:      true
+				type: uint: unit: "bytes"
+			}
+			shared_group: {
+				description: """
+					The name of a disk buffer shared with other sinks.
+
+					Sinks with the same `shared_group` read from a single disk buffer, so each event is only written to
+					disk once, rather than once per sink. Events are only removed from the buffer once every sink
+					sharing it has acknowledged them.
+
+					Sinks sharing a buffer must have the same inputs and buffer configuration, and the shared buffer
+					must be the only stage of their buffers.
+					"""
+				relevant_when: "type = \"disk\""
+				required:      false
+				type: string: examples: ["durable_fanout"]
+			}
+			type: {
+				description: "The type of buffer to use."
+				required:    false
+				type: string: {
+					default: "memory"
+					enum: {
+						disk: """
+														Events are buffered on disk.
+
+														This is less performant, but more durable. Data that has been synchronized to disk will not
+														be lost if Vector is restarted forcefully or crashes.
+
+														Data is synchronized to disk every 500ms.
+														"""
+						memory: """
+														Events are buffered in memory.
+
+														This is more performant, but less durable. Data will be lost if Vector is restarted
+														forcefully or crashes.
+														"""
+					}
+				}
+			}
+			when_full: {
+				description: "Event handling behavior when a buffer is full."
+				required:    false
+				type: string: {
+					default: "block"
+					enum: {
+						block: """
+														Wait for free space in the buffer.
+
+														This applies backpressure up the topology, signalling that sources should slow down
+														the acceptance/consumption of events. This means that while no data is lost, data will pile
+														up at the edge.
+														"""
+						drop_newest: """
+														Drops the event instead of waiting for free space in buffer.
+
+														The event will be intentionally dropped. This mode is typically used when performance is the
+														highest priority, and it is preferable to temporarily lose events rather than cause a
+														slowdown in the acceptance/consumption of events.
+														"""
+						drop_oldest: """
+														Drops the oldest events in the buffer to make room for the new event.
+
+														The oldest events will be intentionally dropped. This mode is typically used when the most
+														recent data is the most valuable, and it is preferable to lose older events rather than
+														cause a slowdown in the acceptance/consumption of events.
+
+														For disk buffers, events are dropped a whole data file at a time, starting with the oldest
+														data file that the reader is not currently reading from. If there is no such data file, the
+														newest event is dropped instead.
+														"""
+						overflow: """
+														Overflows to the next stage in the buffer topology.
+
+														If the current buffer stage is full, attempt to send this event to the next buffer stage.
+														That stage may also be configured overflow, and so on, but ultimately the last stage in a
+														buffer topology must use one of the other handling behaviors. This means that next stage may
+														potentially be able to buffer the event, but it may also block or drop the event.
+
+														Once an event has overflowed, subsequent events are also sent to the next stage until it
+														has been drained, so that events are still read out of the buffer in the order they were
+														written.
+
+														This mode can only be used when two or more buffer stages are configured.
+														"""
+					}
+				}
+			}
 		}
-		no_proxy: {
-			description: """
-				A list of hosts to avoid proxying.
-
-				Multiple patterns are allowed:
-
-				| Pattern             | Example match                                                               |
-				| ------------------- | --------------------------------------------------------------------------- |
-				| Domain names        | `example.com` matches requests to `example.com`                     |
-				| Wildcard domains    | `.example.com` matches requests to `example.com` and its subdomains |
-				| IP addresses        | `127.0.0.1` matches requests to `127.0.0.1`                         |
-				| [CIDR][cidr] blocks | `192.168.0.0/16` matches requests to any IP addresses in this range     |
-				| Splat               | `*` matches all hosts                                                   |
-
-				[cidr]: https://en.wikipedia.org/wiki/Classless_Inter-Domain_Routing
-				"""
-			required: false
-			type: array: {
-				default: []
-				items: type: string: examples: ["localhost", ".foo.bar", "*"]
+	}
+	proxy: {
+		description: """
+			Proxy configuration.
+
+			Configure to proxy traffic through an HTTP(S) proxy when making external requests.
+
+			Similar to common proxy configuration convention, you can set different proxies
+			to use based on the type of traffic being proxied, as well as set specific hosts that
+			should not be proxied.
+			"""
+		required: false
+		type: object: options: {
+			enabled: {
+				description: "Enables proxying support."
+				required:    false
+				type: bool: default: true
+			}
+			http: {
+				description: """
+					Proxy endpoint to use when proxying HTTP traffic.
+
+					Must be a valid URI string.
+					"""
+				required: false
+				type: string: examples: ["http://foo.bar:3128"]
+			}
+			https: {
+				description: """
+					Proxy endpoint to use when proxying HTTPS traffic.
+
+					Must be a valid URI string.
+					"""
+				required: false
+				type: string: examples: ["http://foo.bar:3128"]
+			}
+			no_proxy: {
+				description: """
+					A list of hosts to avoid proxying.
+
+					Multiple patterns are allowed:
+
+					| Pattern             | Example match                                                               |
+					| ------------------- | --------------------------------------------------------------------------- |
+					| Domain names        | `example.com` matches requests to `example.com`                     |
+					| Wildcard domains    | `.example.com` matches requests to `example.com` and its subdomains |
+					| IP addresses        | `127.0.0.1` matches requests to `127.0.0.1`                         |
+					| [CIDR][cidr] blocks | `192.168.0.0/16` matches requests to any IP addresses in this range     |
+					| Splat               | `*` matches all hosts                                                   |
+
+					[cidr]: https://en.wikipedia.org/wiki/Classless_Inter-Domain_Routing
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["localhost", ".foo.bar", "*"]
+				}
 			}
 		}
 	}
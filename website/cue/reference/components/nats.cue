@@ -47,5 +47,15 @@ components: _nats: {
 				The `nats` source/sink uses [`nats.rs`](\(urls.nats_rs)) under the hood.
 				"""
 		}
+		jetstream: {
+			title: "JetStream"
+			body: """
+				Core NATS delivers messages at most once, so messages published while Vector is not
+				subscribed are lost. With `jetstream` configured, the source instead consumes a
+				JetStream stream through a durable consumer, acknowledging each message once its
+				events are delivered, and the sink waits for the stream to store each message before
+				its events are marked as delivered.
+				"""
+		}
 	}
 }
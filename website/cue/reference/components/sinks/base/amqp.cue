@@ -323,9 +323,54 @@ base: components: sinks: amqp: configuration: {
 				type: string: {}
 			}
 			expiration_ms: {
-				description: "Expiration for AMQP messages (in milliseconds)"
-				required:    false
-				type: uint: {}
+				description: """
+					Expiration for AMQP messages (in milliseconds)
+
+					This can be either a number, or a template that renders to one.
+					"""
+				required: false
+				type: {
+					string: {
+						examples: ["{{ ttl_ms }}"]
+						syntax: "template"
+					}
+					uint: examples: [60000]
+				}
+			}
+			headers: {
+				description: """
+					Headers for the AMQP messages.
+
+					The values are sent as strings, and can be templated.
+					"""
+				required: false
+				type: object: {
+					examples: [{
+						source: "vector"
+						tenant: "{{ tenant }}"
+					}]
+					options: "*": {
+						description: "An AMQP message header."
+						required:    true
+						type: string: syntax: "template"
+					}
+				}
+			}
+			priority: {
+				description: """
+					Priority for the AMQP messages, between 0 and 255.
+
+					This can be either a number, or a template that renders to one. The queues must be
+					declared with a maximum priority for brokers to take it into account.
+					"""
+				required: false
+				type: {
+					string: {
+						examples: ["{{ priority }}"]
+						syntax: "template"
+					}
+					uint: examples: [5]
+				}
 			}
 		}
 	}
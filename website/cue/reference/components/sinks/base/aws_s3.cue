@@ -586,7 +586,8 @@ base: components: sinks: aws_s3: configuration: {
 		description: """
 			The filename extension to use in the object key.
 
-			This overrides setting the extension based on the configured `compression`.
+			This overrides setting the extension based on the configured `compression`, or on the
+			configured `batch_encoding`.
 			"""
 		required: false
 		type: string: examples: [
@@ -191,6 +191,9 @@ base: components: sinks: clickhouse: configuration: {
 			Data format.
 
 			The format to parse input data.
+
+			With the binary formats, fields are mapped to the columns of the same name, and events with
+			fields that can't be converted to the type of their column are rejected.
 			"""
 		required: false
 		type: string: {
@@ -199,6 +202,18 @@ base: components: sinks: clickhouse: configuration: {
 				json_as_object: "JSONAsObject."
 				json_as_string: "JSONAsString."
 				json_each_row:  "JSONEachRow."
+				native: """
+					Native.
+
+					Batches are encoded column by column from the schema of the table, which is fetched when
+					the sink starts, so the table can't be templated.
+					"""
+				row_binary: """
+					RowBinary.
+
+					Rows are encoded from the schema of the table, which is fetched when the sink starts, so
+					the table can't be templated.
+					"""
 			}
 		}
 	}
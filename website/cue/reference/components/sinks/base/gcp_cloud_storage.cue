@@ -459,7 +459,8 @@ base: components: sinks: gcp_cloud_storage: configuration: {
 		description: """
 			The filename extension to use in the object key.
 
-			If not specified, the extension is determined by the compression scheme used.
+			If not specified, the extension is determined by the compression scheme used, or by the
+			batch encoding used.
 			"""
 		required: false
 		type: string: {}
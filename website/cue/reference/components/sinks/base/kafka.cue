@@ -338,6 +338,26 @@ base: components: sinks: kafka: configuration: {
 			}
 		}
 	}
+	headers: {
+		description: """
+			Headers to add to the Kafka records.
+
+			The values are rendered from templates, and are written after the headers from
+			`headers_key`. Headers whose template fails to render are not written.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				source: "vector"
+				tenant: "{{ tenant }}"
+			}]
+			options: "*": {
+				description: "A Kafka record header."
+				required:    true
+				type: string: syntax: "template"
+			}
+		}
+	}
 	headers_key: {
 		description: """
 			The log field name to use for the Kafka headers.
@@ -357,6 +377,16 @@ base: components: sinks: kafka: configuration: {
 		required: false
 		type: string: {}
 	}
+	idempotence: {
+		description: """
+			Enables the idempotent producer.
+
+			Records are written exactly once and in order per partition, even when the producer retries
+			sending them. Always enabled when `transaction` is set.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	key_field: {
 		description: """
 			The log field name or tag key to use for the topic key.
@@ -364,8 +394,7 @@ base: components: sinks: kafka: configuration: {
 			If the field does not exist in the log or in the tags, a blank value is used. If
 			unspecified, the key is not sent.
 
-			Kafka uses a hash of the key to choose the partition or uses round-robin if the record has
-			no key.
+			Unless `partition` is set, the `partitioner` uses a hash of the key to choose the partition.
 			"""
 		required: false
 		type: string: examples: ["user_id", ".my_topic", "%my_topic"]
@@ -401,6 +430,45 @@ base: components: sinks: kafka: configuration: {
 			unit: "milliseconds"
 		}
 	}
+	partition: {
+		description: """
+			The partition to write events to.
+
+			This can be either a partition number, or a template that renders to one, such as
+			`{{ partition }}` to use the value of a field. If unset, or if the template does not render
+			to a valid partition number, the `partitioner` chooses the partition.
+			"""
+		required: false
+		type: {
+			int: examples: [0]
+			string: {
+				examples: ["{{ partition }}"]
+				syntax: "template"
+			}
+		}
+	}
+	partitioner: {
+		description: """
+			The partitioner choosing the partition of records, when `partition` is not set.
+
+			If unset, the `consistent_random` partitioner of librdkafka is used, which hashes keys with
+			CRC32. Records with the same key then land on different partitions than those written by the
+			Java client.
+			"""
+		required: false
+		type: string: enum: {
+			murmur2: """
+				Hashes keys with murmur2, like the Java client, and writes each record without a key to a
+				random partition.
+				"""
+			random: "Writes each record to a random partition, regardless of its key."
+			sticky: """
+				Hashes keys with murmur2, like the Java client, and writes records without a key to the
+				same partition until `sticky.partitioning.linger.ms` elapses, like the default partitioner of
+				the Java client.
+				"""
+		}
+	}
 	sasl: {
 		description: "Configuration for SASL authentication when interacting with Kafka."
 		required:    false
@@ -551,4 +619,46 @@ base: components: sinks: kafka: configuration: {
 			syntax: "template"
 		}
 	}
+	transaction: {
+		description: """
+			Kafka transaction configuration.
+
+			When set, the events of each batch are written in a single Kafka transaction, and are only
+			acknowledged once the transaction is committed. The offsets of the messages these events were
+			consumed from, by `kafka` sources with `exactly_once` enabled, are committed in the same
+			transaction, so that a Kafka-to-Kafka pipeline does not duplicate records when it is restarted.
+
+			The `batch` settings determine how many events are written in each transaction.
+
+			The sink stops if the producer fails fatally, for example when it is fenced off by another
+			producer with the same transactional ID.
+			"""
+		required: false
+		type: object: options: {
+			timeout_ms: {
+				description: """
+					Maximum time, in milliseconds, a transaction can take to complete.
+
+					The broker aborts transactions that stay open for longer. This is also the timeout of the
+					operations initializing, committing, and aborting transactions.
+					"""
+				required: false
+				type: uint: {
+					default: 60000
+					examples: [60000]
+					unit: "milliseconds"
+				}
+			}
+			transactional_id: {
+				description: """
+					The transactional ID of the producer.
+
+					It must be unique to this sink and stable across restarts, so that transactions left open by
+					a previous run are aborted, and so that producers from previous runs are fenced off.
+					"""
+				required: true
+				type: string: examples: ["vector-kafka-sink-1"]
+			}
+		}
+	}
 }
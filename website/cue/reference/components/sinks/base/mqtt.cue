@@ -28,13 +28,24 @@ base: components: sinks: mqtt: configuration: {
 		}
 	}
 	clean_session: {
-		description: "If set to true, the MQTT session is cleaned on login."
-		required:    false
+		description: """
+			If set to true, the MQTT session is cleaned on login.
+
+			Otherwise the broker keeps the session, including subscriptions and messages queued
+			while disconnected, across reconnects of the same client ID.
+			"""
+		required: false
 		type: bool: default: false
 	}
 	client_id: {
-		description: "MQTT client ID."
-		required:    false
+		description: """
+			MQTT client ID.
+
+			If not set, a random client ID is generated on every start. Persistent sessions can only
+			be resumed with a fixed client ID, so the `mqtt` source requires one unless
+			`clean_session` is enabled.
+			"""
+		required: false
 		type: string: {}
 	}
 	encoding: {
@@ -313,6 +324,74 @@ base: components: sinks: mqtt: configuration: {
 		required:    false
 		type: uint: default: 1883
 	}
+	properties: {
+		description: """
+			MQTT 5 properties to publish the messages with.
+
+			Templated properties that fail to render are left unset, rather than dropping the event.
+			"""
+		required: false
+		type: object: options: {
+			content_type: {
+				description: "The content type of the messages."
+				required:    false
+				type: string: {
+					examples: ["application/json"]
+					syntax: "template"
+				}
+			}
+			message_expiry_interval_secs: {
+				description: """
+					The time, in seconds, after which the broker discards the messages if they are not yet
+					delivered.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			response_topic: {
+				description: "The topic that receivers of the messages should send their responses to."
+				required:    false
+				type: string: {
+					examples: ["responses/{{ service }}"]
+					syntax: "template"
+				}
+			}
+			user_properties: {
+				description: """
+					User properties to add to the messages.
+
+					Both the keys and the values are forwarded as is, but the values can be templated.
+					"""
+				required: false
+				type: object: {
+					examples: [{
+						service: "{{ service }}"
+						source:  "vector"
+					}]
+					options: "*": {
+						description: "A user property to add to the messages."
+						required:    true
+						type: string: syntax: "template"
+					}
+				}
+			}
+		}
+	}
+	protocol_version: {
+		description: "Version of the MQTT protocol to use."
+		required:    false
+		type: string: {
+			default: "v3.1.1"
+			enum: {
+				"v3.1.1": "MQTT 3.1.1."
+				v5: """
+					MQTT 5.0.
+
+					This is required to publish messages with `properties`.
+					"""
+			}
+		}
+	}
 	quality_of_service: {
 		description: "Supported Quality of Service types for MQTT."
 		required:    false
@@ -325,6 +404,11 @@ base: components: sinks: mqtt: configuration: {
 			}
 		}
 	}
+	retain: {
+		description: "Whether the broker should retain the messages, as the last message of their topic."
+		required:    false
+		type: bool: default: false
+	}
 	tls: {
 		description: "Configures the TLS options for incoming/outgoing connections."
 		required:    false
@@ -383,6 +383,19 @@ base: components: sinks: nats: configuration: {
 			}
 		}
 	}
+	jetstream: {
+		description: """
+			Whether to publish messages to [JetStream][jetstream].
+
+			When enabled, the sink waits for the server to acknowledge each message, so that events are
+			only marked as delivered once the message is stored by the stream capturing its subject.
+			Publishing to a subject that no stream captures then fails.
+
+			[jetstream]: https://docs.nats.io/nats-concepts/jetstream
+			"""
+		required: false
+		type: bool: default: false
+	}
 	request: {
 		description: """
 			Middleware settings for outbound requests.
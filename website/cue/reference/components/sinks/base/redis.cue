@@ -77,6 +77,12 @@ base: components: sinks: redis: configuration: {
 
 					This is the default.
 					"""
+				stream: """
+					The Redis `stream` type.
+
+					Each message is appended to the stream as a new entry, which consumers can read at their own
+					pace and acknowledge.
+					"""
 			}
 		}
 	}
@@ -562,4 +568,36 @@ base: components: sinks: redis: configuration: {
 			}
 		}
 	}
+	stream_option: {
+		description: "Stream-specific options."
+		required:    false
+		type: object: options: {
+			approximate: {
+				description: """
+					Whether to trim the stream to roughly `maxlen` entries, rather than exactly.
+
+					Approximate trimming is much more efficient, and the stream is never trimmed below `maxlen`.
+					"""
+				required: false
+				type: bool: default: true
+			}
+			field: {
+				description: "The field of the stream entries to store the encoded message in."
+				required:    false
+				type: string: {
+					default: "message"
+					examples: ["payload"]
+				}
+			}
+			maxlen: {
+				description: """
+					The maximum number of entries to keep in the stream.
+
+					Older entries are trimmed as new ones are added. By default, the stream is not trimmed.
+					"""
+				required: false
+				type: uint: examples: [100000]
+			}
+		}
+	}
 }
@@ -80,4 +80,36 @@ components: sinks: clickhouse: {
 		metrics: null
 		traces:  false
 	}
+
+	how_it_works: {
+		binary_formats: {
+			title: "Binary formats"
+			body: """
+				With the `row_binary` and `native` formats, events are encoded in the binary formats of
+				ClickHouse rather than as JSON, which takes much less work for the server to parse. The
+				columns of the table, and their types, are fetched from `system.columns` when the sink
+				starts, so neither `database` nor `table` can be templated.
+
+				Each column takes the value of the field with the same name. Columns with dots in their
+				name, such as those of `Nested` structures, fall back to the nested field of that path.
+				Values are converted to the type of their column where they can be: strings are parsed
+				as numbers, dates, UUIDs, or IP addresses, timestamps are converted for `Date`,
+				`DateTime`, and `DateTime64` columns, and enums take either the name or the number of a
+				value. Date-time strings without an offset are in the time zone of their column, or in
+				UTC for columns without one. `LowCardinality`, `Nullable`, `Array`, and `Map` columns are
+				supported.
+
+				Missing fields, and null values in columns that aren't `Nullable`, are left for ClickHouse
+				to fill in with the default of their column. Rows are sent as `RowBinaryWithDefaults`
+				with the `row_binary` format, and with the `native` format, the events of a batch are
+				sent in a block for each set of columns they have values for. Events with a field that
+				can't be converted to the type of its column are rejected, without failing the rest of
+				their batch.
+
+				`MATERIALIZED` and `ALIAS` columns are left for ClickHouse to compute. The sink fails to
+				start if a column has a type it can't encode, such as `Decimal` or `Tuple`, unless the
+				column has a default, in which case it is left out of inserts.
+				"""
+		}
+	}
 }
@@ -66,7 +66,19 @@ components: sinks: kafka: {
 		traces: false
 	}
 
-	how_it_works: components._kafka.how_it_works
+	how_it_works: components._kafka.how_it_works & {
+		partitioning: {
+			title: "Partitioning"
+			body: """
+				Each record is written to the partition rendered from `partition`, if set. Otherwise, the
+				`partitioner` chooses the partition from a hash of the record key, taken from `key_field`.
+				By default, librdkafka hashes keys with CRC32, while the Java client uses murmur2, so
+				records with the same key land on different partitions depending on the producer. To write
+				records to the same partitions as Java producers, set `partitioner` to `sticky`, which
+				behaves like the default partitioner of the Java client, or to `murmur2`.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		kafka_queue_messages:                components.sources.internal_metrics.output.metrics.kafka_queue_messages
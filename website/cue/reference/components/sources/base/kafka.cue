@@ -289,6 +289,25 @@ base: components: sources: kafka: configuration: {
 		required: false
 		type: uint: examples: [2500, 5000]
 	}
+	exactly_once: {
+		description: """
+			Hands off committing consumer offsets to transactional `kafka` sinks.
+
+			When enabled, this source does not commit offsets itself. Instead, `kafka` sinks with a
+			`transaction` configured commit the offsets of the messages they write in the same transaction
+			as the produced records, so that a Kafka-to-Kafka pipeline does not duplicate records when it
+			is restarted. To that end, the consumer group, topic, partition, and offset of each message are
+			added to the event metadata, under `%kafka`, regardless of the log namespace.
+
+			The source must send its events directly to a single `kafka` sink with a `transaction`
+			configured, and to no other component. When a transaction is aborted, the partitions of its
+			messages are rewound, so that they're consumed again.
+
+			Offsets are only committed once events are written, so acknowledgements must be enabled.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	fetch_wait_max_ms: {
 		description: "Maximum time the broker may wait to fill the response."
 		required:    false
@@ -546,6 +565,65 @@ base: components: sources: kafka: configuration: {
 			}
 		}
 	}
+	seek: {
+		description: """
+			Moves each partition to the given position the first time it is assigned to the source.
+
+			Unlike `auto_offset_reset`, this overrides the offsets committed for the consumer group, which
+			allows replaying data from a point in time or from known offsets, for example when onboarding a
+			topic or after an incident. Partitions assigned again after a consumer group rebalance resume
+			from their committed offsets, until the source is restarted.
+			"""
+		required: false
+		type: object: options: {
+			offsets: {
+				description:   "The offsets to start consuming from."
+				relevant_when: "position = \"offsets\""
+				required:      true
+				type: array: items: type: object: options: {
+					offset: {
+						description: "The offset of the first message to consume."
+						required:    true
+						type: int: examples: [42]
+					}
+					partition: {
+						description: "The partition number."
+						required:    true
+						type: int: examples: [0]
+					}
+					topic: {
+						description: "The topic of the partition."
+						required:    true
+						type: string: examples: ["topic-1"]
+					}
+				}
+			}
+			position: {
+				description: "The position to start consuming from."
+				required:    true
+				type: string: enum: {
+					earliest: "Start from the earliest message retained in each partition."
+					latest:   "Start from the end of each partition, only consuming messages produced from then on."
+					offsets: """
+						Start from explicit offsets.
+
+						Partitions that are not listed are consumed from their committed offsets.
+						"""
+					timestamp: """
+						Start from the earliest message of each partition with a timestamp at or after the given time.
+
+						Partitions without any such message are consumed from their end.
+						"""
+				}
+			}
+			timestamp: {
+				description:   "The time to start consuming from."
+				relevant_when: "position = \"timestamp\""
+				required:      true
+				type: string: examples: ["2024-06-01T00:00:00Z"]
+			}
+		}
+	}
 	session_timeout_ms: {
 		description: "The Kafka session timeout."
 		required:    false
@@ -1,6 +1,27 @@
 package metadata
 
 base: components: sources: nats: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
 	auth: {
 		description: "Configuration of the authentication strategy when interacting with NATS."
 		required:    false
@@ -435,15 +456,83 @@ base: components: sources: nats: configuration: {
 			}
 		}
 	}
+	jetstream: {
+		description: """
+			Configuration for consuming messages from a [JetStream][jetstream] stream, rather than from core
+			NATS subjects.
+
+			Messages are acknowledged to the server explicitly, once their events are delivered if
+			acknowledgements are enabled, so that messages which aren't delivered are redelivered.
+
+			[jetstream]: https://docs.nats.io/nats-concepts/jetstream
+			"""
+		required: false
+		type: object: options: {
+			ack_wait_secs: {
+				description: """
+					The amount of time the server waits for a message to be acknowledged before redelivering it.
+
+					With end-to-end acknowledgements enabled, messages are only acknowledged once their events
+					are delivered, so this must be longer than it takes the sinks to deliver them. Like the
+					other consumer settings, this only applies if the consumer is created by Vector.
+					"""
+				required: false
+				type: uint: {
+					default: 30
+					unit:    "seconds"
+				}
+			}
+			consumer: {
+				description: """
+					The name of the durable [consumer][nats_consumer] to consume messages with.
+
+					If the consumer doesn't exist yet, it is created with explicit acknowledgements and with
+					`subject` as its filter subject. An existing consumer is used as it is, and must be of the
+					configured `consumer_type`.
+
+					[nats_consumer]: https://docs.nats.io/nats-concepts/jetstream/consumers
+					"""
+				required: true
+				type: string: examples: ["vector"]
+			}
+			consumer_type: {
+				description: "The type of JetStream consumer to consume messages with."
+				required:    false
+				type: string: {
+					default: "pull"
+					enum: {
+						pull: "A pull consumer, which messages are fetched from in batches."
+						push: "A push consumer, which delivers messages to a subject that is subscribed to."
+					}
+				}
+			}
+			stream: {
+				description: """
+					The name of the [stream][nats_stream] to consume messages from.
+
+					[nats_stream]: https://docs.nats.io/nats-concepts/jetstream/streams
+					"""
+				required: true
+				type: string: examples: ["events"]
+			}
+		}
+	}
 	queue: {
-		description: "The NATS queue group to join."
-		required:    false
+		description: """
+			The NATS queue group to join.
+
+			With `jetstream` set, this is the deliver group of a push consumer if it is created by
+			Vector.
+			"""
+		required: false
 		type: string: {}
 	}
 	subject: {
 		description: """
 			The NATS [subject][nats_subject] to pull messages from.
 
+			With `jetstream` set, this is the filter subject of the consumer if it is created by Vector.
+
 			[nats_subject]: https://docs.nats.io/nats-concepts/subjects
 			"""
 		required: true
@@ -89,7 +89,14 @@ base: components: sources: pulsar: configuration: {
 		type: string: examples: ["consumer-name"]
 	}
 	dead_letter_queue_policy: {
-		description: "Dead Letter Queue policy configuration."
+		description: """
+			Dead Letter Queue policy configuration.
+
+			Messages that are negatively acknowledged, such as those whose events are rejected by a sink
+			when end-to-end acknowledgements are enabled, are sent to the dead letter topic once they have
+			been redelivered `max_redeliver_count` times. Requires a `shared` or `key_shared`
+			`subscription_type`.
+			"""
 		required:    false
 		type: object: options: {
 			dead_letter_topic: {
@@ -446,6 +453,21 @@ base: components: sources: pulsar: configuration: {
 			}
 		}
 	}
+	negative_ack_redelivery_delay_ms: {
+		description: """
+			The delay before a message is redelivered after it was rejected or failed to be delivered.
+
+			When end-to-end acknowledgements are enabled, a message whose events are rejected by a sink
+			is negatively acknowledged once this delay has passed, making the broker redeliver it. By
+			default, messages are negatively acknowledged as soon as the events fail.
+			"""
+		required: false
+		type: uint: {
+			default: 0
+			examples: [1000, 60000]
+			unit: "milliseconds"
+		}
+	}
 	priority_level: {
 		description: """
 			The consumer's priority level.
@@ -457,11 +479,179 @@ base: components: sources: pulsar: configuration: {
 		required: false
 		type: int: {}
 	}
+	schema: {
+		description: """
+			Decoding of messages written with a schema.
+
+			Messages written with an `AVRO` or `JSON` schema are decoded into structured events, using the
+			schema they were written with, as fetched from the Pulsar admin API. Other messages, such as
+			those without a schema or with a `STRING` schema, are decoded with `decoding`.
+			"""
+		required: false
+		type: object: options: {
+			admin_endpoint: {
+				description: "The endpoint of the Pulsar admin API."
+				required:    true
+				type: string: examples: ["http://127.0.0.1:8080"]
+			}
+			auth: {
+				description: """
+					Configuration of the authentication strategy for HTTP requests.
+		
+					HTTP authentication should be used with HTTPS only, as the authentication credentials are passed as an
+					HTTP header without any additional encryption beyond what is provided by the transport itself.
+					"""
+				required: false
+				type: object: options: {
+					password: {
+						description:   "The basic authentication password."
+						relevant_when: "strategy = \"basic\""
+						required:      true
+						type: string: examples: ["${PASSWORD}", "password"]
+					}
+					strategy: {
+						description: "The authentication strategy to use."
+						required:    true
+						type: string: enum: {
+							basic: """
+								Basic authentication.
+		
+								The username and password are concatenated and encoded via [base64][base64].
+		
+								[base64]: https://en.wikipedia.org/wiki/Base64
+								"""
+							bearer: """
+								Bearer authentication.
+		
+								The bearer token value (OAuth2, JWT, etc.) is passed as-is.
+								"""
+						}
+					}
+					token: {
+						description:   "The bearer authentication token."
+						relevant_when: "strategy = \"bearer\""
+						required:      true
+						type: string: {}
+					}
+					user: {
+						description:   "The basic authentication username."
+						relevant_when: "strategy = \"basic\""
+						required:      true
+						type: string: examples: ["${USERNAME}", "username"]
+					}
+				}
+			}
+			tls: {
+				description: "TLS configuration."
+				required:    false
+				type: object: options: {
+					alpn_protocols: {
+						description: """
+							Sets the list of supported ALPN protocols.
+		
+							Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+							that they are defined.
+							"""
+						required: false
+						type: array: items: type: string: examples: ["h2"]
+					}
+					ca_file: {
+						description: """
+							Absolute path to an additional CA certificate file.
+		
+							The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/certificate_authority.crt"]
+					}
+					crt_file: {
+						description: """
+							Absolute path to a certificate file used to identify this server.
+		
+							The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+							an inline string in PEM format.
+		
+							If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.crt"]
+					}
+					key_file: {
+						description: """
+							Absolute path to a private key file used to identify this server.
+		
+							The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.key"]
+					}
+					key_pass: {
+						description: """
+							Passphrase used to unlock the encrypted key file.
+		
+							This has no effect unless `key_file` is set.
+							"""
+						required: false
+						type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+					}
+					verify_certificate: {
+						description: """
+							Enables certificate verification. For components that create a server, this requires that the
+							client connections have a valid client certificate. For components that initiate requests,
+							this validates that the upstream has a valid certificate.
+		
+							If enabled, certificates must not be expired and must be issued by a trusted
+							issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+							certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+							so on until the verification process reaches a root certificate.
+		
+							Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+							"""
+						required: false
+						type: bool: {}
+					}
+					verify_hostname: {
+						description: """
+							Enables hostname verification.
+		
+							If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+							the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+		
+							Only relevant for outgoing connections.
+		
+							Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+							"""
+						required: false
+						type: bool: {}
+					}
+				}
+			}
+		}
+	}
 	subscription_name: {
 		description: "The Pulsar subscription name."
 		required:    false
 		type: string: examples: ["subscription_name"]
 	}
+	subscription_type: {
+		description: "The subscription type of the consumer."
+		required:    false
+		type: string: {
+			default: "shared"
+			enum: {
+				exclusive: "Only one consumer may attach to the subscription."
+				failover: """
+					Messages are delivered to a single active consumer, with the other consumers taking over if
+					it disconnects.
+					"""
+				key_shared: """
+					Messages are distributed across the consumers of the subscription, with messages of the same
+					key always delivered to the same consumer.
+					"""
+				shared: "Messages are distributed across the consumers of the subscription, in a round-robin fashion."
+			}
+		}
+	}
 	topics: {
 		description: "The Pulsar topic names to read events from."
 		required:    true
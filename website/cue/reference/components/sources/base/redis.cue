@@ -1,8 +1,29 @@
 package metadata
 
 base: components: sources: redis: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
 	data_type: {
-		description: "The Redis data type (`list` or `channel`) to use."
+		description: "The Redis data type (`list`, `channel`, or `stream`) to use."
 		required:    false
 		type: string: {
 			default: "list"
@@ -13,6 +34,12 @@ base: components: sources: redis: configuration: {
 					This is based on Redis' Pub/Sub capabilities.
 					"""
 				list: "The `list` data type."
+				stream: """
+					The `stream` data type.
+
+					Entries are read as part of a consumer group, which allows the stream to be shared between
+					several instances of the source.
+					"""
 			}
 		}
 	}
@@ -363,12 +390,38 @@ base: components: sources: redis: configuration: {
 	list: {
 		description: "Options for the Redis `list` data type."
 		required:    false
-		type: object: options: method: {
-			description: "Method for getting events from the `list` data type."
-			required:    true
-			type: string: enum: {
-				lpop: "Pop messages from the head of the list."
-				rpop: "Pop messages from the tail of the list."
+		type: object: options: {
+			method: {
+				description: "Method for getting events from the `list` data type."
+				required:    true
+				type: string: enum: {
+					lpop: "Pop messages from the head of the list."
+					rpop: "Pop messages from the tail of the list."
+				}
+			}
+			processing_key: {
+				description: """
+					The Redis key of the processing list to use in reliable mode.
+
+					Each instance of the source reading from the same list must use a different processing list.
+
+					By default, this is the `key` followed by `:processing:` and the hostname.
+					"""
+				required: false
+				type: string: examples: ["vector:processing:vector-0"]
+			}
+			reliable: {
+				description: """
+					Whether to read messages reliably, rather than popping them off the list.
+
+					Each message is moved into a processing list with `BLMOVE`, and only removed from it once
+					its events are delivered, or sent on when acknowledgements are disabled. Messages whose
+					events failed to be delivered are pushed back onto the other end of the list to be read
+					again. Messages left in the processing list, for example by a crash, are moved back onto the
+					list when the source starts.
+					"""
+				required: false
+				type: bool: default: false
 			}
 		}
 	}
@@ -383,6 +436,69 @@ base: components: sources: redis: configuration: {
 		required: false
 		type: string: examples: ["redis_key"]
 	}
+	stream: {
+		description: "Options for the Redis `stream` data type."
+		required:    false
+		type: object: options: {
+			claim_min_idle_secs: {
+				description: """
+					The time, in seconds, that an entry must have gone unacknowledged before it is claimed
+					from the consumer it was delivered to.
+
+					This is how entries delivered to a consumer that went away are eventually processed. The
+					source checks for such entries on the same interval.
+
+					Set to `0` to disable claiming entries from other consumers.
+					"""
+				required: false
+				type: uint: {
+					default: 300
+					unit:    "seconds"
+				}
+			}
+			consumer: {
+				description: """
+					The name of the consumer within the consumer group.
+
+					Each instance of the source reading from the same group must use a different name.
+
+					By default, the hostname is used.
+					"""
+				required: false
+				type: string: examples: ["vector-0"]
+			}
+			count: {
+				description: "The maximum number of entries to read from the stream at a time."
+				required:    false
+				type: uint: default: 100
+			}
+			field: {
+				description: """
+					The field of the stream entries that holds the message to decode.
+
+					Entries without this field are acknowledged and dropped.
+					"""
+				required: false
+				type: string: {
+					default: "message"
+					examples: ["payload"]
+				}
+			}
+			group: {
+				description: """
+					The name of the consumer group to read the stream with.
+
+					The group is created, along with the stream, if it does not exist yet. A new group starts
+					at the beginning of the stream.
+					"""
+				required: false
+				type: string: {
+					default: "vector"
+					examples: ["vector"]
+				}
+			}
+		}
+	}
 	url: {
 		description: """
 			The Redis URL to connect to.
@@ -253,6 +253,33 @@ components: sources: internal_metrics: {
 			description:       "The number of events dropped by this non-blocking buffer."
 			type:              "counter"
 			default_namespace: "vector"
+			tags: _component_tags & {
+				intentional: {
+					description: "True if the events were dropped intentionally, due to the buffer's `when_full` behavior, or false if due to an error."
+					required:    true
+				}
+				reason: {
+					description: "The reason the events were dropped."
+					required:    true
+					enum: {
+						"corrupted_events": "The events were corrupted or could not be read from a disk buffer."
+						"drop_newest":      "The buffer was full, and the newest events were dropped."
+						"drop_oldest":      "The buffer was full, and the oldest events were dropped to make room."
+						"expired":          "The events were older than the disk buffer's `max_age_secs`, and were dropped."
+					}
+				}
+			}
+		}
+		buffer_overflowed_bytes_total: {
+			description:       "The number of bytes this buffer stage overflowed to the next stage of the buffer."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_overflowed_events_total: {
+			description:       "The number of events this buffer stage overflowed to the next stage of the buffer."
+			type:              "counter"
+			default_namespace: "vector"
 			tags:              _component_tags
 		}
 		buffer_received_event_bytes_total: {
@@ -532,6 +559,36 @@ components: sources: internal_metrics: {
 				}
 			}
 		}
+		kafka_partition_assignments_total: {
+			description:       "The total number of times a Kafka partition was assigned to the consumer."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				topic: {
+					description: "The Kafka topic."
+					required:    true
+				}
+				partition: {
+					description: "The Kafka partition."
+					required:    true
+				}
+			}
+		}
+		kafka_partition_revocations_total: {
+			description:       "The total number of times a Kafka partition was revoked from the consumer."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				topic: {
+					description: "The Kafka topic."
+					required:    true
+				}
+				partition: {
+					description: "The Kafka partition."
+					required:    true
+				}
+			}
+		}
 		files_added_total: {
 			description:       "The total number of files Vector has found to watch."
 			type:              "counter"
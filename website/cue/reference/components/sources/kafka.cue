@@ -98,7 +98,28 @@ components: sources: kafka: {
 		kafka_consumed_messages_total:       components.sources.internal_metrics.output.metrics.kafka_consumed_messages_total
 		kafka_consumed_messages_bytes_total: components.sources.internal_metrics.output.metrics.kafka_consumed_messages_bytes_total
 		kafka_consumer_lag:                  components.sources.internal_metrics.output.metrics.kafka_consumer_lag
+		kafka_partition_assignments_total:   components.sources.internal_metrics.output.metrics.kafka_partition_assignments_total
+		kafka_partition_revocations_total:   components.sources.internal_metrics.output.metrics.kafka_partition_revocations_total
 	}
 
-	how_it_works: components._kafka.how_it_works
+	how_it_works: components._kafka.how_it_works & {
+		seeking: {
+			title: "Replaying data"
+			body: """
+				By default, the consumer group resumes from its committed offsets, and `auto_offset_reset`
+				only applies to partitions without any. To replay a window of data, for example through a
+				new pipeline or after an incident, set `seek` to start from the earliest or latest
+				messages, from the first message produced at or after a `timestamp`, or from explicit
+				`offsets` per topic partition.
+
+				Each partition is moved to that position the first time it is assigned to the source.
+				Partitions assigned again after a consumer group rebalance resume from their committed
+				offsets, so that data is only replayed once, until the source is restarted. Remove the
+				`seek` option once the replay is done. Partition assignments and revocations are logged
+				and counted by the `kafka_partition_assignments_total` and
+				`kafka_partition_revocations_total` metrics, and `metrics.topic_lag_metric` exposes the
+				lag of each partition, to follow the progress of the replay.
+				"""
+		}
+	}
 }
@@ -0,0 +1,102 @@
+package metadata
+
+components: sources: mqtt: {
+	title: "MQTT"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.mqtt
+				interface: {
+					socket: {
+						direction: "incoming"
+						port:      1883
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.mqtt.configuration
+
+	output: logs: record: {
+		description: "An individual MQTT message."
+		fields: {
+			message: {
+				description: "The raw payload of the MQTT message."
+				required:    true
+				type: string: {
+					examples: ["{\"temperature\": 21.5}"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["mqtt"]
+				}
+			}
+			timestamp: fields._current_timestamp
+			topic: {
+				description: "The topic the MQTT message was published to."
+				required:    true
+				type: string: {
+					examples: ["sensors/kitchen/temperature"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		persistent_sessions: {
+			title: "Persistent sessions"
+			body: """
+				With `clean_session` disabled and a fixed `client_id`, the broker keeps the source's
+				session while it is disconnected, and delivers the messages published to its
+				subscriptions in the meantime once it reconnects. With acknowledgements enabled,
+				messages are only acknowledged to the broker once their events are delivered, so
+				messages that were in flight when Vector stopped are delivered again as well.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
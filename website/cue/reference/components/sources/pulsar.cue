@@ -160,6 +160,44 @@ components: sources: pulsar: {
 		}
 	}
 
+	how_it_works: {
+		subscriptions: {
+			title: "Subscription types"
+			body: """
+				The source consumes its topics through a `shared` subscription by default, distributing
+				messages across every Vector instance using the same `subscription_name`. Use
+				`key_shared` to keep the messages of a key on the same instance, or `exclusive` and
+				`failover` to have a single instance consume the subscription, preserving the order of
+				messages.
+				"""
+		}
+		redelivery: {
+			title: "Redelivery and dead letters"
+			body: """
+				With end-to-end acknowledgements enabled, messages are acknowledged once their events are
+				delivered, and negatively acknowledged when their events are rejected, making the broker
+				redeliver them. Set `negative_ack_redelivery_delay_ms` to wait before the redelivery, for
+				example to let a failing sink recover.
+
+				With a `dead_letter_queue_policy`, messages redelivered more than `max_redeliver_count`
+				times are sent to the `dead_letter_topic` instead. The broker only tracks redeliveries of
+				`shared` and `key_shared` subscriptions, so other subscription types are rejected.
+				"""
+		}
+		schemas: {
+			title: "Schemas"
+			body: """
+				When `schema` is set, messages written with an `AVRO` or `JSON` schema are decoded into
+				structured events, using the schema version they were written with. The schemas are
+				fetched from the Pulsar admin API at `schema.admin_endpoint` and cached. A failure to fetch
+				a schema is cached for 30 seconds before the schema is fetched again. Messages without a
+				schema or with another schema type, and messages that fail to decode with their schema or
+				whose schema couldn't be fetched, are decoded with `decoding` instead, the latter being
+				logged and counted as errors.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
 		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
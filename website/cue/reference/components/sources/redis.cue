@@ -5,7 +5,7 @@ components: sources: redis: {
 
 	features: {
 		auto_generated:   true
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			tls: enabled:        false
@@ -94,5 +94,32 @@ components: sources: redis: {
 				API.
 				"""
 		}
+		streams: {
+			title: "Streams"
+			body: """
+				With the `stream` data type, the source reads entries as a member of a consumer group,
+				creating the group if needed. An entry is acknowledged with `XACK` once its events are
+				delivered, when end-to-end acknowledgements are enabled, and as soon as they are sent on
+				otherwise. Entries whose events failed to be delivered are left pending.
+
+				On start up, the source first reads the entries that were delivered to its consumer but
+				never acknowledged. Entries left pending by any consumer for longer than
+				`stream.claim_min_idle_secs` are claimed and read again, so that no entry is lost to a
+				consumer that went away. Together, this gives at-least-once delivery.
+				"""
+		}
+		reliable_lists: {
+			title: "Reliable lists"
+			body: """
+				By default, the `list` data type pops messages off the list, so messages that were read but
+				not yet delivered are lost if Vector stops unexpectedly. With `list.reliable` enabled, each
+				message is instead moved into a processing list with `BLMOVE`, and only removed from it once
+				its events are delivered. Messages whose events failed to be delivered are pushed back onto
+				the list to be read again.
+
+				The processing list belongs to one instance of the source. When the source starts, any
+				messages left in it are moved back onto the list, so that they are read again first.
+				"""
+		}
 	}
 }
@@ -0,0 +1,107 @@
+package metadata
+
+components: sources: websocket: {
+	title: "WebSocket"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.websocket
+				interface: {
+					socket: {
+						direction: "incoming"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator", "sidecar"]
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.websocket.configuration
+
+	output: logs: record: {
+		description: "An individual WebSocket message."
+		fields: {
+			host: {
+				description: "The IP address of the client the message was received from, in `server` mode."
+				required:    false
+				type: string: {
+					examples: ["192.168.0.10"]
+				}
+			}
+			message: {
+				description: "The payload of the WebSocket message."
+				required:    true
+				type: string: {
+					examples: ["{\"price\": 42}"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["websocket"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		modes: {
+			title: "Client and server modes"
+			body: """
+				In `client` mode, the source connects to the server at `uri` and reconnects with an
+				exponential backoff whenever the connection is lost. The `initial_message`, such as a
+				subscription request, is sent again after every reconnect. Pings can be sent on an
+				interval to detect connections that went away silently.
+
+				In `server` mode, the source accepts connections from any number of clients, such as
+				browsers or devices, on `address`.
+
+				In both modes, text and binary messages are decoded with the configured `framing` and
+				`decoding`, while control frames are handled by the source itself.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+		open_connections:       components.sources.internal_metrics.output.metrics.open_connections
+	}
+}